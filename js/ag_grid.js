@@ -1,77 +1,251 @@
-export function create_grid(ag_grid_data, grid_div_id) {
-  if (navigator.userAgent.match(/Android/i)
-    || navigator.userAgent.match(/webOS/i)
-    || navigator.userAgent.match(/iPhone/i)
-    || navigator.userAgent.match(/iPad/i)
-    || navigator.userAgent.match(/iPod/i)
-    || navigator.userAgent.match(/BlackBerry/i)
-    || navigator.userAgent.match(/Windows Phone/i)) {
-      // set up grid properties including providing data
-      const gridOptions = {
-        columnDefs: ag_grid_data.col_defs,
-        domLayout: "autoHeight",
-        animateRows: true,
-        defaultColDef: {
-          flex: 1,
-          minWidth: 40,
-          filter: true,
-          resizable: true,
-          wrapText: true,
-          sortable: true,
-          enableRowGroup: true,
-        },
-        rowHeight: 20,
-        headerHeight: 30,
-        alwaysShowHorizontalScroll: true,
-        alwaysShowVerticalScroll: true,
-        rowGroupPanelShow: "always",
-        groupDisplayType: 'groupRows',
-        suppressDragLeaveHidesColumns: true,
-        rowData: ag_grid_data.row_data,
-      };
-
-      // setup the grid after the page has finished loading
-      var gridDiv = document.querySelector("#".concat(grid_div_id));
-      new agGrid.Grid(gridDiv, gridOptions);
-    } else {
-      // set up grid properties including providing data
-      const gridOptions = {
-        columnDefs: ag_grid_data.col_defs,
-        domLayout: "normal",
-        animateRows: true,
-        rowSelection: "multiple",
-        defaultColDef: {
-          flex: 1,
-          minWidth: 100,
-          filter: true,
-          resizable: true,
-          wrapText: true,
-          sortable: true,
-          enableRowGroup: true,
-        },
-        rowHeight: 30,
-        headerHeight: 40,
-        alwaysShowHorizontalScroll: true,
-        rowSelection: "multiple",
-        enableCellTextSelection: true,
-        columnHoverHighlight: true,
-        rowDragManaged: true,
-        rowDragEntireRow: true,
-        rowDragMultiRow: true,
-        rowGroupPanelShow: "always",
-        groupDisplayType: 'groupRows',
-        suppressDragLeaveHidesColumns: true,
-        rowData: ag_grid_data.row_data,
-      };
-
-      // setup the grid after the page has finished loading
-      var gridDiv = document.querySelector("#".concat(grid_div_id));
-      new agGrid.Grid(gridDiv, gridOptions);
+// cells listed in a row's __changed_cells are highlighted, the list is split on \u001f
+function changed_cell_class(params) {
+  if (params.data && params.data.__changed_cells) {
+    return params.data.__changed_cells.split("\u001f").includes(params.colDef.field)
+      ? "diff-changed-cell"
+      : undefined;
+  }
+  return undefined;
+}
+
+// classes and text set by the formatting rules, see apply_rules in src/formatting.rs
+function cell_class(params) {
+  const classes = [changed_cell_class(params)];
+  if (params.data) {
+    classes.push(params.data["__class." + params.colDef.field]);
+  }
+  const joined = classes.filter((name) => name).join(" ");
+  return joined === "" ? undefined : joined;
+}
+
+function formatted_value(data, field, value) {
+  if (data && data["__text." + field] !== undefined) {
+    return data["__text." + field];
+  }
+  return value;
+}
+
+// a decimal number with the separators of locale, undefined for anything else, see
+// Locale::format_number in src/i18n.rs
+function format_number(text, locale) {
+  const match = /^\s*(-?)(\d+)(?:\.(\d+))?\s*$/.exec(text);
+  if (!match) {
+    return undefined;
+  }
+  const [, sign, integer, fraction] = match;
+  const grouped = integer.replace(/\B(?=(\d{3})+(?!\d))/g, locale.group_separator);
+  return sign + grouped + (fraction === undefined ? "" : locale.decimal_separator + fraction);
+}
+
+// a complete ISO 8601 date in the date order of locale, keeping the time as written,
+// undefined for anything else, see Locale::format_date in src/i18n.rs
+function format_date(text, locale) {
+  const match = /^\s*(\d{4})-(\d{2})-(\d{2})(?:T([\d:.+\-Z]+))?\s*$/.exec(text);
+  if (!match) {
+    return undefined;
+  }
+  const [, year, month, day, time] = match;
+  if (Number(month) < 1 || Number(month) > 12 || Number(day) < 1 || Number(day) > 31) {
+    return undefined;
+  }
+  const date = locale.date_pattern.replace("YYYY", year).replace("MM", month).replace("DD", day);
+  return time === undefined ? date : date + " " + time;
+}
+
+// the cell as shown: the text of a formatting rule, else numbers and dates of the
+// localeNumber and localeDate columns written the way the grid's locale writes them
+function localized_value(data, col_def, value, locale) {
+  if (data && data["__text." + col_def.field] !== undefined) {
+    return data["__text." + col_def.field];
+  }
+  if (locale && value !== undefined && value !== null) {
+    const localized =
+      col_def.type === "localeNumber" || col_def.type === "localeNumberValue"
+        ? format_number(String(value), locale)
+      : col_def.type === "localeDate" ? format_date(String(value), locale)
+      : undefined;
+    if (localized !== undefined) {
+      return localized;
     }
+  }
+  return value;
+}
 
-  
+// coded cells filter on "code — decode", see decode_rows in src/codelists.rs
+function filter_value(data, field) {
+  if (data && data["__filter." + field] !== undefined) {
+    return data["__filter." + field];
+  }
+  return data ? data[field] : undefined;
+}
+
+// grids created so far, keyed by the id of the div they live in
+const grids = {};
 
-  // return result
-  return true;
+// rows are identified by __row_index so transactions can update and remove them
+function row_id(params) {
+  return String(params.data.__row_index);
 }
 
+// the grid in a div, wrapped for the GridHandle bindings in src/ag_grid.rs
+class GridHandle {
+  constructor(grid_div_id, gridOptions) {
+    this.grid_div_id = grid_div_id;
+    this.options = gridOptions;
+  }
+
+  set_row_data(row_data) {
+    this.options.api.setRowData(row_data);
+  }
+
+  set_column_defs(col_defs) {
+    this.options.api.setColumnDefs(col_defs);
+  }
+
+  apply_transaction(transaction) {
+    this.options.api.applyTransaction(transaction);
+  }
+
+  set_filter_model(filter_model) {
+    this.options.api.setFilterModel(filter_model);
+  }
+
+  get_filter_model() {
+    return this.options.api.getFilterModel();
+  }
+
+  get_column_state() {
+    return this.options.columnApi.getColumnState();
+  }
+
+  apply_column_state(column_state) {
+    this.options.columnApi.applyColumnState({
+      state: column_state,
+      applyOrder: true,
+      defaultState: { sort: null },
+    });
+  }
+
+  // back to the column order, visibility and sort of the column definitions
+  reset_column_state() {
+    this.options.columnApi.resetColumnState();
+  }
+
+  displayed_row_count() {
+    return this.options.api.getDisplayedRowCount();
+  }
+
+  // cells are exported as shown, fields separated the way the grid's locale expects
+  export_csv(file_name) {
+    const locale = this.options.context;
+    this.options.api.exportDataAsCsv({
+      fileName: file_name,
+      columnSeparator: locale ? locale.csv_separator : ",",
+      processCellCallback: (params) =>
+        localized_value(params.node.data, params.column.getColDef(), params.value, locale),
+    });
+  }
+
+  // hand the clicked row's index back to Rust, group rows carry no data
+  on_row_clicked(callback) {
+    this.options.api.addEventListener("rowClicked", (event) => {
+      if (event.data && event.data.__row_index !== undefined) {
+        callback(Number(event.data.__row_index));
+      }
+    });
+  }
+
+  on_selection_changed(callback) {
+    this.options.api.addEventListener("selectionChanged", () => {
+      callback(this.options.api.getSelectedRows().map((row) => Number(row.__row_index)));
+    });
+  }
+
+  on_sort_changed(callback) {
+    this.options.api.addEventListener("sortChanged", () => callback(this.get_column_state()));
+  }
+
+  on_filter_changed(callback) {
+    this.options.api.addEventListener("filterChanged", () => callback(this.get_filter_model()));
+  }
+
+  // filters, sorts, hidden or moved columns; column drags report once the drag is done
+  on_state_changed(callback) {
+    const changed = () => callback(this.get_filter_model(), this.get_column_state());
+    for (const event of ["filterChanged", "sortChanged", "columnVisible", "columnPinned", "dragStopped"]) {
+      this.options.api.addEventListener(event, changed);
+    }
+  }
+
+  destroy() {
+    this.options.api.destroy();
+    if (grids[this.grid_div_id] === this) {
+      delete grids[this.grid_div_id];
+    }
+  }
+}
+
+// the grid currently shown in the div, undefined without one
+export function find_grid(grid_div_id) {
+  return grids[grid_div_id];
+}
+
+function localized_cell(params) {
+  return localized_value(params.data, params.colDef, params.value, params.context);
+}
+
+// the number a cell holds, null for empty cells and anything else
+function number_value(value) {
+  if (value === undefined || value === null || value === "") {
+    return null;
+  }
+  const number = Number(value);
+  return Number.isNaN(number) ? null : number;
+}
+
+// numbers by value, cells without one first
+function compare_numbers(a, b) {
+  const [x, y] = [number_value(a), number_value(b)];
+  if (x === null || y === null) {
+    return (x === null ? 0 : 1) - (y === null ? 0 : 1);
+  }
+  return x - y;
+}
+
+// grid_options come from GridOptions in src/layout.rs, only the hooks that call back into JS are added here
+export function create_grid(ag_grid_data, grid_div_id, grid_options) {
+  // a div only ever holds one grid, drop the previous one before building a new one
+  if (grids[grid_div_id]) {
+    grids[grid_div_id].destroy();
+  }
+  const gridOptions = {
+    ...grid_options,
+    columnDefs: ag_grid_data.col_defs,
+    defaultColDef: {
+      ...grid_options.defaultColDef,
+      cellClass: cell_class,
+      valueFormatter: (params) => formatted_value(params.data, params.colDef.field, params.value),
+      filterValueGetter: (params) => filter_value(params.data, params.colDef.field),
+    },
+    // see GridOptions::with_locale in src/layout.rs
+    columnTypes: {
+      localeNumber: { valueFormatter: localized_cell },
+      localeNumberValue: {
+        valueFormatter: localized_cell,
+        comparator: compare_numbers,
+        filter: "agNumberColumnFilter",
+        filterValueGetter: (params) => number_value(params.data ? params.data[params.colDef.field] : undefined),
+      },
+      localeDate: { valueFormatter: localized_cell },
+    },
+    getRowClass: (params) => params.data ? params.data.__row_class : undefined,
+    getRowId: row_id,
+    rowData: ag_grid_data.row_data,
+  };
+
+  // setup the grid after the page has finished loading
+  var gridDiv = document.querySelector("#".concat(grid_div_id));
+  new agGrid.Grid(gridDiv, gridOptions);
+  grids[grid_div_id] = new GridHandle(grid_div_id, gridOptions);
+  return grids[grid_div_id];
+}
@@ -2,3 +2,8 @@ export function get_host() {
     return 'my-secret-host.com'
 }
 
+
+// see js/features.js
+export function shared_views_enabled() {
+    return false
+}
@@ -0,0 +1,10 @@
+// Optional backend features, set per deployment in config.js. Unlike config.js this file is not
+// replaced per deployment, so a config.js without a setting leaves the feature off.
+
+import * as config from "./config.js";
+
+// true when the API serves /api/v1/views for sharing named views across the team, turned on by
+// `export function shared_views_enabled() { return true }` in config.js
+export function shared_views_enabled() {
+    return typeof config.shared_views_enabled === "function" && config.shared_views_enabled() === true
+}
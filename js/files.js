@@ -0,0 +1,8 @@
+export function download_text(file_name, mime_type, text) {
+  const url = URL.createObjectURL(new Blob([text], { type: mime_type }));
+  const anchor = document.createElement("a");
+  anchor.href = url;
+  anchor.download = file_name;
+  anchor.click();
+  URL.revokeObjectURL(url);
+}
@@ -0,0 +1,164 @@
+// Tables pinned for offline use, kept in IndexedDB and encrypted with AES-GCM under a key derived
+// from the user's passphrase. Only schema, table, row count and pin time are stored in the clear
+// so the selectors can list pinned tables before the store is unlocked.
+
+const DB_NAME = "styeward-offline";
+const TABLES = "tables";
+const META = "meta";
+const PBKDF2_ITERATIONS = 310000;
+// encrypted with the derived key when the store is created, a wrong passphrase fails to decrypt it
+const CHECK_TEXT = "styeward";
+
+// the derived key, only ever held in memory
+let key = null;
+
+function request_promise(request) {
+  return new Promise((resolve, reject) => {
+    request.onsuccess = () => resolve(request.result);
+    request.onerror = () => reject(request.error);
+  });
+}
+
+function open_db() {
+  const request = indexedDB.open(DB_NAME, 2);
+  request.onupgradeneeded = (event) => {
+    if (event.oldVersion < 1) {
+      request.result.createObjectStore(TABLES, { keyPath: "key" });
+      request.result.createObjectStore(META);
+      return;
+    }
+    // version 1 keyed tables by "schema.table", which schema "a.b" with table "c" shares with
+    // schema "a" and table "b.c"
+    const store = request.transaction.objectStore(TABLES);
+    store.openCursor().onsuccess = (cursor_event) => {
+      const cursor = cursor_event.target.result;
+      if (!cursor) {
+        return;
+      }
+      const entry = cursor.value;
+      if (typeof entry.key === "string") {
+        cursor.delete();
+        store.put({ ...entry, key: table_key(entry.schema, entry.table) });
+      }
+      cursor.continue();
+    };
+  };
+  return request_promise(request);
+}
+
+async function with_store(name, mode, action) {
+  const db = await open_db();
+  try {
+    return await request_promise(action(db.transaction(name, mode).objectStore(name)));
+  } finally {
+    db.close();
+  }
+}
+
+async function derive_key(passphrase, salt) {
+  const material = await crypto.subtle.importKey(
+    "raw", new TextEncoder().encode(passphrase), "PBKDF2", false, ["deriveKey"]);
+  return crypto.subtle.deriveKey(
+    { name: "PBKDF2", salt, iterations: PBKDF2_ITERATIONS, hash: "SHA-256" },
+    material, { name: "AES-GCM", length: 256 }, false, ["encrypt", "decrypt"]);
+}
+
+async function encrypt(with_key, text) {
+  const iv = crypto.getRandomValues(new Uint8Array(12));
+  const ciphertext = await crypto.subtle.encrypt(
+    { name: "AES-GCM", iv }, with_key, new TextEncoder().encode(text));
+  return { iv, ciphertext };
+}
+
+async function decrypt(with_key, sealed) {
+  const plaintext = await crypto.subtle.decrypt(
+    { name: "AES-GCM", iv: sealed.iv }, with_key, sealed.ciphertext);
+  return new TextDecoder().decode(plaintext);
+}
+
+// a compound key, names may hold any character
+function table_key(schema, table) {
+  return [schema, table];
+}
+
+// resolves to false when the passphrase does not open the existing store
+export async function offline_unlock(passphrase) {
+  let meta = await with_store(META, "readonly", (store) => store.get("key"));
+  if (!meta) {
+    const salt = crypto.getRandomValues(new Uint8Array(16));
+    const created = await derive_key(passphrase, salt);
+    meta = { salt, check: await encrypt(created, CHECK_TEXT) };
+    await with_store(META, "readwrite", (store) => store.put(meta, "key"));
+    key = created;
+    return true;
+  }
+  const derived = await derive_key(passphrase, meta.salt);
+  try {
+    if ((await decrypt(derived, meta.check)) !== CHECK_TEXT) {
+      return false;
+    }
+  } catch (error) {
+    return false;
+  }
+  key = derived;
+  return true;
+}
+
+export function offline_unlocked() {
+  return key !== null;
+}
+
+export function offline_lock() {
+  key = null;
+}
+
+export async function offline_put(schema, table, pinned_at, rows, json) {
+  if (key === null) {
+    throw new Error("the offline store is locked");
+  }
+  const sealed = await encrypt(key, json);
+  await with_store(TABLES, "readwrite", (store) =>
+    store.put({ key: table_key(schema, table), schema, table, pinned_at, rows, sealed }));
+}
+
+// resolves to null when the table is not pinned
+export async function offline_get(schema, table) {
+  if (key === null) {
+    throw new Error("the offline store is locked");
+  }
+  const entry = await with_store(TABLES, "readonly", (store) => store.get(table_key(schema, table)));
+  if (!entry) {
+    return null;
+  }
+  return { pinned_at: entry.pinned_at, json: await decrypt(key, entry.sealed) };
+}
+
+export async function offline_list() {
+  const entries = await with_store(TABLES, "readonly", (store) => store.getAll());
+  return entries.map(({ schema, table, pinned_at, rows }) => ({ schema, table, pinned_at, rows }));
+}
+
+export async function offline_delete(schema, table) {
+  await with_store(TABLES, "readwrite", (store) => store.delete(table_key(schema, table)));
+}
+
+// drops every pinned table and the passphrase check, for a forgotten passphrase
+export async function offline_reset() {
+  key = null;
+  await with_store(TABLES, "readwrite", (store) => store.clear());
+  await with_store(META, "readwrite", (store) => store.clear());
+}
+
+// the service worker caches the app shell, see js/service_worker.js
+export function register_service_worker(script_url) {
+  if (!("serviceWorker" in navigator)) {
+    return;
+  }
+  navigator.serviceWorker.register(script_url).then(
+    () => navigator.serviceWorker.ready.then((registration) => {
+      // hand over what this page already loaded, the worker was not there to see it
+      const urls = performance.getEntriesByType("resource").map((entry) => entry.name);
+      registration.active.postMessage({ type: "cache-urls", urls });
+    }),
+    (error) => console.error("offline error: unable to register the service worker:", error));
+}
@@ -0,0 +1,129 @@
+// Caches the app shell, the trunk dist output under /site and the CDN scripts and styles, so the
+// app starts without a connection. API responses are never cached here: table data only goes
+// offline through the encrypted store in js/offline_store.js.
+
+const CACHE_NAME = "styeward-shell-v1";
+const SCOPE = "/site/";
+const INDEX = "/site/index.html";
+// the worker's files keep their names across builds, see src/worker_client.rs
+const WORKER_FILES = ["/site/worker.js", "/site/worker_bg.wasm"];
+// copied as they are by trunk, their names stay the same when they change
+const UNHASHED_FILES = ["/site/manifest.webmanifest", "/site/icon.svg"];
+const SNIPPETS = "/site/snippets/";
+const CDN_ORIGINS = ["https://cdn.jsdelivr.net"];
+
+function is_shell(url) {
+  const parsed = new URL(url, self.location.href);
+  if (parsed.origin === self.location.origin) {
+    return parsed.pathname.startsWith(SCOPE);
+  }
+  return CDN_ORIGINS.includes(parsed.origin);
+}
+
+function is_cdn(url) {
+  return CDN_ORIGINS.includes(new URL(url, self.location.href).origin);
+}
+
+// files which may change without changing their name, served network first
+function is_unhashed(url) {
+  const pathname = new URL(url, self.location.href).pathname;
+  return WORKER_FILES.includes(pathname) || UNHASHED_FILES.includes(pathname)
+    || pathname.startsWith(SNIPPETS);
+}
+
+// only successful responses are cached, the CDN ones may be opaque as their status is unknown
+function is_cacheable(url, response) {
+  return response.ok || (response.type === "opaque" && is_cdn(url));
+}
+
+function is_index(response) {
+  const type = response.headers.get("content-type") || "";
+  return response.ok && type.startsWith("text/html");
+}
+
+async function cache_response(key, url, response) {
+  if (is_cacheable(url, response)) {
+    const cache = await caches.open(CACHE_NAME);
+    await cache.put(key, response);
+  }
+}
+
+async function cache_urls(urls) {
+  await Promise.all(urls.filter(is_shell).map((url) => {
+    const same_origin = new URL(url, self.location.href).origin === self.location.origin;
+    // the CDN files are loaded by plain script and link tags, so they are cached opaque, which
+    // cache.add refuses
+    const request = new Request(url, { mode: same_origin ? "same-origin" : "no-cors" });
+    return fetch(request)
+      .then((response) => cache_response(request, url, response))
+      .catch((error) => console.warn("offline error: unable to cache", url, error));
+  }));
+}
+
+// the index and every script, style and wasm file it links to
+async function cache_index() {
+  const response = await fetch(INDEX, { cache: "no-cache" });
+  if (!is_index(response)) {
+    throw new Error(`unable to fetch ${INDEX}: ${response.status}`);
+  }
+  const html = await response.clone().text();
+  const cache = await caches.open(CACHE_NAME);
+  await cache.put(INDEX, response);
+  const linked = [...html.matchAll(/(?:href|src)="([^"]+)"/g)].map((match) => match[1]);
+  await cache_urls(linked.concat(WORKER_FILES).map((url) => new URL(url, self.location.href).href));
+}
+
+self.addEventListener("install", (event) => {
+  event.waitUntil(cache_index().then(() => self.skipWaiting()));
+});
+
+self.addEventListener("activate", (event) => {
+  event.waitUntil(caches.keys()
+    .then((names) => Promise.all(names.filter((name) => name !== CACHE_NAME).map((name) => caches.delete(name))))
+    .then(() => self.clients.claim()));
+});
+
+self.addEventListener("message", (event) => {
+  if (event.data && event.data.type === "cache-urls") {
+    event.waitUntil(cache_urls(event.data.urls));
+  }
+});
+
+self.addEventListener("fetch", (event) => {
+  const request = event.request;
+  if (request.method !== "GET") {
+    return;
+  }
+  // every route of the app is index.html, fetch it fresh and fall back to the cached copy
+  if (request.mode === "navigate" && new URL(request.url).pathname.startsWith("/site")) {
+    event.respondWith(fetch(request)
+      .then((response) => {
+        if (is_index(response)) {
+          const copy = response.clone();
+          caches.open(CACHE_NAME).then((cache) => cache.put(INDEX, copy));
+        }
+        return response;
+      })
+      .catch(() => caches.match(INDEX)));
+    return;
+  }
+  if (!is_shell(request.url)) {
+    return;
+  }
+  // the worker has to match the app and the files above keep their names, fetch them fresh and
+  // fall back to the cached copy
+  if (is_unhashed(request.url)) {
+    event.respondWith(fetch(request)
+      .then((response) => {
+        cache_response(request, request.url, response.clone());
+        return response;
+      })
+      .catch(() => caches.match(request)));
+    return;
+  }
+  // trunk hashes the names of the files it builds, a cached one never goes stale
+  event.respondWith(caches.match(request).then((cached) => cached || fetch(request).then((response) => {
+    cache_response(request, request.url, response.clone());
+    return response;
+  })));
+});
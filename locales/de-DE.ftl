@@ -0,0 +1,480 @@
+## Navigation
+
+nav-label = Hauptmenü
+nav-toggle = Navigation ein- oder ausblenden
+nav-home = Start
+nav-data-tables = Datentabellen
+nav-search = Suche
+locale-label = Sprache
+not-found = Nicht gefunden
+
+## Pages
+
+home-intro = Öffnen Sie die Seite Datentabellen, um Datentabellen in Echtzeit anzuzeigen.
+data-tables-intro = Klinische Studiendaten von Agenus nach Schema und Tabelle erkunden.
+table-selection-label = Tabellenauswahl
+
+## Schema and table selection
+
+schema-label = Schema
+table-label = Tabelle
+select-schema = Schema wählen
+select-table = Tabelle wählen
+select-loading = wird geladen...
+select-error = Fehler!
+select-contact-support = FEHLER: Wenden Sie sich an den Support.
+schemas-not-loaded = Die Schemas konnten nicht geladen werden.
+tables-not-loaded = Die Tabellen konnten nicht geladen werden.
+current-selection = Aktuelle Auswahl
+no-table-selected = Keine Tabelle ausgewählt
+clear-selection = Zurücksetzen
+
+## Undo history
+
+history-label = Verlauf
+history-steps = Verlauf
+history-undo = Rückgängig
+history-redo = Wiederholen
+history-undo-step = { $step } rückgängig machen
+history-redo-step = { $step } wiederholen
+history-with-keys = { $title } ({ $keys })
+history-start = Beginn der Sitzung
+list-separator = {", "}
+change-separator = {"; "}
+history-changes = { $changes }
+history-clear-filters = Filter entfernen
+history-filter = { $column } filtern
+history-clear-filter = Filter auf { $column } entfernen
+history-change-filters = Filter auf { $columns } ändern
+history-clear-sort = Sortierung entfernen
+history-sort = Nach { $columns } sortieren
+history-sort-ascending = { $column } aufsteigend
+history-sort-descending = { $column } absteigend
+history-hide = { $columns } ausblenden
+history-show = { $columns } einblenden
+history-show-and-hide = { $columns } ein- und ausblenden
+history-move = { $column } verschieben
+history-reorder = Spalten neu anordnen
+action-select-schema = Schema { $schema } wählen
+action-select-table = Tabelle { $table } wählen
+action-clear-selection = Auswahl zurücksetzen
+action-open-table = { $table } öffnen
+action-with-view = Ansicht { $view }
+action-with-filter = { $column } enthält { $value }
+action-parts = { $parts }
+action-change-grid = Raster von { $table } ändern
+action-apply-view = Ansicht { $view } anwenden
+action-undo =
+    { $steps ->
+        [one] Rückgängig
+       *[other] { $steps } Schritte rückgängig
+    }
+action-redo =
+    { $steps ->
+        [one] Wiederholen
+       *[other] { $steps } Schritte wiederholen
+    }
+
+## Table loading
+
+file-size = { $size } { $unit }
+progress-bytes-of = { $bytes } von { $total }
+progress =
+    { $rows ->
+        [one] { $bytes }, { $rows } Zeile
+       *[other] { $bytes }, { $rows } Zeilen
+    }
+pinned-at = angeheftet { $time }
+pinned-just-now = gerade angeheftet
+pinned-minutes-ago =
+    { $count ->
+        [one] vor { $count } Minute angeheftet
+       *[other] vor { $count } Minuten angeheftet
+    }
+pinned-hours-ago =
+    { $count ->
+        [one] vor { $count } Stunde angeheftet
+       *[other] vor { $count } Stunden angeheftet
+    }
+pinned-days-ago =
+    { $count ->
+        [one] vor { $count } Tag angeheftet
+       *[other] vor { $count } Tagen angeheftet
+    }
+table-loading = { $table } wird geladen
+table-loaded =
+    { $rows ->
+        [one] { $table } geladen, { $rows } Zeile
+       *[other] { $table } geladen, { $rows } Zeilen
+    }
+table-loaded-offline =
+    { $rows ->
+        [one] Offline-Kopie von { $table } geladen, { $rows } Zeile
+       *[other] Offline-Kopie von { $table } geladen, { $rows } Zeilen
+    }
+table-not-loaded = { $table } konnte nicht geladen werden.
+table-offline-locked = { $table } kann nicht über die API geladen werden. Entsperren Sie den Offline-Speicher, um die auf diesem Gerät angeheftete Kopie zu öffnen.
+table-download = Tabellendownload
+table-loading-progress = Laden: { $progress }
+table-offline-copy = Offline-Kopie von { $table }, { $age }. Aktualisieren Sie die Tabelle, sobald Sie wieder online sind.
+data-link-expired = Der Link zu den Tabellendaten ist abgelaufen.
+data-link-refused = Der Link zu den Tabellendaten wurde abgelehnt, er ist womöglich abgelaufen.
+data-link-unreachable = Die Tabellendaten konnten nicht heruntergeladen werden, ihr Link ist womöglich abgelaufen.
+
+## Table toolbar
+
+table-region = Tabelle { $table }
+table-view-mode = Anzeigemodus
+table-mode-grid = Raster
+table-mode-pivot = Pivot
+table-mode-compare = Vergleich
+table-refresh = Aktualisieren
+table-views = Ansichten
+table-view = Ansicht: { $view }
+table-checks = Prüfungen
+table-stats = Statistik
+table-formatting =
+    { $rules ->
+        [0] Formatierung
+       *[other] Formatierung ({ $rules })
+    }
+table-codelists =
+    { $columns ->
+        [0] Codelisten
+       *[other] Codelisten ({ $columns })
+    }
+table-offline = Offline
+table-offline-pinned = Offline (angeheftete Kopie)
+table-infer-csv-types = CSV-Typen erkennen
+table-export-csv = CSV exportieren
+command-toggle-pivot = Pivot ein- oder ausschalten
+command-refresh-table = Tabelle aktualisieren
+checks-showing-failing =
+    { $rows ->
+        [one] Die { $rows } Zeile, die { $check } verletzt
+       *[other] Die { $rows } Zeilen, die { $check } verletzen
+    }
+checks-show-all-rows = Alle Zeilen anzeigen
+column-codelist = Codeliste: { $codelist }
+column-nonconformant = Nicht konformer Name: { $issue }
+cdisc-standard-sdtm = SDTM
+cdisc-standard-adam = ADaM
+cdisc-domain = { $standard } { $code }
+cdisc-domain-named = { $standard } { $code }: { $name }
+cdisc-variable-issue = { $variable }: { $issue }
+cdisc-issue-too-long = länger als { $length } Zeichen
+cdisc-issue-first-letter = beginnt nicht mit einem Großbuchstaben
+cdisc-issue-characters = enthält andere Zeichen als Großbuchstaben, Ziffern und Unterstriche
+cdisc-issue-prefix = Präfix { $prefix } ist nicht der Domain-Code, erwartet { $expected }
+define-variable = { $name }: { $label }
+define-origin = Herkunft: { $origin }
+define-derivation = Ableitung: { $derivation }
+
+## Grid
+
+grid-rows =
+    { $rows ->
+        [one] { $rows } Zeile
+       *[other] { $rows } Zeilen
+    }
+grid-rows-shown = { $shown } von { $rows } Zeilen
+grid-selected = { $rows } ausgewählt
+grid-sort-ascending = { $column } aufst.
+grid-sort-descending = { $column } abst.
+grid-sorted-by = sortiert nach { $columns }
+grid-label = Tabellenzeilen
+grid-filter = { $column } filtern
+grid-filter-placeholder = Filter
+grid-pin = { $column } anheften
+grid-pin-column = Spalte anheften
+grid-unpin-column = Spalte lösen
+grid-resize = Breite von { $column } ändern
+
+## Offline store
+
+offline-banner-title = Offline.
+offline-banner = Nur auf diesem Gerät angeheftete Tabellen können geöffnet werden, ihre Daten sind möglicherweise veraltet.
+offline-passphrase = Passphrase des Offline-Speichers
+offline-unlock = Entsperren
+offline-unlocking = Wird entsperrt…
+offline-forget = Passphrase vergessen? Angeheftete Tabellen entfernen
+offline-forgotten = Alle angehefteten Tabellen wurden entfernt, geben Sie eine neue Passphrase ein.
+offline-pinning = Wird angeheftet…
+offline-pinned =
+    { $rows ->
+        [one] { $rows } Zeile von { $table } angeheftet.
+       *[other] { $rows } Zeilen von { $table } angeheftet.
+    }
+offline-entry =
+    { $rows ->
+        [one] { $rows } Zeile, { $age }
+       *[other] { $rows } Zeilen, { $age }
+    }
+offline-unpin = Lösen
+offline-pin = Diese Tabelle anheften
+offline-update-pin = Angeheftete Kopie aktualisieren
+offline-lock = Sperren
+offline-pinned-tables = Tabellen auf diesem Gerät, mit Ihrer Passphrase verschlüsselt
+offline-no-pinned-tables = Keine angehefteten Tabellen.
+
+## Data quality checks
+
+count = { $count }
+clear = Zurücksetzen
+file-unreadable = Die Datei kann nicht gelesen werden: { $error }
+checks-running = Prüfungen laufen…
+checks-none = Für diese Tabelle gibt es keine Prüfungen.
+checks-skipped = übersprungen
+checks-named = Prüfungen: { $name }
+checks-violations =
+    { $violations ->
+        [one] { $violations } Verstoß in { $rows } Zeilen
+       *[other] { $violations } Verstöße in { $rows } Zeilen
+    }
+checks-imported =
+    { $checks ->
+        [one] { $checks } Prüfung importiert.
+       *[other] { $checks } Prüfungen importiert.
+    }
+checks-import = JSON/YAML importieren
+checks-export = JSON exportieren
+check-required = { $columns } erforderlich
+check-unique = ({ $columns }) eindeutig
+check-allowed-values = { $column } in { $values }
+check-range = { $min } ≤ { $column } ≤ { $max }
+check-at-least = { $column } ≥ { $min }
+check-at-most = { $column } ≤ { $max }
+check-numeric = { $column } numerisch
+check-date-order = { $start } ≤ { $end }
+check-reference = { $column } in { $reference }
+check-reference-not-loaded = { $reference } konnte nicht geladen werden
+
+## Code lists
+
+codelists-none = Für diese Tabelle gibt es keine Codelisten.
+codelists-terms =
+    { $terms ->
+        [one] { $codelist } ({ $terms } Begriff)
+       *[other] { $codelist } ({ $terms } Begriffe)
+    }
+codelists-show-raw-codes = Rohcodes anzeigen
+codelists-import = JSON importieren
+codelists-imported =
+    { $codelists ->
+        [one] { $codelists } Codeliste importiert.
+       *[other] { $codelists } Codelisten importiert.
+    }
+codelists-clear-imported = { $codelists } importierte entfernen
+
+## Command palette
+
+palette-label = Befehlspalette
+palette-placeholder = Befehl oder Tabelle eingeben
+palette-results = Ergebnisse
+palette-open-table = Tabelle öffnen…
+palette-open-table-named = { $table } öffnen
+palette-loading-tables = Tabellen werden geladen…
+palette-no-matches = Keine Treffer
+command-show-all = Alle Befehle anzeigen
+command-keybindings = Tastenkürzel konfigurieren
+command-go-home = Zur Startseite
+command-go-data-tables = Zu den Datentabellen
+command-go-search = Zur Suche
+keybindings-title = Tastenkürzel
+keybindings-recording = Neue Tasten drücken, Escape zum Abbrechen
+keybindings-not-bound = Nicht belegt
+keybindings-change = Ändern
+keybindings-remove = Entfernen
+keybindings-reset = Zurücksetzen
+
+## Formatting rules
+
+cancel = Abbrechen
+formatting-rules = Regeln, der Reihe nach angewendet
+formatting-up = nach oben
+formatting-edit = bearbeiten
+formatting-delete = löschen
+formatting-new-rule = Neue Regel
+formatting-edit-rule = Regel bearbeiten
+formatting-add-rule = Regel hinzufügen
+formatting-save-rule = Regel speichern
+formatting-column = Spalte
+formatting-condition = Bedingung
+formatting-comparison = Vergleich
+formatting-threshold = Schwellenwert
+formatting-number = Zahl
+formatting-pattern = regulärer Ausdruck
+formatting-values = Werte
+formatting-values-placeholder = Werte, durch Kommas getrennt
+formatting-low-column = untere Spalte
+formatting-high-column = obere Spalte
+formatting-text = Text
+formatting-text-color = Textfarbe
+formatting-fill = Füllung
+formatting-fill-color = Füllfarbe
+formatting-bold = fett
+formatting-value-format = Werteformat
+formatting-decimals = Dezimalstellen
+condition-kind-always = immer
+condition-kind-threshold = Schwellenwert
+condition-kind-matches = entspricht
+condition-kind-one-of = eines von
+condition-kind-outside-range = außerhalb des Bereichs
+condition-always = immer
+condition-threshold = { $comparison } { $threshold }
+condition-matches = entspricht /{ $pattern }/
+condition-one-of = in { $values }
+condition-outside-range = außerhalb { $low }..{ $high }
+color-red = rot
+color-orange = orange
+color-green = grün
+color-blue = blau
+style-color = Text { $color }
+style-fill = Füllung { $color }
+style-bold = fett
+format-as-is = unverändert
+format-date = DD-MMM-YYYY
+format-kind-decimals = Dezimalstellen
+format-decimals =
+    { $decimals ->
+        [one] { $decimals } Dezimalstelle
+       *[other] { $decimals } Dezimalstellen
+    }
+format-rule = { $column } { $condition } → { $effects }
+
+## Pivot
+
+pivot-fields = Felder
+pivot-rows = Zeilen
+pivot-columns = Spalten
+pivot-values = Werte
+pivot-keyboard-hint = Pfeil nach oben und unten verschieben ein Feld in den Bereich darüber oder darunter, Entf entfernt es.
+pivot-clear = Pivot zurücksetzen
+pivot-computing = Pivot wird berechnet
+pivot-remove-field = { $field } entfernen
+pivot-aggregation = Aggregation von { $field }
+pivot-total = Gesamt
+pivot-subtotal = Zwischensumme
+aggregation-count = Anzahl
+aggregation-distinct-count = Anzahl verschiedener
+aggregation-sum = Summe
+aggregation-mean = Mittelwert
+aggregation-min = Minimum
+aggregation-max = Maximum
+
+## Column statistics
+
+profile-running =
+    { $rows ->
+        [one] { $rows } Zeile wird ausgewertet…
+       *[other] { $rows } Zeilen werden ausgewertet…
+    }
+profile-column = Spalte
+profile-type = Typ
+profile-filled = Gefüllt
+profile-empty = Leer
+profile-distinct = Verschieden
+profile-range = Bereich
+profile-mean = { $range } (Mittelwert { $mean })
+profile-most-frequent = Am häufigsten
+kind-empty = leer
+kind-text = Text
+kind-integer = Ganzzahl
+kind-decimal = Dezimalzahl
+kind-boolean = Wahrheitswert
+kind-mixed = gemischt
+
+## Row cards and details
+
+row-number = Zeile { $row }
+cards-filter = Zeilen filtern
+cards-filter-placeholder = Zeilen filtern
+cards-show-more = Mehr anzeigen (noch { $rows })
+close = Schließen
+row-detail-title = Zeilendetails
+row-detail-copy = { $format } kopieren
+row-detail-copied = { $format } kopiert
+row-detail-copied-row = Zeile als { $format } kopiert
+row-detail-copy-failed = Die Zeile konnte nicht in die Zwischenablage kopiert werden.
+row-detail-derivation = Herleitung: { $derivation }
+
+## Search
+
+search-intro = Tabellen und Spalten eines Schemas finden, die einen Wert enthalten.
+search-select-schema = Wählen Sie ein Schema für die Suche.
+search-placeholder = Wert in { $schema } suchen
+search-label = In { $schema } zu suchender Wert
+search-submit = Suchen
+search-progress = { $searched } von { $tables } Tabellen durchsucht.
+search-cancelled = Nach { $searched } von { $tables } Tabellen abgebrochen.
+search-failed = Konnte nicht geladen werden: { $tables }
+
+## Snapshot compare
+
+cached-load = geladen { $loaded }
+cached-load-version = Version { $version } (geladen { $loaded })
+compare-baseline = Vergleichsbasis
+compare-select-load = zwischengespeicherten Stand wählen
+compare-version = oder Version
+compare-load = Laden
+compare-loading = Vergleichsbasis wird geladen
+compare-primary-key = Primärschlüssel
+compare-sequence-column = Sequenzspalte
+compare-no-column = (keine)
+compare-no-baseline = Kein früherer Stand dieser Tabelle ist zwischengespeichert. Aktualisieren Sie die Tabelle oder laden Sie eine Version zum Vergleich.
+compare-duplicate-keys =
+    { $keys ->
+        [one] { $keys } doppelter Schlüssel, nur seine erste Zeile wird verglichen. Fügen Sie dem Schlüssel eine Sequenzspalte hinzu.
+       *[other] { $keys } doppelte Schlüssel, nur ihre erste Zeile wird verglichen. Fügen Sie dem Schlüssel eine Sequenzspalte hinzu.
+    }
+compare-added = +{ $rows } hinzugefügt
+compare-removed = -{ $rows } entfernt
+compare-modified = ~{ $rows } geändert
+compare-unchanged = { $rows } unverändert
+compare-change = Änderung
+compare-change-added = hinzugefügt
+compare-change-removed = entfernt
+compare-change-modified = geändert
+
+## Table description
+
+table-description = Tabellenbeschreibung
+table-description-missing = Eine Beschreibung der Tabelle { $table } ist derzeit nicht verfügbar
+define-load = define.xml laden
+define-loaded =
+    { $datasets ->
+        [one] { $datasets } Datensatz für { $schema } geladen.
+       *[other] { $datasets } Datensätze für { $schema } geladen.
+    }
+define-dataset = Datensatz
+define-class = Klasse
+define-structure = Struktur
+define-keys = Schlüssel
+define-variables = Variablen
+define-comment = Kommentar
+
+## Views
+
+ok = OK
+save = Speichern
+clipboard-failed = In die Zwischenablage kann nicht geschrieben werden.
+views-name = Name der Ansicht
+views-name-placeholder = Ansicht benennen
+views-mine = Meine Ansichten
+views-shared-team = Mit dem Team geteilt
+views-shared-browser = Geteilt (nur dieser Browser)
+views-applied = { $name } (angewendet)
+views-rename = umbenennen
+views-share = teilen
+views-unshare = nicht mehr teilen
+views-copy-link = Link kopieren
+views-delete = löschen
+views-export = JSON exportieren
+views-import = JSON importieren
+views-imported =
+    { $views ->
+        [one] { $views } Ansicht importiert.
+       *[other] { $views } Ansichten importiert.
+    }
+views-shared = „{ $name }“ geteilt.
+views-link-copied = Link kopiert.
@@ -0,0 +1,480 @@
+## Navigation
+
+nav-label = Main
+nav-toggle = Toggle navigation
+nav-home = Home
+nav-data-tables = Data Tables
+nav-search = Search
+locale-label = Language
+not-found = Not Found
+
+## Pages
+
+home-intro = Navigate to the DataTable page to visualize data tables in real time.
+data-tables-intro = Explore Agenus clinical trial data by schema and table.
+table-selection-label = Table selection
+
+## Schema and table selection
+
+schema-label = Schema
+table-label = Table
+select-schema = select schema
+select-table = select table
+select-loading = loading...
+select-error = error!
+select-contact-support = ERROR: Contact Support.
+schemas-not-loaded = The schemas could not be loaded.
+tables-not-loaded = The tables could not be loaded.
+current-selection = Current Selection
+no-table-selected = No table is selected
+clear-selection = Clear
+
+## Undo history
+
+history-label = Undo history
+history-steps = History
+history-undo = Undo
+history-redo = Redo
+history-undo-step = Undo { $step }
+history-redo-step = Redo { $step }
+history-with-keys = { $title } ({ $keys })
+history-start = Start of the session
+list-separator = {", "}
+change-separator = {"; "}
+history-changes = { $changes }
+history-clear-filters = Clear filters
+history-filter = Filter { $column }
+history-clear-filter = Clear filter on { $column }
+history-change-filters = Change filters on { $columns }
+history-clear-sort = Clear sort
+history-sort = Sort by { $columns }
+history-sort-ascending = { $column } ascending
+history-sort-descending = { $column } descending
+history-hide = Hide { $columns }
+history-show = Show { $columns }
+history-show-and-hide = Show and hide { $columns }
+history-move = Move { $column }
+history-reorder = Reorder columns
+action-select-schema = Select schema { $schema }
+action-select-table = Select table { $table }
+action-clear-selection = Clear selection
+action-open-table = Open { $table }
+action-with-view = view { $view }
+action-with-filter = { $column } contains { $value }
+action-parts = { $parts }
+action-change-grid = Change the grid of { $table }
+action-apply-view = Apply view { $view }
+action-undo =
+    { $steps ->
+        [one] Undo
+       *[other] Undo { $steps } steps
+    }
+action-redo =
+    { $steps ->
+        [one] Redo
+       *[other] Redo { $steps } steps
+    }
+
+## Table loading
+
+file-size = { $size } { $unit }
+progress-bytes-of = { $bytes } of { $total }
+progress =
+    { $rows ->
+        [one] { $bytes }, { $rows } row
+       *[other] { $bytes }, { $rows } rows
+    }
+pinned-at = pinned { $time }
+pinned-just-now = pinned just now
+pinned-minutes-ago =
+    { $count ->
+        [one] pinned { $count } minute ago
+       *[other] pinned { $count } minutes ago
+    }
+pinned-hours-ago =
+    { $count ->
+        [one] pinned { $count } hour ago
+       *[other] pinned { $count } hours ago
+    }
+pinned-days-ago =
+    { $count ->
+        [one] pinned { $count } day ago
+       *[other] pinned { $count } days ago
+    }
+table-loading = Loading { $table }
+table-loaded =
+    { $rows ->
+        [one] Loaded { $table }, { $rows } row
+       *[other] Loaded { $table }, { $rows } rows
+    }
+table-loaded-offline =
+    { $rows ->
+        [one] Loaded the offline copy of { $table }, { $rows } row
+       *[other] Loaded the offline copy of { $table }, { $rows } rows
+    }
+table-not-loaded = { $table } could not be loaded.
+table-offline-locked = { $table } cannot be loaded from the API, unlock the offline store to open the copy pinned to this device.
+table-download = Table download
+table-loading-progress = Loading { $progress }
+table-offline-copy = Offline copy of { $table }, { $age }. Refresh once back online for current data.
+data-link-expired = The link to the table data has expired.
+data-link-refused = The link to the table data was refused, it may have expired.
+data-link-unreachable = The table data could not be downloaded, its link may have expired.
+
+## Table toolbar
+
+table-region = Table { $table }
+table-view-mode = View mode
+table-mode-grid = Grid
+table-mode-pivot = Pivot
+table-mode-compare = Compare
+table-refresh = Refresh
+table-views = Views
+table-view = View: { $view }
+table-checks = Checks
+table-stats = Stats
+table-formatting =
+    { $rules ->
+        [0] Formatting
+       *[other] Formatting ({ $rules })
+    }
+table-codelists =
+    { $columns ->
+        [0] Codelists
+       *[other] Codelists ({ $columns })
+    }
+table-offline = Offline
+table-offline-pinned = Offline (pinned copy)
+table-infer-csv-types = Infer CSV types
+table-export-csv = Export CSV
+command-toggle-pivot = Toggle pivot
+command-refresh-table = Refresh table
+checks-showing-failing =
+    { $rows ->
+        [one] Showing the { $rows } row failing { $check }
+       *[other] Showing the { $rows } rows failing { $check }
+    }
+checks-show-all-rows = Show all rows
+column-codelist = Code list: { $codelist }
+column-nonconformant = Non-conformant name: { $issue }
+cdisc-standard-sdtm = SDTM
+cdisc-standard-adam = ADaM
+cdisc-domain = { $standard } { $code }
+cdisc-domain-named = { $standard } { $code }: { $name }
+cdisc-variable-issue = { $variable }: { $issue }
+cdisc-issue-too-long = longer than { $length } characters
+cdisc-issue-first-letter = does not start with an upper case letter
+cdisc-issue-characters = has characters other than upper case letters, digits and underscores
+cdisc-issue-prefix = prefix { $prefix } is not the domain code, expected { $expected }
+define-variable = { $name }: { $label }
+define-origin = Origin: { $origin }
+define-derivation = Derivation: { $derivation }
+
+## Grid
+
+grid-rows =
+    { $rows ->
+        [one] { $rows } row
+       *[other] { $rows } rows
+    }
+grid-rows-shown = { $shown } of { $rows } rows
+grid-selected = { $rows } selected
+grid-sort-ascending = { $column } asc
+grid-sort-descending = { $column } desc
+grid-sorted-by = sorted by { $columns }
+grid-label = Table rows
+grid-filter = Filter { $column }
+grid-filter-placeholder = filter
+grid-pin = Pin { $column }
+grid-pin-column = Pin column
+grid-unpin-column = Unpin column
+grid-resize = Resize { $column }
+
+## Offline store
+
+offline-banner-title = Offline.
+offline-banner = Only tables pinned to this device can be opened, their data may be out of date.
+offline-passphrase = Offline store passphrase
+offline-unlock = Unlock
+offline-unlocking = Unlocking…
+offline-forget = Forgot the passphrase? Remove pinned tables
+offline-forgotten = Removed every pinned table, enter a new passphrase.
+offline-pinning = Pinning…
+offline-pinned =
+    { $rows ->
+        [one] Pinned { $rows } row of { $table }.
+       *[other] Pinned { $rows } rows of { $table }.
+    }
+offline-entry =
+    { $rows ->
+        [one] { $rows } row, { $age }
+       *[other] { $rows } rows, { $age }
+    }
+offline-unpin = Unpin
+offline-pin = Pin this table
+offline-update-pin = Update the pinned copy
+offline-lock = Lock
+offline-pinned-tables = Tables on this device, encrypted with your passphrase
+offline-no-pinned-tables = No pinned tables.
+
+## Data quality checks
+
+count = { $count }
+clear = Clear
+file-unreadable = Unable to read the file: { $error }
+checks-running = Running checks…
+checks-none = No checks apply to this table.
+checks-skipped = skipped
+checks-named = Checks: { $name }
+checks-violations =
+    { $violations ->
+        [one] { $violations } violation in { $rows } rows
+       *[other] { $violations } violations in { $rows } rows
+    }
+checks-imported =
+    { $checks ->
+        [one] Imported { $checks } check.
+       *[other] Imported { $checks } checks.
+    }
+checks-import = Import JSON/YAML
+checks-export = Export JSON
+check-required = { $columns } required
+check-unique = ({ $columns }) unique
+check-allowed-values = { $column } in { $values }
+check-range = { $min } ≤ { $column } ≤ { $max }
+check-at-least = { $column } ≥ { $min }
+check-at-most = { $column } ≤ { $max }
+check-numeric = { $column } numeric
+check-date-order = { $start } ≤ { $end }
+check-reference = { $column } in { $reference }
+check-reference-not-loaded = { $reference } could not be loaded
+
+## Code lists
+
+codelists-none = No code lists apply to this table.
+codelists-terms =
+    { $terms ->
+        [one] { $codelist } ({ $terms } term)
+       *[other] { $codelist } ({ $terms } terms)
+    }
+codelists-show-raw-codes = Show raw codes
+codelists-import = Import JSON
+codelists-imported =
+    { $codelists ->
+        [one] Imported { $codelists } code list.
+       *[other] Imported { $codelists } code lists.
+    }
+codelists-clear-imported = Clear { $codelists } imported
+
+## Command palette
+
+palette-label = Command palette
+palette-placeholder = Type a command or a table
+palette-results = Results
+palette-open-table = Open table…
+palette-open-table-named = Open { $table }
+palette-loading-tables = Loading tables…
+palette-no-matches = No matches
+command-show-all = Show all commands
+command-keybindings = Configure keyboard shortcuts
+command-go-home = Go to Home
+command-go-data-tables = Go to Data Tables
+command-go-search = Go to Search
+keybindings-title = Keyboard shortcuts
+keybindings-recording = Press the new keys, Escape to cancel
+keybindings-not-bound = Not bound
+keybindings-change = Change
+keybindings-remove = Remove
+keybindings-reset = Reset
+
+## Formatting rules
+
+cancel = Cancel
+formatting-rules = Rules, applied in order
+formatting-up = up
+formatting-edit = edit
+formatting-delete = delete
+formatting-new-rule = New rule
+formatting-edit-rule = Edit rule
+formatting-add-rule = Add rule
+formatting-save-rule = Save rule
+formatting-column = column
+formatting-condition = Condition
+formatting-comparison = Comparison
+formatting-threshold = Threshold
+formatting-number = number
+formatting-pattern = regular expression
+formatting-values = Values
+formatting-values-placeholder = values, comma separated
+formatting-low-column = low column
+formatting-high-column = high column
+formatting-text = text
+formatting-text-color = text color
+formatting-fill = fill
+formatting-fill-color = fill color
+formatting-bold = bold
+formatting-value-format = Value format
+formatting-decimals = Decimals
+condition-kind-always = always
+condition-kind-threshold = threshold
+condition-kind-matches = matches
+condition-kind-one-of = one of
+condition-kind-outside-range = outside range
+condition-always = always
+condition-threshold = { $comparison } { $threshold }
+condition-matches = matches /{ $pattern }/
+condition-one-of = in { $values }
+condition-outside-range = outside { $low }..{ $high }
+color-red = red
+color-orange = orange
+color-green = green
+color-blue = blue
+style-color = { $color } text
+style-fill = { $color } fill
+style-bold = bold
+format-as-is = as is
+format-date = DD-MMM-YYYY
+format-kind-decimals = decimals
+format-decimals =
+    { $decimals ->
+        [one] { $decimals } decimal
+       *[other] { $decimals } decimals
+    }
+format-rule = { $column } { $condition } → { $effects }
+
+## Pivot
+
+pivot-fields = Fields
+pivot-rows = Rows
+pivot-columns = Columns
+pivot-values = Values
+pivot-keyboard-hint = Up and Down arrows move a field to the zone above or below, Delete removes it.
+pivot-clear = Clear pivot
+pivot-computing = Computing the pivot
+pivot-remove-field = Remove { $field }
+pivot-aggregation = Aggregation of { $field }
+pivot-total = Total
+pivot-subtotal = Subtotal
+aggregation-count = count
+aggregation-distinct-count = distinct count
+aggregation-sum = sum
+aggregation-mean = mean
+aggregation-min = min
+aggregation-max = max
+
+## Column statistics
+
+profile-running =
+    { $rows ->
+        [one] Profiling { $rows } row…
+       *[other] Profiling { $rows } rows…
+    }
+profile-column = Column
+profile-type = Type
+profile-filled = Filled
+profile-empty = Empty
+profile-distinct = Distinct
+profile-range = Range
+profile-mean = { $range } (mean { $mean })
+profile-most-frequent = Most frequent
+kind-empty = empty
+kind-text = text
+kind-integer = integer
+kind-decimal = decimal
+kind-boolean = boolean
+kind-mixed = mixed
+
+## Row cards and details
+
+row-number = Row { $row }
+cards-filter = Filter rows
+cards-filter-placeholder = filter rows
+cards-show-more = Show more ({ $rows } left)
+close = Close
+row-detail-title = Row Detail
+row-detail-copy = Copy { $format }
+row-detail-copied = Copied { $format }
+row-detail-copied-row = Copied the row as { $format }
+row-detail-copy-failed = The row could not be copied to the clipboard.
+row-detail-derivation = Derivation: { $derivation }
+
+## Search
+
+search-intro = Find the tables and columns of a schema containing a value.
+search-select-schema = Select a schema to search.
+search-placeholder = value to find in { $schema }
+search-label = Value to find in { $schema }
+search-submit = Search
+search-progress = Searched { $searched } of { $tables } tables.
+search-cancelled = Cancelled after { $searched } of { $tables } tables.
+search-failed = Unable to load: { $tables }
+
+## Snapshot compare
+
+cached-load = loaded { $loaded }
+cached-load-version = version { $version } (loaded { $loaded })
+compare-baseline = Baseline
+compare-select-load = select a cached load
+compare-version = or version
+compare-load = Load
+compare-loading = Loading the baseline
+compare-primary-key = Primary key
+compare-sequence-column = Sequence column
+compare-no-column = (none)
+compare-no-baseline = No earlier load of this table is cached. Refresh the table or load a version to compare against.
+compare-duplicate-keys =
+    { $keys ->
+        [one] { $keys } duplicate key, only its first row is compared. Add a sequence column to the key.
+       *[other] { $keys } duplicate keys, only their first row is compared. Add a sequence column to the key.
+    }
+compare-added = +{ $rows } added
+compare-removed = -{ $rows } removed
+compare-modified = ~{ $rows } modified
+compare-unchanged = { $rows } unchanged
+compare-change = Change
+compare-change-added = added
+compare-change-removed = removed
+compare-change-modified = modified
+
+## Table description
+
+table-description = Table Description
+table-description-missing = Description for table { $table } is currently not available
+define-load = Load define.xml
+define-loaded =
+    { $datasets ->
+        [one] Loaded { $datasets } dataset for { $schema }.
+       *[other] Loaded { $datasets } datasets for { $schema }.
+    }
+define-dataset = Dataset
+define-class = Class
+define-structure = Structure
+define-keys = Keys
+define-variables = Variables
+define-comment = Comment
+
+## Views
+
+ok = OK
+save = Save
+clipboard-failed = Unable to write to the clipboard.
+views-name = View name
+views-name-placeholder = name this view
+views-mine = My views
+views-shared-team = Shared with the team
+views-shared-browser = Shared (this browser only)
+views-applied = { $name } (applied)
+views-rename = rename
+views-share = share
+views-unshare = unshare
+views-copy-link = copy link
+views-delete = delete
+views-export = Export JSON
+views-import = Import JSON
+views-imported =
+    { $views ->
+        [one] Imported { $views } view.
+       *[other] Imported { $views } views.
+    }
+views-shared = Shared "{ $name }".
+views-link-copied = Link copied.
@@ -0,0 +1,484 @@
+## Navigation
+
+nav-label = Principal
+nav-toggle = Afficher ou masquer la navigation
+nav-home = Accueil
+nav-data-tables = Tables de données
+nav-search = Recherche
+locale-label = Langue
+not-found = Page introuvable
+
+## Pages
+
+home-intro = Ouvrez la page Tables de données pour visualiser les tables de données en temps réel.
+data-tables-intro = Explorez les données des essais cliniques d’Agenus par schéma et par table.
+table-selection-label = Sélection de la table
+
+## Schema and table selection
+
+schema-label = Schéma
+table-label = Table
+select-schema = choisir un schéma
+select-table = choisir une table
+select-loading = chargement...
+select-error = erreur !
+select-contact-support = ERREUR : contactez le support.
+schemas-not-loaded = Les schémas n’ont pas pu être chargés.
+tables-not-loaded = Les tables n’ont pas pu être chargées.
+current-selection = Sélection actuelle
+no-table-selected = Aucune table n’est sélectionnée
+clear-selection = Effacer
+
+## Undo history
+
+history-label = Historique des modifications
+history-steps = Historique
+history-undo = Annuler
+history-redo = Rétablir
+history-undo-step = Annuler { $step }
+history-redo-step = Rétablir { $step }
+history-with-keys = { $title } ({ $keys })
+history-start = Début de la session
+list-separator = {", "}
+change-separator = {" ; "}
+history-changes = { $changes }
+history-clear-filters = Effacer les filtres
+history-filter = Filtrer { $column }
+history-clear-filter = Effacer le filtre sur { $column }
+history-change-filters = Modifier les filtres sur { $columns }
+history-clear-sort = Effacer le tri
+history-sort = Trier par { $columns }
+history-sort-ascending = { $column } croissant
+history-sort-descending = { $column } décroissant
+history-hide = Masquer { $columns }
+history-show = Afficher { $columns }
+history-show-and-hide = Afficher et masquer { $columns }
+history-move = Déplacer { $column }
+history-reorder = Réordonner les colonnes
+action-select-schema = Choisir le schéma { $schema }
+action-select-table = Choisir la table { $table }
+action-clear-selection = Effacer la sélection
+action-open-table = Ouvrir { $table }
+action-with-view = vue { $view }
+action-with-filter = { $column } contient { $value }
+action-parts = { $parts }
+action-change-grid = Modifier la grille de { $table }
+action-apply-view = Appliquer la vue { $view }
+action-undo =
+    { $steps ->
+        [one] Annuler
+       *[other] Annuler { $steps } étapes
+    }
+action-redo =
+    { $steps ->
+        [one] Rétablir
+       *[other] Rétablir { $steps } étapes
+    }
+
+## Table loading
+
+file-size = { $size } { $unit }
+progress-bytes-of = { $bytes } sur { $total }
+progress =
+    { $rows ->
+        [one] { $bytes }, { $rows } ligne
+       *[other] { $bytes }, { $rows } lignes
+    }
+pinned-at = épinglée { $time }
+pinned-just-now = épinglée à l’instant
+pinned-minutes-ago =
+    { $count ->
+        [one] épinglée il y a { $count } minute
+       *[other] épinglée il y a { $count } minutes
+    }
+pinned-hours-ago =
+    { $count ->
+        [one] épinglée il y a { $count } heure
+       *[other] épinglée il y a { $count } heures
+    }
+pinned-days-ago =
+    { $count ->
+        [one] épinglée il y a { $count } jour
+       *[other] épinglée il y a { $count } jours
+    }
+table-loading = Chargement de { $table }
+table-loaded =
+    { $rows ->
+        [one] { $table } chargée, { $rows } ligne
+       *[other] { $table } chargée, { $rows } lignes
+    }
+table-loaded-offline =
+    { $rows ->
+        [one] Copie hors ligne de { $table } chargée, { $rows } ligne
+       *[other] Copie hors ligne de { $table } chargée, { $rows } lignes
+    }
+table-not-loaded = { $table } n’a pas pu être chargée.
+table-offline-locked = { $table } ne peut pas être chargée depuis l’API, déverrouillez le stockage hors ligne pour ouvrir la copie épinglée sur cet appareil.
+table-download = Téléchargement de la table
+table-loading-progress = Chargement : { $progress }
+table-offline-copy = Copie hors ligne de { $table }, { $age }. Actualisez une fois en ligne pour obtenir les données à jour.
+data-link-expired = Le lien vers les données de la table a expiré.
+data-link-refused = Le lien vers les données de la table a été refusé, il a peut-être expiré.
+data-link-unreachable = Les données de la table n'ont pas pu être téléchargées, leur lien a peut-être expiré.
+
+## Table toolbar
+
+table-region = Table { $table }
+table-view-mode = Mode d’affichage
+table-mode-grid = Grille
+table-mode-pivot = Tableau croisé
+table-mode-compare = Comparaison
+table-refresh = Actualiser
+table-views = Vues
+table-view = Vue : { $view }
+table-checks = Contrôles
+table-stats = Statistiques
+table-formatting =
+    { $rules ->
+        [0] Mise en forme
+       *[other] Mise en forme ({ $rules })
+    }
+table-codelists =
+    { $columns ->
+        [0] Listes de codes
+       *[other] Listes de codes ({ $columns })
+    }
+table-offline = Hors ligne
+table-offline-pinned = Hors ligne (copie épinglée)
+table-infer-csv-types = Déduire les types CSV
+table-export-csv = Exporter en CSV
+command-toggle-pivot = Afficher ou masquer le tableau croisé
+command-refresh-table = Actualiser la table
+checks-showing-failing =
+    { $rows ->
+        [one] { $rows } ligne ne respecte pas { $check }
+       *[other] { $rows } lignes ne respectent pas { $check }
+    }
+checks-show-all-rows = Afficher toutes les lignes
+column-codelist = Liste de codes : { $codelist }
+column-nonconformant = Nom non conforme : { $issue }
+cdisc-standard-sdtm = SDTM
+cdisc-standard-adam = ADaM
+cdisc-domain = { $standard } { $code }
+cdisc-domain-named = { $standard } { $code } : { $name }
+cdisc-variable-issue = { $variable } : { $issue }
+cdisc-issue-too-long = plus de { $length } caractères
+cdisc-issue-first-letter = ne commence pas par une majuscule
+cdisc-issue-characters = contient des caractères autres que des majuscules, des chiffres et des tirets bas
+cdisc-issue-prefix = le préfixe { $prefix } n'est pas le code du domaine, { $expected } attendu
+define-variable = { $name } : { $label }
+define-origin = Origine : { $origin }
+define-derivation = Dérivation : { $derivation }
+
+## Grid
+
+grid-rows =
+    { $rows ->
+        [one] { $rows } ligne
+       *[other] { $rows } lignes
+    }
+grid-rows-shown = { $shown } lignes sur { $rows }
+grid-selected =
+    { $rows ->
+        [one] { $rows } sélectionnée
+       *[other] { $rows } sélectionnées
+    }
+grid-sort-ascending = { $column } croiss.
+grid-sort-descending = { $column } décroiss.
+grid-sorted-by = triée par { $columns }
+grid-label = Lignes de la table
+grid-filter = Filtrer { $column }
+grid-filter-placeholder = filtre
+grid-pin = Épingler { $column }
+grid-pin-column = Épingler la colonne
+grid-unpin-column = Désépingler la colonne
+grid-resize = Redimensionner { $column }
+
+## Offline store
+
+offline-banner-title = Hors ligne.
+offline-banner = Seules les tables épinglées sur cet appareil peuvent être ouvertes, leurs données ne sont peut-être pas à jour.
+offline-passphrase = Phrase secrète du stockage hors ligne
+offline-unlock = Déverrouiller
+offline-unlocking = Déverrouillage…
+offline-forget = Phrase secrète oubliée ? Supprimer les tables épinglées
+offline-forgotten = Toutes les tables épinglées ont été supprimées, saisissez une nouvelle phrase secrète.
+offline-pinning = Épinglage…
+offline-pinned =
+    { $rows ->
+        [one] { $rows } ligne de { $table } épinglée.
+       *[other] { $rows } lignes de { $table } épinglées.
+    }
+offline-entry =
+    { $rows ->
+        [one] { $rows } ligne, { $age }
+       *[other] { $rows } lignes, { $age }
+    }
+offline-unpin = Désépingler
+offline-pin = Épingler cette table
+offline-update-pin = Mettre à jour la copie épinglée
+offline-lock = Verrouiller
+offline-pinned-tables = Tables sur cet appareil, chiffrées avec votre phrase secrète
+offline-no-pinned-tables = Aucune table épinglée.
+
+## Data quality checks
+
+count = { $count }
+clear = Effacer
+file-unreadable = Impossible de lire le fichier : { $error }
+checks-running = Contrôles en cours…
+checks-none = Aucun contrôle ne s’applique à cette table.
+checks-skipped = ignoré
+checks-named = Contrôles : { $name }
+checks-violations =
+    { $violations ->
+        [one] { $violations } anomalie dans { $rows } lignes
+       *[other] { $violations } anomalies dans { $rows } lignes
+    }
+checks-imported =
+    { $checks ->
+        [one] { $checks } contrôle importé.
+       *[other] { $checks } contrôles importés.
+    }
+checks-import = Importer JSON/YAML
+checks-export = Exporter en JSON
+check-required = { $columns } obligatoire
+check-unique = ({ $columns }) unique
+check-allowed-values = { $column } dans { $values }
+check-range = { $min } ≤ { $column } ≤ { $max }
+check-at-least = { $column } ≥ { $min }
+check-at-most = { $column } ≤ { $max }
+check-numeric = { $column } numérique
+check-date-order = { $start } ≤ { $end }
+check-reference = { $column } dans { $reference }
+check-reference-not-loaded = { $reference } n’a pas pu être chargée
+
+## Code lists
+
+codelists-none = Aucune liste de codes ne s’applique à cette table.
+codelists-terms =
+    { $terms ->
+        [one] { $codelist } ({ $terms } terme)
+       *[other] { $codelist } ({ $terms } termes)
+    }
+codelists-show-raw-codes = Afficher les codes bruts
+codelists-import = Importer JSON
+codelists-imported =
+    { $codelists ->
+        [one] { $codelists } liste de codes importée.
+       *[other] { $codelists } listes de codes importées.
+    }
+codelists-clear-imported = Effacer les { $codelists } importées
+
+## Command palette
+
+palette-label = Palette de commandes
+palette-placeholder = Saisissez une commande ou une table
+palette-results = Résultats
+palette-open-table = Ouvrir une table…
+palette-open-table-named = Ouvrir { $table }
+palette-loading-tables = Chargement des tables…
+palette-no-matches = Aucun résultat
+command-show-all = Afficher toutes les commandes
+command-keybindings = Configurer les raccourcis clavier
+command-go-home = Aller à l’accueil
+command-go-data-tables = Aller aux tables de données
+command-go-search = Aller à la recherche
+keybindings-title = Raccourcis clavier
+keybindings-recording = Appuyez sur les nouvelles touches, Échap pour annuler
+keybindings-not-bound = Non attribué
+keybindings-change = Modifier
+keybindings-remove = Supprimer
+keybindings-reset = Réinitialiser
+
+## Formatting rules
+
+cancel = Annuler
+formatting-rules = Règles, appliquées dans l’ordre
+formatting-up = monter
+formatting-edit = modifier
+formatting-delete = supprimer
+formatting-new-rule = Nouvelle règle
+formatting-edit-rule = Modifier la règle
+formatting-add-rule = Ajouter la règle
+formatting-save-rule = Enregistrer la règle
+formatting-column = colonne
+formatting-condition = Condition
+formatting-comparison = Comparaison
+formatting-threshold = Seuil
+formatting-number = nombre
+formatting-pattern = expression régulière
+formatting-values = Valeurs
+formatting-values-placeholder = valeurs, séparées par des virgules
+formatting-low-column = colonne basse
+formatting-high-column = colonne haute
+formatting-text = texte
+formatting-text-color = couleur du texte
+formatting-fill = fond
+formatting-fill-color = couleur de fond
+formatting-bold = gras
+formatting-value-format = Format des valeurs
+formatting-decimals = Décimales
+condition-kind-always = toujours
+condition-kind-threshold = seuil
+condition-kind-matches = correspond à
+condition-kind-one-of = parmi
+condition-kind-outside-range = hors intervalle
+condition-always = toujours
+condition-threshold = { $comparison } { $threshold }
+condition-matches = correspond à /{ $pattern }/
+condition-one-of = dans { $values }
+condition-outside-range = hors de { $low }..{ $high }
+color-red = rouge
+color-orange = orange
+color-green = vert
+color-blue = bleu
+style-color = texte { $color }
+style-fill = fond { $color }
+style-bold = gras
+format-as-is = tel quel
+format-date = DD-MMM-YYYY
+format-kind-decimals = décimales
+format-decimals =
+    { $decimals ->
+        [one] { $decimals } décimale
+       *[other] { $decimals } décimales
+    }
+format-rule = { $column } { $condition } → { $effects }
+
+## Pivot
+
+pivot-fields = Champs
+pivot-rows = Lignes
+pivot-columns = Colonnes
+pivot-values = Valeurs
+pivot-keyboard-hint = Les flèches haut et bas déplacent un champ vers la zone au-dessus ou en dessous, Suppr le retire.
+pivot-clear = Effacer le tableau croisé
+pivot-computing = Calcul du tableau croisé
+pivot-remove-field = Retirer { $field }
+pivot-aggregation = Agrégation de { $field }
+pivot-total = Total
+pivot-subtotal = Sous-total
+aggregation-count = nombre
+aggregation-distinct-count = nombre distinct
+aggregation-sum = somme
+aggregation-mean = moyenne
+aggregation-min = min
+aggregation-max = max
+
+## Column statistics
+
+profile-running =
+    { $rows ->
+        [one] Analyse de { $rows } ligne…
+       *[other] Analyse de { $rows } lignes…
+    }
+profile-column = Colonne
+profile-type = Type
+profile-filled = Renseignées
+profile-empty = Vides
+profile-distinct = Distinctes
+profile-range = Intervalle
+profile-mean = { $range } (moyenne { $mean })
+profile-most-frequent = Les plus fréquentes
+kind-empty = vide
+kind-text = texte
+kind-integer = entier
+kind-decimal = décimal
+kind-boolean = booléen
+kind-mixed = mixte
+
+## Row cards and details
+
+row-number = Ligne { $row }
+cards-filter = Filtrer les lignes
+cards-filter-placeholder = filtrer les lignes
+cards-show-more = Afficher plus ({ $rows } restantes)
+close = Fermer
+row-detail-title = Détail de la ligne
+row-detail-copy = Copier en { $format }
+row-detail-copied = Copié en { $format }
+row-detail-copied-row = Ligne copiée en { $format }
+row-detail-copy-failed = La ligne n’a pas pu être copiée dans le presse-papiers.
+row-detail-derivation = Dérivation : { $derivation }
+
+## Search
+
+search-intro = Trouvez les tables et les colonnes d’un schéma qui contiennent une valeur.
+search-select-schema = Choisissez un schéma à parcourir.
+search-placeholder = valeur à trouver dans { $schema }
+search-label = Valeur à trouver dans { $schema }
+search-submit = Rechercher
+search-progress = { $searched } tables parcourues sur { $tables }.
+search-cancelled = Annulée après { $searched } tables sur { $tables }.
+search-failed = Impossible de charger : { $tables }
+
+## Snapshot compare
+
+cached-load = chargée { $loaded }
+cached-load-version = version { $version } (chargée { $loaded })
+compare-baseline = Référence
+compare-select-load = choisir un chargement en cache
+compare-version = ou version
+compare-load = Charger
+compare-loading = Chargement de la référence
+compare-primary-key = Clé primaire
+compare-sequence-column = Colonne de séquence
+compare-no-column = (aucune)
+compare-no-baseline = Aucun chargement antérieur de cette table n’est en cache. Actualisez la table ou chargez une version à comparer.
+compare-duplicate-keys =
+    { $keys ->
+        [one] { $keys } clé en double, seule sa première ligne est comparée. Ajoutez une colonne de séquence à la clé.
+       *[other] { $keys } clés en double, seule leur première ligne est comparée. Ajoutez une colonne de séquence à la clé.
+    }
+compare-added = +{ $rows } ajoutées
+compare-removed = -{ $rows } supprimées
+compare-modified = ~{ $rows } modifiées
+compare-unchanged = { $rows } inchangées
+compare-change = Modification
+compare-change-added = ajoutée
+compare-change-removed = supprimée
+compare-change-modified = modifiée
+
+## Table description
+
+table-description = Description de la table
+table-description-missing = La description de la table { $table } n’est pas disponible pour le moment
+define-load = Charger define.xml
+define-loaded =
+    { $datasets ->
+        [one] { $datasets } jeu de données chargé pour { $schema }.
+       *[other] { $datasets } jeux de données chargés pour { $schema }.
+    }
+define-dataset = Jeu de données
+define-class = Classe
+define-structure = Structure
+define-keys = Clés
+define-variables = Variables
+define-comment = Commentaire
+
+## Views
+
+ok = OK
+save = Enregistrer
+clipboard-failed = Impossible d’écrire dans le presse-papiers.
+views-name = Nom de la vue
+views-name-placeholder = nommer cette vue
+views-mine = Mes vues
+views-shared-team = Partagées avec l’équipe
+views-shared-browser = Partagées (ce navigateur uniquement)
+views-applied = { $name } (appliquée)
+views-rename = renommer
+views-share = partager
+views-unshare = ne plus partager
+views-copy-link = copier le lien
+views-delete = supprimer
+views-export = Exporter en JSON
+views-import = Importer JSON
+views-imported =
+    { $views ->
+        [one] { $views } vue importée.
+       *[other] { $views } vues importées.
+    }
+views-shared = « { $name } » partagée.
+views-link-copied = Lien copié.
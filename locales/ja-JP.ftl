@@ -0,0 +1,404 @@
+## Navigation
+
+nav-label = メイン
+nav-toggle = ナビゲーションの切り替え
+nav-home = ホーム
+nav-data-tables = データテーブル
+nav-search = 検索
+locale-label = 言語
+not-found = ページが見つかりません
+
+## Pages
+
+home-intro = データテーブルのページを開くと、データテーブルをリアルタイムで表示できます。
+data-tables-intro = Agenusの臨床試験データをスキーマとテーブルごとに閲覧します。
+table-selection-label = テーブルの選択
+
+## Schema and table selection
+
+schema-label = スキーマ
+table-label = テーブル
+select-schema = スキーマを選択
+select-table = テーブルを選択
+select-loading = 読み込み中...
+select-error = エラー!
+select-contact-support = エラー: サポートにお問い合わせください。
+schemas-not-loaded = スキーマを読み込めませんでした。
+tables-not-loaded = テーブルを読み込めませんでした。
+current-selection = 現在の選択
+no-table-selected = テーブルが選択されていません
+clear-selection = クリア
+
+## Undo history
+
+history-label = 操作履歴
+history-steps = 履歴
+history-undo = 元に戻す
+history-redo = やり直す
+history-undo-step = { $step }を元に戻す
+history-redo-step = { $step }をやり直す
+history-with-keys = { $title } ({ $keys })
+history-start = セッションの開始
+list-separator = 、
+change-separator = 。
+history-changes = { $changes }
+history-clear-filters = フィルターをクリア
+history-filter = { $column }をフィルター
+history-clear-filter = { $column }のフィルターをクリア
+history-change-filters = { $columns }のフィルターを変更
+history-clear-sort = 並べ替えをクリア
+history-sort = { $columns }で並べ替え
+history-sort-ascending = { $column }の昇順
+history-sort-descending = { $column }の降順
+history-hide = { $columns }を非表示
+history-show = { $columns }を表示
+history-show-and-hide = { $columns }の表示を切り替え
+history-move = { $column }を移動
+history-reorder = 列を並べ替え
+action-select-schema = スキーマ{ $schema }を選択
+action-select-table = テーブル{ $table }を選択
+action-clear-selection = 選択をクリア
+action-open-table = { $table }を開く
+action-with-view = ビュー{ $view }
+action-with-filter = { $column }に{ $value }を含む
+action-parts = { $parts }
+action-change-grid = { $table }のグリッドを変更
+action-apply-view = ビュー{ $view }を適用
+action-undo =
+    { $steps ->
+        [1] 元に戻す
+       *[other] { $steps }ステップ元に戻す
+    }
+action-redo =
+    { $steps ->
+        [1] やり直す
+       *[other] { $steps }ステップやり直す
+    }
+
+## Table loading
+
+file-size = { $size } { $unit }
+progress-bytes-of = { $bytes } / { $total }
+progress = { $bytes }、{ $rows }行
+pinned-at = { $time }に保存
+pinned-just-now = たった今保存
+pinned-minutes-ago = { $count }分前に保存
+pinned-hours-ago = { $count }時間前に保存
+pinned-days-ago = { $count }日前に保存
+table-loading = { $table }を読み込み中
+table-loaded = { $table }を読み込みました、{ $rows }行
+table-loaded-offline = { $table }のオフラインコピーを読み込みました、{ $rows }行
+table-not-loaded = { $table }を読み込めませんでした。
+table-offline-locked = { $table }はAPIから読み込めません。このデバイスに保存したコピーを開くには、オフラインストアのロックを解除してください。
+table-download = テーブルのダウンロード
+table-loading-progress = 読み込み中: { $progress }
+table-offline-copy = { $table }のオフラインコピー、{ $age }。オンラインに戻ったら更新して最新のデータを取得してください。
+data-link-expired = テーブルデータへのリンクの有効期限が切れています。
+data-link-refused = テーブルデータへのリンクが拒否されました。有効期限が切れている可能性があります。
+data-link-unreachable = テーブルデータをダウンロードできませんでした。リンクの有効期限が切れている可能性があります。
+
+## Table toolbar
+
+table-region = テーブル{ $table }
+table-view-mode = 表示モード
+table-mode-grid = グリッド
+table-mode-pivot = ピボット
+table-mode-compare = 比較
+table-refresh = 更新
+table-views = ビュー
+table-view = ビュー: { $view }
+table-checks = チェック
+table-stats = 統計
+table-formatting =
+    { $rules ->
+        [0] 書式
+       *[other] 書式 ({ $rules })
+    }
+table-codelists =
+    { $columns ->
+        [0] コードリスト
+       *[other] コードリスト ({ $columns })
+    }
+table-offline = オフライン
+table-offline-pinned = オフライン (保存済みのコピー)
+table-infer-csv-types = CSVの型を推定
+table-export-csv = CSVをエクスポート
+command-toggle-pivot = ピボットの切り替え
+command-refresh-table = テーブルを更新
+checks-showing-failing = { $check }に違反する{ $rows }行を表示中
+checks-show-all-rows = すべての行を表示
+column-codelist = コードリスト: { $codelist }
+column-nonconformant = 規約に合わない名前: { $issue }
+cdisc-standard-sdtm = SDTM
+cdisc-standard-adam = ADaM
+cdisc-domain = { $standard } { $code }
+cdisc-domain-named = { $standard } { $code }: { $name }
+cdisc-variable-issue = { $variable }: { $issue }
+cdisc-issue-too-long = { $length }文字を超えています
+cdisc-issue-first-letter = 英大文字で始まっていません
+cdisc-issue-characters = 英大文字、数字、アンダースコア以外の文字を含んでいます
+cdisc-issue-prefix = 接頭辞{ $prefix }はドメインコードではありません。正しくは{ $expected }です
+define-variable = { $name }: { $label }
+define-origin = 由来: { $origin }
+define-derivation = 導出: { $derivation }
+
+## Grid
+
+grid-rows = { $rows }行
+grid-rows-shown = { $rows }行中{ $shown }行
+grid-selected = { $rows }行を選択
+grid-sort-ascending = { $column } 昇順
+grid-sort-descending = { $column } 降順
+grid-sorted-by = { $columns }で並べ替え済み
+grid-label = テーブルの行
+grid-filter = { $column }をフィルター
+grid-filter-placeholder = フィルター
+grid-pin = { $column }を固定
+grid-pin-column = 列を固定
+grid-unpin-column = 列の固定を解除
+grid-resize = { $column }の幅を変更
+
+## Offline store
+
+offline-banner-title = オフラインです。
+offline-banner = このデバイスに保存したテーブルのみ開けます。データが古い可能性があります。
+offline-passphrase = オフラインストアのパスフレーズ
+offline-unlock = ロック解除
+offline-unlocking = ロックを解除中…
+offline-forget = パスフレーズを忘れた場合は、保存したテーブルを削除
+offline-forgotten = 保存したテーブルをすべて削除しました。新しいパスフレーズを入力してください。
+offline-pinning = 保存中…
+offline-pinned = { $table }の{ $rows }行を保存しました。
+offline-entry = { $rows }行、{ $age }
+offline-unpin = 保存を解除
+offline-pin = このテーブルを保存
+offline-update-pin = 保存したコピーを更新
+offline-lock = ロック
+offline-pinned-tables = このデバイスのテーブル (パスフレーズで暗号化)
+offline-no-pinned-tables = 保存したテーブルはありません。
+
+## Data quality checks
+
+count = { $count }
+clear = クリア
+file-unreadable = ファイルを読み込めません: { $error }
+checks-running = チェックを実行中…
+checks-none = このテーブルに適用されるチェックはありません。
+checks-skipped = スキップ
+checks-named = チェック: { $name }
+checks-violations = { $rows }行に{ $violations }件の違反
+checks-imported = { $checks }件のチェックをインポートしました。
+checks-import = JSON/YAMLをインポート
+checks-export = JSONをエクスポート
+check-required = { $columns }は必須
+check-unique = ({ $columns })は一意
+check-allowed-values = { $column }は{ $values }のいずれか
+check-range = { $min } ≤ { $column } ≤ { $max }
+check-at-least = { $column } ≥ { $min }
+check-at-most = { $column } ≤ { $max }
+check-numeric = { $column }は数値
+check-date-order = { $start } ≤ { $end }
+check-reference = { $column }は{ $reference }に存在
+check-reference-not-loaded = { $reference }を読み込めませんでした
+
+## Code lists
+
+codelists-none = このテーブルに適用されるコードリストはありません。
+codelists-terms = { $codelist } ({ $terms }語)
+codelists-show-raw-codes = 元のコードを表示
+codelists-import = JSONをインポート
+codelists-imported = { $codelists }件のコードリストをインポートしました。
+codelists-clear-imported = インポートした{ $codelists }件をクリア
+
+## Command palette
+
+palette-label = コマンドパレット
+palette-placeholder = コマンドまたはテーブルを入力
+palette-results = 結果
+palette-open-table = テーブルを開く…
+palette-open-table-named = { $table }を開く
+palette-loading-tables = テーブルを読み込み中…
+palette-no-matches = 一致するものはありません
+command-show-all = すべてのコマンドを表示
+command-keybindings = キーボードショートカットを設定
+command-go-home = ホームへ移動
+command-go-data-tables = データテーブルへ移動
+command-go-search = 検索へ移動
+keybindings-title = キーボードショートカット
+keybindings-recording = 新しいキーを押してください (Escapeでキャンセル)
+keybindings-not-bound = 未割り当て
+keybindings-change = 変更
+keybindings-remove = 削除
+keybindings-reset = リセット
+
+## Formatting rules
+
+cancel = キャンセル
+formatting-rules = ルール (上から順に適用)
+formatting-up = 上へ
+formatting-edit = 編集
+formatting-delete = 削除
+formatting-new-rule = 新しいルール
+formatting-edit-rule = ルールを編集
+formatting-add-rule = ルールを追加
+formatting-save-rule = ルールを保存
+formatting-column = 列
+formatting-condition = 条件
+formatting-comparison = 比較
+formatting-threshold = しきい値
+formatting-number = 数値
+formatting-pattern = 正規表現
+formatting-values = 値
+formatting-values-placeholder = 値 (カンマ区切り)
+formatting-low-column = 下限の列
+formatting-high-column = 上限の列
+formatting-text = 文字
+formatting-text-color = 文字色
+formatting-fill = 背景
+formatting-fill-color = 背景色
+formatting-bold = 太字
+formatting-value-format = 値の書式
+formatting-decimals = 小数点以下の桁数
+condition-kind-always = 常に
+condition-kind-threshold = しきい値
+condition-kind-matches = パターンに一致
+condition-kind-one-of = いずれかの値
+condition-kind-outside-range = 範囲外
+condition-always = 常に
+condition-threshold = { $comparison } { $threshold }
+condition-matches = /{ $pattern }/に一致
+condition-one-of = { $values }のいずれか
+condition-outside-range = { $low }..{ $high }の範囲外
+color-red = 赤
+color-orange = オレンジ
+color-green = 緑
+color-blue = 青
+style-color = 文字色{ $color }
+style-fill = 背景色{ $color }
+style-bold = 太字
+format-as-is = そのまま
+format-date = DD-MMM-YYYY
+format-kind-decimals = 小数
+format-decimals = 小数点以下{ $decimals }桁
+format-rule = { $column } { $condition } → { $effects }
+
+## Pivot
+
+pivot-fields = フィールド
+pivot-rows = 行
+pivot-columns = 列
+pivot-values = 値
+pivot-keyboard-hint = 上下の矢印キーでフィールドを上または下の領域へ移動し、Deleteキーで削除します。
+pivot-clear = ピボットをクリア
+pivot-computing = ピボットを計算中
+pivot-remove-field = { $field }を削除
+pivot-aggregation = { $field }の集計方法
+pivot-total = 合計
+pivot-subtotal = 小計
+aggregation-count = 個数
+aggregation-distinct-count = 一意の個数
+aggregation-sum = 合計
+aggregation-mean = 平均
+aggregation-min = 最小
+aggregation-max = 最大
+
+## Column statistics
+
+profile-running = { $rows }行を分析中…
+profile-column = 列
+profile-type = 型
+profile-filled = 値あり
+profile-empty = 空
+profile-distinct = 一意
+profile-range = 範囲
+profile-mean = { $range } (平均 { $mean })
+profile-most-frequent = 最頻値
+kind-empty = 空
+kind-text = 文字列
+kind-integer = 整数
+kind-decimal = 小数
+kind-boolean = 真偽値
+kind-mixed = 混在
+
+## Row cards and details
+
+row-number = { $row }行目
+cards-filter = 行をフィルター
+cards-filter-placeholder = 行をフィルター
+cards-show-more = さらに表示 (残り{ $rows }行)
+close = 閉じる
+row-detail-title = 行の詳細
+row-detail-copy = { $format }でコピー
+row-detail-copied = { $format }でコピーしました
+row-detail-copied-row = 行を{ $format }でコピーしました
+row-detail-copy-failed = 行をクリップボードにコピーできませんでした。
+row-detail-derivation = 導出: { $derivation }
+
+## Search
+
+search-intro = 値を含むスキーマのテーブルと列を検索します。
+search-select-schema = 検索するスキーマを選択してください。
+search-placeholder = { $schema }で検索する値
+search-label = { $schema }で検索する値
+search-submit = 検索
+search-progress = { $tables }テーブル中{ $searched }テーブルを検索しました。
+search-cancelled = { $tables }テーブル中{ $searched }テーブルでキャンセルしました。
+search-failed = 読み込めませんでした: { $tables }
+
+## Snapshot compare
+
+cached-load = { $loaded }に読み込み
+cached-load-version = バージョン{ $version } ({ $loaded }に読み込み)
+compare-baseline = 比較元
+compare-select-load = キャッシュされた読み込みを選択
+compare-version = またはバージョン
+compare-load = 読み込む
+compare-loading = 比較元を読み込み中
+compare-primary-key = 主キー
+compare-sequence-column = 連番の列
+compare-no-column = (なし)
+compare-no-baseline = このテーブルの以前の読み込みはキャッシュされていません。テーブルを更新するか、比較するバージョンを読み込んでください。
+compare-duplicate-keys = 重複したキーが{ $keys }件あり、最初の行のみ比較しています。キーに連番の列を追加してください。
+compare-added = +{ $rows }行追加
+compare-removed = -{ $rows }行削除
+compare-modified = ~{ $rows }行変更
+compare-unchanged = { $rows }行変更なし
+compare-change = 変更
+compare-change-added = 追加
+compare-change-removed = 削除
+compare-change-modified = 変更
+
+## Table description
+
+table-description = テーブルの説明
+table-description-missing = テーブル{ $table }の説明は現在ありません
+define-load = define.xmlを読み込む
+define-loaded = { $schema }のデータセットを{ $datasets }件読み込みました。
+define-dataset = データセット
+define-class = クラス
+define-structure = 構造
+define-keys = キー
+define-variables = 変数
+define-comment = コメント
+
+## Views
+
+ok = OK
+save = 保存
+clipboard-failed = クリップボードに書き込めません。
+views-name = ビューの名前
+views-name-placeholder = このビューの名前
+views-mine = マイビュー
+views-shared-team = チームと共有
+views-shared-browser = 共有 (このブラウザーのみ)
+views-applied = { $name } (適用中)
+views-rename = 名前を変更
+views-share = 共有
+views-unshare = 共有を解除
+views-copy-link = リンクをコピー
+views-delete = 削除
+views-export = JSONをエクスポート
+views-import = JSONをインポート
+views-imported = { $views }件のビューをインポートしました。
+views-shared = 「{ $name }」を共有しました。
+views-link-copied = リンクをコピーしました。
@@ -0,0 +1,81 @@
+#[cfg(test)]
+pub mod audit;
+#[cfg(test)]
+mod test_a11y;
+
+use std::rc::Rc;
+
+use yew::prelude::Reducible;
+
+/// How a screen reader reads an announcement: after what it is reading, or interrupting it.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Politeness {
+    Polite,
+    Assertive,
+}
+
+/// A message for screen readers, read from the live regions of `LiveRegion`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Announcement {
+    pub text: String,
+    pub politeness: Politeness,
+}
+
+impl Announcement {
+    /// Progress, such as a table starting or finishing to load.
+    pub fn status(text: impl Into<String>) -> Announcement {
+        Announcement {
+            text: text.into(),
+            politeness: Politeness::Polite,
+        }
+    }
+
+    /// Errors, read straight away.
+    pub fn alert(text: impl Into<String>) -> Announcement {
+        Announcement {
+            text: text.into(),
+            politeness: Politeness::Assertive,
+        }
+    }
+}
+
+/// The text of the polite and the assertive live region.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct LiveRegions {
+    pub status: String,
+    pub alert: String,
+}
+
+impl LiveRegions {
+    /// The regions once `announcement` is made. Screen readers only read a region whose text
+    /// changed, so an announcement repeating the current text gets a trailing no-break space
+    /// toggled.
+    pub fn announce(&self, announcement: &Announcement) -> LiveRegions {
+        let mut regions = self.clone();
+        let region = match announcement.politeness {
+            Politeness::Polite => &mut regions.status,
+            Politeness::Assertive => &mut regions.alert,
+        };
+        *region = if region.trim_end_matches('\u{a0}') == announcement.text
+            && !region.ends_with('\u{a0}')
+        {
+            format!("{}\u{a0}", announcement.text)
+        } else {
+            announcement.text.clone()
+        };
+        regions
+    }
+}
+
+impl Reducible for LiveRegions {
+    type Action = Announcement;
+
+    fn reduce(self: Rc<Self>, action: Announcement) -> Rc<Self> {
+        Rc::new(self.announce(&action))
+    }
+}
+
+/// Keys activating a focused control, as they do a button.
+pub fn is_activation_key(key: &str) -> bool {
+    matches!(key, "Enter" | " " | "Spacebar")
+}
@@ -0,0 +1,265 @@
+//! Accessibility assertions for the tests: components are rendered to HTML with yew's server
+//! renderer and the markup checked against the rules every widget of the app follows.
+
+use std::collections::{HashMap, HashSet};
+
+use yew::prelude::*;
+use yew::LocalServerRenderer;
+
+const VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+/// Elements a keyboard reaches without a `tabindex`.
+const FOCUSABLE_ELEMENTS: [&str; 5] = ["a", "button", "input", "select", "textarea"];
+/// Roles of widgets operated with the keyboard, which must be focusable.
+const INTERACTIVE_ROLES: [&str; 7] = [
+    "button", "link", "checkbox", "switch", "tab", "menuitem", "slider",
+];
+/// Roles a screen reader announces by name, which must have one.
+const NAMED_ROLES: [&str; 7] = [
+    "dialog",
+    "alertdialog",
+    "progressbar",
+    "region",
+    "grid",
+    "listbox",
+    "slider",
+];
+
+/// An element of the markup with the text inside it.
+#[derive(Debug)]
+pub struct Element {
+    pub name: String,
+    pub attributes: HashMap<String, String>,
+    pub text: String,
+    /// Indices of the enclosing elements, innermost last.
+    pub ancestors: Vec<usize>,
+}
+
+impl Element {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(String::as_str)
+    }
+
+    fn has_label_attribute(&self, ids: &HashSet<&str>) -> bool {
+        self.attribute("aria-label")
+            .is_some_and(|label| !label.trim().is_empty())
+            || self
+                .attribute("aria-labelledby")
+                .is_some_and(|labelled_by| {
+                    labelled_by.split_whitespace().all(|id| ids.contains(id))
+                })
+    }
+}
+
+/// The elements of `markup` in document order.
+pub fn elements(markup: &str) -> Vec<Element> {
+    let mut elements = Vec::<Element>::new();
+    let mut open = Vec::<usize>::new();
+    let mut rest = markup;
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.split_once("-->").map_or("", |(_, after)| after);
+        } else if let Some(closing) = rest.strip_prefix("</") {
+            let (name, after) = closing.split_once('>').unwrap_or((closing, ""));
+            let name = name.trim().to_lowercase();
+            if let Some(position) = open.iter().rposition(|index| elements[*index].name == name) {
+                open.truncate(position);
+            }
+            rest = after;
+        } else if let Some(tag) = rest.strip_prefix('<') {
+            let (element, self_closing, after) = parse_tag(tag, open.clone());
+            let void = self_closing || VOID_ELEMENTS.contains(&element.name.as_str());
+            elements.push(element);
+            if !void {
+                open.push(elements.len() - 1);
+            }
+            rest = after;
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = rest[..end].trim();
+            if !text.is_empty() {
+                for index in &open {
+                    elements[*index].text.push_str(text);
+                    elements[*index].text.push(' ');
+                }
+            }
+            rest = &rest[end..];
+        }
+    }
+    elements
+}
+
+/// The element whose start tag `tag` begins, past its `<`, if it closes itself and the markup
+/// after the tag.
+fn parse_tag(tag: &str, ancestors: Vec<usize>) -> (Element, bool, &str) {
+    let name_end = tag
+        .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .unwrap_or(tag.len());
+    let mut element = Element {
+        name: tag[..name_end].to_lowercase(),
+        attributes: HashMap::new(),
+        text: String::new(),
+        ancestors,
+    };
+    let mut rest = &tag[name_end..];
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix("/>") {
+            return (element, true, after);
+        }
+        if let Some(after) = rest.strip_prefix('>') {
+            return (element, false, after);
+        }
+        if rest.is_empty() {
+            return (element, false, rest);
+        }
+        let name_end = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')
+            .unwrap_or(rest.len())
+            .max(1);
+        let name = rest[..name_end].to_lowercase();
+        rest = rest[name_end..].trim_start();
+        let value = match rest.strip_prefix('=') {
+            Some(after) => {
+                let after = after.trim_start();
+                match after.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let quoted = &after[1..];
+                        let end = quoted.find(quote).unwrap_or(quoted.len());
+                        rest = quoted.get(end + 1..).unwrap_or("");
+                        quoted[..end].to_owned()
+                    }
+                    _ => {
+                        let end = after
+                            .find(|c: char| c.is_whitespace() || c == '>')
+                            .unwrap_or(after.len());
+                        rest = &after[end..];
+                        after[..end].to_owned()
+                    }
+                }
+            }
+            None => String::new(),
+        };
+        element.attributes.insert(name, value);
+    }
+}
+
+/// What keeps `markup` from being operable and understandable with a keyboard and a screen
+/// reader, one line per problem.
+pub fn audit(markup: &str) -> Vec<String> {
+    let elements = elements(markup);
+    let mut issues = Vec::new();
+    let mut ids = HashSet::<&str>::new();
+    for element in &elements {
+        if let Some(id) = element.attribute("id") {
+            if !ids.insert(id) {
+                issues.push(format!("the id \"{id}\" is used more than once"));
+            }
+        }
+    }
+    let labelled: HashSet<&str> = elements
+        .iter()
+        .filter(|element| element.name == "label")
+        .filter_map(|element| element.attribute("for"))
+        .collect();
+
+    for element in &elements {
+        let describe = || match element.attribute("id") {
+            Some(id) => format!("<{} id=\"{id}\">", element.name),
+            None => format!("<{}>", element.name),
+        };
+        let role = element.attribute("role").unwrap_or_default();
+        let is_form_control = match element.name.as_str() {
+            "select" | "textarea" => true,
+            "input" => !matches!(
+                element.attribute("type").unwrap_or("text"),
+                "hidden" | "submit" | "button" | "reset"
+            ),
+            _ => false,
+        };
+        if is_form_control
+            && !element.has_label_attribute(&ids)
+            && !element
+                .attribute("id")
+                .is_some_and(|id| labelled.contains(id))
+            && !element
+                .ancestors
+                .iter()
+                .any(|index| elements[*index].name == "label")
+        {
+            issues.push(format!("{} has no label", describe()));
+        }
+        if (element.name == "button" || role == "button")
+            && element.text.trim().is_empty()
+            && !element.has_label_attribute(&ids)
+            && element.attribute("title").is_none_or(str::is_empty)
+        {
+            issues.push(format!("{} has no accessible name", describe()));
+        }
+        if INTERACTIVE_ROLES.contains(&role)
+            && !FOCUSABLE_ELEMENTS.contains(&element.name.as_str())
+            && element.attribute("tabindex").is_none()
+        {
+            issues.push(format!(
+                "{} with the role {role} cannot be reached with the keyboard",
+                describe()
+            ));
+        }
+        let focusable_separator = role == "separator" && element.attribute("tabindex").is_some();
+        if (NAMED_ROLES.contains(&role) || focusable_separator)
+            && !element.has_label_attribute(&ids)
+        {
+            issues.push(format!(
+                "{} with the role {role} has no accessible name",
+                describe()
+            ));
+        }
+        if element.name == "img" && element.attribute("alt").is_none() {
+            issues.push(format!("{} has no alt text", describe()));
+        }
+        if element.name == "a"
+            && element
+                .ancestors
+                .iter()
+                .any(|index| elements[*index].name == "a")
+        {
+            issues.push(format!("{} is nested in another link", describe()));
+        }
+    }
+    issues
+}
+
+/// Panics listing the problems `audit` finds in `markup`.
+pub fn assert_accessible(markup: &str) {
+    let issues = audit(markup);
+    assert!(
+        issues.is_empty(),
+        "accessibility issues:\n  {}\nin the markup:\n{markup}",
+        issues.join("\n  ")
+    );
+}
+
+#[derive(Properties, PartialEq)]
+struct MarkupProp {
+    html: Html,
+}
+
+#[function_component]
+fn Markup(prop: &MarkupProp) -> Html {
+    prop.html.clone()
+}
+
+/// The HTML a server render of `html` produces.
+pub async fn render(html: Html) -> String {
+    render_component::<Markup>(MarkupProp { html }).await
+}
+
+/// The HTML a server render of the component `C` with `props` produces.
+pub async fn render_component<C: BaseComponent>(props: C::Properties) -> String {
+    LocalServerRenderer::<C>::with_props(props)
+        .hydratable(false)
+        .render()
+        .await
+}
@@ -0,0 +1,128 @@
+use super::audit::{assert_accessible, audit, elements, render, render_component};
+use super::{is_activation_key, Announcement, LiveRegions};
+use crate::components::live_region::{LiveRegion, LiveRegionProp};
+use wasm_bindgen_test::wasm_bindgen_test;
+use yew::prelude::{html, Children};
+
+#[wasm_bindgen_test]
+fn test_repeated_announcements_change_the_region() {
+    let regions = LiveRegions::default().announce(&Announcement::status("Loaded sdtm.ae"));
+    assert_eq!(regions.status, "Loaded sdtm.ae");
+    assert_eq!(regions.alert, "");
+    // The same text again must still change the region for screen readers to read it.
+    let repeated = regions.announce(&Announcement::status("Loaded sdtm.ae"));
+    assert_ne!(repeated.status, regions.status);
+    assert_eq!(repeated.status.trim_end_matches('\u{a0}'), "Loaded sdtm.ae");
+    let again = repeated.announce(&Announcement::status("Loaded sdtm.ae"));
+    assert_eq!(again.status, "Loaded sdtm.ae");
+
+    let alerted = again.announce(&Announcement::alert("Unable to load sdtm.dm"));
+    assert_eq!(alerted.status, "Loaded sdtm.ae");
+    assert_eq!(alerted.alert, "Unable to load sdtm.dm");
+}
+
+#[wasm_bindgen_test]
+fn test_activation_keys() {
+    assert!(is_activation_key("Enter"));
+    assert!(is_activation_key(" "));
+    assert!(!is_activation_key("Tab"));
+}
+
+#[wasm_bindgen_test]
+fn test_elements_reads_nesting_and_text() {
+    let parsed = elements(
+        "<div id=\"a\" hidden><!-- note --><label>Name <input type=text value='x > y'></label>\
+         <br/><span>after</span></div>",
+    );
+    let names: Vec<&str> = parsed.iter().map(|element| element.name.as_str()).collect();
+    assert_eq!(names, vec!["div", "label", "input", "br", "span"]);
+    assert_eq!(parsed[0].attribute("hidden"), Some(""));
+    assert_eq!(parsed[2].attribute("value"), Some("x > y"));
+    assert_eq!(parsed[2].ancestors, vec![0, 1]);
+    assert_eq!(parsed[4].ancestors, vec![0]);
+    assert_eq!(parsed[0].text.trim(), "Name after");
+}
+
+#[wasm_bindgen_test]
+fn test_audit_finds_unlabelled_controls() {
+    let issues = audit(
+        "<select id=\"schema-select\"><option>dm</option></select>\
+         <input type=\"text\" placeholder=\"filter\">\
+         <button type=\"button\"></button>",
+    );
+    assert_eq!(
+        issues,
+        vec![
+            "<select id=\"schema-select\"> has no label",
+            "<input> has no label",
+            "<button> has no accessible name",
+        ]
+    );
+    assert_accessible(
+        "<label for=\"schema-select\">Schema</label><select id=\"schema-select\"></select>\
+         <label>Rows <input type=\"search\"></label>\
+         <input type=\"text\" aria-label=\"Filter aeterm\">\
+         <button type=\"button\" aria-label=\"Close\"></button>\
+         <input type=\"hidden\" value=\"1\">",
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_audit_finds_widgets_out_of_keyboard_reach() {
+    let issues = audit(
+        "<div role=\"button\">Open</div>\
+         <div role=\"dialog\" id=\"d\"></div>\
+         <div role=\"progressbar\"></div>\
+         <p id=\"x\"></p><p id=\"x\"></p>\
+         <a href=\"/\"><a href=\"/site\">Home</a></a>",
+    );
+    assert_eq!(
+        issues,
+        vec![
+            "the id \"x\" is used more than once",
+            "<div> with the role button cannot be reached with the keyboard",
+            "<div id=\"d\"> with the role dialog has no accessible name",
+            "<div> with the role progressbar has no accessible name",
+            "<a> is nested in another link",
+        ]
+    );
+    assert_accessible(
+        "<div role=\"button\" tabindex=\"0\">Open</div>\
+         <h5 id=\"title\">Row Detail</h5><div role=\"dialog\" aria-labelledby=\"title\"></div>\
+         <div role=\"separator\"></div>",
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_render_produces_auditable_markup() {
+    let markup = render(html! {
+        <div role="region" aria-label="Table sdtm.ae">
+            <button type="button" disabled={true}>{"Undo"}</button>
+        </div>
+    })
+    .await;
+    assert!(markup.contains("aria-label=\"Table sdtm.ae\""), "{markup}");
+    assert_accessible(&markup);
+}
+
+#[wasm_bindgen_test]
+async fn test_live_region_renders_polite_and_assertive_regions() {
+    let markup = render_component::<LiveRegion>(LiveRegionProp {
+        children: Children::new(vec![html! { <p>{"content"}</p> }]),
+    })
+    .await;
+    let parsed = elements(&markup);
+    let live: Vec<(Option<&str>, Option<&str>)> = parsed
+        .iter()
+        .filter(|element| element.attribute("aria-live").is_some())
+        .map(|element| (element.attribute("role"), element.attribute("aria-live")))
+        .collect();
+    assert_eq!(
+        live,
+        vec![
+            (Some("status"), Some("polite")),
+            (Some("alert"), Some("assertive"))
+        ]
+    );
+    assert_accessible(&markup);
+}
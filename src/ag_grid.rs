@@ -0,0 +1,287 @@
+#[cfg(test)]
+mod test_ag_grid;
+
+use crate::layout::GridOptions;
+use crate::views::{ColumnSort, GridState};
+
+use std::collections::{HashMap, HashSet};
+
+use gloo_utils::format::JsValueSerdeExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use wasm_bindgen::prelude::*;
+
+/// Row key AG Grid uses as the row id, see `getRowId` in ag_grid.js.
+pub const ROW_INDEX_FIELD: &str = "__row_index";
+
+#[wasm_bindgen(module = "/js/ag_grid.js")]
+extern "C" {
+    type JsGridHandle;
+
+    fn create_grid(
+        ag_grid_data: JsValue,
+        grid_div_id: String,
+        grid_options: JsValue,
+    ) -> JsGridHandle;
+    fn find_grid(grid_div_id: String) -> Option<JsGridHandle>;
+
+    #[wasm_bindgen(method)]
+    fn set_row_data(this: &JsGridHandle, row_data: JsValue);
+    #[wasm_bindgen(method)]
+    fn set_column_defs(this: &JsGridHandle, col_defs: JsValue);
+    #[wasm_bindgen(method)]
+    fn apply_transaction(this: &JsGridHandle, transaction: JsValue);
+    #[wasm_bindgen(method)]
+    fn set_filter_model(this: &JsGridHandle, filter_model: JsValue);
+    #[wasm_bindgen(method)]
+    fn get_filter_model(this: &JsGridHandle) -> JsValue;
+    #[wasm_bindgen(method)]
+    fn get_column_state(this: &JsGridHandle) -> JsValue;
+    #[wasm_bindgen(method)]
+    fn apply_column_state(this: &JsGridHandle, column_state: JsValue);
+    #[wasm_bindgen(method)]
+    fn reset_column_state(this: &JsGridHandle);
+    #[wasm_bindgen(method)]
+    fn displayed_row_count(this: &JsGridHandle) -> usize;
+    #[wasm_bindgen(method)]
+    fn export_csv(this: &JsGridHandle, file_name: String);
+    #[wasm_bindgen(method)]
+    fn on_row_clicked(this: &JsGridHandle, callback: &Closure<dyn Fn(usize)>);
+    #[wasm_bindgen(method)]
+    fn on_selection_changed(this: &JsGridHandle, callback: &Closure<dyn Fn(Vec<usize>)>);
+    #[wasm_bindgen(method)]
+    fn on_sort_changed(this: &JsGridHandle, callback: &Closure<dyn Fn(JsValue)>);
+    #[wasm_bindgen(method)]
+    fn on_filter_changed(this: &JsGridHandle, callback: &Closure<dyn Fn(JsValue)>);
+    #[wasm_bindgen(method)]
+    fn on_state_changed(this: &JsGridHandle, callback: &Closure<dyn Fn(JsValue, JsValue)>);
+    #[wasm_bindgen(method)]
+    fn destroy(this: &JsGridHandle);
+}
+
+/// One entry of AG Grid's column state.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnState {
+    pub col_id: String,
+    #[serde(default)]
+    pub hide: Option<bool>,
+    #[serde(default)]
+    pub sort: Option<String>,
+    #[serde(default)]
+    pub sort_index: Option<i64>,
+    #[serde(default)]
+    pub width: Option<f64>,
+    #[serde(default)]
+    pub pinned: Option<String>,
+}
+
+/// Rows to add, update and remove, matched on `ROW_INDEX_FIELD`.
+#[derive(Serialize, Clone, PartialEq, Debug, Default)]
+pub struct RowTransaction {
+    pub add: Vec<HashMap<String, String>>,
+    pub update: Vec<HashMap<String, String>>,
+    pub remove: Vec<HashMap<String, String>>,
+}
+
+impl RowTransaction {
+    pub fn is_empty(&self) -> bool {
+        self.add.is_empty() && self.update.is_empty() && self.remove.is_empty()
+    }
+}
+
+/// The transaction turning the rows `old` into `new`.
+pub fn row_transaction(
+    old: &[HashMap<String, String>],
+    new: &[HashMap<String, String>],
+) -> RowTransaction {
+    let old_rows: HashMap<&String, &HashMap<String, String>> = old
+        .iter()
+        .filter_map(|row| row.get(ROW_INDEX_FIELD).map(|index| (index, row)))
+        .collect();
+    let mut transaction = RowTransaction::default();
+    let mut kept = HashSet::<&String>::new();
+    for row in new {
+        let index = match row.get(ROW_INDEX_FIELD) {
+            Some(index) => index,
+            None => continue,
+        };
+        match old_rows.get(index) {
+            Some(old_row) => {
+                kept.insert(index);
+                if *old_row != row {
+                    transaction.update.push(row.clone());
+                }
+            }
+            None => transaction.add.push(row.clone()),
+        }
+    }
+    for (index, _) in old_rows.iter().filter(|(index, _)| !kept.contains(*index)) {
+        transaction.remove.push(HashMap::from([(
+            String::from(ROW_INDEX_FIELD),
+            (*index).clone(),
+        )]));
+    }
+    transaction
+}
+
+/// Sorted columns in a column state, highest priority first.
+pub fn sort_from_column_state(column_state: &[ColumnState]) -> Vec<ColumnSort> {
+    match serde_json::to_value(column_state) {
+        Ok(value) => GridState::from_ag_grid(Value::Null, &value).sort,
+        Err(_) => Vec::new(),
+    }
+}
+
+/// An AG Grid in a div.
+///
+/// A handle returned by `create` owns the grid and destroys it when dropped, one returned by
+/// `find` only borrows it.
+pub struct GridHandle {
+    grid: JsGridHandle,
+    owned: bool,
+    listeners: Vec<Box<dyn std::any::Any>>,
+}
+
+impl GridHandle {
+    /// Builds a grid in the div, replacing the grid already there.
+    pub fn create<T: Serialize>(
+        ag_grid_data: &T,
+        grid_div_id: &str,
+        grid_options: &GridOptions,
+    ) -> GridHandle {
+        GridHandle {
+            grid: create_grid(
+                to_js(ag_grid_data),
+                grid_div_id.to_owned(),
+                to_js(grid_options),
+            ),
+            owned: true,
+            listeners: Vec::new(),
+        }
+    }
+
+    /// The grid currently in the div, if any.
+    pub fn find(grid_div_id: &str) -> Option<GridHandle> {
+        find_grid(grid_div_id.to_owned()).map(|grid| GridHandle {
+            grid,
+            owned: false,
+            listeners: Vec::new(),
+        })
+    }
+
+    pub fn set_row_data(&self, row_data: &[HashMap<String, String>]) {
+        self.grid.set_row_data(to_js(&row_data));
+    }
+
+    pub fn set_column_defs(&self, col_defs: &[HashMap<String, String>]) {
+        self.grid.set_column_defs(to_js(&col_defs));
+    }
+
+    pub fn apply_transaction(&self, transaction: &RowTransaction) {
+        self.grid.apply_transaction(to_js(transaction));
+    }
+
+    pub fn set_filter_model(&self, filter_model: &Value) {
+        self.grid.set_filter_model(to_js(filter_model));
+    }
+
+    pub fn get_filter_model(&self) -> Value {
+        from_js(&self.grid.get_filter_model()).unwrap_or(Value::Null)
+    }
+
+    pub fn get_column_state(&self) -> Vec<ColumnState> {
+        from_js(&self.grid.get_column_state()).unwrap_or_default()
+    }
+
+    pub fn displayed_row_count(&self) -> usize {
+        self.grid.displayed_row_count()
+    }
+
+    /// Downloads the rows passing the filters, in grid order, as `file_name`.
+    pub fn export_csv(&self, file_name: &str) {
+        self.grid.export_csv(file_name.to_owned());
+    }
+
+    /// Filters, sorts and column layout, as a view stores them.
+    pub fn grid_state(&self) -> GridState {
+        let column_state = serde_json::to_value(self.get_column_state()).unwrap_or(Value::Null);
+        GridState::from_ag_grid(self.get_filter_model(), &column_state)
+    }
+
+    /// Applies a state as a view stores it. A state listing no columns starts from the column
+    /// definitions, so the default state undoes every change.
+    pub fn apply_grid_state(&self, state: &GridState) {
+        if state.column_order.is_empty() {
+            self.grid.reset_column_state();
+        }
+        let ag_grid = state.to_ag_grid();
+        self.grid
+            .apply_column_state(to_js(&ag_grid["column_state"]));
+        self.set_filter_model(&ag_grid["filter_model"]);
+    }
+
+    /// Receives the `ROW_INDEX_FIELD` of each clicked row.
+    pub fn on_row_clicked(&mut self, callback: impl Fn(usize) + 'static) {
+        let closure = Closure::<dyn Fn(usize)>::new(callback);
+        self.grid.on_row_clicked(&closure);
+        self.listeners.push(Box::new(closure));
+    }
+
+    /// Receives the `ROW_INDEX_FIELD` of the selected rows whenever the selection changes.
+    pub fn on_selection_changed(&mut self, callback: impl Fn(Vec<usize>) + 'static) {
+        let closure = Closure::<dyn Fn(Vec<usize>)>::new(callback);
+        self.grid.on_selection_changed(&closure);
+        self.listeners.push(Box::new(closure));
+    }
+
+    pub fn on_sort_changed(&mut self, callback: impl Fn(Vec<ColumnSort>) + 'static) {
+        let closure = Closure::<dyn Fn(JsValue)>::new(move |column_state: JsValue| {
+            let column_state: Vec<ColumnState> = from_js(&column_state).unwrap_or_default();
+            callback(sort_from_column_state(&column_state));
+        });
+        self.grid.on_sort_changed(&closure);
+        self.listeners.push(Box::new(closure));
+    }
+
+    pub fn on_filter_changed(&mut self, callback: impl Fn(Value) + 'static) {
+        let closure = Closure::<dyn Fn(JsValue)>::new(move |filter_model: JsValue| {
+            callback(from_js(&filter_model).unwrap_or(Value::Null));
+        });
+        self.grid.on_filter_changed(&closure);
+        self.listeners.push(Box::new(closure));
+    }
+
+    /// Receives the grid's state whenever the user filters, sorts, hides or moves columns.
+    pub fn on_state_changed(&mut self, callback: impl Fn(GridState) + 'static) {
+        let closure = Closure::<dyn Fn(JsValue, JsValue)>::new(
+            move |filter_model: JsValue, column_state: JsValue| {
+                let filter_model = from_js(&filter_model).unwrap_or(Value::Null);
+                let column_state = from_js(&column_state).unwrap_or(Value::Null);
+                callback(GridState::from_ag_grid(filter_model, &column_state));
+            },
+        );
+        self.grid.on_state_changed(&closure);
+        self.listeners.push(Box::new(closure));
+    }
+
+    pub fn destroy(mut self) {
+        self.owned = true;
+    }
+}
+
+impl Drop for GridHandle {
+    fn drop(&mut self) {
+        if self.owned {
+            self.grid.destroy();
+        }
+    }
+}
+
+fn to_js<T: Serialize + ?Sized>(value: &T) -> JsValue {
+    <JsValue as JsValueSerdeExt>::from_serde(value).unwrap_or(JsValue::NULL)
+}
+
+fn from_js<T: for<'de> Deserialize<'de>>(value: &JsValue) -> Option<T> {
+    <JsValue as JsValueSerdeExt>::into_serde::<T>(value).ok()
+}
@@ -0,0 +1,57 @@
+use super::{row_transaction, sort_from_column_state, ColumnState, ROW_INDEX_FIELD};
+use crate::views::ColumnSort;
+use std::collections::HashMap;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn row(index: usize, aeterm: &str) -> HashMap<String, String> {
+    HashMap::from([
+        (String::from(ROW_INDEX_FIELD), index.to_string()),
+        (String::from("aeterm"), String::from(aeterm)),
+    ])
+}
+
+#[wasm_bindgen_test]
+fn test_row_transaction_of_identical_rows_is_empty() {
+    let rows = vec![row(0, "Fatigue"), row(1, "Nausea")];
+    assert!(row_transaction(&rows, &rows).is_empty());
+}
+
+#[wasm_bindgen_test]
+fn test_row_transaction_adds_updates_and_removes() {
+    let old = vec![row(0, "Fatigue"), row(1, "Nausea"), row(2, "Rash")];
+    let new = vec![row(0, "Fatigue"), row(1, "Vomiting")];
+    let transaction = row_transaction(&old, &new);
+    assert!(transaction.add.is_empty());
+    assert_eq!(transaction.update, vec![row(1, "Vomiting")]);
+    assert_eq!(
+        transaction.remove,
+        vec![HashMap::from([(
+            String::from(ROW_INDEX_FIELD),
+            String::from("2")
+        )])]
+    );
+
+    let grown = row_transaction(&new, &old);
+    assert_eq!(grown.add, vec![row(2, "Rash")]);
+    assert_eq!(grown.update, vec![row(1, "Nausea")]);
+    assert!(grown.remove.is_empty());
+}
+
+#[wasm_bindgen_test]
+fn test_column_state_reads_ag_grid_json() {
+    let column_state: Vec<ColumnState> = serde_json::from_str(
+        r#"[
+            {"colId": "subject", "width": 120, "hide": false, "pinned": "left", "sort": null, "sortIndex": null, "flex": 1},
+            {"colId": "aetoxgr", "hide": false, "sort": "desc", "sortIndex": 0}
+        ]"#,
+    )
+    .unwrap();
+    assert_eq!(column_state[0].pinned.as_deref(), Some("left"));
+    assert_eq!(
+        sort_from_column_state(&column_state),
+        vec![ColumnSort {
+            column: String::from("aetoxgr"),
+            descending: true
+        }]
+    );
+}
@@ -0,0 +1,457 @@
+#[cfg(test)]
+mod test_cdisc;
+
+use crate::i18n::{Message, MessageArg};
+
+use std::cmp::Ordering;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Standard {
+    Sdtm,
+    Adam,
+}
+
+impl Standard {
+    pub fn label(&self) -> Message {
+        Message::new(match self {
+            Standard::Sdtm => "cdisc-standard-sdtm",
+            Standard::Adam => "cdisc-standard-adam",
+        })
+    }
+}
+
+const SDTM_DOMAINS: [(&str, &str); 31] = [
+    ("AE", "Adverse Events"),
+    ("CM", "Concomitant Medications"),
+    ("CO", "Comments"),
+    ("DM", "Demographics"),
+    ("DS", "Disposition"),
+    ("DV", "Protocol Deviations"),
+    ("EG", "ECG Test Results"),
+    ("EX", "Exposure"),
+    ("FA", "Findings About"),
+    ("IE", "Inclusion/Exclusion Criteria Not Met"),
+    ("LB", "Laboratory Test Results"),
+    ("MB", "Microbiology Specimen"),
+    ("MH", "Medical History"),
+    ("PC", "Pharmacokinetics Concentrations"),
+    ("PE", "Physical Examination"),
+    ("PP", "Pharmacokinetics Parameters"),
+    ("PR", "Procedures"),
+    ("QS", "Questionnaires"),
+    ("RS", "Disease Response"),
+    ("SC", "Subject Characteristics"),
+    ("SE", "Subject Elements"),
+    ("SU", "Substance Use"),
+    ("SV", "Subject Visits"),
+    ("TA", "Trial Arms"),
+    ("TE", "Trial Elements"),
+    ("TI", "Trial Inclusion/Exclusion Criteria"),
+    ("TR", "Tumor Results"),
+    ("TS", "Trial Summary"),
+    ("TU", "Tumor Identification"),
+    ("TV", "Trial Visits"),
+    ("VS", "Vital Signs"),
+];
+
+const ADAM_DATASETS: [(&str, &str); 8] = [
+    ("ADSL", "Subject-Level Analysis Dataset"),
+    ("ADAE", "Adverse Events Analysis Dataset"),
+    ("ADCM", "Concomitant Medications Analysis Dataset"),
+    ("ADEG", "ECG Analysis Dataset"),
+    ("ADEX", "Exposure Analysis Dataset"),
+    ("ADLB", "Laboratory Analysis Dataset"),
+    ("ADTTE", "Time-to-Event Analysis Dataset"),
+    ("ADVS", "Vital Signs Analysis Dataset"),
+];
+
+/// Labels of variables named the same in every domain and dataset.
+const VARIABLE_LABELS: [(&str, &str); 55] = [
+    ("STUDYID", "Study Identifier"),
+    ("DOMAIN", "Domain Abbreviation"),
+    ("USUBJID", "Unique Subject Identifier"),
+    ("SUBJID", "Subject Identifier for the Study"),
+    ("SITEID", "Study Site Identifier"),
+    ("RFSTDTC", "Subject Reference Start Date/Time"),
+    ("RFENDTC", "Subject Reference End Date/Time"),
+    ("RFICDTC", "Date/Time of Informed Consent"),
+    ("DTHDTC", "Date/Time of Death"),
+    ("DTHFL", "Subject Death Flag"),
+    ("BRTHDTC", "Date/Time of Birth"),
+    ("AGE", "Age"),
+    ("AGEU", "Age Units"),
+    ("SEX", "Sex"),
+    ("RACE", "Race"),
+    ("ETHNIC", "Ethnicity"),
+    ("ARMCD", "Planned Arm Code"),
+    ("ARM", "Description of Planned Arm"),
+    ("ACTARMCD", "Actual Arm Code"),
+    ("ACTARM", "Description of Actual Arm"),
+    ("COUNTRY", "Country"),
+    ("VISITNUM", "Visit Number"),
+    ("VISIT", "Visit Name"),
+    ("VISITDY", "Planned Study Day of Visit"),
+    ("EPOCH", "Epoch"),
+    ("PARAMCD", "Parameter Code"),
+    ("PARAM", "Parameter"),
+    ("PARCAT1", "Parameter Category 1"),
+    ("AVAL", "Analysis Value"),
+    ("AVALC", "Analysis Value (C)"),
+    ("BASE", "Baseline Value"),
+    ("CHG", "Change from Baseline"),
+    ("PCHG", "Percent Change from Baseline"),
+    ("DTYPE", "Derivation Type"),
+    ("ADT", "Analysis Date"),
+    ("ADY", "Analysis Relative Day"),
+    ("ASTDT", "Analysis Start Date"),
+    ("AENDT", "Analysis End Date"),
+    ("AVISIT", "Analysis Visit"),
+    ("AVISITN", "Analysis Visit (N)"),
+    ("ABLFL", "Baseline Record Flag"),
+    ("ANL01FL", "Analysis Flag 01"),
+    ("TRTP", "Planned Treatment"),
+    ("TRTA", "Actual Treatment"),
+    ("TRT01P", "Planned Treatment for Period 01"),
+    ("TRT01A", "Actual Treatment for Period 01"),
+    ("TRTSDT", "Date of First Exposure to Treatment"),
+    ("TRTEDT", "Date of Last Exposure to Treatment"),
+    ("TRTEMFL", "Treatment Emergent Analysis Flag"),
+    ("SAFFL", "Safety Population Flag"),
+    ("ITTFL", "Intent-To-Treat Population Flag"),
+    ("FASFL", "Full Analysis Set Population Flag"),
+    ("CNSR", "Censor"),
+    ("STARTDT", "Time-to-Event Origin Date for Subject"),
+    ("SRCDOM", "Source Data"),
+];
+
+/// Labels of the SDTM variables named by the domain code and a suffix, `--SEQ` is `AESEQ` in AE.
+const PREFIXED_LABELS: [(&str, &str); 47] = [
+    ("STRESC", "Character Result/Finding in Std Format"),
+    ("STRESN", "Numeric Result/Finding in Standard Units"),
+    ("STRESU", "Standard Units"),
+    ("ORRESU", "Original Units"),
+    ("ORNRLO", "Normal Range Lower Limit in Orig Unit"),
+    ("ORNRHI", "Normal Range Upper Limit in Orig Unit"),
+    ("STNRLO", "Normal Range Lower Limit-Std Units"),
+    ("STNRHI", "Normal Range Upper Limit-Std Units"),
+    ("REASND", "Reason Not Done"),
+    ("BODSYS", "Body System or Organ Class"),
+    ("METHOD", "Method of Test or Examination"),
+    ("TESTCD", "Short Name of Test"),
+    ("ORRES", "Result or Finding in Original Units"),
+    ("NRIND", "Reference Range Indicator"),
+    ("STDTC", "Start Date/Time"),
+    ("ENDTC", "End Date/Time"),
+    ("TOXGR", "Standard Toxicity Grade"),
+    ("DECOD", "Dictionary-Derived Term"),
+    ("GRPID", "Group ID"),
+    ("REFID", "Reference ID"),
+    ("ROUTE", "Route of Administration"),
+    ("STDY", "Study Day of Start"),
+    ("ENDY", "Study Day of End"),
+    ("TERM", "Reported Term"),
+    ("TEST", "Name of Test"),
+    ("STAT", "Completion Status"),
+    ("BLFL", "Baseline Flag"),
+    ("SPID", "Sponsor-Defined Identifier"),
+    ("SCAT", "Subcategory"),
+    ("SPEC", "Specimen Type"),
+    ("DOSE", "Dose"),
+    ("DOSU", "Dose Units"),
+    ("SEQ", "Sequence Number"),
+    ("CAT", "Category"),
+    ("SEV", "Severity/Intensity"),
+    ("SER", "Serious Event"),
+    ("ACN", "Action Taken with Study Treatment"),
+    ("REL", "Causality"),
+    ("OUT", "Outcome"),
+    ("DTC", "Date/Time of Collection"),
+    ("TRT", "Name of Treatment"),
+    ("POS", "Position of Subject"),
+    ("LOC", "Location"),
+    ("LAT", "Laterality"),
+    ("DUR", "Duration"),
+    ("EVAL", "Evaluator"),
+    ("DY", "Study Day"),
+];
+
+const MAX_VARIABLE_NAME_LENGTH: usize = 8;
+
+/// The SDTM domain or ADaM dataset a table holds.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Domain {
+    /// The domain code, e.g. `AE`, or the dataset name, e.g. `ADSL`.
+    pub code: String,
+    pub standard: Standard,
+}
+
+/// A variable whose name does not follow the standard.
+#[derive(Clone, PartialEq, Debug)]
+pub struct VariableIssue {
+    pub variable: String,
+    pub message: Message,
+}
+
+impl VariableIssue {
+    /// The issue with the variable named, e.g. "LBSEQ: prefix LB is not the domain code".
+    pub fn describe(&self) -> Message {
+        Message::new("cdisc-variable-issue")
+            .with("variable", self.variable.clone())
+            .with_arg("issue", MessageArg::Message(self.message.clone()))
+    }
+}
+
+impl Domain {
+    /// The standard name of the domain, `None` for sponsor-defined ones.
+    pub fn name(&self) -> Option<&'static str> {
+        let names: &[(&str, &str)] = match self.standard {
+            Standard::Sdtm => &SDTM_DOMAINS,
+            Standard::Adam => &ADAM_DATASETS,
+        };
+        names
+            .iter()
+            .find(|(code, _)| *code == self.code)
+            .map(|(_, name)| *name)
+    }
+
+    /// The standard, code and name, e.g. "SDTM AE: Adverse Events". Names are the standard's.
+    pub fn describe(&self) -> Message {
+        let message = match self.name() {
+            Some(name) => Message::new("cdisc-domain-named").with("name", name),
+            None => Message::new("cdisc-domain"),
+        };
+        message
+            .with_arg("standard", MessageArg::Message(self.standard.label()))
+            .with("code", self.code.clone())
+    }
+
+    /// The standard label of a variable, e.g. "Sequence Number" for AESEQ in AE.
+    pub fn label(&self, variable: &str) -> Option<&'static str> {
+        let variable = variable.to_ascii_uppercase();
+        if let Some((_, label)) = VARIABLE_LABELS.iter().find(|(name, _)| *name == variable) {
+            return Some(label);
+        }
+        match self.standard {
+            Standard::Sdtm => variable
+                .strip_prefix(&self.code)
+                .and_then(|suffix| PREFIXED_LABELS.iter().find(|(name, _)| *name == suffix))
+                .map(|(_, label)| *label),
+            Standard::Adam => None,
+        }
+    }
+
+    /// The fields identifying a record, kept in view by pinning them.
+    pub fn key_variables(&self, fields: &[String]) -> Vec<String> {
+        let keys = match self.standard {
+            Standard::Sdtm => vec![
+                String::from("USUBJID"),
+                format!("{}SEQ", self.code),
+                format!("{}TESTCD", self.code),
+            ],
+            Standard::Adam => vec![String::from("USUBJID"), String::from("PARAMCD")],
+        };
+        keys.iter()
+            .filter_map(|key| {
+                fields
+                    .iter()
+                    .find(|field| field.eq_ignore_ascii_case(key))
+                    .cloned()
+            })
+            .collect()
+    }
+
+    /// Fields breaking the naming rules: at most 8 upper case letters, digits and underscores
+    /// starting with a letter, and in SDTM the domain code as the prefix of `--` variables.
+    pub fn variable_issues(&self, fields: &[String]) -> Vec<VariableIssue> {
+        fields
+            .iter()
+            .filter_map(|field| {
+                self.variable_issue(field).map(|message| VariableIssue {
+                    variable: field.clone(),
+                    message,
+                })
+            })
+            .collect()
+    }
+
+    fn variable_issue(&self, field: &str) -> Option<Message> {
+        if field.len() > MAX_VARIABLE_NAME_LENGTH {
+            return Some(
+                Message::new("cdisc-issue-too-long").with("length", MAX_VARIABLE_NAME_LENGTH),
+            );
+        }
+        if !field.starts_with(|first: char| first.is_ascii_uppercase()) {
+            return Some(Message::new("cdisc-issue-first-letter"));
+        }
+        if !field
+            .chars()
+            .all(|letter| letter.is_ascii_uppercase() || letter.is_ascii_digit() || letter == '_')
+        {
+            return Some(Message::new("cdisc-issue-characters"));
+        }
+        if self.standard == Standard::Sdtm
+            && !VARIABLE_LABELS.iter().any(|(name, _)| *name == field)
+            && field.len() > 2
+            && !field.starts_with(&self.code)
+        {
+            let (prefix, suffix) = field.split_at(2);
+            if PREFIXED_LABELS.iter().any(|(name, _)| *name == suffix) {
+                return Some(
+                    Message::new("cdisc-issue-prefix")
+                        .with("prefix", String::from(prefix))
+                        .with("expected", format!("{}{suffix}", self.code)),
+                );
+            }
+        }
+        None
+    }
+}
+
+/// The domain of a table, from its name, e.g. `ae`, `sdtm_ae` or `adsl`, or else from its
+/// variables: ADaM datasets have PARAMCD or TRT01P, SDTM domains a `--SEQ` next to USUBJID.
+/// Only the variables make other `AD` names ADaM datasets, so `adverse_events` is AE.
+pub fn detect_domain(table: &str, fields: &[String]) -> Option<Domain> {
+    let table = table.to_ascii_uppercase();
+    for part in table.rsplit(['.', '_', '-']) {
+        if SDTM_DOMAINS.iter().any(|(code, _)| *code == part) {
+            return Some(Domain {
+                code: String::from(part),
+                standard: Standard::Sdtm,
+            });
+        }
+        if ADAM_DATASETS.iter().any(|(name, _)| *name == part) {
+            return Some(Domain {
+                code: String::from(part),
+                standard: Standard::Adam,
+            });
+        }
+    }
+
+    let upper: Vec<String> = fields
+        .iter()
+        .map(|field| field.to_ascii_uppercase())
+        .collect();
+    if !upper.iter().any(|field| field == "USUBJID") {
+        return None;
+    }
+    if upper
+        .iter()
+        .any(|field| field == "PARAMCD" || field == "TRT01P")
+    {
+        // Named by the part of the name that looks like a dataset name, e.g. ADRS in study_adrs.
+        let code = table
+            .rsplit(['.', '_', '-'])
+            .find(|part| {
+                part.starts_with("AD")
+                    && (3..=MAX_VARIABLE_NAME_LENGTH).contains(&part.len())
+                    && part.chars().all(|letter| letter.is_ascii_alphanumeric())
+            })
+            .map(String::from)
+            .unwrap_or(table);
+        return Some(Domain {
+            code,
+            standard: Standard::Adam,
+        });
+    }
+    upper
+        .iter()
+        .find(|field| field.len() == 5 && field.ends_with("SEQ"))
+        .map(|field| Domain {
+            code: String::from(&field[..2]),
+            standard: Standard::Sdtm,
+        })
+}
+
+/// An ISO 8601 date as SDTM `--DTC` variables hold it, where any part may be missing, e.g.
+/// `2023-04` or `2023---15` for the 15th of an unknown month.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct IsoDate {
+    pub year: Option<u32>,
+    pub month: Option<u32>,
+    pub day: Option<u32>,
+    /// The time as written, e.g. `10:30`.
+    pub time: Option<String>,
+}
+
+impl IsoDate {
+    pub fn parse(value: &str) -> Option<IsoDate> {
+        let value = value.trim();
+        let (date, time) = match value.split_once('T') {
+            Some((date, time)) => (date, Some(time)),
+            None => (value, None),
+        };
+        let mut rest = date;
+        let year = take_component(&mut rest, 4)?;
+        let mut parsed = IsoDate {
+            year,
+            ..IsoDate::default()
+        };
+        if !rest.is_empty() {
+            rest = rest.strip_prefix('-')?;
+            parsed.month = take_component(&mut rest, 2)?;
+        }
+        if !rest.is_empty() {
+            rest = rest.strip_prefix('-')?;
+            parsed.day = take_component(&mut rest, 2)?;
+        }
+        if !rest.is_empty()
+            || parsed.month.is_some_and(|month| !(1..=12).contains(&month))
+            || parsed.day.is_some_and(|day| !(1..=31).contains(&day))
+        {
+            return None;
+        }
+        if let Some(time) = time {
+            if time.is_empty()
+                || !time
+                    .chars()
+                    .all(|letter| letter.is_ascii_digit() || ":.+-Z".contains(letter))
+            {
+                return None;
+            }
+            parsed.time = Some(String::from(time));
+        }
+        if parsed.year.is_none() && parsed.month.is_none() && parsed.day.is_none() {
+            return None;
+        }
+        Some(parsed)
+    }
+
+    /// Whether this date is known to be after `other`. Parts missing from either date are
+    /// unknown, so 2023-04 is not after 2023-04-17 and 2023-05 is.
+    pub fn is_after(&self, other: &IsoDate) -> bool {
+        for (part, other_part) in [
+            (self.year, other.year),
+            (self.month, other.month),
+            (self.day, other.day),
+        ] {
+            match (part, other_part) {
+                (Some(part), Some(other_part)) if part != other_part => return part > other_part,
+                (Some(_), Some(_)) => (),
+                _ => return false,
+            }
+        }
+        match (&self.time, &other.time) {
+            (Some(time), Some(other_time)) => {
+                let precision = time.len().min(other_time.len());
+                time[..precision].cmp(&other_time[..precision]) == Ordering::Greater
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Reads a component of `digits` digits, or a single `-` standing for a missing one.
+fn take_component(rest: &mut &str, digits: usize) -> Option<Option<u32>> {
+    if let Some(after) = rest.strip_prefix('-') {
+        if rest.len() == 1 || after.starts_with('-') {
+            *rest = after;
+            return Some(None);
+        }
+    }
+    let component = rest.get(..digits)?;
+    if !component.chars().all(|digit| digit.is_ascii_digit()) {
+        return None;
+    }
+    *rest = &rest[digits..];
+    component.parse::<u32>().ok().map(Some)
+}
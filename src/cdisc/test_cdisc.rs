@@ -0,0 +1,132 @@
+use super::{detect_domain, Domain, IsoDate, Standard};
+use crate::i18n::Locale;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn fields(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| String::from(*name)).collect()
+}
+
+fn sdtm(code: &str) -> Domain {
+    Domain {
+        code: String::from(code),
+        standard: Standard::Sdtm,
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_detect_domain() {
+    assert_eq!(detect_domain("ae", &[]), Some(sdtm("AE")));
+    assert_eq!(detect_domain("sdtm_lb", &[]), Some(sdtm("LB")));
+    assert_eq!(
+        detect_domain("adsl", &[]).map(|domain| domain.standard),
+        Some(Standard::Adam)
+    );
+    // Sponsor-defined domains are found from their variables.
+    assert_eq!(
+        detect_domain(
+            "custom",
+            &fields(&["STUDYID", "USUBJID", "XPSEQ", "XPTERM"])
+        ),
+        Some(sdtm("XP"))
+    );
+    assert_eq!(
+        detect_domain("efficacy", &fields(&["USUBJID", "PARAMCD", "AVAL"]))
+            .map(|domain| domain.standard),
+        Some(Standard::Adam)
+    );
+    assert_eq!(detect_domain("events", &fields(&["subject", "term"])), None);
+    assert_eq!(
+        detect_domain("adverse", &fields(&["subject", "term"])),
+        None
+    );
+    // Names starting with AD are not ADaM datasets unless their variables say so.
+    assert_eq!(
+        detect_domain(
+            "adverse_events",
+            &fields(&["STUDYID", "USUBJID", "AESEQ", "AETERM"])
+        ),
+        Some(sdtm("AE"))
+    );
+    assert_eq!(
+        detect_domain("address", &fields(&["USUBJID", "STREET"])),
+        None
+    );
+    assert_eq!(
+        detect_domain("study_adrs", &fields(&["USUBJID", "PARAMCD", "AVALC"])),
+        Some(Domain {
+            code: String::from("ADRS"),
+            standard: Standard::Adam,
+        })
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_labels_and_key_variables() {
+    let ae = sdtm("AE");
+    assert_eq!(ae.label("USUBJID"), Some("Unique Subject Identifier"));
+    assert_eq!(ae.label("AESEQ"), Some("Sequence Number"));
+    assert_eq!(ae.label("aestdtc"), Some("Start Date/Time"));
+    assert_eq!(ae.label("LBSEQ"), None);
+    assert_eq!(
+        ae.key_variables(&fields(&["STUDYID", "AETERM", "AESEQ", "usubjid"])),
+        fields(&["usubjid", "AESEQ"])
+    );
+    assert_eq!(ae.describe().text(Locale::EnUs), "SDTM AE: Adverse Events");
+    assert_eq!(ae.describe().text(Locale::FrFr), "SDTM AE : Adverse Events");
+    assert_eq!(sdtm("XX").describe().text(Locale::DeDe), "SDTM XX");
+}
+
+#[wasm_bindgen_test]
+fn test_variable_issues() {
+    let ae = sdtm("AE");
+    let issues = ae.variable_issues(&fields(&[
+        "STUDYID",
+        "AESEQ",
+        "RFSTDTC",
+        "LBSEQ",
+        "subject",
+        "AE_TERM_LONG",
+        "AE TERM",
+    ]));
+    let flagged: Vec<&str> = issues.iter().map(|issue| issue.variable.as_str()).collect();
+    assert_eq!(flagged, vec!["LBSEQ", "subject", "AE_TERM_LONG", "AE TERM"]);
+    assert_eq!(
+        issues[0].describe().text(Locale::EnUs),
+        "LBSEQ: prefix LB is not the domain code, expected AESEQ"
+    );
+    assert_eq!(issues[2].message.text(Locale::DeDe), "länger als 8 Zeichen");
+}
+
+#[wasm_bindgen_test]
+fn test_iso_date_partial_dates() {
+    let date = |value: &str| IsoDate::parse(value);
+    assert_eq!(
+        date("2023-04-05T10:30"),
+        Some(IsoDate {
+            year: Some(2023),
+            month: Some(4),
+            day: Some(5),
+            time: Some(String::from("10:30")),
+        })
+    );
+    assert_eq!(
+        date("2023---15"),
+        Some(IsoDate {
+            year: Some(2023),
+            month: None,
+            day: Some(15),
+            time: None,
+        })
+    );
+    assert_eq!(date("--12-15").and_then(|date| date.month), Some(12));
+    assert_eq!(date("2023-4"), None);
+    assert_eq!(date("2023-13"), None);
+    assert_eq!(date(""), None);
+
+    let after = |start: &str, end: &str| date(start).unwrap().is_after(&date(end).unwrap());
+    assert!(after("2023-04-12", "2023-04-10"));
+    assert!(after("2023-05", "2023-04-17"));
+    assert!(!after("2023-04", "2023-04-17"));
+    assert!(!after("2023---15", "2023-04-10"));
+    assert!(after("2023-04-10T11:00", "2023-04-10T09:30"));
+}
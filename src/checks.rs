@@ -0,0 +1,357 @@
+#[cfg(test)]
+mod test_checks;
+
+use crate::cdisc::IsoDate;
+use crate::i18n::{Message, MessageArg};
+use crate::table::Table;
+
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+pub enum ChecksError {
+    ParseError(String),
+    SerializeError(String),
+}
+
+impl ChecksError {
+    pub fn message(&self) -> &String {
+        match self {
+            ChecksError::ParseError(msg) | ChecksError::SerializeError(msg) => msg,
+        }
+    }
+}
+
+/// What one check verifies, tagged by `check` in JSON and YAML, e.g.
+/// `{"check": "unique", "columns": ["subject", "AESEQ"]}`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(tag = "check", rename_all = "snake_case")]
+pub enum Check {
+    /// Every row has a value in each of the columns, null and blank cells have none.
+    Required { columns: Vec<String> },
+    /// No two rows share the same values in the columns. Rows with a blank in the columns are
+    /// left to `Required`.
+    Unique { columns: Vec<String> },
+    /// Values of the column are in a code list, blanks are left to `Required`.
+    AllowedValues { column: String, values: Vec<String> },
+    /// Values of the column are numbers within the bounds.
+    Range {
+        column: String,
+        #[serde(default)]
+        min: Option<f64>,
+        #[serde(default)]
+        max: Option<f64>,
+    },
+    /// The ISO 8601 date in `start` is not after the one in `end`.
+    DateOrder { start: String, end: String },
+    /// Every value of the column is a value of `reference_column` in the table `reference_table`
+    /// of the same schema, e.g. every subject is in the demographics.
+    Reference {
+        column: String,
+        reference_table: String,
+        reference_column: String,
+    },
+}
+
+impl Check {
+    /// The columns of the checked table the check reads.
+    pub fn columns(&self) -> Vec<&String> {
+        match self {
+            Check::Required { columns } | Check::Unique { columns } => columns.iter().collect(),
+            Check::AllowedValues { column, .. }
+            | Check::Range { column, .. }
+            | Check::Reference { column, .. } => vec![column],
+            Check::DateOrder { start, end } => vec![start, end],
+        }
+    }
+
+    pub fn describe(&self) -> Message {
+        let list = |values: &Vec<String>| MessageArg::list("list-separator", values.clone());
+        match self {
+            Check::Required { columns } => {
+                Message::new("check-required").with_arg("columns", list(columns))
+            }
+            Check::Unique { columns } => {
+                Message::new("check-unique").with_arg("columns", list(columns))
+            }
+            Check::AllowedValues { column, values } => Message::new("check-allowed-values")
+                .with("column", column.clone())
+                .with_arg("values", list(values)),
+            Check::Range { column, min, max } => {
+                let message = match (min, max) {
+                    (Some(_), Some(_)) => Message::new("check-range"),
+                    (Some(_), None) => Message::new("check-at-least"),
+                    (None, Some(_)) => Message::new("check-at-most"),
+                    (None, None) => Message::new("check-numeric"),
+                };
+                [("min", min), ("max", max)]
+                    .into_iter()
+                    .filter_map(|(name, bound)| bound.map(|bound| (name, bound)))
+                    .fold(
+                        message.with("column", column.clone()),
+                        |message, (name, bound)| message.with(name, bound),
+                    )
+            }
+            Check::DateOrder { start, end } => Message::new("check-date-order")
+                .with("start", start.clone())
+                .with("end", end.clone()),
+            Check::Reference {
+                column,
+                reference_table,
+                reference_column,
+            } => Message::new("check-reference")
+                .with("column", column.clone())
+                .with("reference", format!("{reference_table}.{reference_column}")),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct CheckRule {
+    /// The table the check is for, `None` to check every table with the check's columns.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table: Option<String>,
+    #[serde(flatten)]
+    pub check: Check,
+}
+
+impl CheckRule {
+    pub fn applies_to(&self, table: &str, fields: &[String]) -> bool {
+        match &self.table {
+            Some(rule_table) => rule_table.eq_ignore_ascii_case(table),
+            None => self
+                .check
+                .columns()
+                .iter()
+                .all(|column| fields.contains(column)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct RuleSet {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub checks: Vec<CheckRule>,
+}
+
+impl RuleSet {
+    /// Reads a rule set written as JSON, or as YAML when the text is not a JSON object.
+    pub fn parse(text: &str) -> Result<RuleSet, ChecksError> {
+        if text.trim_start().starts_with('{') {
+            serde_json::from_str::<RuleSet>(text).map_err(|error| {
+                ChecksError::ParseError(format!("checks error: invalid JSON rule set: {error}"))
+            })
+        } else {
+            serde_yaml::from_str::<RuleSet>(text).map_err(|error| {
+                ChecksError::ParseError(format!("checks error: invalid YAML rule set: {error}"))
+            })
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, ChecksError> {
+        serde_json::to_string_pretty(self).map_err(|error| {
+            ChecksError::SerializeError(format!(
+                "checks error: unable to serialize the rule set: {error}"
+            ))
+        })
+    }
+
+    /// The `(table, column)` pairs whose values the reference checks of `table` need.
+    pub fn references(&self, table: &str, fields: &[String]) -> BTreeSet<(String, String)> {
+        self.checks
+            .iter()
+            .filter(|rule| rule.applies_to(table, fields))
+            .filter_map(|rule| match &rule.check {
+                Check::Reference {
+                    reference_table,
+                    reference_column,
+                    ..
+                } => Some((reference_table.clone(), reference_column.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// The rows of a table failing one check.
+#[derive(Clone, PartialEq, Debug)]
+pub struct CheckResult {
+    pub rule: CheckRule,
+    /// Positions in the checked rows, in order.
+    pub rows: Vec<usize>,
+    /// Set when the check could not run, e.g. its reference table did not load.
+    pub skipped: Option<Message>,
+}
+
+/// Values of reference columns keyed by `(table, column)`, see `reference_values`.
+pub type ReferenceValues = HashMap<(String, String), HashSet<String>>;
+
+/// The values of a column other tables are checked against, written the way the checks compare
+/// them. Nulls are no value of the column.
+pub fn reference_values(data: &Table, column: &str) -> HashSet<String> {
+    let column = match data.column_index(column) {
+        Some(column) => Some(column),
+        None => return HashSet::new(),
+    };
+    (0..data.len())
+        .map(|row| cell(data, row, column))
+        .filter(|value| !value.is_empty())
+        .map(Cow::into_owned)
+        .collect()
+}
+
+/// Runs the checks of `rule_set` that apply to `table` over the rows of `data`. Null cells are
+/// blank, only `Required` flags them.
+pub fn run_checks(
+    rule_set: &RuleSet,
+    table: &str,
+    data: &Table,
+    references: &ReferenceValues,
+) -> Vec<CheckResult> {
+    let fields = data.column_names();
+    rule_set
+        .checks
+        .iter()
+        .filter(|rule| rule.applies_to(table, &fields))
+        .map(|rule| {
+            let mut result = CheckResult {
+                rule: rule.clone(),
+                rows: Vec::new(),
+                skipped: None,
+            };
+            match &rule.check {
+                Check::Reference {
+                    column,
+                    reference_table,
+                    reference_column,
+                } => match references.get(&(reference_table.clone(), reference_column.clone())) {
+                    Some(values) => {
+                        let column = data.column_index(column);
+                        result.rows = failing_rows(data, |row| {
+                            let value = cell(data, row, column);
+                            !value.is_empty() && !values.contains(value.as_ref())
+                        })
+                    }
+                    None => {
+                        result.skipped = Some(
+                            Message::new("check-reference-not-loaded")
+                                .with("reference", format!("{reference_table}.{reference_column}")),
+                        )
+                    }
+                },
+                check => result.rows = check_rows(check, data),
+            }
+            result
+        })
+        .collect()
+}
+
+fn check_rows(check: &Check, data: &Table) -> Vec<usize> {
+    let index = |column: &String| data.column_index(column);
+    match check {
+        Check::Required { columns } => {
+            let columns: Vec<Option<usize>> = columns.iter().map(index).collect();
+            failing_rows(data, |row| {
+                columns
+                    .iter()
+                    .any(|column| cell(data, row, *column).is_empty())
+            })
+        }
+        Check::Unique { columns } => {
+            let columns: Vec<Option<usize>> = columns.iter().map(index).collect();
+            let keys: Vec<Vec<Cow<str>>> = (0..data.len())
+                .map(|row| {
+                    columns
+                        .iter()
+                        .map(|column| cell(data, row, *column))
+                        .collect()
+                })
+                .collect();
+            let mut counts = HashMap::<&Vec<Cow<str>>, usize>::new();
+            for key in keys.iter() {
+                // Rows without the whole key are left to `Required`.
+                if key.iter().all(|value| !value.is_empty()) {
+                    *counts.entry(key).or_default() += 1;
+                }
+            }
+            (0..data.len())
+                .filter(|row| counts.get(&keys[*row]).is_some_and(|count| *count > 1))
+                .collect()
+        }
+        Check::AllowedValues { column, values } => {
+            let column = index(column);
+            failing_rows(data, |row| {
+                let value = cell(data, row, column);
+                if value.is_empty() {
+                    return false;
+                }
+                let number = column.and_then(|column| data.number(row, column));
+                !values.iter().any(|allowed| match number {
+                    // Numbers are allowed by value, 1.0 is an allowed "1".
+                    Some(number) => allowed
+                        .trim()
+                        .parse::<f64>()
+                        .is_ok_and(|allowed| allowed == number),
+                    None => *allowed == value,
+                })
+            })
+        }
+        Check::Range { column, min, max } => {
+            let column = index(column);
+            failing_rows(data, |row| {
+                let value = cell(data, row, column);
+                if value.is_empty() {
+                    return false;
+                }
+                match value.parse::<f64>() {
+                    Ok(number) => {
+                        min.is_some_and(|min| number < min) || max.is_some_and(|max| number > max)
+                    }
+                    Err(_) => true,
+                }
+            })
+        }
+        Check::DateOrder { start, end } => {
+            let (start, end) = (index(start), index(end));
+            failing_rows(data, |row| {
+                date_after(&cell(data, row, start), &cell(data, row, end))
+            })
+        }
+        Check::Reference { .. } => Vec::new(),
+    }
+}
+
+fn failing_rows<F>(data: &Table, fails: F) -> Vec<usize>
+where
+    F: Fn(usize) -> bool,
+{
+    (0..data.len()).filter(|row| fails(*row)).collect()
+}
+
+/// The trimmed text of a cell, empty when it is null or the row lacks the column. Numbers are
+/// written in full and without trailing zeros, `1.0` as `1`, not rounded the way they show.
+fn cell(data: &Table, row: usize, column: Option<usize>) -> Cow<'_, str> {
+    let column = match column {
+        Some(column) if !data.columns()[column].is_null(row) => column,
+        _ => return Cow::Borrowed(""),
+    };
+    if let Some(number) = data.number(row, column) {
+        return Cow::Owned(number.to_string());
+    }
+    match data.text(row, column) {
+        Some(Cow::Borrowed(text)) => Cow::Borrowed(text.trim()),
+        Some(Cow::Owned(text)) => Cow::Owned(text.trim().to_owned()),
+        None => Cow::Borrowed(""),
+    }
+}
+
+/// Whether the ISO 8601 date `start` is known to be after `end`, see `IsoDate::is_after`.
+fn date_after(start: &str, end: &str) -> bool {
+    match (IsoDate::parse(start), IsoDate::parse(end)) {
+        (Some(start), Some(end)) => start.is_after(&end),
+        _ => false,
+    }
+}
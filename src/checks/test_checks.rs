@@ -0,0 +1,216 @@
+use super::{reference_values, run_checks, Check, CheckRule, ReferenceValues, RuleSet};
+use crate::table::Table;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn row(pairs: &[(&str, &str)]) -> Map<String, Value> {
+    pairs
+        .iter()
+        .map(|(key, value)| (String::from(*key), Value::from(*value)))
+        .collect()
+}
+
+fn table(rows: Vec<Map<String, Value>>) -> Table {
+    Table::from_records(&rows).ok().unwrap()
+}
+
+fn adverse_events() -> Table {
+    table(vec![
+        row(&[
+            ("subject", "001"),
+            ("AESEQ", "1"),
+            ("AESEV", "MILD"),
+            ("AETOXGR", "2"),
+            ("AESTDTC", "2023-04-01"),
+            ("AEENDTC", "2023-04-09"),
+        ]),
+        row(&[
+            ("subject", "001"),
+            ("AESEQ", "1"),
+            ("AESEV", "SEVERE"),
+            ("AETOXGR", "7"),
+            ("AESTDTC", "2023-04-12"),
+            ("AEENDTC", "2023-04-10"),
+        ]),
+        row(&[
+            ("subject", "009"),
+            ("AESEQ", "1"),
+            ("AESEV", "Mild"),
+            ("AETOXGR", "n/a"),
+            ("AESTDTC", "2023-05"),
+            ("AEENDTC", "2023-05-02"),
+        ]),
+        row(&[
+            ("subject", ""),
+            ("AESEQ", "2"),
+            ("AESEV", ""),
+            ("AETOXGR", ""),
+            ("AESTDTC", ""),
+            ("AEENDTC", ""),
+        ]),
+    ])
+}
+
+const RULE_SET_YAML: &str = "
+name: AE checks
+checks:
+  - check: required
+    columns: [subject]
+  - check: unique
+    table: ae
+    columns: [subject, AESEQ]
+  - check: allowed_values
+    column: AESEV
+    values: [MILD, MODERATE, SEVERE]
+  - check: range
+    column: AETOXGR
+    min: 1
+    max: 5
+  - check: date_order
+    start: AESTDTC
+    end: AEENDTC
+  - check: reference
+    column: subject
+    reference_table: dm
+    reference_column: subject
+  - check: required
+    table: dm
+    columns: [subject]
+";
+
+#[wasm_bindgen_test]
+fn test_run_checks_finds_violations() {
+    let rule_set = RuleSet::parse(RULE_SET_YAML).ok().unwrap();
+    let references: ReferenceValues = HashMap::from([(
+        (String::from("dm"), String::from("subject")),
+        HashSet::from([String::from("001"), String::from("002")]),
+    )]);
+    let results = run_checks(&rule_set, "ae", &adverse_events(), &references);
+    let failing: Vec<Vec<usize>> = results.iter().map(|result| result.rows.clone()).collect();
+    // The dm check does not apply to ae.
+    assert_eq!(
+        failing,
+        vec![vec![3], vec![0, 1], vec![2], vec![1, 2], vec![1], vec![2]]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_run_checks_skips_missing_references() {
+    let rule_set = RuleSet {
+        name: String::new(),
+        checks: vec![CheckRule {
+            table: None,
+            check: Check::Reference {
+                column: String::from("subject"),
+                reference_table: String::from("dm"),
+                reference_column: String::from("subject"),
+            },
+        }],
+    };
+    let fields = vec![String::from("subject")];
+    assert_eq!(
+        rule_set
+            .references("ae", &fields)
+            .into_iter()
+            .collect::<Vec<_>>(),
+        vec![(String::from("dm"), String::from("subject"))]
+    );
+    let results = run_checks(&rule_set, "ae", &adverse_events(), &ReferenceValues::new());
+    assert!(results[0].rows.is_empty());
+    assert!(results[0].skipped.is_some());
+}
+
+#[wasm_bindgen_test]
+fn test_rule_set_json_round_trip() {
+    let rule_set = RuleSet::parse(RULE_SET_YAML).ok().unwrap();
+    let json = rule_set.to_json().ok().unwrap();
+    assert!(json.contains("\"check\": \"allowed_values\""));
+    assert_eq!(RuleSet::parse(&json).ok(), Some(rule_set));
+    assert!(RuleSet::parse("{\"checks\": [{\"check\": \"unknown\"}]}").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_run_checks_treats_nulls_as_blank() {
+    let rule_set = RuleSet::parse(RULE_SET_YAML).ok().unwrap();
+    let null_row = |subject: Value, seq: i64| -> Map<String, Value> {
+        [
+            ("subject", subject),
+            ("AESEQ", Value::from(seq)),
+            ("AESEV", Value::Null),
+            ("AETOXGR", Value::Null),
+            ("AESTDTC", Value::Null),
+            ("AEENDTC", Value::from("2023-04-10")),
+        ]
+        .into_iter()
+        .map(|(key, value)| (String::from(key), value))
+        .collect()
+    };
+    let mut tox = row(&[
+        ("subject", "001"),
+        ("AESEV", "MILD"),
+        ("AESTDTC", "2023-04-01"),
+    ]);
+    tox.insert(String::from("AESEQ"), Value::from(3));
+    tox.insert(String::from("AETOXGR"), Value::from(2));
+    tox.insert(String::from("AEENDTC"), Value::Null);
+    let data = table(vec![
+        null_row(Value::Null, 1),
+        null_row(Value::Null, 1),
+        null_row(Value::from("001"), 2),
+        tox,
+    ]);
+    let references: ReferenceValues = HashMap::from([(
+        (String::from("dm"), String::from("subject")),
+        HashSet::from([String::from("001")]),
+    )]);
+    let results = run_checks(&rule_set, "ae", &data, &references);
+    let failing: Vec<Vec<usize>> = results.iter().map(|result| result.rows.clone()).collect();
+    // Only `Required` flags the null subjects: nulls are not duplicates, out of range, outside
+    // the code list or missing from the reference table.
+    assert_eq!(
+        failing,
+        vec![vec![0, 1], vec![], vec![], vec![], vec![], vec![]]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_run_checks_compares_numbers_by_value() {
+    let rule = |check: Check| CheckRule { table: None, check };
+    let rule_set = RuleSet {
+        name: String::new(),
+        checks: vec![
+            rule(Check::AllowedValues {
+                column: String::from("DOSE"),
+                values: vec![String::from("1"), String::from("2.50")],
+            }),
+            rule(Check::Unique {
+                columns: vec![String::from("DOSE")],
+            }),
+            rule(Check::Reference {
+                column: String::from("DOSE"),
+                reference_table: String::from("doses"),
+                reference_column: String::from("DOSE"),
+            }),
+        ],
+    };
+    // Shown as 1.00000, 2.50000, 1.00000 and 1.00000.
+    let data = table(
+        [1.0, 2.5, 1.000001, 1.000002]
+            .into_iter()
+            .map(|dose| Map::from_iter([(String::from("DOSE"), Value::from(dose))]))
+            .collect(),
+    );
+    let doses = table(vec![row(&[("DOSE", "1")]), row(&[("DOSE", "2.5")])]);
+    let references: ReferenceValues = HashMap::from([(
+        (String::from("doses"), String::from("DOSE")),
+        reference_values(&doses, "DOSE"),
+    )]);
+    let results = run_checks(&rule_set, "ex", &data, &references);
+    let failing: Vec<Vec<usize>> = results.iter().map(|result| result.rows.clone()).collect();
+    assert_eq!(failing, vec![vec![2, 3], vec![], vec![2, 3]]);
+    assert_eq!(
+        reference_values(&data, "DOSE"),
+        HashSet::from(["1", "2.5", "1.000001", "1.000002"].map(String::from))
+    );
+}
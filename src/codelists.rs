@@ -0,0 +1,206 @@
+#[cfg(test)]
+mod test_codelists;
+
+use crate::formatting::FORMATTED_TEXT_PREFIX;
+use crate::StyewardConfig;
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+use gloo_net::http::Request;
+use serde::{Deserialize, Serialize};
+use url::Url;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::console::error_1;
+use yew::prelude::*;
+
+/// Hidden field holding the text the grid filters a decoded cell on, "code — decode", so either
+/// form matches.
+pub const FILTER_TEXT_PREFIX: &str = "__filter.";
+
+pub enum CodelistError {
+    EndpointError(String),
+    GlooError(String),
+    HttpStatusError(String),
+    ParseError(String),
+}
+
+impl CodelistError {
+    pub fn message(&self) -> &String {
+        match self {
+            CodelistError::EndpointError(msg)
+            | CodelistError::GlooError(msg)
+            | CodelistError::HttpStatusError(msg)
+            | CodelistError::ParseError(msg) => msg,
+        }
+    }
+}
+
+/// Terms of one code list and the columns holding its codes.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct Codelist {
+    pub name: String,
+    /// Column names, or `table.column` for a column of one table only.
+    #[serde(default)]
+    pub columns: Vec<String>,
+    /// Decodes by code.
+    pub terms: BTreeMap<String, String>,
+}
+
+impl Codelist {
+    pub fn applies_to(&self, table: &str, column: &str) -> bool {
+        self.columns
+            .iter()
+            .any(|entry| match entry.rsplit_once('.') {
+                Some((entry_table, entry_column)) => {
+                    entry_table.eq_ignore_ascii_case(table)
+                        && entry_column.eq_ignore_ascii_case(column)
+                }
+                None => entry.eq_ignore_ascii_case(column),
+            })
+    }
+
+    /// The cell text of a code, "code — decode", `None` for codes not in the list.
+    pub fn render(&self, code: &str) -> Option<String> {
+        let decode = self.terms.get(code.trim())?;
+        Some(format!("{code} \u{2014} {decode}"))
+    }
+}
+
+/// Code lists by priority, the first one applying to a column decodes it, e.g.
+/// `{"codelists": [{"name": "SEX", "columns": ["SEX"], "terms": {"1": "Male", "2": "Female"}}]}`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct CodelistRegistry {
+    #[serde(default)]
+    pub codelists: Vec<Codelist>,
+}
+
+impl CodelistRegistry {
+    pub fn parse(json: &str) -> Result<CodelistRegistry, CodelistError> {
+        serde_json::from_str::<CodelistRegistry>(json).map_err(|error| {
+            CodelistError::ParseError(format!("codelist error: invalid registry: {error}"))
+        })
+    }
+
+    /// This registry's code lists first, then those of `fallback`.
+    pub fn with_fallback(&self, fallback: &CodelistRegistry) -> CodelistRegistry {
+        CodelistRegistry {
+            codelists: self
+                .codelists
+                .iter()
+                .chain(fallback.codelists.iter())
+                .cloned()
+                .collect(),
+        }
+    }
+
+    pub fn for_column(&self, table: &str, column: &str) -> Option<&Codelist> {
+        self.codelists
+            .iter()
+            .find(|codelist| codelist.applies_to(table, column))
+    }
+
+    /// The code list of each column that has one.
+    pub fn columns<'a>(&'a self, table: &str, fields: &[String]) -> Vec<(String, &'a Codelist)> {
+        fields
+            .iter()
+            .filter_map(|field| Some((field.clone(), self.for_column(table, field)?)))
+            .collect()
+    }
+
+    /// Adds the `FORMATTED_TEXT_PREFIX` and `FILTER_TEXT_PREFIX` keys of the coded cells.
+    pub fn decode_rows(
+        &self,
+        table: &str,
+        fields: &[String],
+        rows: &mut [HashMap<String, String>],
+    ) {
+        let columns = self.columns(table, fields);
+        if columns.is_empty() {
+            return;
+        }
+        for row in rows.iter_mut() {
+            let texts: Vec<(&String, String)> = columns
+                .iter()
+                .filter_map(|(column, codelist)| Some((column, codelist.render(row.get(column)?)?)))
+                .collect();
+            for (column, text) in texts {
+                row.insert(format!("{FILTER_TEXT_PREFIX}{column}"), text.clone());
+                row.insert(format!("{FORMATTED_TEXT_PREFIX}{column}"), text);
+            }
+        }
+    }
+}
+
+thread_local! {
+    static SERVED: RefCell<HashMap<String, Rc<CodelistRegistry>>> = RefCell::new(HashMap::new());
+}
+
+/// The registry served at `/api/v1/{schema}/codelists`, empty when there is none.
+pub(crate) async fn load_served(
+    config: &StyewardConfig,
+    schema: &str,
+) -> Result<Rc<CodelistRegistry>, CodelistError> {
+    if let Some(registry) = SERVED.with(|served| served.borrow().get(schema).cloned()) {
+        return Ok(registry);
+    }
+    let endpoint = format!(
+        "{}://{}:{}/api/v1/{schema}/codelists",
+        config.scheme, config.host, config.port
+    );
+    let url = Url::parse(&endpoint)
+        .map_err(|error| CodelistError::EndpointError(format!("codelist error: {error}")))?;
+    let response = Request::get(url.as_str())
+        .send()
+        .await
+        .map_err(|error| CodelistError::GlooError(format!("codelist error: {error}")))?;
+    let registry = if response.status() == 404 {
+        CodelistRegistry::default()
+    } else if !response.ok() {
+        return Err(CodelistError::HttpStatusError(format!(
+            "codelist error: status {} loading {endpoint}",
+            response.status()
+        )));
+    } else {
+        let json = response
+            .text()
+            .await
+            .map_err(|error| CodelistError::GlooError(format!("codelist error: {error}")))?;
+        CodelistRegistry::parse(&json)?
+    };
+    let registry = Rc::new(registry);
+    SERVED.with(|served| {
+        served
+            .borrow_mut()
+            .insert(String::from(schema), registry.clone())
+    });
+    Ok(registry)
+}
+
+/// The registry served for the schema, empty until it loads or when the backend has none.
+#[hook]
+pub fn use_served_codelists(schema: String) -> Rc<CodelistRegistry> {
+    let registry = use_state(|| Rc::new(CodelistRegistry::default()));
+    let config = use_context::<StyewardConfig>();
+    {
+        let registry = registry.clone();
+        use_effect_with_deps(
+            move |schema| {
+                if let Some(config) = config {
+                    let schema = schema.clone();
+                    spawn_local(async move {
+                        match load_served(&config, &schema).await {
+                            Ok(loaded) => registry.set(loaded),
+                            Err(error) => error_1(&JsValue::from(error.message())),
+                        }
+                    });
+                }
+                || ()
+            },
+            schema,
+        );
+    }
+    (*registry).clone()
+}
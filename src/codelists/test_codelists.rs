@@ -0,0 +1,67 @@
+use super::{Codelist, CodelistRegistry};
+use std::collections::{BTreeMap, HashMap};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const REGISTRY_JSON: &str = r#"{
+    "codelists": [
+        {"name": "SEX", "columns": ["SEX"], "terms": {"1": "Male", "2": "Female"}},
+        {"name": "ARM", "columns": ["dm.ARMCD"], "terms": {"A": "Placebo", "B": "Drug 10 mg"}}
+    ]
+}"#;
+
+fn row(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(key, value)| (String::from(*key), String::from(*value)))
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn test_registry_matches_columns() {
+    let registry = CodelistRegistry::parse(REGISTRY_JSON).ok().unwrap();
+    assert_eq!(
+        registry
+            .for_column("ae", "sex")
+            .map(|list| list.name.as_str()),
+        Some("SEX")
+    );
+    assert_eq!(
+        registry
+            .for_column("DM", "armcd")
+            .map(|list| list.name.as_str()),
+        Some("ARM")
+    );
+    assert!(registry.for_column("ex", "ARMCD").is_none());
+
+    let local = CodelistRegistry {
+        codelists: vec![Codelist {
+            name: String::from("SEX (local)"),
+            columns: vec![String::from("SEX")],
+            terms: BTreeMap::from([(String::from("M"), String::from("Male"))]),
+        }],
+    };
+    let merged = local.with_fallback(&registry);
+    assert_eq!(
+        merged
+            .for_column("dm", "SEX")
+            .map(|list| list.name.as_str()),
+        Some("SEX (local)")
+    );
+    assert!(CodelistRegistry::parse("{\"codelists\": [{\"name\": \"SEX\"}]}").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_decode_rows() {
+    let registry = CodelistRegistry::parse(REGISTRY_JSON).ok().unwrap();
+    let fields = vec![String::from("SEX"), String::from("ARMCD")];
+    let mut rows = vec![
+        row(&[("SEX", "1"), ("ARMCD", "B")]),
+        row(&[("SEX", "9"), ("ARMCD", "")]),
+    ];
+    registry.decode_rows("dm", &fields, &mut rows);
+    assert_eq!(rows[0]["__text.SEX"], "1 \u{2014} Male");
+    assert_eq!(rows[0]["__filter.ARMCD"], "B \u{2014} Drug 10 mg");
+    // Codes outside the list keep their raw value.
+    assert!(!rows[1].contains_key("__text.SEX"));
+    assert!(!rows[1].contains_key("__filter.ARMCD"));
+}
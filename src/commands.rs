@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod test_commands;
+
+use std::collections::HashMap;
+
+pub enum CommandError {
+    KeyBindingError(String),
+}
+
+impl CommandError {
+    pub fn message(&self) -> &String {
+        match self {
+            CommandError::KeyBindingError(msg) => msg,
+        }
+    }
+}
+
+/// An action of the app, listed in the command palette and run by its key binding.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Command {
+    /// Stable id, configured key bindings are saved under it.
+    pub id: String,
+    pub title: String,
+    /// The key binding used unless the user configured another, e.g. "Ctrl+Shift+E".
+    pub default_keys: Option<String>,
+}
+
+impl Command {
+    pub fn new(id: &str, title: &str) -> Command {
+        Command {
+            id: id.to_owned(),
+            title: title.to_owned(),
+            default_keys: None,
+        }
+    }
+
+    pub fn with_keys(self, keys: &str) -> Command {
+        Command {
+            default_keys: Some(keys.to_owned()),
+            ..self
+        }
+    }
+
+    /// The binding running the command: the configured one, where an empty one removes the
+    /// default, or else the default.
+    pub fn binding(&self, configured: &HashMap<String, String>) -> Option<KeyBinding> {
+        let keys = match configured.get(&self.id) {
+            Some(keys) => keys,
+            None => self.default_keys.as_ref()?,
+        };
+        KeyBinding::parse(keys).ok()
+    }
+}
+
+/// A key pressed with modifiers, as a keydown event reports it.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct KeyPress {
+    pub key: String,
+    /// Control, or Command on macOS.
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl KeyPress {
+    /// Modifier keys pressed on their own, which bind to nothing.
+    pub fn is_modifier(&self) -> bool {
+        matches!(
+            self.key.as_str(),
+            "Control" | "Shift" | "Alt" | "Meta" | "AltGraph" | "CapsLock" | "OS"
+        )
+    }
+}
+
+/// A key with modifiers, written as "Ctrl+Shift+Z".
+#[derive(Clone, PartialEq, Debug)]
+pub struct KeyBinding {
+    /// The key, lower case.
+    pub key: String,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl KeyBinding {
+    pub fn parse(text: &str) -> Result<KeyBinding, CommandError> {
+        let mut binding = KeyBinding {
+            key: String::new(),
+            ctrl: false,
+            shift: false,
+            alt: false,
+        };
+        let parts: Vec<&str> = text.split('+').map(str::trim).collect();
+        let (key, modifiers) = match parts.split_last() {
+            Some((key, modifiers)) if !key.is_empty() => (key, modifiers),
+            _ => {
+                return Err(CommandError::KeyBindingError(format!(
+                    "command error: no key in the binding \"{text}\"."
+                )))
+            }
+        };
+        for modifier in modifiers {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" | "cmd" | "command" | "meta" => binding.ctrl = true,
+                "shift" => binding.shift = true,
+                "alt" | "option" => binding.alt = true,
+                _ => {
+                    return Err(CommandError::KeyBindingError(format!(
+                        "command error: unknown modifier {modifier} in the binding \"{text}\"."
+                    )))
+                }
+            }
+        }
+        binding.key = key.to_lowercase();
+        Ok(binding)
+    }
+
+    /// The binding a key press would record.
+    pub fn from_press(press: &KeyPress) -> KeyBinding {
+        KeyBinding {
+            key: press.key.to_lowercase(),
+            ctrl: press.ctrl,
+            shift: press.shift,
+            alt: press.alt,
+        }
+    }
+
+    pub fn matches(&self, press: &KeyPress) -> bool {
+        *self == KeyBinding::from_press(press)
+    }
+
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::<String>::new();
+        if self.ctrl {
+            parts.push(String::from("Ctrl"));
+        }
+        if self.alt {
+            parts.push(String::from("Alt"));
+        }
+        if self.shift {
+            parts.push(String::from("Shift"));
+        }
+        // Letters upper case, named keys such as "escape" capitalized.
+        let mut chars = self.key.chars();
+        parts.push(match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        });
+        parts.join("+")
+    }
+}
+
+/// How well `query` matches `text`, higher is better, `None` when the characters of `query` do
+/// not all appear in `text` in order. Matches at word starts and runs of matched characters
+/// count most, so "opae" finds "Open sdtm.ae" before "Open adam.adae".
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous_match = None::<usize>;
+    for wanted in query {
+        let index = (next..text.len()).find(|index| text[*index] == wanted)?;
+        score += 1;
+        if index == 0 || !text[index - 1].is_alphanumeric() {
+            score += 8;
+        }
+        if previous_match.is_some_and(|previous| previous + 1 == index) {
+            score += 5;
+        }
+        previous_match = Some(index);
+        next = index + 1;
+    }
+    Some(score)
+}
+
+/// The items matching `query`, best first. Of equal matches the shorter text comes first, then
+/// the items keep their order.
+pub fn rank<'a, T>(query: &str, items: &'a [T], text: impl Fn(&T) -> &str) -> Vec<&'a T> {
+    let mut matches: Vec<(u32, usize, &T)> = items
+        .iter()
+        .filter_map(|item| {
+            let text = text(item);
+            fuzzy_score(query, text).map(|score| (score, text.len(), item))
+        })
+        .collect();
+    matches.sort_by(|(a, a_len, _), (b, b_len, _)| b.cmp(a).then(a_len.cmp(b_len)));
+    matches.into_iter().map(|(_, _, item)| item).collect()
+}
@@ -0,0 +1,83 @@
+use super::{fuzzy_score, rank, Command, KeyBinding, KeyPress};
+use std::collections::HashMap;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn press(key: &str, ctrl: bool, shift: bool) -> KeyPress {
+    KeyPress {
+        key: String::from(key),
+        ctrl,
+        shift,
+        alt: false,
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_parse_key_bindings() {
+    let binding = KeyBinding::parse("Ctrl+Shift+Z").ok().unwrap();
+    assert!(binding.matches(&press("Z", true, true)));
+    assert!(!binding.matches(&press("z", true, false)));
+    assert_eq!(binding.describe(), "Ctrl+Shift+Z");
+
+    let binding = KeyBinding::parse("cmd + k").ok().unwrap();
+    assert!(binding.matches(&press("k", true, false)));
+    assert_eq!(binding.describe(), "Ctrl+K");
+
+    let binding = KeyBinding::parse("Alt+ArrowUp").ok().unwrap();
+    assert_eq!(binding.describe(), "Alt+Arrowup");
+
+    assert!(KeyBinding::parse("").is_err());
+    assert!(KeyBinding::parse("Ctrl+").is_err());
+    assert!(KeyBinding::parse("Hyper+K").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_recorded_binding_round_trips() {
+    let recorded = KeyBinding::from_press(&press("E", true, true));
+    let parsed = KeyBinding::parse(&recorded.describe()).ok().unwrap();
+    assert_eq!(parsed, recorded);
+    assert!(press("Shift", false, true).is_modifier());
+}
+
+#[wasm_bindgen_test]
+fn test_configured_bindings() {
+    let undo = Command::new("history.undo", "Undo").with_keys("Ctrl+Z");
+    let mut configured = HashMap::new();
+    assert!(undo
+        .binding(&configured)
+        .is_some_and(|binding| binding.matches(&press("z", true, false))));
+    configured.insert(String::from("history.undo"), String::from("Alt+U"));
+    assert_eq!(
+        undo.binding(&configured).map(|binding| binding.describe()),
+        Some(String::from("Alt+U"))
+    );
+    // An empty binding unbinds the command.
+    configured.insert(String::from("history.undo"), String::new());
+    assert_eq!(undo.binding(&configured), None);
+    assert_eq!(
+        Command::new("table.refresh", "Refresh").binding(&configured),
+        None
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_fuzzy_matching() {
+    assert_eq!(fuzzy_score("xyz", "Export CSV"), None);
+    assert_eq!(fuzzy_score("", "Export CSV"), Some(0));
+    assert!(fuzzy_score("ecsv", "Export CSV").is_some());
+    assert!(fuzzy_score("opae", "Open sdtm.ae") > fuzzy_score("opae", "Open adam.adae"));
+
+    let titles = [
+        "Go to Home",
+        "Open sdtm.ae",
+        "Export CSV",
+        "Open adam.adae",
+        "Open sdtm.dm",
+    ];
+    let ranked: Vec<&&str> = rank("ae", &titles, |title| title);
+    assert_eq!(ranked, vec![&"Open sdtm.ae", &"Open adam.adae"]);
+    let ranked: Vec<&&str> = rank("open", &titles, |title| title);
+    assert_eq!(
+        ranked,
+        vec![&"Open sdtm.ae", &"Open sdtm.dm", &"Open adam.adae"]
+    );
+}
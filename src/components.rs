@@ -1,21 +1,46 @@
+pub mod checks_panel;
+pub mod codelists_panel;
+pub mod command_palette;
 pub mod current_selection;
+pub mod formatting_panel;
+pub mod live_region;
+pub mod locale;
+#[cfg(feature = "native-grid")]
+pub mod native_grid;
 pub mod nav;
+pub mod offline_panel;
+pub mod pivot_builder;
+pub mod profile_panel;
+pub mod row_cards;
+pub mod row_detail;
 pub mod schema;
+pub mod search_page;
+pub mod snapshot_compare;
+pub mod store;
 pub mod table_description;
 pub mod table_selection;
 pub mod table_visualization;
+pub mod undo_history;
+pub mod views_panel;
 
-use crate::state::StyewardState;
+use crate::state::{ColumnFilter, StyewardAction};
 
 use current_selection::CurrentSelection;
+use locale::use_locale;
 use nav::NavBar;
 use schema::SchemaSelection;
+use serde::Deserialize;
+use store::StoreProvider;
+use table_description::TableDescription;
 use table_selection::TableSelection;
 use table_visualization::TableVisualization;
+use undo_history::UndoHistory;
 use yew::prelude::*;
+use yew_router::prelude::use_location;
 
 #[function_component]
 pub fn Home() -> Html {
+    let locale = use_locale();
     html! {
         <div class="container-fluid bg-light m-0 p-0" style="height: 100vh">
             <div class="row">
@@ -24,7 +49,7 @@ pub fn Home() -> Html {
             <div class="row bg-light text-dark px-3 py-4">
                 <div class="col">
                     <span class="align-middle">
-                        {"Navigate to the DataTable page to visualize data tables in real time."}
+                        {locale.tr("home-intro")}
                     </span>
                 </div>
             </div>
@@ -32,12 +57,47 @@ pub fn Home() -> Html {
     }
 }
 
+#[derive(Properties, PartialEq)]
+pub struct StyewardDataTablesProp {
+    #[prop_or_default]
+    pub schema: Option<String>,
+    #[prop_or_default]
+    pub table: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ViewQuery {
+    view: Option<String>,
+    /// `column` and `value` pre-filter the grid, see `SearchPage`.
+    column: Option<String>,
+    value: Option<String>,
+}
+
 #[function_component]
-pub fn StyewardDataTables() -> Html {
-    let initial_state = use_state(|| StyewardState {
-        current_schema: None,
-        current_table: None,
-    });
+pub fn StyewardDataTables(prop: &StyewardDataTablesProp) -> Html {
+    let query = use_location().and_then(|location| location.query::<ViewQuery>().ok());
+    let (view, filter) = match query {
+        Some(query) => {
+            let filter = match (query.column, query.value) {
+                (Some(column), Some(value)) => Some(ColumnFilter { column, value }),
+                _ => None,
+            };
+            (query.view, filter)
+        }
+        None => (None, None),
+    };
+    // Without a table in the route, the page keeps the selection it has.
+    let route = match (prop.schema.clone(), prop.table.clone()) {
+        (Some(schema), Some(table)) => Some(StyewardAction::OpenTable {
+            schema,
+            table,
+            view,
+            filter,
+        }),
+        _ => None,
+    };
+
+    let locale = use_locale();
 
     html! {
         <div class="container-fluid bg-light m-0 p-0" style="height: 100vh">
@@ -46,25 +106,29 @@ pub fn StyewardDataTables() -> Html {
             </div>
             <div class="row bg-light text-dark px-3 py-4">
                 <div class="col">
-                    <span class="align-middle">{"Explore Agenus clinical trial data by schema and table."}</span>
+                    <span class="align-middle">{locale.tr("data-tables-intro")}</span>
                 </div>
             </div>
             <div class="row px-3">
-                <ContextProvider<UseStateHandle<StyewardState>> context={initial_state}>
+                <StoreProvider route={route} sync_url={true}>
                     <div class="container px-2">
-                        <div class="row">
-                            <div class="col shadow-sm px-0 ms-3 flex" style="max-width: 300px;"><SchemaSelection/></div>
-                            <div class="col shadow-sm px-0 ms-3 me-3 flex" style="max-width: 300px;"><TableSelection/></div>
-                        </div>
-                         <div class="row">
-                            <div class="col-auto px-0 ms-3 flex" style="max-width: 300px;"> <CurrentSelection/></div>
-                        </div>
+                        <section aria-label={locale.tr("table-selection-label")}>
+                            <div class="row">
+                                <div class="col shadow-sm px-0 ms-3 flex" style="max-width: 300px;"><SchemaSelection/></div>
+                                <div class="col shadow-sm px-0 ms-3 me-3 flex" style="max-width: 300px;"><TableSelection/></div>
+                                <div class="col-auto px-0 ms-auto me-3"><UndoHistory/></div>
+                            </div>
+                            <div class="row">
+                                <div class="col-auto px-0 ms-3 flex" style="max-width: 300px;"> <CurrentSelection/></div>
+                                <div class="col-auto px-0 ms-3 flex" style="max-width: 420px;"><TableDescription/></div>
+                            </div>
+                        </section>
 
                         <div class="row p-0 m-0 justify-content-md-center" style="min-height: 500px; height: calc(100vh - 290px);">
                             <TableVisualization/>
                         </div>
                     </div>
-                </ContextProvider<UseStateHandle<StyewardState>>>
+                </StoreProvider>
             </div>
         </div>
     }
@@ -0,0 +1,232 @@
+use crate::checks::{reference_values, run_checks, CheckResult, ReferenceValues, RuleSet};
+use crate::components::locale::use_locale;
+use crate::components::views_panel::download_text;
+use crate::table::Table;
+use crate::table_cache;
+use crate::workspace::Workspace;
+use crate::StyewardConfig;
+
+use std::rc::Rc;
+
+use gloo_file::futures::read_as_text;
+use gloo_file::File;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{Event, HtmlInputElement};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct ChecksPanelProp {
+    pub schema: String,
+    pub table: String,
+    pub fields: Vec<String>,
+    pub data: Rc<Table>,
+    /// Receives the description of a check and the positions of the rows failing it.
+    pub on_select: Callback<(String, Vec<usize>)>,
+}
+
+/// Runs the workspace's data quality checks over the table and lists the violations.
+#[function_component]
+pub fn ChecksPanel(prop: &ChecksPanelProp) -> Html {
+    let locale = use_locale();
+    let rule_set = use_state(|| Workspace::load().checks);
+    let results = use_state(|| None::<Vec<CheckResult>>);
+    let status = use_state(|| None::<String>);
+    // Bumped by every run, a run that finds it changed was superseded and drops its results.
+    let generation = use_mut_ref(|| 0_u32);
+    let config = use_context::<StyewardConfig>();
+
+    {
+        let results = results.clone();
+        let generation = generation.clone();
+        use_effect_with_deps(
+            move |(rule_set, schema, table, fields, data)| {
+                results.set(None);
+                *generation.borrow_mut() += 1;
+                let run = *generation.borrow();
+                let (rule_set, schema, table, fields, data) = (
+                    rule_set.clone(),
+                    schema.clone(),
+                    table.clone(),
+                    fields.clone(),
+                    data.clone(),
+                );
+                spawn_local(async move {
+                    let references =
+                        load_references(config.as_ref(), &rule_set, &schema, &table, &fields).await;
+                    if *generation.borrow() == run {
+                        results.set(Some(run_checks(&rule_set, &table, &data, &references)));
+                    }
+                });
+                || ()
+            },
+            (
+                (*rule_set).clone(),
+                prop.schema.clone(),
+                prop.table.clone(),
+                prop.fields.clone(),
+                prop.data.clone(),
+            ),
+        );
+    }
+
+    let update_rule_set = {
+        let rule_set = rule_set.clone();
+        move |new_rule_set: RuleSet| {
+            let mut workspace = Workspace::load();
+            workspace.checks = new_rule_set.clone();
+            workspace.save();
+            rule_set.set(new_rule_set);
+        }
+    };
+    let on_import = {
+        let status = status.clone();
+        let update_rule_set = update_rule_set.clone();
+        Callback::from(move |event: Event| {
+            let input = match event.target_dyn_into::<HtmlInputElement>() {
+                Some(input) => input,
+                None => return,
+            };
+            let file = match input.files().and_then(|files| files.get(0)) {
+                Some(file) => File::from(file),
+                None => return,
+            };
+            input.set_value("");
+            let status = status.clone();
+            let update_rule_set = update_rule_set.clone();
+            spawn_local(async move {
+                let text = match read_as_text(&file).await {
+                    Ok(text) => text,
+                    Err(error) => {
+                        status.set(Some(locale.tr_args(
+                            "file-unreadable",
+                            &[("error", error.to_string().into())],
+                        )));
+                        return;
+                    }
+                };
+                match RuleSet::parse(&text) {
+                    Ok(imported) => {
+                        status.set(Some(locale.tr_args(
+                            "checks-imported",
+                            &[("checks", imported.checks.len().into())],
+                        )));
+                        update_rule_set(imported);
+                    }
+                    Err(error) => status.set(Some(error.message().clone())),
+                }
+            });
+        })
+    };
+    let on_export = {
+        let rule_set = rule_set.clone();
+        let status = status.clone();
+        Callback::from(move |_: MouseEvent| match rule_set.to_json() {
+            Ok(json) => download_text(
+                String::from("checks.json"),
+                String::from("application/json"),
+                json,
+            ),
+            Err(error) => status.set(Some(error.message().clone())),
+        })
+    };
+    let on_clear = {
+        let update_rule_set = update_rule_set.clone();
+        Callback::from(move |_: MouseEvent| update_rule_set(RuleSet::default()))
+    };
+
+    let result_rows = match &*results {
+        None => {
+            html! { <li class="list-group-item px-2 py-1 text-muted">{locale.tr("checks-running")}</li> }
+        }
+        Some(results) if results.is_empty() => html! {
+            <li class="list-group-item px-2 py-1 text-muted">{locale.tr("checks-none")}</li>
+        },
+        Some(results) => html! {
+            {for results.iter().map(|result| {
+                let description = result.rule.check.describe().text(locale);
+                let (badge_class, badge) = match &result.skipped {
+                    Some(_) => ("badge bg-secondary", locale.tr("checks-skipped")),
+                    None if result.rows.is_empty() => ("badge bg-success", String::from("0")),
+                    None => ("badge bg-danger", locale.tr_args("count", &[("count", result.rows.len().into())])),
+                };
+                let onclick = {
+                    let on_select = prop.on_select.clone();
+                    let description = description.clone();
+                    let rows = result.rows.clone();
+                    Callback::from(move |_: MouseEvent| on_select.emit((description.clone(), rows.clone())))
+                };
+                let label = if result.rows.is_empty() {
+                    html! { <span>{description.clone()}</span> }
+                } else {
+                    html! {
+                        <button type="button" class="btn btn-link btn-sm p-0 text-start" {onclick}>{description.clone()}</button>
+                    }
+                };
+                html! {
+                    <li class="list-group-item px-2 py-1 d-flex align-items-start gap-2" title={result.skipped.as_ref().map(|skipped| skipped.text(locale))}>
+                        <span class={badge_class}>{badge}</span>
+                        {label}
+                    </li>
+                }
+            })}
+        },
+    };
+    let violations: usize = results
+        .as_ref()
+        .map(|results| results.iter().map(|result| result.rows.len()).sum())
+        .unwrap_or(0);
+    let status_line = match &*status {
+        Some(message) => html! { <div class="text-muted mt-2">{message.clone()}</div> },
+        None => html! {},
+    };
+    let title = match rule_set.name.as_str() {
+        "" => locale.tr("table-checks"),
+        name => locale.tr_args("checks-named", &[("name", name.into())]),
+    };
+
+    html! {
+        <div class="card shadow position-absolute start-0 p-2" style="top: 100%; z-index: 1050; width: 340px; font-size: 12px;">
+            <div class="fw-semibold">{title}</div>
+            <div class="text-muted mb-1">
+                {locale.tr_args(
+                    "checks-violations",
+                    &[("violations", violations.into()), ("rows", prop.data.len().into())],
+                )}
+            </div>
+            <ul class="list-group list-group-flush mb-2" style="max-height: 50vh; overflow-y: auto;">{result_rows}</ul>
+            <div class="d-flex gap-2">
+                <label class="btn btn-sm btn-outline-dark mb-0">
+                    {locale.tr("checks-import")}
+                    <input type="file" accept="application/json,.json,.yaml,.yml" class="visually-hidden" onchange={on_import}/>
+                </label>
+                <button type="button" class="btn btn-sm btn-outline-dark" onclick={on_export}>{locale.tr("checks-export")}</button>
+                <button type="button" class="btn btn-sm btn-outline-danger" onclick={on_clear}>{locale.tr("clear")}</button>
+            </div>
+            {status_line}
+        </div>
+    }
+}
+
+/// The values of the columns the reference checks point to, from the cache or loaded. Tables
+/// that fail to load are left out, and their checks skipped.
+async fn load_references(
+    config: Option<&StyewardConfig>,
+    rule_set: &RuleSet,
+    schema: &String,
+    table: &str,
+    fields: &[String],
+) -> ReferenceValues {
+    let mut references = ReferenceValues::new();
+    let config = match config {
+        Some(config) => config,
+        None => return references,
+    };
+    for (reference_table, reference_column) in rule_set.references(table, fields) {
+        if let Some(data) = table_cache::latest_or_load(config, schema, &reference_table).await {
+            let reference = &data.table;
+            let values = reference_values(reference, &reference_column);
+            references.insert((reference_table, reference_column), values);
+        }
+    }
+    references
+}
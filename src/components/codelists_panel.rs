@@ -0,0 +1,117 @@
+use crate::codelists::CodelistRegistry;
+use crate::components::locale::use_locale;
+
+use gloo_file::futures::read_as_text;
+use gloo_file::File;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{Event, HtmlInputElement};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct CodelistsPanelProp {
+    pub table: String,
+    pub fields: Vec<String>,
+    /// Every code list the grid decodes with: imported, served and from the define.xml.
+    pub registry: CodelistRegistry,
+    /// Number of code lists imported from a local file.
+    pub imported: usize,
+    pub on_import: Callback<CodelistRegistry>,
+    pub show_raw_codes: bool,
+    pub on_show_raw_codes: Callback<bool>,
+}
+
+/// The code lists decoding the table's columns, the raw codes toggle and the local import.
+#[function_component]
+pub fn CodelistsPanel(prop: &CodelistsPanelProp) -> Html {
+    let locale = use_locale();
+    let status = use_state(|| None::<String>);
+    let on_import = {
+        let status = status.clone();
+        let on_import = prop.on_import.clone();
+        Callback::from(move |event: Event| {
+            let input = match event.target_dyn_into::<HtmlInputElement>() {
+                Some(input) => input,
+                None => return,
+            };
+            let file = match input.files().and_then(|files| files.get(0)) {
+                Some(file) => File::from(file),
+                None => return,
+            };
+            input.set_value("");
+            let status = status.clone();
+            let on_import = on_import.clone();
+            spawn_local(async move {
+                let text = match read_as_text(&file).await {
+                    Ok(text) => text,
+                    Err(error) => {
+                        status.set(Some(locale.tr_args(
+                            "file-unreadable",
+                            &[("error", error.to_string().into())],
+                        )));
+                        return;
+                    }
+                };
+                match CodelistRegistry::parse(&text) {
+                    Ok(imported) => {
+                        status.set(Some(locale.tr_args(
+                            "codelists-imported",
+                            &[("codelists", imported.codelists.len().into())],
+                        )));
+                        on_import.emit(imported);
+                    }
+                    Err(error) => status.set(Some(error.message().clone())),
+                }
+            });
+        })
+    };
+    let on_clear = {
+        let on_import = prop.on_import.clone();
+        Callback::from(move |_: MouseEvent| on_import.emit(CodelistRegistry::default()))
+    };
+    let on_show_raw_codes = {
+        let on_show_raw_codes = prop.on_show_raw_codes.clone();
+        let show_raw_codes = prop.show_raw_codes;
+        Callback::from(move |_: Event| on_show_raw_codes.emit(!show_raw_codes))
+    };
+
+    let columns = prop.registry.columns(&prop.table, &prop.fields);
+    let column_rows = if columns.is_empty() {
+        html! { <li class="list-group-item px-2 py-1 text-muted">{locale.tr("codelists-none")}</li> }
+    } else {
+        html! {
+            {for columns.iter().map(|(column, codelist)| html! {
+                <li class="list-group-item px-2 py-1 d-flex justify-content-between gap-2">
+                    <span class="fw-semibold">{column.clone()}</span>
+                    <span class="text-muted">{locale.tr_args("codelists-terms", &[("codelist", codelist.name.clone().into()), ("terms", codelist.terms.len().into())])}</span>
+                </li>
+            })}
+        }
+    };
+    let status_line = match &*status {
+        Some(message) => html! { <div class="text-muted mt-2">{message.clone()}</div> },
+        None => html! {},
+    };
+
+    html! {
+        <div class="card shadow position-absolute start-0 p-2" style="top: 100%; z-index: 1050; width: 320px; font-size: 12px;">
+            <div class="form-check form-switch mb-1">
+                <input class="form-check-input" type="checkbox" id="show-raw-codes"
+                 checked={prop.show_raw_codes} onchange={on_show_raw_codes}/>
+                <label class="form-check-label" for="show-raw-codes">{locale.tr("codelists-show-raw-codes")}</label>
+            </div>
+            <ul class="list-group list-group-flush mb-2" style="max-height: 50vh; overflow-y: auto;">{column_rows}</ul>
+            <div class="d-flex gap-2 align-items-center">
+                <label class="btn btn-sm btn-outline-dark mb-0">
+                    {locale.tr("codelists-import")}
+                    <input type="file" accept="application/json,.json" class="visually-hidden" onchange={on_import}/>
+                </label>
+                if prop.imported > 0 {
+                    <button type="button" class="btn btn-sm btn-outline-danger" onclick={on_clear}>
+                        {locale.tr_args("codelists-clear-imported", &[("codelists", prop.imported.into())])}
+                    </button>
+                }
+            </div>
+            {status_line}
+        </div>
+    }
+}
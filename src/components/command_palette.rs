@@ -0,0 +1,650 @@
+use crate::commands::{rank, Command, KeyBinding, KeyPress};
+use crate::components::locale::use_locale;
+use crate::components::schema::get_schemas;
+use crate::components::table_selection::get_tables;
+use crate::i18n::Locale;
+use crate::workspace::Workspace;
+use crate::{MainRoute, StyewardConfig};
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use futures::future::join_all;
+use gloo_utils::document;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{console::error_1, HtmlElement, HtmlInputElement, KeyboardEvent};
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+/// The command opening the palette, the only one bound in text fields too.
+const OPEN_PALETTE: &str = "palette.open";
+/// Results listed at once, typing narrows them down.
+const MAX_RESULTS: usize = 12;
+
+struct Registered {
+    token: u64,
+    command: Command,
+    action: Callback<()>,
+}
+
+#[derive(Default)]
+struct Registry {
+    last_token: u64,
+    commands: Vec<Registered>,
+    /// Configured key bindings by command id, see `Workspace::keybindings`.
+    keybindings: HashMap<String, String>,
+}
+
+/// The commands registered by the components on the page, see `use_command`.
+#[derive(Clone)]
+pub struct CommandRegistry(Rc<RefCell<Registry>>);
+
+impl PartialEq for CommandRegistry {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl CommandRegistry {
+    fn new() -> CommandRegistry {
+        CommandRegistry(Rc::new(RefCell::new(Registry {
+            keybindings: Workspace::load().keybindings,
+            ..Registry::default()
+        })))
+    }
+
+    /// Registers a command until `unregister` is called with the returned token. A command
+    /// registered again under the same id replaces the earlier one while it is registered.
+    pub fn register(&self, command: Command, action: Callback<()>) -> u64 {
+        let mut registry = self.0.borrow_mut();
+        registry.last_token += 1;
+        let token = registry.last_token;
+        registry.commands.push(Registered {
+            token,
+            command,
+            action,
+        });
+        token
+    }
+
+    pub fn unregister(&self, token: u64) {
+        self.0
+            .borrow_mut()
+            .commands
+            .retain(|registered| registered.token != token);
+    }
+
+    /// The registered commands, by title.
+    pub fn commands(&self) -> Vec<Command> {
+        let registry = self.0.borrow();
+        let mut commands: Vec<Command> = Vec::new();
+        for registered in registry.commands.iter().rev() {
+            if !commands
+                .iter()
+                .any(|command| command.id == registered.command.id)
+            {
+                commands.push(registered.command.clone());
+            }
+        }
+        commands.sort_by(|a, b| a.title.cmp(&b.title));
+        commands
+    }
+
+    pub fn run(&self, id: &str) {
+        let action = self
+            .0
+            .borrow()
+            .commands
+            .iter()
+            .rev()
+            .find(|registered| registered.command.id == id)
+            .map(|registered| registered.action.clone());
+        if let Some(action) = action {
+            action.emit(());
+        }
+    }
+
+    pub fn keybindings(&self) -> HashMap<String, String> {
+        self.0.borrow().keybindings.clone()
+    }
+
+    /// The keys running a command, e.g. "Ctrl+Z", for tooltips.
+    pub fn keys(&self, id: &str) -> Option<String> {
+        let keybindings = self.keybindings();
+        self.commands()
+            .into_iter()
+            .find(|command| command.id == id)?
+            .binding(&keybindings)
+            .map(|binding| binding.describe())
+    }
+
+    /// Binds a command to `keys`, `None` restores its default binding.
+    pub fn set_keybinding(&self, id: &str, keys: Option<String>) {
+        let mut workspace = Workspace::load();
+        match keys {
+            Some(keys) => workspace.keybindings.insert(id.to_owned(), keys),
+            None => workspace.keybindings.remove(id),
+        };
+        workspace.save();
+        self.0.borrow_mut().keybindings = workspace.keybindings;
+    }
+
+    /// The command bound to a key press.
+    fn bound_to(&self, press: &KeyPress) -> Option<String> {
+        let keybindings = self.keybindings();
+        self.commands()
+            .into_iter()
+            .find(|command| {
+                command
+                    .binding(&keybindings)
+                    .is_some_and(|binding| binding.matches(press))
+            })
+            .map(|command| command.id)
+    }
+}
+
+/// Registers `command` while the calling component is mounted. `action` is the one of the last
+/// render, so it sees the component's current state.
+#[hook]
+pub fn use_command(command: Command, action: Callback<()>) {
+    let registry = use_context::<CommandRegistry>();
+    let latest = use_mut_ref(|| action.clone());
+    *latest.borrow_mut() = action;
+    use_effect_with_deps(
+        move |command| {
+            let registered = registry.map(|registry| {
+                let action = Callback::from(move |_| {
+                    let action = latest.borrow().clone();
+                    action.emit(());
+                });
+                (registry.register(command.clone(), action), registry)
+            });
+            move || {
+                if let Some((token, registry)) = registered {
+                    registry.unregister(token);
+                }
+            }
+        },
+        command,
+    );
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum PaletteMode {
+    Commands,
+    Tables,
+    Keybindings,
+}
+
+#[derive(Clone, PartialEq)]
+enum PaletteItem {
+    Command(Command),
+    Table { schema: String, table: String },
+}
+
+impl PaletteItem {
+    fn text(&self, locale: Locale) -> String {
+        match self {
+            PaletteItem::Command(command) => command.title.clone(),
+            PaletteItem::Table { schema, table } => locale.tr_args(
+                "palette-open-table-named",
+                &[("table", format!("{schema}.{table}").into())],
+            ),
+        }
+    }
+}
+
+fn key_press(event: &KeyboardEvent) -> KeyPress {
+    KeyPress {
+        key: event.key(),
+        ctrl: event.ctrl_key() || event.meta_key(),
+        shift: event.shift_key(),
+        alt: event.alt_key(),
+    }
+}
+
+/// Whether the key press lands in a field the user types in.
+fn in_text_field(event: &KeyboardEvent) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<HtmlElement>().ok())
+        .is_some_and(|element| {
+            matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+                || element.is_content_editable()
+        })
+}
+
+#[derive(Properties, PartialEq)]
+pub struct CommandPaletteProp {
+    pub children: Children,
+}
+
+/// Provides the command registry to `children`, runs commands by their key bindings and shows
+/// the palette listing them, with the tables of every schema, on Ctrl+K.
+#[function_component]
+pub fn CommandPalette(prop: &CommandPaletteProp) -> Html {
+    let locale = use_locale();
+    let registry = use_memo(|_| CommandRegistry::new(), ());
+    let registry = (*registry).clone();
+    let mode = use_state(|| None::<PaletteMode>);
+    let query = use_state(String::new);
+    let selected = use_state(|| 0_usize);
+    let tables = use_state(|| None::<Rc<Vec<(String, String)>>>);
+    let recording = use_state(|| None::<String>);
+    let input = use_node_ref();
+    let dialog = use_node_ref();
+    let config = use_context::<StyewardConfig>();
+    let navigator = use_navigator();
+
+    let open = {
+        let mode = mode.clone();
+        let query = query.clone();
+        let selected = selected.clone();
+        let recording = recording.clone();
+        Callback::from(move |target: PaletteMode| {
+            query.set(String::new());
+            selected.set(0);
+            recording.set(None);
+            mode.set(Some(target));
+        })
+    };
+    let close = {
+        let mode = mode.clone();
+        Callback::from(move |_: ()| mode.set(None))
+    };
+
+    {
+        let registry = registry.clone();
+        use_effect_with_deps(
+            move |_| {
+                let listener =
+                    Closure::<dyn Fn(KeyboardEvent)>::new(move |event: KeyboardEvent| {
+                        let press = key_press(&event);
+                        if press.is_modifier() {
+                            return;
+                        }
+                        let Some(id) = registry.bound_to(&press) else {
+                            return;
+                        };
+                        // Text fields keep their own shortcuts, such as undoing typing.
+                        if id != OPEN_PALETTE && in_text_field(&event) {
+                            return;
+                        }
+                        event.prevent_default();
+                        registry.run(&id);
+                    });
+                let _ = document()
+                    .add_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref());
+                move || {
+                    let _ = document().remove_event_listener_with_callback(
+                        "keydown",
+                        listener.as_ref().unchecked_ref(),
+                    );
+                }
+            },
+            (),
+        );
+    }
+    // The tables of every schema, fetched once the palette is first opened.
+    {
+        let tables = tables.clone();
+        let requested = use_mut_ref(|| false);
+        use_effect_with_deps(
+            move |is_open| {
+                if let (true, false, Some(config)) = (*is_open, *requested.borrow(), config) {
+                    *requested.borrow_mut() = true;
+                    spawn_local(async move {
+                        tables.set(Some(Rc::new(fetch_tables(&config).await)));
+                    });
+                }
+                || ()
+            },
+            mode.is_some(),
+        );
+    }
+    {
+        let input = input.clone();
+        let dialog = dialog.clone();
+        use_effect_with_deps(
+            move |mode| {
+                let focused = match mode {
+                    Some(PaletteMode::Keybindings) => dialog.cast::<HtmlElement>(),
+                    Some(_) => input.cast::<HtmlElement>(),
+                    None => None,
+                };
+                if let Some(element) = focused {
+                    let _ = element.focus();
+                }
+                || ()
+            },
+            *mode,
+        );
+    }
+
+    let items: Vec<PaletteItem> = match *mode {
+        Some(PaletteMode::Commands) | Some(PaletteMode::Tables) => {
+            let mut candidates: Vec<PaletteItem> = Vec::new();
+            if *mode == Some(PaletteMode::Commands) {
+                candidates.extend(registry.commands().into_iter().map(PaletteItem::Command));
+            }
+            if *mode == Some(PaletteMode::Tables) || !query.trim().is_empty() {
+                candidates.extend(tables.iter().flat_map(|tables| tables.iter()).map(
+                    |(schema, table)| PaletteItem::Table {
+                        schema: schema.clone(),
+                        table: table.clone(),
+                    },
+                ));
+            }
+            let texts: Vec<(String, PaletteItem)> = candidates
+                .into_iter()
+                .map(|item| (item.text(locale), item))
+                .collect();
+            rank(&query, &texts, |(text, _)| text)
+                .into_iter()
+                .take(MAX_RESULTS)
+                .map(|(_, item)| item.clone())
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+    let run_item = {
+        let registry = registry.clone();
+        let close = close.clone();
+        Callback::from(move |item: PaletteItem| {
+            close.emit(());
+            match item {
+                PaletteItem::Command(command) => registry.run(&command.id),
+                PaletteItem::Table { schema, table } => {
+                    if let Some(navigator) = &navigator {
+                        navigator.push(&MainRoute::DataTableView { schema, table });
+                    }
+                }
+            }
+        })
+    };
+
+    let on_keydown = {
+        let items = items.clone();
+        let selected = selected.clone();
+        let run_item = run_item.clone();
+        let close = close.clone();
+        let recording = recording.clone();
+        let registry = registry.clone();
+        Callback::from(move |event: KeyboardEvent| {
+            if let Some(id) = &*recording {
+                let press = key_press(&event);
+                event.prevent_default();
+                event.stop_propagation();
+                if press.key == "Escape" {
+                    recording.set(None);
+                } else if !press.is_modifier() {
+                    registry.set_keybinding(id, Some(KeyBinding::from_press(&press).describe()));
+                    recording.set(None);
+                }
+                return;
+            }
+            match event.key().as_str() {
+                "Escape" => close.emit(()),
+                "ArrowDown" if !items.is_empty() => {
+                    event.prevent_default();
+                    selected.set((*selected + 1) % items.len());
+                }
+                "ArrowUp" if !items.is_empty() => {
+                    event.prevent_default();
+                    selected.set((*selected + items.len() - 1) % items.len());
+                }
+                "Enter" => {
+                    if let Some(item) = items.get(*selected) {
+                        event.prevent_default();
+                        run_item.emit(item.clone());
+                    }
+                }
+                _ => return,
+            }
+            // The palette's keys are not commands.
+            event.stop_propagation();
+        })
+    };
+
+    let palette = match *mode {
+        None => html! {},
+        Some(current_mode) => {
+            let on_input = {
+                let query = query.clone();
+                let selected = selected.clone();
+                Callback::from(move |event: InputEvent| {
+                    let input: HtmlInputElement = event.target_unchecked_into();
+                    query.set(input.value());
+                    selected.set(0);
+                })
+            };
+            let body = match current_mode {
+                PaletteMode::Keybindings => keybindings_list(locale, &registry, &recording),
+                _ => {
+                    let loading = tables.is_none();
+                    let results = items.iter().enumerate().map(|(index, item)| {
+                        let class = if index == *selected {
+                            "list-group-item list-group-item-action active d-flex"
+                        } else {
+                            "list-group-item list-group-item-action d-flex"
+                        };
+                        let binding = match item {
+                            PaletteItem::Command(command) => command
+                                .binding(&registry.keybindings())
+                                .map(|binding| binding.describe()),
+                            PaletteItem::Table { .. } => None,
+                        };
+                        let onclick = {
+                            let run_item = run_item.clone();
+                            let item = item.clone();
+                            Callback::from(move |_: MouseEvent| run_item.emit(item.clone()))
+                        };
+                        html! {
+                            <li {class} id={format!("palette-option-{index}")} role="option" aria-selected={(index == *selected).to_string()} {onclick} style="cursor: pointer;">
+                                <span>{item.text(locale)}</span>
+                                if let Some(binding) = binding {
+                                    <kbd class="ms-auto">{binding}</kbd>
+                                }
+                            </li>
+                        }
+                    });
+                    let active_option =
+                        (!items.is_empty()).then(|| format!("palette-option-{}", *selected));
+                    let placeholder = match current_mode {
+                        PaletteMode::Tables => locale.tr("palette-open-table"),
+                        _ => locale.tr("palette-placeholder"),
+                    };
+                    html! {
+                        <>
+                            <input ref={input.clone()} type="text" class="form-control border-0 border-bottom rounded-0"
+                             placeholder={placeholder.clone()} aria-label={placeholder}
+                             role="combobox" aria-expanded="true" aria-controls="palette-options"
+                             aria-activedescendant={active_option}
+                             value={(*query).clone()} oninput={on_input}/>
+                            <ul id="palette-options" class="list-group list-group-flush" role="listbox" aria-label={locale.tr("palette-results")}
+                             style="max-height: 50vh; overflow-y: auto;">
+                                {for results}
+                                if items.is_empty() {
+                                    <li class="list-group-item text-muted">
+                                        {locale.tr(if loading { "palette-loading-tables" } else { "palette-no-matches" })}
+                                    </li>
+                                }
+                            </ul>
+                        </>
+                    }
+                }
+            };
+            let on_backdrop = {
+                let close = close.clone();
+                Callback::from(move |_: MouseEvent| close.emit(()))
+            };
+            html! {
+                <div class="position-fixed top-0 start-0 w-100 h-100" style="z-index: 1070; background: rgba(0, 0, 0, 0.3);" onclick={on_backdrop}>
+                    <div ref={dialog.clone()} class="card shadow mx-auto" role="dialog" aria-modal="true" aria-label={locale.tr("palette-label")} tabindex="-1"
+                     style="margin-top: 12vh; width: 560px; max-width: 95vw; font-size: 14px;"
+                     onclick={Callback::from(|event: MouseEvent| event.stop_propagation())}
+                     onkeydown={on_keydown}>
+                        {body}
+                    </div>
+                </div>
+            }
+        }
+    };
+
+    html! {
+        <ContextProvider<CommandRegistry> context={registry}>
+            <BuiltinCommands {open} />
+            {for prop.children.iter()}
+            {palette}
+        </ContextProvider<CommandRegistry>>
+    }
+}
+
+/// Every command with its key binding, changed by pressing the new keys.
+fn keybindings_list(
+    locale: Locale,
+    registry: &CommandRegistry,
+    recording: &UseStateHandle<Option<String>>,
+) -> Html {
+    let keybindings = registry.keybindings();
+    let rows = registry.commands().into_iter().map(|command| {
+        let binding = command
+            .binding(&keybindings)
+            .map(|binding| binding.describe());
+        let configured = keybindings.contains_key(&command.id);
+        let is_recording = recording.as_deref() == Some(command.id.as_str());
+        let on_change = {
+            let recording = recording.clone();
+            let id = command.id.clone();
+            Callback::from(move |_: MouseEvent| recording.set(Some(id.clone())))
+        };
+        let on_remove = {
+            let registry = registry.clone();
+            let recording = recording.clone();
+            let id = command.id.clone();
+            Callback::from(move |_: MouseEvent| {
+                registry.set_keybinding(&id, Some(String::new()));
+                recording.set(None);
+            })
+        };
+        let on_reset = {
+            let registry = registry.clone();
+            let recording = recording.clone();
+            let id = command.id.clone();
+            Callback::from(move |_: MouseEvent| {
+                registry.set_keybinding(&id, None);
+                recording.set(None);
+            })
+        };
+        html! {
+            <li class="list-group-item d-flex align-items-center gap-2">
+                <span>{command.title.clone()}</span>
+                <span class="ms-auto">
+                    if is_recording {
+                        <span class="text-primary">{locale.tr("keybindings-recording")}</span>
+                    } else if let Some(binding) = binding {
+                        <kbd>{binding}</kbd>
+                    } else {
+                        <span class="text-muted">{locale.tr("keybindings-not-bound")}</span>
+                    }
+                </span>
+                <button type="button" class="btn btn-sm btn-outline-secondary" onclick={on_change}>{locale.tr("keybindings-change")}</button>
+                <button type="button" class="btn btn-sm btn-outline-secondary" onclick={on_remove}>{locale.tr("keybindings-remove")}</button>
+                if configured {
+                    <button type="button" class="btn btn-sm btn-link" onclick={on_reset}>{locale.tr("keybindings-reset")}</button>
+                }
+            </li>
+        }
+    });
+    html! {
+        <>
+            <div class="card-header">{locale.tr("keybindings-title")}</div>
+            <ul class="list-group list-group-flush" style="max-height: 60vh; overflow-y: auto;">
+                {for rows}
+            </ul>
+        </>
+    }
+}
+
+/// Schema and table names from the existing endpoints. Schemas that fail to list are logged and
+/// left out.
+async fn fetch_tables(config: &StyewardConfig) -> Vec<(String, String)> {
+    let schema_path = String::from("api/v1/schemas");
+    let schemas = match get_schemas(&config.scheme, &config.host, config.port, &schema_path).await {
+        Ok(schemas) => schemas,
+        Err(error) => {
+            error_1(&JsValue::from(format!(
+                "command palette error: {}",
+                error.message()
+            )));
+            return Vec::new();
+        }
+    };
+    let listed = join_all(schemas.iter().map(|schema| async move {
+        let tables = get_tables(&config.scheme, &config.host, config.port, schema).await;
+        (schema.clone(), tables)
+    }))
+    .await;
+    let mut tables = Vec::new();
+    for (schema, listed) in listed {
+        match listed {
+            Ok(names) => tables.extend(names.into_iter().map(|table| (schema.clone(), table))),
+            Err(error) => error_1(&JsValue::from(format!(
+                "command palette error: {}",
+                error.message()
+            ))),
+        }
+    }
+    tables
+}
+
+#[derive(Properties, PartialEq)]
+struct BuiltinCommandsProp {
+    open: Callback<PaletteMode>,
+}
+
+/// The palette's own commands and the pages of the app.
+#[function_component]
+fn BuiltinCommands(prop: &BuiltinCommandsProp) -> Html {
+    let locale = use_locale();
+    let navigator = use_navigator();
+    let open = |mode: PaletteMode| {
+        let open = prop.open.clone();
+        Callback::from(move |_| open.emit(mode))
+    };
+    let go_to = |route: MainRoute| {
+        let navigator = navigator.clone();
+        Callback::from(move |_| {
+            if let Some(navigator) = &navigator {
+                navigator.push(&route);
+            }
+        })
+    };
+    use_command(
+        Command::new(OPEN_PALETTE, &locale.tr("command-show-all")).with_keys("Ctrl+K"),
+        open(PaletteMode::Commands),
+    );
+    use_command(
+        Command::new("palette.open_table", &locale.tr("palette-open-table")),
+        open(PaletteMode::Tables),
+    );
+    use_command(
+        Command::new("palette.keybindings", &locale.tr("command-keybindings")),
+        open(PaletteMode::Keybindings),
+    );
+    use_command(
+        Command::new("navigate.home", &locale.tr("command-go-home")),
+        go_to(MainRoute::Home),
+    );
+    use_command(
+        Command::new("navigate.data_tables", &locale.tr("command-go-data-tables")),
+        go_to(MainRoute::DataTable),
+    );
+    use_command(
+        Command::new("navigate.search", &locale.tr("command-go-search")),
+        go_to(MainRoute::Search),
+    );
+    html! {}
+}
@@ -0,0 +1,402 @@
+use crate::components::locale::use_locale;
+use crate::formatting::{
+    CellStyle, Comparison, Condition, FormatRule, FormattingError, Highlight, ValueFormat,
+};
+
+use wasm_bindgen::JsCast;
+use web_sys::{Event, HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+const CONDITION_KINDS: [&str; 5] = ["always", "threshold", "matches", "one of", "outside range"];
+/// Message ids of `CONDITION_KINDS`, in the same order.
+const CONDITION_KIND_LABELS: [&str; 5] = [
+    "condition-kind-always",
+    "condition-kind-threshold",
+    "condition-kind-matches",
+    "condition-kind-one-of",
+    "condition-kind-outside-range",
+];
+const VALUE_FORMATS: [&str; 3] = ["as is", "DD-MMM-YYYY", "decimals"];
+/// Message ids of `VALUE_FORMATS`, in the same order.
+const VALUE_FORMAT_LABELS: [&str; 3] = ["format-as-is", "format-date", "format-kind-decimals"];
+
+/// The rule being written in the panel's form, as typed.
+#[derive(Clone, PartialEq, Default)]
+struct RuleDraft {
+    column: String,
+    condition_kind: String,
+    comparison: String,
+    threshold: String,
+    pattern: String,
+    values: String,
+    low_field: String,
+    high_field: String,
+    color: String,
+    background: String,
+    bold: bool,
+    value_format: String,
+    decimals: String,
+}
+
+impl RuleDraft {
+    fn new(column: Option<&String>) -> RuleDraft {
+        RuleDraft {
+            column: column.cloned().unwrap_or_default(),
+            condition_kind: String::from(CONDITION_KINDS[0]),
+            comparison: String::from(Comparison::Greater.label()),
+            value_format: String::from(VALUE_FORMATS[0]),
+            decimals: String::from("1"),
+            ..RuleDraft::default()
+        }
+    }
+
+    fn from_rule(rule: &FormatRule) -> RuleDraft {
+        let mut draft = RuleDraft::new(Some(&rule.column));
+        match &rule.condition {
+            Condition::Always => (),
+            Condition::Threshold {
+                comparison,
+                threshold,
+            } => {
+                draft.condition_kind = String::from("threshold");
+                draft.comparison = String::from(comparison.label());
+                draft.threshold = threshold.to_string();
+            }
+            Condition::Matches { pattern } => {
+                draft.condition_kind = String::from("matches");
+                draft.pattern = pattern.clone();
+            }
+            Condition::OneOf { values } => {
+                draft.condition_kind = String::from("one of");
+                draft.values = values.join(", ");
+            }
+            Condition::OutsideRange {
+                low_field,
+                high_field,
+            } => {
+                draft.condition_kind = String::from("outside range");
+                draft.low_field = low_field.clone();
+                draft.high_field = high_field.clone();
+            }
+        }
+        draft.color = rule
+            .style
+            .color
+            .map(|color| color.label())
+            .unwrap_or("")
+            .to_string();
+        draft.background = rule
+            .style
+            .background
+            .map(|background| background.label())
+            .unwrap_or("")
+            .to_string();
+        draft.bold = rule.style.bold;
+        match rule.format {
+            ValueFormat::AsIs => (),
+            ValueFormat::Date => draft.value_format = String::from(VALUE_FORMATS[1]),
+            ValueFormat::Decimals(decimals) => {
+                draft.value_format = String::from(VALUE_FORMATS[2]);
+                draft.decimals = decimals.to_string();
+            }
+        }
+        draft
+    }
+
+    fn to_rule(&self) -> Result<FormatRule, FormattingError> {
+        let condition = match self.condition_kind.as_str() {
+            "threshold" => Condition::Threshold {
+                comparison: Comparison::from_label(&self.comparison).unwrap_or(Comparison::Greater),
+                threshold: self.threshold.trim().parse::<f64>().map_err(|_| {
+                    FormattingError::RuleError(format!(
+                        "formatting error: \"{}\" is not a number.",
+                        self.threshold
+                    ))
+                })?,
+            },
+            "matches" => Condition::Matches {
+                pattern: self.pattern.clone(),
+            },
+            "one of" => Condition::OneOf {
+                values: self
+                    .values
+                    .split(',')
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+                    .collect(),
+            },
+            "outside range" => Condition::OutsideRange {
+                low_field: self.low_field.clone(),
+                high_field: self.high_field.clone(),
+            },
+            _ => Condition::Always,
+        };
+        let format = match self.value_format.as_str() {
+            "DD-MMM-YYYY" => ValueFormat::Date,
+            "decimals" => {
+                ValueFormat::Decimals(self.decimals.trim().parse::<usize>().map_err(|_| {
+                    FormattingError::RuleError(format!(
+                        "formatting error: \"{}\" is not a number of decimals.",
+                        self.decimals
+                    ))
+                })?)
+            }
+            _ => ValueFormat::AsIs,
+        };
+        let rule = FormatRule {
+            column: self.column.clone(),
+            condition,
+            style: CellStyle {
+                color: Highlight::from_label(&self.color),
+                background: Highlight::from_label(&self.background),
+                bold: self.bold,
+            },
+            format,
+        };
+        rule.validate()?;
+        Ok(rule)
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct FormattingPanelProp {
+    pub fields: Vec<String>,
+    pub rules: Vec<FormatRule>,
+    /// Receives the whole list of rules after every change.
+    pub on_change: Callback<Vec<FormatRule>>,
+}
+
+/// Lists, adds, edits and reorders the formatting rules of a table.
+#[function_component]
+pub fn FormattingPanel(prop: &FormattingPanelProp) -> Html {
+    let locale = use_locale();
+    let draft = use_state(|| RuleDraft::new(prop.fields.first()));
+    let editing = use_state(|| None::<usize>);
+    let status = use_state(|| None::<String>);
+
+    let on_save = {
+        let draft = draft.clone();
+        let editing = editing.clone();
+        let status = status.clone();
+        let rules = prop.rules.clone();
+        let on_change = prop.on_change.clone();
+        let first_field = prop.fields.first().cloned();
+        Callback::from(move |_: MouseEvent| match draft.to_rule() {
+            Ok(rule) => {
+                let mut rules = rules.clone();
+                match *editing {
+                    Some(index) if index < rules.len() => rules[index] = rule,
+                    _ => rules.push(rule),
+                }
+                on_change.emit(rules);
+                editing.set(None);
+                draft.set(RuleDraft::new(first_field.as_ref()));
+                status.set(None);
+            }
+            Err(error) => status.set(Some(error.message().clone())),
+        })
+    };
+
+    let rule_rows = prop.rules.iter().enumerate().map(|(index, rule)| {
+        let on_edit = {
+            let draft = draft.clone();
+            let editing = editing.clone();
+            let rule = rule.clone();
+            Callback::from(move |_: MouseEvent| {
+                draft.set(RuleDraft::from_rule(&rule));
+                editing.set(Some(index));
+            })
+        };
+        let on_up = {
+            let rules = prop.rules.clone();
+            let on_change = prop.on_change.clone();
+            Callback::from(move |_: MouseEvent| {
+                let mut rules = rules.clone();
+                rules.swap(index - 1, index);
+                on_change.emit(rules);
+            })
+        };
+        let on_delete = {
+            let rules = prop.rules.clone();
+            let on_change = prop.on_change.clone();
+            let editing = editing.clone();
+            Callback::from(move |_: MouseEvent| {
+                let mut rules = rules.clone();
+                rules.remove(index);
+                on_change.emit(rules);
+                editing.set(None);
+            })
+        };
+        let up = if index > 0 {
+            html! { <button type="button" class="btn btn-link btn-sm p-0 me-2" onclick={on_up}>{locale.tr("formatting-up")}</button> }
+        } else {
+            html! {}
+        };
+        html! {
+            <li class="list-group-item px-2 py-1">
+                <div class={classes!((*editing == Some(index)).then_some("fw-semibold"))}>{rule.describe().text(locale)}</div>
+                <div>
+                    <button type="button" class="btn btn-link btn-sm p-0 me-2" onclick={on_edit}>{locale.tr("formatting-edit")}</button>
+                    {up}
+                    <button type="button" class="btn btn-link btn-sm p-0 text-danger" onclick={on_delete}>{locale.tr("formatting-delete")}</button>
+                </div>
+            </li>
+        }
+    });
+
+    let field_select = |current: &String, set: fn(&mut RuleDraft, String), placeholder: &str| {
+        let placeholder = locale.tr(placeholder);
+        html! {
+            <select class="form-select form-select-sm" aria-label={placeholder.clone()} onchange={draft_setter(&draft, set)}>
+                <option value="" selected={current.is_empty()}>{placeholder}</option>
+                {for prop.fields.iter().map(|field| html! {
+                    <option value={field.clone()} selected={field == current}>{field.clone()}</option>
+                })}
+            </select>
+        }
+    };
+    let condition_inputs = match draft.condition_kind.as_str() {
+        "threshold" => html! {
+            <div class="input-group input-group-sm mb-1">
+                <select class="form-select form-select-sm" style="max-width: 60px;" aria-label={locale.tr("formatting-comparison")}
+                 onchange={draft_setter(&draft, |draft, value| draft.comparison = value)}>
+                    {for Comparison::ALL.iter().map(|comparison| html! {
+                        <option value={comparison.label()} selected={comparison.label() == draft.comparison}>{comparison.label()}</option>
+                    })}
+                </select>
+                <input type="text" class="form-control" placeholder={locale.tr("formatting-number")} aria-label={locale.tr("formatting-threshold")} value={draft.threshold.clone()}
+                 onchange={draft_setter(&draft, |draft, value| draft.threshold = value)}/>
+            </div>
+        },
+        "matches" => html! {
+            <input type="text" class="form-control form-control-sm mb-1" placeholder={locale.tr("formatting-pattern")}
+             aria-label={locale.tr("formatting-pattern")}
+             value={draft.pattern.clone()} onchange={draft_setter(&draft, |draft, value| draft.pattern = value)}/>
+        },
+        "one of" => html! {
+            <input type="text" class="form-control form-control-sm mb-1" placeholder={locale.tr("formatting-values-placeholder")}
+             aria-label={locale.tr("formatting-values")}
+             value={draft.values.clone()} onchange={draft_setter(&draft, |draft, value| draft.values = value)}/>
+        },
+        "outside range" => html! {
+            <div class="d-flex gap-1 mb-1">
+                {field_select(&draft.low_field, |draft, value| draft.low_field = value, "formatting-low-column")}
+                {field_select(&draft.high_field, |draft, value| draft.high_field = value, "formatting-high-column")}
+            </div>
+        },
+        _ => html! {},
+    };
+    let highlight_select = |current: &String, set: fn(&mut RuleDraft, String), none: &str| {
+        html! {
+            <select class="form-select form-select-sm" aria-label={locale.tr(&format!("{none}-color"))} onchange={draft_setter(&draft, set)}>
+                <option value="" selected={current.is_empty()}>{locale.tr(none)}</option>
+                {for Highlight::ALL.iter().map(|highlight| html! {
+                    <option value={highlight.label()} selected={highlight.label() == current}>{highlight.name().text(locale)}</option>
+                })}
+            </select>
+        }
+    };
+    let decimals_input = if draft.value_format == VALUE_FORMATS[2] {
+        html! {
+            <input type="number" min="0" max="10" class="form-control form-control-sm" style="max-width: 60px;"
+             aria-label={locale.tr("formatting-decimals")} value={draft.decimals.clone()} onchange={draft_setter(&draft, |draft, value| draft.decimals = value)}/>
+        }
+    } else {
+        html! {}
+    };
+    let on_bold = {
+        let draft = draft.clone();
+        Callback::from(move |event: Event| {
+            if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                let mut new_draft = (*draft).clone();
+                new_draft.bold = input.checked();
+                draft.set(new_draft);
+            }
+        })
+    };
+    let on_cancel = {
+        let draft = draft.clone();
+        let editing = editing.clone();
+        let first_field = prop.fields.first().cloned();
+        Callback::from(move |_: MouseEvent| {
+            draft.set(RuleDraft::new(first_field.as_ref()));
+            editing.set(None);
+        })
+    };
+    let cancel = if editing.is_some() {
+        html! { <button type="button" class="btn btn-sm btn-outline-dark" onclick={on_cancel}>{locale.tr("cancel")}</button> }
+    } else {
+        html! {}
+    };
+    let status_line = match &*status {
+        Some(message) => {
+            html! { <div class="text-danger mt-2" role="alert">{message.clone()}</div> }
+        }
+        None => html! {},
+    };
+
+    html! {
+        <div class="card shadow position-absolute start-0 p-2" style="top: 100%; z-index: 1050; width: 340px; font-size: 12px;">
+            <div class="fw-semibold">{locale.tr("formatting-rules")}</div>
+            <ul class="list-group list-group-flush mb-2">{for rule_rows}</ul>
+            <div class="fw-semibold">{locale.tr(if editing.is_some() { "formatting-edit-rule" } else { "formatting-new-rule" })}</div>
+            <div class="d-flex gap-1 mb-1">
+                {field_select(&draft.column, |draft, value| draft.column = value, "formatting-column")}
+                <select class="form-select form-select-sm" aria-label={locale.tr("formatting-condition")}
+                 onchange={draft_setter(&draft, |draft, value| draft.condition_kind = value)}>
+                    {for CONDITION_KINDS.iter().zip(CONDITION_KIND_LABELS).map(|(kind, label)| html! {
+                        <option value={*kind} selected={*kind == draft.condition_kind}>{locale.tr(label)}</option>
+                    })}
+                </select>
+            </div>
+            {condition_inputs}
+            <div class="d-flex gap-1 mb-1 align-items-center">
+                {highlight_select(&draft.color, |draft, value| draft.color = value, "formatting-text")}
+                {highlight_select(&draft.background, |draft, value| draft.background = value, "formatting-fill")}
+                <label class="form-check-label text-nowrap">
+                    <input type="checkbox" class="form-check-input me-1" checked={draft.bold} onchange={on_bold}/>
+                    {locale.tr("formatting-bold")}
+                </label>
+            </div>
+            <div class="d-flex gap-1 mb-2">
+                <select class="form-select form-select-sm" aria-label={locale.tr("formatting-value-format")}
+                 onchange={draft_setter(&draft, |draft, value| draft.value_format = value)}>
+                    {for VALUE_FORMATS.iter().zip(VALUE_FORMAT_LABELS).map(|(format, label)| html! {
+                        <option value={*format} selected={*format == draft.value_format}>{locale.tr(label)}</option>
+                    })}
+                </select>
+                {decimals_input}
+            </div>
+            <div class="d-flex gap-2">
+                <button type="button" class="btn btn-sm btn-dark" onclick={on_save}>
+                    {locale.tr(if editing.is_some() { "formatting-save-rule" } else { "formatting-add-rule" })}
+                </button>
+                {cancel}
+            </div>
+            {status_line}
+        </div>
+    }
+}
+
+/// Updates one field of the draft from the value of the input or select that changed.
+fn draft_setter(
+    draft: &UseStateHandle<RuleDraft>,
+    set: fn(&mut RuleDraft, String),
+) -> Callback<Event> {
+    let draft = draft.clone();
+    Callback::from(move |event: Event| {
+        let value = match event.target() {
+            Some(target) => match target.dyn_ref::<HtmlSelectElement>() {
+                Some(select) => select.value(),
+                None => match target.dyn_ref::<HtmlInputElement>() {
+                    Some(input) => input.value(),
+                    None => return,
+                },
+            },
+            None => return,
+        };
+        let mut new_draft = (*draft).clone();
+        set(&mut new_draft, value);
+        draft.set(new_draft);
+    })
+}
@@ -0,0 +1,52 @@
+use crate::a11y::{Announcement, LiveRegions};
+
+use yew::prelude::*;
+
+/// Makes announcements to screen readers, provided by `LiveRegion`.
+#[derive(Clone, PartialEq)]
+pub struct Announcer(UseReducerDispatcher<LiveRegions>);
+
+impl Announcer {
+    pub fn announce(&self, announcement: Announcement) {
+        self.0.dispatch(announcement);
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct LiveRegionProp {
+    pub children: Children,
+}
+
+/// The polite and assertive live regions of the app, read by screen readers whenever their text
+/// changes. Components below announce loading and errors through `use_announcement`.
+#[function_component]
+pub fn LiveRegion(prop: &LiveRegionProp) -> Html {
+    let regions = use_reducer(LiveRegions::default);
+    let announcer = Announcer(regions.dispatcher());
+    html! {
+        <ContextProvider<Announcer> context={announcer}>
+            {for prop.children.iter()}
+            <div class="visually-hidden" role="status" aria-live="polite" aria-atomic="true">
+                {regions.status.clone()}
+            </div>
+            <div class="visually-hidden" role="alert" aria-live="assertive" aria-atomic="true">
+                {regions.alert.clone()}
+            </div>
+        </ContextProvider<Announcer>>
+    }
+}
+
+/// Announces `announcement` whenever it changes to another one.
+#[hook]
+pub fn use_announcement(announcement: Option<Announcement>) {
+    let announcer = use_context::<Announcer>();
+    use_effect_with_deps(
+        move |announcement| {
+            if let (Some(announcer), Some(announcement)) = (announcer, announcement.clone()) {
+                announcer.announce(announcement);
+            }
+            || ()
+        },
+        announcement,
+    );
+}
@@ -0,0 +1,89 @@
+use crate::i18n::Locale;
+use crate::workspace::Workspace;
+
+use gloo_utils::document;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+/// The UI locale and a way to change it, provided by `LocaleProvider`.
+#[derive(Clone, PartialEq)]
+pub struct LocaleHandle {
+    pub locale: Locale,
+    pub set_locale: Callback<Locale>,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct LocaleProviderProp {
+    pub children: Children,
+}
+
+/// Provides the locale the user picked, or the browser's preferred one, to `children`. The
+/// pick is saved in the workspace and the document's `lang` follows it.
+#[function_component]
+pub fn LocaleProvider(prop: &LocaleProviderProp) -> Html {
+    let locale = use_state_eq(|| Workspace::load().locale.unwrap_or_else(Locale::detect));
+    use_effect_with_deps(
+        |locale| {
+            if let Some(root) = document().document_element() {
+                let _ = root.set_attribute("lang", locale.tag());
+            }
+            || ()
+        },
+        *locale,
+    );
+    let set_locale = {
+        let locale = locale.clone();
+        Callback::from(move |picked: Locale| {
+            let mut workspace = Workspace::load();
+            workspace.locale = Some(picked);
+            workspace.save();
+            locale.set(picked);
+        })
+    };
+    let handle = LocaleHandle {
+        locale: *locale,
+        set_locale,
+    };
+    html! {
+        <ContextProvider<LocaleHandle> context={handle}>
+            {for prop.children.iter()}
+        </ContextProvider<LocaleHandle>>
+    }
+}
+
+/// The UI locale, English outside a `LocaleProvider`.
+#[hook]
+pub fn use_locale() -> Locale {
+    use_context::<LocaleHandle>()
+        .map(|handle| handle.locale)
+        .unwrap_or_default()
+}
+
+/// Picks the UI locale among those with a catalog, each listed in its own language.
+#[function_component]
+pub fn LocaleSwitcher() -> Html {
+    let handle = use_context::<LocaleHandle>();
+    let locale = handle
+        .as_ref()
+        .map(|handle| handle.locale)
+        .unwrap_or_default();
+    let onchange = Callback::from(move |event: Event| {
+        let picked = event
+            .target()
+            .and_then(|target| target.dyn_into::<HtmlSelectElement>().ok())
+            .and_then(|select| Locale::from_tag(&select.value()));
+        if let (Some(handle), Some(picked)) = (handle.as_ref(), picked) {
+            handle.set_locale.emit(picked);
+        }
+    });
+    html! {
+        <select class="form-select form-select-sm w-auto" aria-label={locale.tr("locale-label")} {onchange}>
+            {for Locale::ALL.iter().map(|option| html! {
+                <option value={option.tag()} lang={option.tag()} selected={*option == locale}>
+                    {option.name()}
+                </option>
+            })}
+        </select>
+    }
+}
@@ -0,0 +1,521 @@
+use crate::a11y::is_activation_key;
+use crate::components::locale::use_locale;
+use crate::formatting::{cell_classes, localized_value};
+use crate::grid::{
+    apply_grid_state, aria_sort, column_in_view, column_layout, filters_from_model, grid_state,
+    move_column, row_window, step_column, toggle_sort, visible_rows, GridColumn, MIN_COLUMN_WIDTH,
+};
+use crate::views::{ColumnSort, GridState};
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{DragEvent, Element, HtmlElement, HtmlInputElement, ResizeObserver};
+use yew::prelude::*;
+
+const ROW_HEIGHT: f64 = 30.0;
+const HEADER_HEIGHT: f64 = 36.0;
+const FILTER_HEIGHT: f64 = 34.0;
+/// Rows rendered above and below the viewport so fast scrolling does not flash empty space.
+const OVERSCAN_ROWS: usize = 8;
+/// Pixels an arrow key widens or narrows a column by.
+const RESIZE_STEP: f64 = 10.0;
+
+thread_local! {
+    static NATIVE_GRID_STATE: RefCell<GridState> = RefCell::new(GridState::default());
+}
+
+/// Filters, sorts and column layout of the native grid, as `current_grid_state` reports them.
+pub fn native_grid_state() -> GridState {
+    NATIVE_GRID_STATE.with(|state| state.borrow().clone())
+}
+
+#[derive(Properties, PartialEq)]
+pub struct NativeGridProp {
+    pub row_data: Rc<Vec<HashMap<String, String>>>,
+    pub fields: Vec<String>,
+    /// Fields pinned to the left when the grid is first shown.
+    #[prop_or_default]
+    pub pinned: Vec<String>,
+    /// Header tooltips by field, e.g. the CDISC variable labels.
+    #[prop_or_default]
+    pub header_tooltips: HashMap<String, String>,
+    /// Column types by field of the columns written the locale's way, see `i18n::column_type`.
+    #[prop_or_default]
+    pub column_types: HashMap<String, String>,
+    /// Receives the index in `row_data` of the row the user clicked.
+    #[prop_or_default]
+    pub on_row_clicked: Option<Callback<usize>>,
+    #[prop_or_default]
+    pub grid_state: Option<GridState>,
+    /// Receives the grid's state whenever the user filters, sorts, hides or moves columns.
+    #[prop_or_default]
+    pub on_state_changed: Option<Callback<GridState>>,
+    /// What screen readers call the grid, "Table rows" in the UI's language by default.
+    #[prop_or_default]
+    pub label: Option<AttrValue>,
+}
+
+#[derive(Clone, Copy, PartialEq, Default)]
+struct Viewport {
+    scroll_top: f64,
+    scroll_left: f64,
+    width: f64,
+    height: f64,
+}
+
+#[derive(Clone, PartialEq)]
+struct Resize {
+    field: String,
+    start_x: i32,
+    start_width: f64,
+}
+
+fn initial_columns(
+    fields: &[String],
+    pinned: &[String],
+    grid_state: Option<&GridState>,
+) -> Vec<GridColumn> {
+    let mut columns: Vec<GridColumn> = fields
+        .iter()
+        .map(|field| GridColumn {
+            pinned: pinned.contains(field),
+            ..GridColumn::new(field)
+        })
+        .collect();
+    if let Some(state) = grid_state {
+        apply_grid_state(&mut columns, state);
+    }
+    columns
+}
+
+/// A virtualized data grid rendered by Yew: only the rows and columns in view are in the DOM.
+#[function_component]
+pub fn NativeGrid(prop: &NativeGridProp) -> Html {
+    let locale = use_locale();
+    let columns =
+        use_state(|| initial_columns(&prop.fields, &prop.pinned, prop.grid_state.as_ref()));
+    let sort = use_state(|| {
+        prop.grid_state
+            .as_ref()
+            .map(|state| state.sort.clone())
+            .unwrap_or_default()
+    });
+    let filters = use_state(|| {
+        prop.grid_state
+            .as_ref()
+            .map(|state| filters_from_model(&state.filter_model))
+            .unwrap_or_default()
+    });
+    let viewport = use_state(Viewport::default);
+    let resize = use_state(|| None::<Resize>);
+    let container = use_node_ref();
+
+    // The state the grid shows and the columns it shows it for.
+    let shown = use_mut_ref(|| None::<(Vec<String>, GridState)>);
+    {
+        let columns = columns.clone();
+        let sort = sort.clone();
+        let filters = filters.clone();
+        let shown = shown.clone();
+        let reset = (
+            prop.fields.clone(),
+            prop.pinned.clone(),
+            prop.grid_state.clone(),
+        );
+        use_effect_with_deps(
+            move |(fields, pinned, grid_state)| {
+                // The state may be the one the grid just reported, resetting would lose widths.
+                let is_shown = matches!(
+                    (&*shown.borrow(), grid_state),
+                    (Some((shown_fields, shown_state)), Some(grid_state))
+                        if shown_fields == fields && shown_state == grid_state
+                );
+                if !is_shown {
+                    columns.set(initial_columns(fields, pinned, grid_state.as_ref()));
+                    let state = grid_state.clone().unwrap_or_default();
+                    sort.set(state.sort);
+                    filters.set(filters_from_model(&state.filter_model));
+                }
+                || ()
+            },
+            reset,
+        );
+    }
+    {
+        let state = grid_state(&columns, &filters, &sort);
+        let fields = prop.fields.clone();
+        let on_state_changed = prop.on_state_changed.clone();
+        use_effect_with_deps(
+            move |state| {
+                NATIVE_GRID_STATE.with(|current| *current.borrow_mut() = state.clone());
+                let reported = shown.borrow_mut().replace((fields, state.clone()));
+                // The first state is the grid as it was given, not a change.
+                if let (Some(_), Some(callback)) = (reported, on_state_changed) {
+                    callback.emit(state.clone());
+                }
+                || ()
+            },
+            state,
+        );
+    }
+    let measure = {
+        let container = container.clone();
+        let viewport = viewport.clone();
+        move || {
+            if let Some(element) = container.cast::<Element>() {
+                viewport.set(Viewport {
+                    scroll_top: element.scroll_top() as f64,
+                    scroll_left: element.scroll_left() as f64,
+                    width: element.client_width() as f64,
+                    height: element.client_height() as f64,
+                });
+            }
+        }
+    };
+    {
+        // Measured again whenever the grid changes size, e.g. when the window is resized or the
+        // layout switches between phone and tablet, not only when it scrolls.
+        let measure = measure.clone();
+        let container = container.clone();
+        use_effect_with_deps(
+            move |_| {
+                measure();
+                let listener = Closure::<dyn Fn()>::new(measure);
+                let observer = ResizeObserver::new(listener.as_ref().unchecked_ref()).ok();
+                if let (Some(observer), Some(element)) = (&observer, container.cast::<Element>()) {
+                    observer.observe(&element);
+                }
+                move || {
+                    if let Some(observer) = observer {
+                        observer.disconnect();
+                    }
+                    drop(listener);
+                }
+            },
+            (),
+        );
+    }
+
+    let visible = use_memo(
+        |(row_data, filters, sort)| visible_rows(row_data, filters, sort),
+        (prop.row_data.clone(), (*filters).clone(), (*sort).clone()),
+    );
+
+    let layout = column_layout(&columns);
+    let total_width: f64 = layout.iter().map(|(_, column)| column.width).sum();
+    let pinned_width: f64 = layout
+        .iter()
+        .filter(|(_, column)| column.pinned)
+        .map(|(_, column)| column.width)
+        .sum();
+    let in_view: Vec<(f64, &GridColumn)> = layout
+        .iter()
+        .filter(|(offset, column)| {
+            column.pinned
+                || column_in_view(
+                    *offset,
+                    column.width,
+                    pinned_width,
+                    viewport.scroll_left,
+                    viewport.width,
+                )
+        })
+        .cloned()
+        .collect();
+    let cell_style = |offset: f64, column: &GridColumn, height: f64| {
+        let left = if column.pinned {
+            viewport.scroll_left + offset
+        } else {
+            offset
+        };
+        format!(
+            "left: {left}px; width: {}px; height: {height}px;",
+            column.width
+        )
+    };
+
+    let header_cells = in_view.iter().map(|(offset, column)| {
+        let field = column.field.clone();
+        let on_sort = {
+            let sort = sort.clone();
+            let field = field.clone();
+            Callback::from(move |event: MouseEvent| {
+                sort.set(toggle_sort(&sort, &field, event.shift_key()))
+            })
+        };
+        // Enter or Space sorts like a click, Shift adds to the sort, Alt+arrows move the column.
+        let on_header_key = {
+            let sort = sort.clone();
+            let columns = columns.clone();
+            let field = field.clone();
+            Callback::from(move |event: KeyboardEvent| {
+                let key = event.key();
+                if is_activation_key(&key) {
+                    event.prevent_default();
+                    sort.set(toggle_sort(&sort, &field, event.shift_key()));
+                } else if event.alt_key() && (key == "ArrowLeft" || key == "ArrowRight") {
+                    event.prevent_default();
+                    let mut next = (*columns).clone();
+                    step_column(&mut next, &field, key == "ArrowRight");
+                    columns.set(next);
+                }
+            })
+        };
+        let on_resize_key = {
+            let columns = columns.clone();
+            let field = field.clone();
+            Callback::from(move |event: KeyboardEvent| {
+                let step = match event.key().as_str() {
+                    "ArrowLeft" => -RESIZE_STEP,
+                    "ArrowRight" => RESIZE_STEP,
+                    _ => return,
+                };
+                event.prevent_default();
+                let mut next = (*columns).clone();
+                if let Some(column) = next.iter_mut().find(|column| column.field == field) {
+                    column.width = (column.width + step).max(MIN_COLUMN_WIDTH);
+                }
+                columns.set(next);
+            })
+        };
+        let on_pin = {
+            let columns = columns.clone();
+            let field = field.clone();
+            Callback::from(move |event: MouseEvent| {
+                event.stop_propagation();
+                let mut next = (*columns).clone();
+                if let Some(column) = next.iter_mut().find(|column| column.field == field) {
+                    column.pinned = !column.pinned;
+                }
+                columns.set(next);
+            })
+        };
+        let on_resize_start = {
+            let resize = resize.clone();
+            let field = field.clone();
+            let start_width = column.width;
+            Callback::from(move |event: MouseEvent| {
+                event.stop_propagation();
+                event.prevent_default();
+                resize.set(Some(Resize {
+                    field: field.clone(),
+                    start_x: event.client_x(),
+                    start_width,
+                }));
+            })
+        };
+        let on_drag_start = {
+            let field = field.clone();
+            Callback::from(move |event: DragEvent| {
+                if let Some(data) = event.data_transfer() {
+                    let _ = data.set_data("text/plain", &field);
+                }
+            })
+        };
+        let on_drag_over = Callback::from(|event: DragEvent| event.prevent_default());
+        let on_drop = {
+            let columns = columns.clone();
+            let field = field.clone();
+            Callback::from(move |event: DragEvent| {
+                event.prevent_default();
+                let dragged = event
+                    .data_transfer()
+                    .and_then(|data| data.get_data("text/plain").ok())
+                    .unwrap_or_default();
+                if dragged.is_empty() || dragged == field {
+                    return;
+                }
+                let mut next = (*columns).clone();
+                move_column(&mut next, &dragged, Some(&field));
+                columns.set(next);
+            })
+        };
+        let indicator = sort_indicator(&sort, &field);
+        let class = classes!(
+            "native-grid-header-cell",
+            column.pinned.then_some("native-grid-pinned")
+        );
+        html! {
+            <div {class} style={cell_style(*offset, column, HEADER_HEIGHT)} draggable="true"
+             role="columnheader" aria-sort={aria_sort(&sort, &field)}
+             ondragstart={on_drag_start} ondragover={on_drag_over} ondrop={on_drop}>
+                <span class="native-grid-header-label" role="button" tabindex="0"
+                 onclick={on_sort} onkeydown={on_header_key}
+                 title={prop.header_tooltips.get(&field).cloned().unwrap_or_else(|| field.clone())}>
+                    {field.clone()}{indicator}
+                </span>
+                <button type="button" class="native-grid-pin" onclick={on_pin}
+                 aria-pressed={column.pinned.to_string()} aria-label={locale.tr_args("grid-pin", &[("column", field.clone().into())])}
+                 title={locale.tr(if column.pinned { "grid-unpin-column" } else { "grid-pin-column" })}>
+                    {if column.pinned { "\u{25C2}" } else { "\u{25B8}" }}
+                </button>
+                <div class="native-grid-resize" role="separator" aria-orientation="vertical" tabindex="0"
+                 aria-label={locale.tr_args("grid-resize", &[("column", field.clone().into())])} aria-valuenow={format!("{:.0}", column.width)}
+                 onmousedown={on_resize_start} onkeydown={on_resize_key}></div>
+            </div>
+        }
+    });
+    let filter_cells = in_view.iter().map(|(offset, column)| {
+        let on_filter = {
+            let filters = filters.clone();
+            let field = column.field.clone();
+            Callback::from(move |event: InputEvent| {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    let mut next: BTreeMap<String, String> = (*filters).clone();
+                    next.insert(field.clone(), input.value());
+                    filters.set(next);
+                }
+            })
+        };
+        let class = classes!(
+            "native-grid-filter-cell",
+            column.pinned.then_some("native-grid-pinned")
+        );
+        html! {
+            <div {class} role="gridcell" style={format!("top: {HEADER_HEIGHT}px; {}", cell_style(*offset, column, FILTER_HEIGHT))}>
+                <input type="text" class="form-control form-control-sm" placeholder={locale.tr("grid-filter-placeholder")}
+                 aria-label={locale.tr_args("grid-filter", &[("column", column.field.clone().into())])}
+                 value={filters.get(&column.field).cloned().unwrap_or_default()} oninput={on_filter}/>
+            </div>
+        }
+    });
+
+    let body_height = viewport.height - HEADER_HEIGHT - FILTER_HEIGHT;
+    let window = row_window(
+        viewport.scroll_top,
+        body_height,
+        ROW_HEIGHT,
+        visible.len(),
+        OVERSCAN_ROWS,
+    );
+    let first_rendered = window.start;
+    let rows = visible[window.clone()]
+        .iter()
+        .zip(window)
+        .map(|(row_index, position)| {
+            let row = &prop.row_data[*row_index];
+            let on_click = prop.on_row_clicked.clone().map(|callback| {
+                let row_index = *row_index;
+                Callback::from(move |_: MouseEvent| callback.emit(row_index))
+            });
+            // Enter opens the row like a click, the arrow keys move between rows.
+            let on_key = {
+                let on_row_clicked = prop.on_row_clicked.clone();
+                let row_index = *row_index;
+                Callback::from(move |event: KeyboardEvent| {
+                    let row = match event.target_dyn_into::<HtmlElement>() {
+                        Some(row) => row,
+                        None => return,
+                    };
+                    let sibling = match event.key().as_str() {
+                        "Enter" => {
+                            if let Some(callback) = &on_row_clicked {
+                                callback.emit(row_index);
+                            }
+                            return;
+                        }
+                        "ArrowDown" => row.next_element_sibling(),
+                        "ArrowUp" => row.previous_element_sibling(),
+                        _ => return,
+                    };
+                    event.prevent_default();
+                    if let Some(sibling) = sibling.and_then(|sibling| sibling.dyn_into::<HtmlElement>().ok()) {
+                        let _ = sibling.focus();
+                    }
+                })
+            };
+            let class = classes!(
+                "native-grid-row",
+                (position % 2 == 1).then_some("native-grid-row-odd")
+            );
+            // Tab enters the rows once, at the first one rendered.
+            let tabindex = if position == first_rendered { "0" } else { "-1" };
+            html! {
+                <div key={row_index.to_string()} {class} role="row" aria-rowindex={(position + 3).to_string()}
+                 {tabindex} onclick={on_click} onkeydown={on_key}
+                 style={format!("top: {}px; width: {total_width}px; height: {ROW_HEIGHT}px;", position as f64 * ROW_HEIGHT)}>
+                    {for in_view.iter().map(|(offset, column)| {
+                        let class = classes!(
+                            "native-grid-cell",
+                            column.pinned.then_some("native-grid-pinned"),
+                            cell_classes(row, &column.field).to_owned()
+                        );
+                        html! {
+                            <div {class} role="gridcell" style={cell_style(*offset, column, ROW_HEIGHT)}>
+                                {localized_value(row, &column.field, &prop.column_types, locale).unwrap_or_default()}
+                            </div>
+                        }
+                    })}
+                </div>
+            }
+        });
+
+    let on_scroll = {
+        let measure = measure.clone();
+        Callback::from(move |_: Event| measure())
+    };
+    let on_mouse_move = {
+        let columns = columns.clone();
+        let resize = resize.clone();
+        Callback::from(move |event: MouseEvent| {
+            if let Some(resize) = &*resize {
+                let width = (resize.start_width + (event.client_x() - resize.start_x) as f64)
+                    .max(MIN_COLUMN_WIDTH);
+                let mut next = (*columns).clone();
+                if let Some(column) = next.iter_mut().find(|column| column.field == resize.field) {
+                    column.width = width;
+                }
+                columns.set(next);
+            }
+        })
+    };
+    let on_resize_end = {
+        let resize = resize.clone();
+        Callback::from(move |_: MouseEvent| {
+            if resize.is_some() {
+                resize.set(None);
+            }
+        })
+    };
+
+    html! {
+        <div ref={container} class="native-grid" onscroll={on_scroll} role="grid" aria-label={prop.label.clone().unwrap_or_else(|| locale.tr("grid-label").into())}
+         aria-rowcount={(visible.len() + 2).to_string()} aria-colcount={layout.len().to_string()}
+         onmousemove={on_mouse_move} onmouseup={on_resize_end.clone()} onmouseleave={on_resize_end}>
+            <div class="native-grid-header" role="rowgroup"
+             style={format!("width: {total_width}px; height: {}px;", HEADER_HEIGHT + FILTER_HEIGHT)}>
+                <div role="row" aria-rowindex="1">{for header_cells}</div>
+                <div role="row" aria-rowindex="2">{for filter_cells}</div>
+            </div>
+            <div class="native-grid-body" role="rowgroup"
+             style={format!("width: {total_width}px; height: {}px;", visible.len() as f64 * ROW_HEIGHT)}>
+                {for rows}
+            </div>
+        </div>
+    }
+}
+
+fn sort_indicator(sort: &[ColumnSort], field: &str) -> String {
+    match sort
+        .iter()
+        .position(|column_sort| column_sort.column == field)
+    {
+        Some(priority) => {
+            let arrow = if sort[priority].descending {
+                "\u{25BC}"
+            } else {
+                "\u{25B2}"
+            };
+            if sort.len() > 1 {
+                format!(" {arrow}{}", priority + 1)
+            } else {
+                format!(" {arrow}")
+            }
+        }
+        None => String::new(),
+    }
+}
@@ -1,3 +1,5 @@
+use crate::components::locale::{use_locale, LocaleSwitcher};
+use crate::components::offline_panel::OfflineBanner;
 use crate::MainRoute;
 
 use yew::prelude::*;
@@ -5,23 +7,33 @@ use yew_router::prelude::*;
 
 #[function_component]
 pub fn NavBar() -> Html {
+    let locale = use_locale();
     html! {
-        <nav class="navbar navbar-dark navbar-expand-lg bg-dark px-3 py-2">
-            <div class="container-fluid px-1">
-                <button class="navbar-toggler" type="button" data-bs-toggle="collapse" data-bs-target="#navbarText" aria-controls="navbarText" aria-expanded="false" aria-label="Toggle navigation">
-                    <span class="navbar-toggler-icon"></span>
-                </button>
-                <div class="collapse navbar-collapse" id="navbarText">
-                    <ul class="navbar-nav">
-                        <li class="nav-item">
-                            <a class="nav-link"><Link<MainRoute> to={MainRoute::Home}>{ "Home" }</Link<MainRoute>></a>
-                        </li>
-                        <li class="nav-item">
-                            <a class="nav-link"><Link<MainRoute> to={MainRoute::DataTable}>{ "Data Tables" }</Link<MainRoute>></a>
-                        </li>
-                    </ul>
+        <>
+            <nav class="navbar navbar-dark navbar-expand-lg bg-dark px-3 py-2" aria-label={locale.tr("nav-label")}>
+                <div class="container-fluid px-1">
+                    <button class="navbar-toggler" type="button" data-bs-toggle="collapse" data-bs-target="#navbarText" aria-controls="navbarText" aria-expanded="false" aria-label={locale.tr("nav-toggle")}>
+                        <span class="navbar-toggler-icon"></span>
+                    </button>
+                    <div class="collapse navbar-collapse" id="navbarText">
+                        <ul class="navbar-nav">
+                            <li class="nav-item">
+                                <Link<MainRoute> classes="nav-link" to={MainRoute::Home}>{ locale.tr("nav-home") }</Link<MainRoute>>
+                            </li>
+                            <li class="nav-item">
+                                <Link<MainRoute> classes="nav-link" to={MainRoute::DataTable}>{ locale.tr("nav-data-tables") }</Link<MainRoute>>
+                            </li>
+                            <li class="nav-item">
+                                <Link<MainRoute> classes="nav-link" to={MainRoute::Search}>{ locale.tr("nav-search") }</Link<MainRoute>>
+                            </li>
+                        </ul>
+                        <div class="ms-auto">
+                            <LocaleSwitcher/>
+                        </div>
+                    </div>
                 </div>
-            </div>
-        </nav>
+            </nav>
+            <OfflineBanner/>
+        </>
     }
 }
@@ -0,0 +1,234 @@
+use crate::components::locale::use_locale;
+use crate::offline::{self, pinned_age, use_online, PinnedTable};
+use crate::table::Table;
+
+use std::rc::Rc;
+
+use js_sys::Date;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+/// Shown under the navigation bar while the browser is offline.
+#[function_component]
+pub fn OfflineBanner() -> Html {
+    let locale = use_locale();
+    if use_online() {
+        return html! {};
+    }
+    html! {
+        <div class="alert alert-warning rounded-0 mb-0 py-1 px-3" role="status" style="font-size: 12px;">
+            <strong>{locale.tr("offline-banner-title")}</strong>
+            {" "}{locale.tr("offline-banner")}
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct UnlockFormProp {
+    pub on_unlocked: Callback<()>,
+}
+
+/// Asks for the passphrase of the offline store, the first one entered creates it.
+#[function_component]
+pub fn UnlockForm(prop: &UnlockFormProp) -> Html {
+    let locale = use_locale();
+    let input = use_node_ref();
+    let status = use_state(|| None::<String>);
+    let onsubmit = {
+        let input = input.clone();
+        let status = status.clone();
+        let on_unlocked = prop.on_unlocked.clone();
+        Callback::from(move |event: SubmitEvent| {
+            event.prevent_default();
+            let passphrase = match input.cast::<HtmlInputElement>() {
+                Some(input) => input.value(),
+                None => return,
+            };
+            if passphrase.is_empty() {
+                return;
+            }
+            let status = status.clone();
+            let on_unlocked = on_unlocked.clone();
+            status.set(Some(locale.tr("offline-unlocking")));
+            spawn_local(async move {
+                match offline::unlock(&passphrase).await {
+                    Ok(()) => {
+                        status.set(None);
+                        on_unlocked.emit(());
+                    }
+                    Err(error) => status.set(Some(error.message().clone())),
+                }
+            });
+        })
+    };
+    let on_forget = {
+        let status = status.clone();
+        Callback::from(move |_: MouseEvent| {
+            let status = status.clone();
+            spawn_local(async move {
+                match offline::forget_all().await {
+                    Ok(()) => status.set(Some(locale.tr("offline-forgotten"))),
+                    Err(error) => status.set(Some(error.message().clone())),
+                }
+            });
+        })
+    };
+
+    html! {
+        <form {onsubmit}>
+            <label class="form-label mb-1" for="offline-passphrase">{locale.tr("offline-passphrase")}</label>
+            <div class="input-group input-group-sm">
+                <input ref={input} id="offline-passphrase" type="password" class="form-control" autocomplete="current-password"/>
+                <button type="submit" class="btn btn-dark">{locale.tr("offline-unlock")}</button>
+            </div>
+            if let Some(message) = &*status {
+                <div class="text-muted mt-1">{message.clone()}</div>
+            }
+            <button type="button" class="btn btn-link btn-sm px-0" onclick={on_forget}>
+                {locale.tr("offline-forget")}
+            </button>
+        </form>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct OfflinePanelProp {
+    pub schema: String,
+    pub table: String,
+    pub data: Rc<Table>,
+}
+
+/// Pins the table to the device for offline use and lists the pinned tables.
+#[function_component]
+pub fn OfflinePanel(prop: &OfflinePanelProp) -> Html {
+    let locale = use_locale();
+    let unlocked = use_state(offline::is_unlocked);
+    let pinned = use_state(Vec::<PinnedTable>::new);
+    let status = use_state(|| None::<String>);
+    let reload = {
+        let pinned = pinned.clone();
+        let status = status.clone();
+        move || {
+            let pinned = pinned.clone();
+            let status = status.clone();
+            spawn_local(async move {
+                match offline::pinned_tables().await {
+                    Ok(tables) => pinned.set(tables),
+                    Err(error) => status.set(Some(error.message().clone())),
+                }
+            });
+        }
+    };
+    {
+        let reload = reload.clone();
+        use_effect_with_deps(
+            move |_| {
+                reload();
+                || ()
+            },
+            (),
+        );
+    }
+
+    let on_unlocked = {
+        let unlocked = unlocked.clone();
+        Callback::from(move |_| unlocked.set(true))
+    };
+    let on_lock = {
+        let unlocked = unlocked.clone();
+        Callback::from(move |_: MouseEvent| {
+            offline::lock();
+            unlocked.set(false);
+        })
+    };
+    let on_pin = {
+        let status = status.clone();
+        let reload = reload.clone();
+        let (schema, table, data) = (prop.schema.clone(), prop.table.clone(), prop.data.clone());
+        Callback::from(move |_: MouseEvent| {
+            let status = status.clone();
+            let reload = reload.clone();
+            let (schema, table, data) = (schema.clone(), table.clone(), data.clone());
+            status.set(Some(locale.tr("offline-pinning")));
+            spawn_local(async move {
+                match offline::pin(&schema, &table, &data).await {
+                    Ok(pinned) => status.set(Some(locale.tr_args(
+                        "offline-pinned",
+                        &[
+                            ("rows", pinned.rows.into()),
+                            (
+                                "table",
+                                format!("{}.{}", pinned.schema, pinned.table).into(),
+                            ),
+                        ],
+                    ))),
+                    Err(error) => status.set(Some(error.message().clone())),
+                }
+                reload();
+            });
+        })
+    };
+
+    let now = Date::now();
+    let is_pinned = pinned
+        .iter()
+        .any(|pinned| pinned.schema == prop.schema && pinned.table == prop.table);
+    let pinned_rows = pinned.iter().map(|entry| {
+        let on_unpin = {
+            let status = status.clone();
+            let reload = reload.clone();
+            let (schema, table) = (entry.schema.clone(), entry.table.clone());
+            Callback::from(move |_: MouseEvent| {
+                let status = status.clone();
+                let reload = reload.clone();
+                let (schema, table) = (schema.clone(), table.clone());
+                spawn_local(async move {
+                    if let Err(error) = offline::unpin(&schema, &table).await {
+                        status.set(Some(error.message().clone()));
+                    }
+                    reload();
+                });
+            })
+        };
+        html! {
+            <li class="list-group-item px-2 py-1 d-flex align-items-center gap-2">
+                <div class="me-auto">
+                    <div class="fw-semibold">{format!("{}.{}", entry.schema, entry.table)}</div>
+                    <div class="text-muted" title={entry.pinned_at.clone()}>
+                        {locale.tr_args("offline-entry", &[
+                            ("rows", entry.rows.into()),
+                            ("age", pinned_age(&entry.pinned_at, now).text(locale).into()),
+                        ])}
+                    </div>
+                </div>
+                <button type="button" class="btn btn-sm btn-outline-danger py-0" onclick={on_unpin}>{locale.tr("offline-unpin")}</button>
+            </li>
+        }
+    });
+
+    html! {
+        <div class="card shadow position-absolute start-0 p-2" style="top: 100%; z-index: 1050; width: 340px; font-size: 12px;">
+            if *unlocked {
+                <div class="d-flex gap-2 mb-2">
+                    <button type="button" class="btn btn-sm btn-dark" onclick={on_pin}>
+                        {locale.tr(if is_pinned { "offline-update-pin" } else { "offline-pin" })}
+                    </button>
+                    <button type="button" class="btn btn-sm btn-outline-dark ms-auto" onclick={on_lock}>{locale.tr("offline-lock")}</button>
+                </div>
+            } else {
+                <div class="mb-2"><UnlockForm {on_unlocked}/></div>
+            }
+            <div class="text-muted">{locale.tr("offline-pinned-tables")}</div>
+            <ul class="list-group list-group-flush" style="max-height: 40vh; overflow-y: auto;">
+                if pinned.is_empty() {
+                    <li class="list-group-item px-2 py-1 text-muted">{locale.tr("offline-no-pinned-tables")}</li>
+                }
+                {for pinned_rows}
+            </ul>
+            if let Some(message) = &*status {
+                <div class="text-muted mt-2">{message.clone()}</div>
+            }
+        </div>
+    }
+}
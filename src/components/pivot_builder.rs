@@ -0,0 +1,473 @@
+use crate::a11y::is_activation_key;
+use crate::components::locale::use_locale;
+use crate::components::table_visualization::{AGGridData, CallJavaScriptCode};
+use crate::i18n::{Locale, NUMBER_VALUE_COLUMN};
+use crate::pivot::{
+    column_header, format_cell, Aggregation, PivotDefinition, PivotResult, PivotRowKind,
+    PivotValue, SUBTOTAL_LABEL, TOTAL_LABEL,
+};
+use crate::table::Table;
+use crate::worker::JobId;
+use crate::worker_client::{self, JobHandle};
+use crate::workspace::Workspace;
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{console::error_1, DragEvent, Event, HtmlSelectElement};
+use yew::prelude::*;
+
+const PIVOT_GRID_DIV_ID: &str = "pivot-grid-div";
+const DRAG_FORMAT: &str = "text/plain";
+
+#[derive(Clone, Copy, PartialEq)]
+enum PivotZone {
+    Available,
+    Rows,
+    Columns,
+    Values,
+}
+
+impl PivotZone {
+    /// The zones top to bottom.
+    const ALL: [PivotZone; 4] = [
+        PivotZone::Available,
+        PivotZone::Rows,
+        PivotZone::Columns,
+        PivotZone::Values,
+    ];
+
+    /// The zone below, or above, this one, where the arrow keys move a field.
+    fn neighbour(&self, down: bool) -> Option<PivotZone> {
+        let index = PivotZone::ALL.iter().position(|zone| zone == self)?;
+        let index = if down {
+            index + 1
+        } else {
+            index.checked_sub(1)?
+        };
+        PivotZone::ALL.get(index).copied()
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            PivotZone::Available => "available",
+            PivotZone::Rows => "rows",
+            PivotZone::Columns => "columns",
+            PivotZone::Values => "values",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<PivotZone> {
+        match name {
+            "available" => Some(PivotZone::Available),
+            "rows" => Some(PivotZone::Rows),
+            "columns" => Some(PivotZone::Columns),
+            "values" => Some(PivotZone::Values),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PivotBuilderProp {
+    pub schema: String,
+    pub table: String,
+    pub fields: Vec<String>,
+    pub data: Rc<Table>,
+    /// The id the worker keeps `data` under.
+    pub data_id: JobId,
+}
+
+#[function_component]
+pub fn PivotBuilder(prop: &PivotBuilderProp) -> Html {
+    let locale = use_locale();
+    let schema = prop.schema.clone();
+    let table = prop.table.clone();
+    let definition = use_state(|| {
+        Workspace::load()
+            .table(&schema, &table)
+            .pivot
+            .unwrap_or_default()
+    });
+
+    let update_definition = {
+        let definition = definition.clone();
+        let schema = prop.schema.clone();
+        let table = prop.table.clone();
+        Callback::from(move |new_definition: PivotDefinition| {
+            let saved_definition = new_definition.clone();
+            Workspace::update_table(&schema, &table, move |table_workspace| {
+                table_workspace.pivot = Some(saved_definition);
+            });
+            definition.set(new_definition);
+        })
+    };
+
+    let on_drop = {
+        let definition = definition.clone();
+        let update_definition = update_definition.clone();
+        Callback::from(move |(target, payload): (PivotZone, String)| {
+            match parse_drag_payload(&payload) {
+                Some((source, field)) => {
+                    let moved = move_field(&definition, source, target, &field);
+                    update_definition.emit(moved);
+                }
+                None => error_1(&JsValue::from(
+                    "pivot builder error: unrecognized drag payload.",
+                )),
+            }
+        })
+    };
+
+    let on_aggregation = {
+        let definition = definition.clone();
+        let update_definition = update_definition.clone();
+        Callback::from(move |(index, aggregation): (usize, Aggregation)| {
+            let mut new_definition = (*definition).clone();
+            if let Some(value) = new_definition.values.get_mut(index) {
+                value.aggregation = aggregation;
+            }
+            update_definition.emit(new_definition);
+        })
+    };
+
+    let on_remove = {
+        let definition = definition.clone();
+        let update_definition = update_definition.clone();
+        Callback::from(move |(zone, index): (PivotZone, usize)| {
+            let mut new_definition = (*definition).clone();
+            match zone {
+                PivotZone::Rows if index < new_definition.rows.len() => {
+                    new_definition.rows.remove(index);
+                }
+                PivotZone::Columns if index < new_definition.columns.len() => {
+                    new_definition.columns.remove(index);
+                }
+                PivotZone::Values if index < new_definition.values.len() => {
+                    new_definition.values.remove(index);
+                }
+                _ => (),
+            };
+            update_definition.emit(new_definition);
+        })
+    };
+
+    let on_clear = {
+        let update_definition = update_definition.clone();
+        Callback::from(move |_: MouseEvent| update_definition.emit(PivotDefinition::default()))
+    };
+
+    // Computed in the worker, the previous result stays on screen until the new one arrives.
+    let result = use_state(|| Rc::new(PivotResult::default()));
+    let pending = use_state(|| false);
+    let job = use_mut_ref(|| None::<JobHandle>);
+    {
+        let result = result.clone();
+        let pending = pending.clone();
+        let data = prop.data.clone();
+        // Rows only ever grow under an id, while the table downloads.
+        use_effect_with_deps(
+            move |(definition, data_id, _rows)| {
+                pending.set(true);
+                let (definition, data_id) = (definition.clone(), *data_id);
+                // Replacing the handle cancels the job computing an outdated pivot.
+                *job.borrow_mut() = Some(JobHandle::spawn(async move {
+                    match worker_client::pivot(data_id, data, definition).await {
+                        Ok(pivot) => result.set(Rc::new(pivot)),
+                        Err(error) => error_1(&JsValue::from(format!(
+                            "pivot builder error: {}",
+                            error.message()
+                        ))),
+                    }
+                    pending.set(false);
+                }));
+                || ()
+            },
+            ((*definition).clone(), prop.data_id, prop.data.len()),
+        );
+    }
+
+    let available: Vec<String> = prop
+        .fields
+        .iter()
+        .filter(|field| !definition.rows.contains(field) && !definition.columns.contains(field))
+        .cloned()
+        .collect();
+    let value_labels: Vec<(String, Option<Aggregation>)> = definition
+        .values
+        .iter()
+        .map(|value| (value.field.clone(), Some(value.aggregation)))
+        .collect();
+    let label_only = |fields: &Vec<String>| -> Vec<(String, Option<Aggregation>)> {
+        fields.iter().map(|field| (field.clone(), None)).collect()
+    };
+
+    html! {
+        <div class="row p-0 m-0 h-100">
+            <div class="col-3 p-2 overflow-auto h-100 bg-white shadow-sm" style="margin-top:10px;">
+                <PivotFieldZone zone={PivotZone::Available} title={locale.tr("pivot-fields")} fields={label_only(&available)}
+                    on_drop={on_drop.clone()} on_remove={on_remove.clone()} on_aggregation={on_aggregation.clone()}/>
+                <PivotFieldZone zone={PivotZone::Rows} title={locale.tr("pivot-rows")} fields={label_only(&definition.rows)}
+                    on_drop={on_drop.clone()} on_remove={on_remove.clone()} on_aggregation={on_aggregation.clone()}/>
+                <PivotFieldZone zone={PivotZone::Columns} title={locale.tr("pivot-columns")} fields={label_only(&definition.columns)}
+                    on_drop={on_drop.clone()} on_remove={on_remove.clone()} on_aggregation={on_aggregation.clone()}/>
+                <PivotFieldZone zone={PivotZone::Values} title={locale.tr("pivot-values")} fields={value_labels}
+                    on_drop={on_drop} on_remove={on_remove} on_aggregation={on_aggregation}/>
+                <p id="pivot-keyboard-hint" class="visually-hidden">
+                    {locale.tr("pivot-keyboard-hint")}
+                </p>
+                <button class="btn btn-sm btn-outline-secondary mt-2" onclick={on_clear}>{locale.tr("pivot-clear")}</button>
+                if *pending {
+                    <span class="spinner-border spinner-border-sm ms-2 mt-2" role="status">
+                        <span class="visually-hidden">{locale.tr("pivot-computing")}</span>
+                    </span>
+                }
+            </div>
+            <div class="col-9 p-0 ps-2 h-100">
+                <CallJavaScriptCode ag_grid_data={pivot_grid_data(locale, &result)} grid_div_id={PIVOT_GRID_DIV_ID}/>
+            </div>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct PivotFieldZoneProp {
+    zone: PivotZone,
+    title: AttrValue,
+    fields: Vec<(String, Option<Aggregation>)>,
+    on_drop: Callback<(PivotZone, String)>,
+    on_remove: Callback<(PivotZone, usize)>,
+    on_aggregation: Callback<(usize, Aggregation)>,
+}
+
+#[function_component]
+fn PivotFieldZone(prop: &PivotFieldZoneProp) -> Html {
+    let locale = use_locale();
+    let zone = prop.zone;
+    let ondragover = Callback::from(|event: DragEvent| event.prevent_default());
+    let ondrop = {
+        let on_drop = prop.on_drop.clone();
+        Callback::from(move |event: DragEvent| {
+            event.prevent_default();
+            match event.data_transfer() {
+                Some(transfer) => match transfer.get_data(DRAG_FORMAT) {
+                    Ok(payload) => on_drop.emit((zone, payload)),
+                    Err(_) => error_1(&JsValue::from(
+                        "pivot builder error: unable to read the dragged field.",
+                    )),
+                },
+                None => error_1(&JsValue::from(
+                    "pivot builder error: drop event without data transfer.",
+                )),
+            }
+        })
+    };
+    let chips = prop.fields.iter().enumerate().map(|(index, (field, aggregation))| {
+        let payload = format!("{}:{}:{}", zone.name(), index, field);
+        // The keyboard moves fields the way dragging them does.
+        let onkeydown = {
+            let on_drop = prop.on_drop.clone();
+            let on_remove = prop.on_remove.clone();
+            let payload = payload.clone();
+            Callback::from(move |event: KeyboardEvent| {
+                let key = event.key();
+                let target = match key.as_str() {
+                    "ArrowDown" => zone.neighbour(true),
+                    "ArrowUp" => zone.neighbour(false),
+                    _ if zone == PivotZone::Available && is_activation_key(&key) => {
+                        Some(PivotZone::Rows)
+                    }
+                    "Delete" | "Backspace" if zone != PivotZone::Available => {
+                        event.prevent_default();
+                        on_remove.emit((zone, index));
+                        return;
+                    }
+                    _ => return,
+                };
+                event.prevent_default();
+                if let Some(target) = target {
+                    on_drop.emit((target, payload.clone()));
+                }
+            })
+        };
+        let ondragstart = Callback::from(move |event: DragEvent| {
+            if let Some(transfer) = event.data_transfer() {
+                if transfer.set_data(DRAG_FORMAT, &payload).is_err() {
+                    error_1(&JsValue::from("pivot builder error: unable to start the drag."));
+                }
+            }
+        });
+        let remove = if zone == PivotZone::Available {
+            html! {}
+        } else {
+            let on_remove = prop.on_remove.clone();
+            let onclick = Callback::from(move |_: MouseEvent| on_remove.emit((zone, index)));
+            html! { <button type="button" class="btn-close btn-close-white ms-1" style="font-size: 8px;" aria-label={locale.tr_args("pivot-remove-field", &[("field", field.clone().into())])} {onclick}></button> }
+        };
+        let select = match aggregation {
+            Some(current) => {
+                let on_aggregation = prop.on_aggregation.clone();
+                let onchange = Callback::from(move |event: Event| {
+                    if let Some(aggregation) = selected_aggregation(event) {
+                        on_aggregation.emit((index, aggregation));
+                    }
+                });
+                html! {
+                    <select class="form-select form-select-sm d-inline-block w-auto ms-1 py-0" style="font-size: 11px;"
+                     aria-label={locale.tr_args("pivot-aggregation", &[("field", field.clone().into())])} {onchange}>
+                        {for Aggregation::ALL.iter().map(|aggregation| html! {
+                            <option value={aggregation.label()} selected={aggregation == current}>{aggregation.name().text(locale)}</option>
+                        })}
+                    </select>
+                }
+            }
+            None => html! {},
+        };
+        let badge_class = if zone == PivotZone::Available { "bg-secondary" } else { "bg-dark" };
+        html! {
+            <span class={classes!("badge", "pivot-field", "d-inline-flex", "align-items-center", badge_class)} draggable="true"
+             role="listitem" tabindex="0" aria-describedby="pivot-keyboard-hint"
+             {ondragstart} {onkeydown}>
+                {field.clone()}{select}{remove}
+            </span>
+        }
+    });
+    html! {
+        <div class="mb-2">
+            <div class="fw-semibold" style="font-size: 12px;">{prop.title.clone()}</div>
+            <div class="pivot-zone" role="list" aria-label={prop.title.clone()} {ondragover} {ondrop}>
+                {for chips}
+            </div>
+        </div>
+    }
+}
+
+fn selected_aggregation(event: Event) -> Option<Aggregation> {
+    match event.target() {
+        Some(target) => match target.dyn_into::<HtmlSelectElement>() {
+            Ok(select_elem) => Aggregation::from_label(&select_elem.value()),
+            Err(_) => {
+                error_1(&JsValue::from(
+                    "pivot builder error: event target is not the aggregation selection element.",
+                ));
+                None
+            }
+        },
+        None => {
+            error_1(&JsValue::from("pivot builder error: target not found."));
+            None
+        }
+    }
+}
+
+fn parse_drag_payload(payload: &str) -> Option<(PivotZone, (usize, String))> {
+    let mut parts = payload.splitn(3, ':');
+    let zone = PivotZone::from_name(parts.next()?)?;
+    let index = parts.next()?.parse::<usize>().ok()?;
+    let field = parts.next()?.to_owned();
+    Some((zone, (index, field)))
+}
+
+fn move_field(
+    definition: &PivotDefinition,
+    source: PivotZone,
+    target: PivotZone,
+    (index, field): &(usize, String),
+) -> PivotDefinition {
+    let mut new_definition = definition.clone();
+    if source == target {
+        return new_definition;
+    }
+    match source {
+        PivotZone::Rows => new_definition.rows.retain(|f| f != field),
+        PivotZone::Columns => new_definition.columns.retain(|f| f != field),
+        // values are copies, so dragging one out of the values zone removes only that copy
+        PivotZone::Values if *index < new_definition.values.len() => {
+            new_definition.values.remove(*index);
+        }
+        _ => (),
+    };
+    match target {
+        PivotZone::Rows => {
+            new_definition.columns.retain(|f| f != field);
+            new_definition.rows.push(field.clone());
+        }
+        PivotZone::Columns => {
+            new_definition.rows.retain(|f| f != field);
+            new_definition.columns.push(field.clone());
+        }
+        PivotZone::Values => new_definition.values.push(PivotValue {
+            field: field.clone(),
+            aggregation: Aggregation::Count,
+        }),
+        PivotZone::Available => (),
+    };
+    new_definition
+}
+
+/// The pivot as grid rows, its total labels written for `locale`. Cells hold the numbers as they
+/// are, the grid writes them for the locale and sorts and filters them by value.
+fn pivot_grid_data(locale: Locale, result: &PivotResult) -> AGGridData {
+    let mut col_defs = Vec::<HashMap<String, String>>::new();
+    let label_fields = if result.row_fields.is_empty() {
+        vec![String::new()]
+    } else {
+        result.row_fields.clone()
+    };
+    for (i, name) in label_fields.iter().enumerate() {
+        let mut field = HashMap::<String, String>::new();
+        field.insert(String::from("field"), format!("r{i}"));
+        field.insert(String::from("headerName"), name.clone());
+        field.insert(String::from("pinned"), String::from("left"));
+        col_defs.push(field);
+    }
+    for (i, column) in result.columns.iter().enumerate() {
+        let mut field = HashMap::<String, String>::new();
+        field.insert(String::from("field"), format!("c{i}"));
+        field.insert(String::from("type"), String::from(NUMBER_VALUE_COLUMN));
+        field.insert(
+            String::from("headerName"),
+            column_header(locale, column, &result.column_fields),
+        );
+        col_defs.push(field);
+    }
+
+    let row_data = result
+        .rows
+        .iter()
+        .map(|pivot_row| {
+            let mut row = HashMap::<String, String>::new();
+            let total_label = match pivot_row.kind {
+                PivotRowKind::Subtotal => Some((SUBTOTAL_LABEL, "pivot-subtotal")),
+                PivotRowKind::GrandTotal => Some((TOTAL_LABEL, "pivot-total")),
+                PivotRowKind::Data => None,
+            };
+            for (i, label) in pivot_row.labels.iter().enumerate() {
+                let label = match total_label {
+                    Some((total, id)) if label == total => locale.tr(id),
+                    _ => label.clone(),
+                };
+                row.insert(format!("r{i}"), label);
+            }
+            for (i, cell) in pivot_row.cells.iter().enumerate() {
+                row.insert(format!("c{i}"), format_cell(*cell));
+            }
+            match pivot_row.kind {
+                PivotRowKind::Subtotal => {
+                    row.insert(String::from("__row_class"), String::from("pivot-subtotal"));
+                }
+                PivotRowKind::GrandTotal => {
+                    row.insert(String::from("__row_class"), String::from("pivot-total"));
+                }
+                PivotRowKind::Data => (),
+            };
+            row
+        })
+        .collect();
+
+    AGGridData {
+        row_data: Rc::new(row_data),
+        col_defs,
+    }
+}
@@ -0,0 +1,126 @@
+use crate::components::locale::use_locale;
+use crate::i18n::Locale;
+use crate::profile::{ColumnKind, ColumnProfile};
+use crate::table::Table;
+use crate::worker::JobId;
+use crate::worker_client::{self, JobHandle};
+
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct ProfilePanelProp {
+    pub data: Rc<Table>,
+    /// The id the worker keeps `data` under.
+    pub data_id: JobId,
+}
+
+/// Statistics of every column, computed in the worker while the panel is open.
+#[function_component]
+pub fn ProfilePanel(prop: &ProfilePanelProp) -> Html {
+    let locale = use_locale();
+    let profile = use_state(|| None::<Result<Rc<Vec<ColumnProfile>>, String>>);
+    let job = use_mut_ref(|| None::<JobHandle>);
+    {
+        let profile = profile.clone();
+        let data = prop.data.clone();
+        use_effect_with_deps(
+            move |(data_id, _rows)| {
+                profile.set(None);
+                let data_id = *data_id;
+                *job.borrow_mut() = Some(JobHandle::spawn(async move {
+                    let result = worker_client::profile(data_id, data).await;
+                    profile.set(Some(
+                        result.map(Rc::new).map_err(|error| error.message().clone()),
+                    ));
+                }));
+                // Closing the panel cancels a profile still being computed.
+                move || drop(job.borrow_mut().take())
+            },
+            (prop.data_id, prop.data.len()),
+        );
+    }
+
+    let body = match &*profile {
+        None => html! {
+            <div class="text-muted d-flex align-items-center gap-2" role="status">
+                <span class="spinner-border spinner-border-sm" aria-hidden="true"></span>
+                {locale.tr_args("profile-running", &[("rows", prop.data.len().into())])}
+            </div>
+        },
+        Some(Err(message)) => html! { <div class="text-danger">{message.clone()}</div> },
+        Some(Ok(columns)) => html! {
+            <table class="table table-sm mb-0">
+                <thead>
+                    <tr>
+                        <th>{locale.tr("profile-column")}</th>
+                        <th>{locale.tr("profile-type")}</th>
+                        <th class="text-end">{locale.tr("profile-filled")}</th>
+                        <th class="text-end">{locale.tr("profile-empty")}</th>
+                        <th class="text-end">{locale.tr("profile-distinct")}</th>
+                        <th>{locale.tr("profile-range")}</th>
+                        <th>{locale.tr("profile-most-frequent")}</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {for columns.iter().map(|column| profile_row(locale, column))}
+                </tbody>
+            </table>
+        },
+    };
+
+    html! {
+        <div class="card shadow position-absolute start-0 p-2" style="top: 100%; z-index: 1050; width: 640px; font-size: 12px;">
+            <div style="max-height: 60vh; overflow-y: auto;">{body}</div>
+        </div>
+    }
+}
+
+fn profile_row(locale: Locale, column: &ColumnProfile) -> Html {
+    let is_number = matches!(column.kind, ColumnKind::Integer | ColumnKind::Float);
+    // The bounds of number columns are written as the locale writes numbers.
+    let bound = |value: &String| match is_number {
+        true => locale.format_number(value).unwrap_or_else(|| value.clone()),
+        false => value.clone(),
+    };
+    let count_text = |count: usize| locale.format_number(&count.to_string()).unwrap_or_default();
+    let range = match (&column.min, &column.max) {
+        (Some(min), Some(max)) if min == max => bound(min),
+        (Some(min), Some(max)) => format!("{} – {}", bound(min), bound(max)),
+        _ => String::new(),
+    };
+    let range = match column.mean {
+        Some(mean) => locale.tr_args(
+            "profile-mean",
+            &[
+                ("range", range.into()),
+                (
+                    "mean",
+                    locale
+                        .format_number(&format!("{mean:.2}"))
+                        .unwrap_or_default()
+                        .into(),
+                ),
+            ],
+        ),
+        None => range,
+    };
+    let top = column
+        .top
+        .iter()
+        .map(|(value, count)| format!("{value} ({})", count_text(*count)))
+        .collect::<Vec<String>>()
+        .join(&locale.tr("list-separator"));
+    html! {
+        <tr>
+            <td class="fw-semibold">{column.name.clone()}</td>
+            <td class="text-muted">{column.kind.describe().text(locale)}</td>
+            <td class="text-end">{count_text(column.filled)}</td>
+            <td class="text-end">{count_text(column.empty)}</td>
+            <td class="text-end">{count_text(column.distinct)}</td>
+            <td>{range}</td>
+            <td class="text-truncate" style="max-width: 200px;" title={top.clone()}>{top}</td>
+        </tr>
+    }
+}
@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod test_row_cards;
+
+use crate::a11y::is_activation_key;
+use crate::components::locale::use_locale;
+use crate::formatting::{cell_classes, localized_value};
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+/// Cards rendered at first and added by each "Show more".
+const CARDS_PER_PAGE: usize = 50;
+
+/// Indices of the rows where any of `fields` contains `query`, ignoring case.
+pub fn matching_rows(
+    rows: &[HashMap<String, String>],
+    fields: &[String],
+    query: &str,
+) -> Vec<usize> {
+    let needle = query.trim().to_lowercase();
+    rows.iter()
+        .enumerate()
+        .filter(|(_, row)| {
+            needle.is_empty()
+                || fields.iter().any(|field| {
+                    row.get(field)
+                        .is_some_and(|value| value.to_lowercase().contains(&needle))
+                })
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+#[derive(Properties, PartialEq)]
+pub struct RowCardsProp {
+    pub row_data: Rc<Vec<HashMap<String, String>>>,
+    pub fields: Vec<String>,
+    /// Field shown as the card title, e.g. the subject.
+    #[prop_or_default]
+    pub title_field: Option<AttrValue>,
+    /// Column types by field of the columns written the locale's way, see `i18n::column_type`.
+    #[prop_or_default]
+    pub column_types: HashMap<String, String>,
+    /// Initial text of the filter box.
+    #[prop_or_default]
+    pub query: Option<String>,
+    /// Receives the index in `row_data` of the card the user tapped.
+    pub on_row_clicked: Callback<usize>,
+}
+
+/// Rows as a list of cards, for screens too narrow for a grid.
+#[function_component]
+pub fn RowCards(prop: &RowCardsProp) -> Html {
+    let locale = use_locale();
+    let query = use_state(|| prop.query.clone().unwrap_or_default());
+    let shown = use_state(|| CARDS_PER_PAGE);
+    let matches = use_memo(
+        |(row_data, fields, query)| matching_rows(row_data, fields, query),
+        (prop.row_data.clone(), prop.fields.clone(), (*query).clone()),
+    );
+
+    let on_query_input = {
+        let query = query.clone();
+        let shown = shown.clone();
+        Callback::from(move |event: InputEvent| {
+            if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                query.set(input.value());
+                shown.set(CARDS_PER_PAGE);
+            }
+        })
+    };
+    let on_show_more = {
+        let shown = shown.clone();
+        Callback::from(move |_: MouseEvent| shown.set(*shown + CARDS_PER_PAGE))
+    };
+
+    let title_field = prop.title_field.as_ref().map(|field| field.to_string());
+    let cards = matches.iter().take(*shown).map(|index| {
+        let row = &prop.row_data[*index];
+        let on_click = {
+            let on_row_clicked = prop.on_row_clicked.clone();
+            let index = *index;
+            Callback::from(move |_: MouseEvent| on_row_clicked.emit(index))
+        };
+        let on_key = {
+            let on_row_clicked = prop.on_row_clicked.clone();
+            let index = *index;
+            Callback::from(move |event: KeyboardEvent| {
+                if is_activation_key(&event.key()) {
+                    event.prevent_default();
+                    on_row_clicked.emit(index);
+                }
+            })
+        };
+        let title = title_field
+            .as_ref()
+            .and_then(|field| row.get(field))
+            .cloned()
+            .unwrap_or_else(|| locale.tr_args("row-number", &[("row", (index + 1).into())]));
+        html! {
+            <div class="card shadow-sm mb-2" role="button" tabindex="0" onclick={on_click} onkeydown={on_key}>
+                <div class="card-body p-2">
+                    <div class="fw-semibold mb-1">{title}</div>
+                    <dl class="row-card-fields mb-0">
+                        {for prop.fields.iter().filter(|field| Some(*field) != title_field.as_ref()).map(|field| html! {
+                            <>
+                                <dt>{field.clone()}</dt>
+                                <dd class={cell_classes(row, field).to_owned()}>
+                                    {localized_value(row, field, &prop.column_types, locale).unwrap_or_default()}
+                                </dd>
+                            </>
+                        })}
+                    </dl>
+                </div>
+            </div>
+        }
+    });
+    let show_more = if matches.len() > *shown {
+        html! {
+            <button type="button" class="btn btn-outline-dark btn-sm w-100 mb-3" onclick={on_show_more}>
+                {locale.tr_args("cards-show-more", &[("rows", (matches.len() - *shown).into())])}
+            </button>
+        }
+    } else {
+        html! {}
+    };
+
+    html! {
+        <div class="row-cards">
+            <input type="search" class="form-control form-control-sm mb-2" placeholder={locale.tr("cards-filter-placeholder")}
+             aria-label={locale.tr("cards-filter")}
+             value={(*query).clone()} oninput={on_query_input}/>
+            <small class="text-muted d-block mb-2" aria-live="polite">
+                {locale.tr_args("grid-rows-shown", &[("shown", matches.len().into()), ("rows", prop.row_data.len().into())])}
+            </small>
+            {for cards}
+            {show_more}
+        </div>
+    }
+}
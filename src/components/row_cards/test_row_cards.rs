@@ -0,0 +1,48 @@
+use super::{matching_rows, RowCards, RowCardsProp};
+use crate::a11y::audit::{assert_accessible, render_component};
+use std::collections::HashMap;
+use std::rc::Rc;
+use wasm_bindgen_test::wasm_bindgen_test;
+use yew::prelude::*;
+
+fn row(subject: &str, aeterm: &str) -> HashMap<String, String> {
+    HashMap::from([
+        (String::from("subject"), String::from(subject)),
+        (String::from("aeterm"), String::from(aeterm)),
+    ])
+}
+
+#[wasm_bindgen_test]
+fn test_matching_rows_searches_every_field() {
+    let rows = vec![
+        row("1023-004", "Pneumonitis"),
+        row("1023-005", "Fatigue"),
+        row("1023-006", "pneumonitis, grade 2"),
+    ];
+    let fields = vec![String::from("subject"), String::from("aeterm")];
+    assert_eq!(matching_rows(&rows, &fields, "PNEUMO"), vec![0, 2]);
+    assert_eq!(matching_rows(&rows, &fields, "005"), vec![1]);
+    assert_eq!(matching_rows(&rows, &fields, " "), vec![0, 1, 2]);
+}
+
+#[wasm_bindgen_test]
+async fn test_row_cards_are_keyboard_operable() {
+    let markup = render_component::<RowCards>(RowCardsProp {
+        row_data: Rc::new(vec![
+            row("1023-004", "Pneumonitis"),
+            row("1023-005", "Fatigue"),
+        ]),
+        fields: vec![String::from("subject"), String::from("aeterm")],
+        title_field: Some(AttrValue::Static("subject")),
+        column_types: HashMap::new(),
+        query: None,
+        on_row_clicked: Callback::noop(),
+    })
+    .await;
+    assert_eq!(
+        markup.matches("role=\"button\" tabindex=\"0\"").count(),
+        2,
+        "{markup}"
+    );
+    assert_accessible(&markup);
+}
@@ -0,0 +1,243 @@
+#[cfg(test)]
+mod test_row_detail;
+
+use crate::a11y::Announcement;
+use crate::components::live_region::Announcer;
+use crate::components::locale::use_locale;
+use crate::define_xml::DatasetDef;
+
+use gloo_utils::document;
+use serde_json::{Map, Value};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::console::error_1;
+use web_sys::HtmlElement;
+use yew::prelude::*;
+
+#[wasm_bindgen(module = "/js/clipboard.js")]
+extern "C" {
+    pub(crate) async fn copy_text(text: String) -> JsValue;
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum CopyFormat {
+    Json,
+    Tsv,
+    Markdown,
+}
+
+impl CopyFormat {
+    fn label(&self) -> &'static str {
+        match self {
+            CopyFormat::Json => "JSON",
+            CopyFormat::Tsv => "TSV",
+            CopyFormat::Markdown => "Markdown",
+        }
+    }
+
+    fn render(&self, record: &Map<String, Value>) -> String {
+        match self {
+            CopyFormat::Json => record_to_json(record),
+            CopyFormat::Tsv => record_to_tsv(record),
+            CopyFormat::Markdown => record_to_markdown(record),
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct RowDetailProp {
+    pub record: Map<String, Value>,
+    pub on_close: Callback<()>,
+    /// The table's define.xml dataset, to show variable labels and derivations.
+    #[prop_or_default]
+    pub documentation: Option<DatasetDef>,
+}
+
+#[function_component]
+pub fn RowDetail(prop: &RowDetailProp) -> Html {
+    let locale = use_locale();
+    let copied = use_state(|| None::<CopyFormat>);
+    let announcer = use_context::<Announcer>();
+    // The panel takes the focus while open and gives it back to where it was once closed.
+    let panel = use_node_ref();
+    {
+        let panel = panel.clone();
+        use_effect_with_deps(
+            move |_| {
+                let previous = document()
+                    .active_element()
+                    .and_then(|element| element.dyn_into::<HtmlElement>().ok());
+                if let Some(panel) = panel.cast::<HtmlElement>() {
+                    let _ = panel.focus();
+                }
+                move || {
+                    if let Some(previous) = previous {
+                        let _ = previous.focus();
+                    }
+                }
+            },
+            (),
+        );
+    }
+    let copy_button = |format: CopyFormat| {
+        let label = locale.tr_args(
+            if *copied == Some(format) {
+                "row-detail-copied"
+            } else {
+                "row-detail-copy"
+            },
+            &[("format", format.label().into())],
+        );
+        let copied = copied.clone();
+        let announcer = announcer.clone();
+        let text = format.render(&prop.record);
+        let onclick = Callback::from(move |_: MouseEvent| {
+            let copied = copied.clone();
+            let announcer = announcer.clone();
+            let text = text.clone();
+            spawn_local(async move {
+                let announcement = if copy_text(text).await.as_bool() == Some(true) {
+                    copied.set(Some(format));
+                    Announcement::status(locale.tr_args(
+                        "row-detail-copied-row",
+                        &[("format", format.label().into())],
+                    ))
+                } else {
+                    error_1(&JsValue::from(
+                        "row detail error: unable to write to the clipboard.",
+                    ));
+                    Announcement::alert(locale.tr("row-detail-copy-failed"))
+                };
+                if let Some(announcer) = announcer {
+                    announcer.announce(announcement);
+                }
+            });
+        });
+        html! { <button type="button" class="btn btn-sm btn-outline-dark me-1" {onclick}>{label}</button> }
+    };
+    let on_close = {
+        let on_close = prop.on_close.clone();
+        Callback::from(move |_: MouseEvent| on_close.emit(()))
+    };
+    let on_keydown = {
+        let on_close = prop.on_close.clone();
+        Callback::from(move |event: KeyboardEvent| {
+            if event.key() == "Escape" {
+                event.prevent_default();
+                on_close.emit(());
+            }
+        })
+    };
+    let fields = prop.record.iter().map(|(key, value)| {
+        let value_class = if value.is_null() {
+            "text-muted fst-italic"
+        } else {
+            ""
+        };
+        let variable = prop
+            .documentation
+            .as_ref()
+            .and_then(|dataset| dataset.variable(key));
+        html! {
+            <>
+                <dt class="col-4 text-break">
+                    {key.clone()}
+                    if let Some(variable) = variable {
+                        <div class="fw-normal text-muted">{variable.label.clone()}</div>
+                    }
+                </dt>
+                <dd class="col-8 text-break" style="white-space: pre-wrap;">
+                    <span class={value_class}>{value_text(value)}</span>
+                    <span class="badge text-bg-light ms-1 fw-normal">{value_type(value)}</span>
+                    if let Some(derivation) = variable.and_then(|variable| variable.derivation.clone()) {
+                        <div class="text-muted fst-italic">{locale.tr_args("row-detail-derivation", &[("derivation", derivation.into())])}</div>
+                    }
+                </dd>
+            </>
+        }
+    });
+
+    html! {
+        <>
+            <div class="offcanvas-backdrop fade show" onclick={on_close.clone()}></div>
+            <div ref={panel} class="offcanvas offcanvas-end show" style="visibility: visible; width: 480px;" tabindex="-1"
+             role="dialog" aria-modal="true" aria-labelledby="row-detail-title" onkeydown={on_keydown}>
+                <div class="offcanvas-header bg-dark text-white">
+                    <h5 id="row-detail-title" class="offcanvas-title">{locale.tr("row-detail-title")}</h5>
+                    <button type="button" class="btn-close btn-close-white" aria-label={locale.tr("close")} onclick={on_close}></button>
+                </div>
+                <div class="offcanvas-body" style="font-size: 12px;">
+                    <div class="mb-3">
+                        {copy_button(CopyFormat::Json)}
+                        {copy_button(CopyFormat::Tsv)}
+                        {copy_button(CopyFormat::Markdown)}
+                    </div>
+                    <dl class="row" style="font-family: courier, monospace;">
+                        {for fields}
+                    </dl>
+                </div>
+            </div>
+        </>
+    }
+}
+
+fn value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// The value as it was received: strings unquoted, everything else as JSON.
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn record_to_json(record: &Map<String, Value>) -> String {
+    match serde_json::to_string_pretty(record) {
+        Ok(json) => json,
+        Err(error) => {
+            error_1(&JsValue::from(format!(
+                "row detail error: unable to serialize the row: {error}"
+            )));
+            String::new()
+        }
+    }
+}
+
+fn record_to_tsv(record: &Map<String, Value>) -> String {
+    let escape = |text: String| text.replace(['\t', '\n', '\r'], " ");
+    let header: Vec<String> = record.keys().map(|key| escape(key.clone())).collect();
+    let values: Vec<String> = record
+        .values()
+        .map(|value| match value {
+            Value::Null => String::new(),
+            other => escape(value_text(other)),
+        })
+        .collect();
+    format!("{}\n{}\n", header.join("\t"), values.join("\t"))
+}
+
+fn record_to_markdown(record: &Map<String, Value>) -> String {
+    let escape = |text: String| {
+        text.replace('|', "\\|")
+            .replace("\r\n", "<br>")
+            .replace('\n', "<br>")
+    };
+    let mut markdown = String::from("| Field | Value |\n| --- | --- |\n");
+    for (key, value) in record {
+        markdown.push_str(&format!(
+            "| {} | {} |\n",
+            escape(key.clone()),
+            escape(value_text(value))
+        ));
+    }
+    markdown
+}
@@ -0,0 +1,86 @@
+use super::{
+    record_to_json, record_to_markdown, record_to_tsv, value_text, RowDetail, RowDetailProp,
+};
+use crate::a11y::audit::{assert_accessible, elements, render_component};
+use crate::table::Table;
+use serde_json::{json, Map, Value};
+use wasm_bindgen_test::wasm_bindgen_test;
+use yew::Callback;
+
+fn record() -> Map<String, Value> {
+    match json!({
+        "aeterm": "Pneumonitis | grade 3\nresolved",
+        "aeseq": 2,
+        "aeser": true,
+        "aeendtc": null,
+        "dose": 0.125
+    }) {
+        Value::Object(map) => map,
+        _ => panic!("record is not an object"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_value_text_keeps_original_types() {
+    let record = record();
+    assert_eq!(value_text(&record["aeendtc"]), "null");
+    assert_eq!(value_text(&record["dose"]), "0.125");
+    assert_eq!(value_text(&record["aeser"]), "true");
+    assert_eq!(
+        value_text(&record["aeterm"]),
+        "Pneumonitis | grade 3\nresolved"
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_record_to_json_round_trips() {
+    let record = record();
+    let parsed: Map<String, Value> = serde_json::from_str(&record_to_json(&record)).unwrap();
+    assert_eq!(parsed, record);
+}
+
+#[wasm_bindgen_test]
+fn test_record_to_tsv_escapes_line_breaks() {
+    let tsv = record_to_tsv(&record());
+    let lines: Vec<&str> = tsv.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0], "aeterm\taeseq\taeser\taeendtc\tdose");
+    assert_eq!(lines[1], "Pneumonitis | grade 3 resolved\t2\ttrue\t\t0.125");
+}
+
+#[wasm_bindgen_test]
+fn test_table_rows_keep_column_order() {
+    let table = Table::from_records(&[record()]).ok().unwrap();
+    let fields: Vec<String> = table.record(0).keys().cloned().collect();
+    assert_eq!(fields, table.column_names());
+    assert_eq!(fields, vec!["aeterm", "aeseq", "aeser", "aeendtc", "dose"]);
+}
+
+#[wasm_bindgen_test]
+fn test_record_to_markdown_escapes_pipes() {
+    let markdown = record_to_markdown(&record());
+    assert!(markdown.starts_with("| Field | Value |\n| --- | --- |\n"));
+    assert!(markdown.contains("| aeterm | Pneumonitis \\| grade 3<br>resolved |\n"));
+    assert!(markdown.contains("| aeendtc | null |\n"));
+}
+
+#[wasm_bindgen_test]
+async fn test_row_detail_is_a_labelled_dialog() {
+    let markup = render_component::<RowDetail>(RowDetailProp {
+        record: record(),
+        on_close: Callback::noop(),
+        documentation: None,
+    })
+    .await;
+    assert_accessible(&markup);
+    let parsed = elements(&markup);
+    let dialog = parsed
+        .iter()
+        .find(|element| element.attribute("role") == Some("dialog"))
+        .expect("the row detail is not a dialog");
+    assert_eq!(
+        dialog.attribute("aria-labelledby"),
+        Some("row-detail-title")
+    );
+    assert_eq!(dialog.attribute("aria-modal"), Some("true"));
+}
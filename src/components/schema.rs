@@ -1,7 +1,12 @@
 #[cfg(test)]
 mod test_schema;
 
-use crate::state::StyewardState;
+use crate::a11y::Announcement;
+use crate::components::live_region::use_announcement;
+use crate::components::locale::use_locale;
+use crate::i18n::Locale;
+use crate::offline::{self, pinned_schemas, use_online};
+use crate::state::{StoreHandle, StyewardAction};
 use crate::StyewardConfig;
 
 use gloo_net;
@@ -11,10 +16,8 @@ use url::Url;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{console::error_1, Event, HtmlSelectElement};
 use yew::functional::use_context;
-use yew::prelude::{
-    function_component, html, Callback, Children, Html, Properties, UseStateHandle,
-};
-use yew::suspense::use_future;
+use yew::prelude::{function_component, html, Callback, Children, Html, Properties};
+use yew::suspense::use_future_with_deps;
 use yew::virtual_dom::{VList, VNode, VTag, VText};
 
 pub enum SchemeSelectionError {
@@ -24,6 +27,17 @@ pub enum SchemeSelectionError {
     DeserializeError(String),
 }
 
+impl SchemeSelectionError {
+    pub fn message(&self) -> &String {
+        match self {
+            SchemeSelectionError::EndpointError(msg)
+            | SchemeSelectionError::GlooError(msg)
+            | SchemeSelectionError::HttpStatusError(msg)
+            | SchemeSelectionError::DeserializeError(msg) => msg,
+        }
+    }
+}
+
 #[derive(Properties, PartialEq)]
 struct ConfigProp {
     config: StyewardConfig,
@@ -40,9 +54,10 @@ fn style_the_select(node: VNode) -> Html {
     }
 }
 
-fn get_error_select(label: String) -> Html {
+fn get_error_select(locale: Locale, label_id: &str) -> Html {
+    let label = locale.tr(label_id);
     let output_node = html! {
-        <select disabled={true}>
+        <select disabled={true} aria-label={locale.tr("schema-label")}>
             <option selected={true} value={label.clone()} disabled={true}>
                 {label.clone()}
             </option>
@@ -53,23 +68,47 @@ fn get_error_select(label: String) -> Html {
 
 #[function_component]
 pub fn SchemaSelection() -> Html {
+    let locale = use_locale();
     match use_context::<StyewardConfig>() {
         Some(config) => {
             html! {<RemoteSelection config={config}/>}
         }
-        None => get_error_select(format!("error!")),
+        None => get_error_select(locale, "select-error"),
     }
 }
 
 #[function_component]
+#[allow(clippy::useless_format)]
 fn RemoteSelection(prop: &ConfigProp) -> Html {
+    let locale = use_locale();
     let config = prop.config.clone();
-    let remote_schemas = use_future(|| async move {
-        let schema_path = format!("api/v1/schemas");
-        get_schemas(&config.scheme, &config.host, config.port, &schema_path)
-            .await
+    let online = use_online();
+    let remote_schemas = use_future_with_deps(
+        |online| async move {
+            let schema_path = format!("api/v1/schemas");
+            let schemas = if *online {
+                get_schemas(&config.scheme, &config.host, config.port, &schema_path).await
+            } else {
+                Err(SchemeSelectionError::GlooError(String::from(
+                    "schema select error: the browser is offline.",
+                )))
+            };
+            match schemas {
+                Err(SchemeSelectionError::GlooError(msg)) => pinned_schemas_or(msg).await,
+                schemas => schemas,
+            }
             .map(get_schema_options)
-    });
+        },
+        online,
+    );
+    let announcement = match &remote_schemas {
+        Ok(result_ref) => (*result_ref)
+            .as_ref()
+            .err()
+            .map(|_| Announcement::alert(locale.tr("schemas-not-loaded"))),
+        Err(_) => None,
+    };
+    use_announcement(announcement);
     match remote_schemas {
         Ok(result_ref) => match &*result_ref {
             Ok(v_list) => {
@@ -87,10 +126,10 @@ fn RemoteSelection(prop: &ConfigProp) -> Html {
                     | SchemeSelectionError::HttpStatusError(m)
                     | SchemeSelectionError::DeserializeError(m) => error_1(&m.into()),
                 };
-                get_error_select(format!("error!"))
+                get_error_select(locale, "select-error")
             }
         },
-        Err(_) => get_error_select(format!("loading...")),
+        Err(_) => get_error_select(locale, "select-loading"),
     }
 }
 
@@ -107,18 +146,20 @@ struct PropSelectCallback {
 
 #[function_component]
 fn FilledSelection(prop: &PropOptionsList) -> Html {
-    match use_context::<UseStateHandle<StyewardState>>() {
-        Some(handle) => {
+    let locale = use_locale();
+    match use_context::<StoreHandle>() {
+        Some(store) => {
+            let current_schema = store.current_schema.clone();
             let cb = Callback::from(move |event: Event| {
-                let handle = handle.clone();
-                update_current_schema(handle, event);
+                let store = store.clone();
+                update_current_schema(store, event);
             });
             html! {
                 <HandledSelection cb={cb}>
-                    <option selected={true} value={"NA"} disabled={true}>
-                        {"select schema"}
+                    <option selected={current_schema.is_none()} value={"NA"} disabled={true}>
+                        {locale.tr("select-schema")}
                     </option>
-                    {for prop.children.iter()}
+                    {for prop.children.iter().map(|child| mark_selected_option(child, &current_schema))}
                 </HandledSelection>
             }
         }
@@ -126,31 +167,33 @@ fn FilledSelection(prop: &PropOptionsList) -> Html {
             error_1(&JsValue::from_str(
                 "schema select error: state context not provided",
             ));
-            get_error_select(format!("ERROR: Contact Support."))
+            get_error_select(locale, "select-contact-support")
         }
     }
 }
 
 #[function_component]
 fn HandledSelection(prop: &PropSelectCallback) -> Html {
+    let locale = use_locale();
     let onchange = prop.cb.clone();
     let output_vnode = html! {
         <select {onchange} id={"schema-select"}>
             {for prop.children.iter()}
         </select>
     };
-    style_the_select(output_vnode)
+    html! {
+        <>
+            <label for="schema-select" class="visually-hidden">{locale.tr("schema-label")}</label>
+            {style_the_select(output_vnode)}
+        </>
+    }
 }
 
-fn update_current_schema(handle: UseStateHandle<StyewardState>, event: Event) {
+fn update_current_schema(store: StoreHandle, event: Event) {
     match event.target() {
         Some(target) => match target.dyn_into::<HtmlSelectElement>() {
             Ok(select_elem) => {
-                let schema = select_elem.value();
-                handle.set(StyewardState {
-                    current_schema: Some(schema),
-                    current_table: None,
-                });
+                store.dispatch(StyewardAction::SelectSchema(select_elem.value()));
             }
             Err(_) => {
                 error_1(&JsValue::from(
@@ -171,6 +214,31 @@ fn get_schema_options(option_strings: Vec<String>) -> VList {
     v_list
 }
 
+fn mark_selected_option(node: VNode, selected: &Option<String>) -> VNode {
+    match (node, selected) {
+        (VNode::VList(v_list), Some(selected)) => {
+            let children = v_list
+                .iter()
+                .cloned()
+                .map(|child| match child {
+                    VNode::VTag(mut tag)
+                        if tag
+                            .attributes
+                            .iter()
+                            .any(|(key, value)| key == "value" && value == selected) =>
+                    {
+                        tag.add_attribute("selected", "selected");
+                        VNode::VTag(tag)
+                    }
+                    other => other,
+                })
+                .collect();
+            VNode::VList(VList::with_children(children, None))
+        }
+        (node, _) => node,
+    }
+}
+
 fn _get_schema_option(option_text: &String) -> VNode {
     let text_node = VNode::VText(VText::new(String::from(option_text)));
     let mut option_node = VTag::new("option");
@@ -179,7 +247,16 @@ fn _get_schema_option(option_text: &String) -> VNode {
     VNode::VTag(Box::new(option_node))
 }
 
-async fn get_schemas(
+/// The schemas with tables pinned to the device when the API cannot be reached, `msg` when none are.
+async fn pinned_schemas_or(msg: String) -> Result<Vec<String>, SchemeSelectionError> {
+    match offline::pinned_tables().await {
+        Ok(pinned) if !pinned.is_empty() => Ok(pinned_schemas(&pinned)),
+        _ => Err(SchemeSelectionError::GlooError(msg)),
+    }
+}
+
+/// Names of the schemas the API serves at `schema_path`.
+pub async fn get_schemas(
     scheme: &String,
     host: &String,
     port: u16,
@@ -219,6 +296,7 @@ fn handle_gloo_error(gloo_error: gloo_net::Error) -> SchemeSelectionError {
     }
 }
 
+#[allow(clippy::useless_format)]
 async fn get_schemas_from_response(
     response: Response,
 ) -> Result<Vec<String>, SchemeSelectionError> {
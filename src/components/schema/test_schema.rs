@@ -1,6 +1,9 @@
-use super::get_schema_options;
+use super::{get_error_select, get_schema_options, HandledSelection, PropSelectCallback};
+use crate::a11y::audit::{assert_accessible, render, render_component};
+use crate::i18n::Locale;
 use rand::{random, thread_rng, Rng};
 use wasm_bindgen_test::wasm_bindgen_test;
+use yew::prelude::{Callback, Children};
 use yew::virtual_dom::VNode;
 
 #[wasm_bindgen_test]
@@ -34,3 +37,18 @@ fn test_get_schema_options_with_empty_list() {
     let v_node_list = get_schema_options(options);
     assert_eq!(v_node_list.iter().count(), 0);
 }
+
+#[wasm_bindgen_test]
+async fn test_schema_selects_are_labelled() {
+    let options = get_schema_options(vec![String::from("sdtm"), String::from("adam")]);
+    let markup = render_component::<HandledSelection>(PropSelectCallback {
+        cb: Callback::noop(),
+        children: Children::new(vec![VNode::VList(options)]),
+    })
+    .await;
+    assert!(markup.contains("<label for=\"schema-select\""), "{markup}");
+    assert_accessible(&markup);
+
+    let markup = render(get_error_select(Locale::EnUs, "select-loading")).await;
+    assert_accessible(&markup);
+}
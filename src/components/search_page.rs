@@ -0,0 +1,336 @@
+use crate::components::locale::use_locale;
+use crate::components::nav::NavBar;
+use crate::components::schema::SchemaSelection;
+use crate::components::store::StoreProvider;
+use crate::components::table_selection::get_tables;
+use crate::components::table_visualization::HIDDEN_FIELD_PREFIX;
+use crate::search::SearchHit;
+use crate::state::StoreHandle;
+use crate::table_cache;
+use crate::worker_client::{self, JobHandle};
+use crate::{MainRoute, StyewardConfig};
+
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use wasm_bindgen::JsValue;
+use web_sys::{console::error_1, HtmlInputElement};
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+/// Tables loaded at the same time while searching.
+const SEARCH_CONCURRENCY: usize = 4;
+const MAX_SAMPLE_ROWS: usize = 3;
+
+#[derive(Clone, PartialEq, Default)]
+struct SearchProgress {
+    searching: bool,
+    cancelled: bool,
+    searched_tables: usize,
+    total_tables: usize,
+    failed_tables: Vec<String>,
+    hits: Vec<SearchHit>,
+}
+
+#[derive(Serialize)]
+struct FilterQuery {
+    column: String,
+    value: String,
+}
+
+#[function_component]
+pub fn SearchPage() -> Html {
+    let locale = use_locale();
+    html! {
+        <div class="container-fluid bg-light m-0 p-0" style="min-height: 100vh">
+            <div class="row">
+                <NavBar/>
+            </div>
+            <div class="row bg-light text-dark px-3 py-4">
+                <div class="col">
+                    <span class="align-middle">{locale.tr("search-intro")}</span>
+                </div>
+            </div>
+            <div class="row px-3">
+                <StoreProvider>
+                    <div class="container px-2">
+                        <div class="row">
+                            <div class="col shadow-sm px-0 ms-3 flex" style="max-width: 300px;"><SchemaSelection/></div>
+                        </div>
+                        <SelectedSchemaSearch/>
+                    </div>
+                </StoreProvider>
+            </div>
+        </div>
+    }
+}
+
+/// Searches the schema selected in the store.
+#[function_component]
+fn SelectedSchemaSearch() -> Html {
+    let schema = use_context::<StoreHandle>().and_then(|store| store.current_schema.clone());
+    html! { <SchemaSearch schema={schema}/> }
+}
+
+#[derive(Properties, PartialEq)]
+struct SchemaSearchProp {
+    schema: Option<String>,
+}
+
+#[function_component]
+fn SchemaSearch(prop: &SchemaSearchProp) -> Html {
+    let locale = use_locale();
+    let config = use_context::<StyewardConfig>();
+    let navigator = use_navigator();
+    let query = use_state(String::new);
+    let searched_query = use_state(String::new);
+    let progress = use_state(SearchProgress::default);
+    // The running search, replacing or dropping it aborts the table loads and searches it has
+    // in flight.
+    let search = use_mut_ref(|| None::<JobHandle>);
+    {
+        let progress = progress.clone();
+        let search = search.clone();
+        use_effect_with_deps(
+            move |_| {
+                search.borrow_mut().take();
+                progress.set(SearchProgress::default());
+                || ()
+            },
+            prop.schema.clone(),
+        );
+    }
+
+    let on_query_input = {
+        let query = query.clone();
+        Callback::from(move |event: InputEvent| {
+            if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                query.set(input.value());
+            }
+        })
+    };
+    let on_search = {
+        let query = query.clone();
+        let searched_query = searched_query.clone();
+        let progress = progress.clone();
+        let search = search.clone();
+        let schema = prop.schema.clone();
+        Callback::from(move |event: SubmitEvent| {
+            event.prevent_default();
+            let (config, schema) = match (config.clone(), schema.clone()) {
+                (Some(config), Some(schema)) => (config, schema),
+                _ => return,
+            };
+            let needle = query.trim().to_string();
+            if needle.is_empty() {
+                return;
+            }
+            searched_query.set(needle.clone());
+            *search.borrow_mut() = Some(JobHandle::spawn(search_schema(
+                config,
+                schema,
+                needle,
+                progress.clone(),
+            )));
+        })
+    };
+    let on_cancel = {
+        let progress = progress.clone();
+        let search = search.clone();
+        Callback::from(move |_: MouseEvent| {
+            search.borrow_mut().take();
+            progress.set(SearchProgress {
+                searching: false,
+                cancelled: true,
+                ..(*progress).clone()
+            });
+        })
+    };
+
+    let schema = match &prop.schema {
+        Some(schema) => schema.clone(),
+        None => {
+            return html! {
+                <div class="row px-3 py-3 text-muted">{locale.tr("search-select-schema")}</div>
+            }
+        }
+    };
+
+    let progress_bar = if progress.total_tables > 0 || progress.searching {
+        let percent = match progress.total_tables {
+            0 => 0,
+            total => progress.searched_tables * 100 / total,
+        };
+        let label = locale.tr_args(
+            if progress.cancelled {
+                "search-cancelled"
+            } else {
+                "search-progress"
+            },
+            &[
+                ("searched", progress.searched_tables.into()),
+                ("tables", progress.total_tables.into()),
+            ],
+        );
+        html! {
+            <div class="mt-3">
+                <div class="progress" style="height: 6px;">
+                    <div class="progress-bar bg-dark" role="progressbar" style={format!("width: {percent}%;")}
+                     aria-valuenow={percent.to_string()} aria-valuemin="0" aria-valuemax="100"></div>
+                </div>
+                <small class="text-muted">{label}</small>
+            </div>
+        }
+    } else {
+        html! {}
+    };
+    let failed = if progress.failed_tables.is_empty() {
+        html! {}
+    } else {
+        html! {
+            <div class="text-danger small mt-1">
+                {locale.tr_args("search-failed", &[("tables", progress.failed_tables.join(&locale.tr("list-separator")).into())])}
+            </div>
+        }
+    };
+    let hits = progress.hits.iter().map(|hit| {
+        let on_open = {
+            let navigator = navigator.clone();
+            let route = MainRoute::DataTableView {
+                schema: schema.clone(),
+                table: hit.table.clone(),
+            };
+            let filter = FilterQuery {
+                column: hit.column.clone(),
+                value: (*searched_query).clone(),
+            };
+            Callback::from(move |_: MouseEvent| {
+                if let Some(navigator) = &navigator {
+                    if let Err(error) = navigator.push_with_query(&route, &filter) {
+                        error_1(&JsValue::from(format!("search error: {error}")));
+                    }
+                }
+            })
+        };
+        html! {
+            <div class="card shadow-sm mt-2">
+                <div class="card-body py-2">
+                    <div class="d-flex align-items-baseline">
+                        <button type="button" class="btn btn-link p-0 fw-semibold" onclick={on_open}>
+                            {format!("{}.{}", hit.table, hit.column)}
+                        </button>
+                        <span class="text-muted small ms-2">
+                            {locale.tr_args("grid-rows", &[("rows", hit.row_count.into())])}
+                        </span>
+                    </div>
+                    {sample_table(hit)}
+                </div>
+            </div>
+        }
+    });
+
+    html! {
+        <div class="row px-3 py-3">
+            <form class="d-flex gap-2 px-0" style="max-width: 620px;" onsubmit={on_search}>
+                <input type="search" class="form-control" placeholder={locale.tr_args("search-placeholder", &[("schema", schema.clone().into())])}
+                 aria-label={locale.tr_args("search-label", &[("schema", schema.clone().into())])}
+                 value={(*query).clone()} oninput={on_query_input}/>
+                <button type="submit" class="btn btn-dark" disabled={progress.searching}>{locale.tr("search-submit")}</button>
+                <button type="button" class="btn btn-outline-dark" disabled={!progress.searching} onclick={on_cancel}>{locale.tr("cancel")}</button>
+            </form>
+            <div class="px-0" style="max-width: 620px;">
+                {progress_bar}
+                {failed}
+            </div>
+            <div class="px-0">{for hits}</div>
+        </div>
+    }
+}
+
+/// The sample rows of a hit, the matching column first.
+fn sample_table(hit: &SearchHit) -> Html {
+    let mut columns: Vec<&String> = match hit.sample_rows.first() {
+        Some(row) => row
+            .keys()
+            .filter(|column| !column.starts_with(HIDDEN_FIELD_PREFIX) && **column != hit.column)
+            .collect(),
+        None => return html! {},
+    };
+    columns.sort();
+    columns.insert(0, &hit.column);
+    html! {
+        <div class="overflow-auto">
+            <table class="table table-sm small mb-0">
+                <thead>
+                    <tr>{for columns.iter().map(|column| html! { <th class="text-nowrap">{(*column).clone()}</th> })}</tr>
+                </thead>
+                <tbody>
+                    {for hit.sample_rows.iter().map(|row| html! {
+                        <tr>
+                            {for columns.iter().map(|column| html! {
+                                <td class="text-nowrap">{row.get(*column).cloned().unwrap_or_default()}</td>
+                            })}
+                        </tr>
+                    })}
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+/// Searches every table of `schema`, publishing hits to `progress` as each table finishes.
+async fn search_schema(
+    config: StyewardConfig,
+    schema: String,
+    needle: String,
+    progress: UseStateHandle<SearchProgress>,
+) {
+    let mut state = SearchProgress {
+        searching: true,
+        ..SearchProgress::default()
+    };
+    progress.set(state.clone());
+
+    let tables = match get_tables(&config.scheme, &config.host, config.port, &schema).await {
+        Ok(tables) => tables,
+        Err(error) => {
+            error_1(&JsValue::from(error.message()));
+            state.searching = false;
+            state.failed_tables.push(schema);
+            progress.set(state);
+            return;
+        }
+    };
+    state.total_tables = tables.len();
+    progress.set(state.clone());
+
+    // Tables are searched in the worker. Aborting the search drops the loads and searches still
+    // running, which cancels them and frees their slots for the next search.
+    let mut searches = stream::iter(tables.into_iter().map(|table| {
+        let config = config.clone();
+        let schema = schema.clone();
+        let needle = needle.clone();
+        async move {
+            let hits = match table_cache::latest_or_load(&config, &schema, &table).await {
+                Some(data) => {
+                    worker_client::search(data.id, data.table, &table, &needle, MAX_SAMPLE_ROWS)
+                        .await
+                        .map_err(|error| error_1(&JsValue::from(error.message())))
+                        .ok()
+                }
+                None => None,
+            };
+            (table, hits)
+        }
+    }))
+    .buffer_unordered(SEARCH_CONCURRENCY);
+    while let Some((table, hits)) = searches.next().await {
+        state.searched_tables += 1;
+        match hits {
+            Some(hits) => state.hits.extend(hits),
+            None => state.failed_tables.push(table),
+        }
+        progress.set(state.clone());
+    }
+    state.searching = false;
+    progress.set(state);
+}
@@ -0,0 +1,359 @@
+use crate::components::locale::use_locale;
+use crate::components::table_visualization::{load_table, AGGridData, CallJavaScriptCode};
+use crate::i18n::Locale;
+use crate::snapshot_diff::{diff_snapshots, SnapshotDiff};
+use crate::table::Table;
+use crate::table_cache::{self, CachedLoad};
+use crate::StyewardConfig;
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{console::error_1, Event, HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+const DIFF_GRID_DIV_ID: &str = "diff-grid-div";
+const DEFAULT_KEY_COLUMN: &str = "subject";
+const CHANGE_FIELD: &str = "_change";
+/// Separates column names in `__changed_cells`, matching `changed_cell_class` in ag_grid.js.
+const CHANGED_CELLS_SEPARATOR: &str = "\u{1f}";
+const NO_COLUMN: &str = "";
+
+#[derive(Properties, PartialEq)]
+pub struct SnapshotCompareProp {
+    pub schema: String,
+    pub table: String,
+    pub fields: Vec<String>,
+    pub data: Rc<Table>,
+}
+
+#[function_component]
+pub fn SnapshotCompare(prop: &SnapshotCompareProp) -> Html {
+    let locale = use_locale();
+    // the most recent cached load is the data on screen, everything before it can be a baseline
+    let earlier_loads = {
+        let mut loads = table_cache::loads(&prop.schema, &prop.table);
+        loads.pop();
+        loads
+    };
+    let baseline = use_state(|| earlier_loads.last().cloned());
+    let primary_key = use_state(|| {
+        if prop.fields.iter().any(|field| field == DEFAULT_KEY_COLUMN) {
+            String::from(DEFAULT_KEY_COLUMN)
+        } else {
+            prop.fields.first().cloned().unwrap_or_default()
+        }
+    });
+    let sequence_column = use_state(|| String::from(NO_COLUMN));
+    let version = use_state(String::new);
+    let loading = use_state(|| false);
+    let load_error = use_state(|| None::<String>);
+
+    let on_baseline = {
+        let baseline = baseline.clone();
+        let earlier_loads = earlier_loads.clone();
+        Callback::from(move |event: Event| {
+            if let Some(value) = selected_value(event) {
+                let load = value
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| earlier_loads.get(index).cloned());
+                baseline.set(load);
+            }
+        })
+    };
+    let on_primary_key = {
+        let primary_key = primary_key.clone();
+        Callback::from(move |event: Event| {
+            if let Some(value) = selected_value(event) {
+                primary_key.set(value);
+            }
+        })
+    };
+    let on_sequence_column = {
+        let sequence_column = sequence_column.clone();
+        Callback::from(move |event: Event| {
+            if let Some(value) = selected_value(event) {
+                sequence_column.set(value);
+            }
+        })
+    };
+    let on_version_input = {
+        let version = version.clone();
+        Callback::from(move |event: InputEvent| {
+            if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                version.set(input.value());
+            }
+        })
+    };
+    let on_load_version = {
+        let config = use_context::<StyewardConfig>();
+        let schema = prop.schema.clone();
+        let table = prop.table.clone();
+        let version = version.clone();
+        let baseline = baseline.clone();
+        let loading = loading.clone();
+        let load_error = load_error.clone();
+        Callback::from(move |_: MouseEvent| {
+            let requested_version = version.trim().to_string();
+            if requested_version.is_empty() {
+                return;
+            }
+            let config = match config.clone() {
+                Some(config) => config,
+                None => {
+                    error_1(&JsValue::from("compare error: the config object is None."));
+                    return;
+                }
+            };
+            let schema = schema.clone();
+            let table = table.clone();
+            let baseline = baseline.clone();
+            let loading = loading.clone();
+            let load_error = load_error.clone();
+            loading.set(true);
+            spawn_local(async move {
+                let result = load_table(
+                    &config.scheme,
+                    &config.host,
+                    config.port,
+                    &schema,
+                    &table,
+                    Some(&requested_version),
+                )
+                .await;
+                match result {
+                    Ok(table_data) => {
+                        let load = CachedLoad {
+                            loaded_at: String::from(js_sys::Date::new_0().to_iso_string()),
+                            version: Some(requested_version),
+                            data: table_data,
+                        };
+                        load_error.set(None);
+                        baseline.set(Some(load));
+                    }
+                    Err(error) => {
+                        error_1(&JsValue::from(error.message()));
+                        load_error.set(Some(error.message().clone()));
+                    }
+                };
+                loading.set(false);
+            });
+        })
+    };
+
+    let mut key_columns = vec![(*primary_key).clone()];
+    if !sequence_column.is_empty() {
+        key_columns.push((*sequence_column).clone());
+    }
+    let diff = use_memo(
+        |(baseline, data, key_columns)| {
+            baseline
+                .as_ref()
+                .map(|load| diff_snapshots(&load.data.table, data, key_columns))
+        },
+        ((*baseline).clone(), prop.data.clone(), key_columns),
+    );
+
+    let column_options = |selected: &str, allow_none: bool| {
+        let none_option = if allow_none {
+            html! { <option value={NO_COLUMN} selected={selected.is_empty()}>{locale.tr("compare-no-column")}</option> }
+        } else {
+            html! {}
+        };
+        html! {
+            <>
+                {none_option}
+                {for prop.fields.iter().map(|field| html! {
+                    <option value={field.clone()} selected={field == selected}>{field.clone()}</option>
+                })}
+            </>
+        }
+    };
+    let baseline_options = earlier_loads.iter().enumerate().map(|(index, load)| {
+        let selected = baseline.as_ref() == Some(load);
+        html! { <option value={index.to_string()} {selected}>{load.label(locale)}</option> }
+    });
+    let fetched_option = match baseline.as_ref() {
+        Some(load) if !earlier_loads.contains(load) => {
+            html! { <option selected={true} disabled={true}>{load.label(locale)}</option> }
+        }
+        _ => html! {},
+    };
+
+    let body = match (&*diff, baseline.as_ref()) {
+        (Some(diff), Some(load)) => html! {
+            <>
+                {diff_summary(locale, diff)}
+                <div class="flex-grow-1" style="min-height: 0;">
+                    <CallJavaScriptCode
+                     ag_grid_data={diff_grid_data(locale, diff, &load.data.table, &prop.data)}
+                     grid_div_id={DIFF_GRID_DIV_ID}/>
+                </div>
+            </>
+        },
+        _ => html! {
+            <div class="text-muted p-3" style="font-size: 12px;">
+                {locale.tr("compare-no-baseline")}
+            </div>
+        },
+    };
+    let load_status = match (*loading, &*load_error) {
+        (true, _) => {
+            html! {
+                <span class="spinner-border spinner-border-sm ms-2" role="status">
+                    <span class="visually-hidden">{locale.tr("compare-loading")}</span>
+                </span>
+            }
+        }
+        (false, Some(message)) => {
+            html! { <span class="text-danger ms-2" role="alert">{message.clone()}</span> }
+        }
+        (false, None) => html! {},
+    };
+
+    html! {
+        <div class="d-flex flex-column h-100">
+            <div class="d-flex flex-wrap align-items-end gap-2 mt-2" style="font-size: 12px;">
+                <div>
+                    <label class="form-label mb-0" for="compare-baseline">{locale.tr("compare-baseline")}</label>
+                    <select id="compare-baseline" class="form-select form-select-sm" onchange={on_baseline}>
+                        <option value="" disabled={true} selected={baseline.is_none()}>{locale.tr("compare-select-load")}</option>
+                        {for baseline_options}
+                        {fetched_option}
+                    </select>
+                </div>
+                <div>
+                    <label class="form-label mb-0" for="compare-version">{locale.tr("compare-version")}</label>
+                    <div class="input-group input-group-sm">
+                        <input id="compare-version" type="text" class="form-control" value={(*version).clone()} oninput={on_version_input}/>
+                        <button type="button" class="btn btn-outline-dark" onclick={on_load_version} disabled={*loading}>{locale.tr("compare-load")}</button>
+                    </div>
+                </div>
+                <div>
+                    <label class="form-label mb-0" for="compare-primary-key">{locale.tr("compare-primary-key")}</label>
+                    <select id="compare-primary-key" class="form-select form-select-sm" onchange={on_primary_key}>
+                        {column_options(&primary_key, false)}
+                    </select>
+                </div>
+                <div>
+                    <label class="form-label mb-0" for="compare-sequence-column">{locale.tr("compare-sequence-column")}</label>
+                    <select id="compare-sequence-column" class="form-select form-select-sm" onchange={on_sequence_column}>
+                        {column_options(&sequence_column, true)}
+                    </select>
+                </div>
+                {load_status}
+            </div>
+            {body}
+        </div>
+    }
+}
+
+fn selected_value(event: Event) -> Option<String> {
+    match event.target() {
+        Some(target) => match target.dyn_into::<HtmlSelectElement>() {
+            Ok(select_elem) => Some(select_elem.value()),
+            Err(_) => {
+                error_1(&JsValue::from(
+                    "compare error: event target is not a selection element.",
+                ));
+                None
+            }
+        },
+        None => {
+            error_1(&JsValue::from("compare error: target not found."));
+            None
+        }
+    }
+}
+
+fn diff_summary(locale: Locale, diff: &SnapshotDiff) -> Html {
+    let duplicates = if diff.duplicate_keys.is_empty() {
+        html! {}
+    } else {
+        html! {
+            <span class="text-warning-emphasis ms-2">
+                {locale.tr_args("compare-duplicate-keys", &[("keys", diff.duplicate_keys.len().into())])}
+            </span>
+        }
+    };
+    html! {
+        <div class="mt-2" style="font-size: 12px;">
+            <span class="badge diff-added text-dark me-1">{locale.tr_args("compare-added", &[("rows", diff.added.len().into())])}</span>
+            <span class="badge diff-removed text-dark me-1">{locale.tr_args("compare-removed", &[("rows", diff.removed.len().into())])}</span>
+            <span class="badge diff-modified text-dark me-1">{locale.tr_args("compare-modified", &[("rows", diff.modified.len().into())])}</span>
+            <span class="badge text-bg-light me-1">{locale.tr_args("compare-unchanged", &[("rows", diff.unchanged.into())])}</span>
+            {duplicates}
+        </div>
+    }
+}
+
+fn diff_grid_data(
+    locale: Locale,
+    diff: &SnapshotDiff,
+    baseline: &Table,
+    current: &Table,
+) -> AGGridData {
+    let mut col_defs = Vec::<HashMap<String, String>>::new();
+    let mut change_col = HashMap::<String, String>::new();
+    change_col.insert(String::from("field"), String::from(CHANGE_FIELD));
+    change_col.insert(String::from("headerName"), locale.tr("compare-change"));
+    change_col.insert(String::from("pinned"), String::from("left"));
+    col_defs.push(change_col);
+    for column in diff.columns.iter() {
+        let mut field = HashMap::<String, String>::new();
+        field.insert(String::from("field"), column.clone());
+        col_defs.push(field);
+    }
+
+    // only the rows that changed are turned into grid rows
+    let tagged = |table: &Table, index: usize, change: &str, row_class: &str| {
+        let mut row = table.display_row(index);
+        row.insert(String::from(CHANGE_FIELD), String::from(change));
+        row.insert(String::from("__row_class"), String::from(row_class));
+        row
+    };
+    let mut row_data = Vec::<HashMap<String, String>>::new();
+    for modified in diff.modified.iter() {
+        let old_row = baseline.display_row(modified.baseline_index);
+        let mut row = tagged(
+            current,
+            modified.current_index,
+            &locale.tr("compare-change-modified"),
+            "diff-modified",
+        );
+        for column in modified.changed_columns.iter() {
+            let old_value = old_row.get(column).cloned().unwrap_or_default();
+            let new_value = row.get(column).cloned().unwrap_or_default();
+            row.insert(column.clone(), format!("{old_value} → {new_value}"));
+        }
+        row.insert(
+            String::from("__changed_cells"),
+            modified.changed_columns.join(CHANGED_CELLS_SEPARATOR),
+        );
+        row_data.push(row);
+    }
+    for index in diff.added.iter() {
+        row_data.push(tagged(
+            current,
+            *index,
+            &locale.tr("compare-change-added"),
+            "diff-added",
+        ));
+    }
+    for index in diff.removed.iter() {
+        row_data.push(tagged(
+            baseline,
+            *index,
+            &locale.tr("compare-change-removed"),
+            "diff-removed",
+        ));
+    }
+
+    AGGridData {
+        row_data: Rc::new(row_data),
+        col_defs,
+    }
+}
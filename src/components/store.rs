@@ -0,0 +1,161 @@
+use crate::i18n::Locale;
+use crate::state::{
+    LoggedAction, Middleware, Persistence, Store, StoreHandle, StyewardAction, StyewardState,
+    DEBUG_STORAGE_KEY,
+};
+use crate::MainRoute;
+
+use std::rc::Rc;
+
+use gloo_storage::{LocalStorage, Storage};
+use js_sys::Date;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct StoreProviderProp {
+    /// The action opening the table the route names. It is applied over the restored selection
+    /// when the page opens, and again when the route changes, e.g. on back navigation.
+    #[prop_or_default]
+    pub route: Option<StyewardAction>,
+    /// Keep the address bar on the selected table.
+    #[prop_or_default]
+    pub sync_url: bool,
+    pub children: Children,
+}
+
+/// Provides the store to `children` and runs the middleware after each action.
+#[function_component]
+pub fn StoreProvider(prop: &StoreProviderProp) -> Html {
+    let route = prop.route.clone();
+    let store = use_reducer(move || {
+        let state = StyewardState::restored();
+        Store::new(match &route {
+            Some(action) => state.reduce(action),
+            None => state,
+        })
+    });
+    let navigator = use_navigator();
+    let sync_url = prop.sync_url;
+    // The last action the middleware ran for, and the state it led to.
+    let handled = use_mut_ref(|| (store.sequence(), store.state().clone()));
+    {
+        let store = store.clone();
+        let sequence = store.sequence();
+        use_effect_with_deps(
+            move |_| {
+                let mut middleware: Vec<Box<dyn Middleware>> = vec![Box::new(Persistence)];
+                if let (true, Some(navigator)) = (sync_url, navigator) {
+                    middleware.push(Box::new(UrlSync(navigator)));
+                }
+                let mut handled = handled.borrow_mut();
+                let last = handled.0;
+                for logged in store.log().filter(|logged| logged.sequence > last) {
+                    for middleware in middleware.iter() {
+                        middleware.after(logged, &handled.1);
+                    }
+                    *handled = (logged.sequence, logged.state.clone());
+                }
+                || ()
+            },
+            sequence,
+        );
+    }
+    {
+        let store = store.clone();
+        use_effect_with_deps(
+            move |route| {
+                if let Some(action) = route {
+                    store.dispatch(action.clone());
+                }
+                || ()
+            },
+            prop.route.clone(),
+        );
+    }
+    let debug = LocalStorage::get::<bool>(DEBUG_STORAGE_KEY).unwrap_or(false);
+
+    html! {
+        <ContextProvider<StoreHandle> context={store.clone()}>
+            {for prop.children.iter()}
+            if debug {
+                <ActionLog store={store}/>
+            }
+        </ContextProvider<StoreHandle>>
+    }
+}
+
+/// Shows the selected table in the address bar, so it can be bookmarked and shared.
+struct UrlSync(Navigator);
+
+impl Middleware for UrlSync {
+    fn after(&self, logged: &LoggedAction, before: &StyewardState) {
+        // Routes open tables, they are in the address bar already.
+        if matches!(logged.action, StyewardAction::OpenTable { .. }) {
+            return;
+        }
+        match (logged.state.selection(), &logged.state.current_schema) {
+            (Some(selection), _) if Some(&selection) != before.selection().as_ref() => {
+                self.0.push(&MainRoute::DataTableView {
+                    schema: selection.schema,
+                    table: selection.table,
+                })
+            }
+            (None, None) if before.current_schema.is_some() => self.0.push(&MainRoute::DataTable),
+            // A schema without a table yet, the address bar keeps the last table.
+            _ => (),
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct ActionLogProp {
+    store: StoreHandle,
+}
+
+/// The debug panel listing the actions applied to the store, newest first. It is for developers
+/// and stays in English.
+#[function_component]
+fn ActionLog(prop: &ActionLogProp) -> Html {
+    let expanded = use_state(|| false);
+    let on_toggle = {
+        let expanded = expanded.clone();
+        Callback::from(move |_: MouseEvent| expanded.set(!*expanded))
+    };
+    let entries = if *expanded {
+        html! {
+            <ul class="list-group list-group-flush" style="max-height: 40vh; overflow-y: auto;">
+                {for prop.store.log().rev().map(log_entry)}
+            </ul>
+        }
+    } else {
+        html! {}
+    };
+    html! {
+        <div class="card shadow position-fixed bottom-0 end-0 m-2" style="z-index: 1060; width: 420px; font-size: 12px;">
+            <button type="button" class="btn btn-sm btn-light text-start" onclick={on_toggle}>
+                {format!("Action log ({})", prop.store.sequence())}
+            </button>
+            {entries}
+        </div>
+    }
+}
+
+fn log_entry(logged: &LoggedAction) -> Html {
+    let time = Date::new(&logged.at.into());
+    let state: &Rc<StyewardState> = &logged.state;
+    let selection = match (&state.current_schema, &state.current_table) {
+        (Some(schema), Some(table)) => format!("{schema}.{table}"),
+        (Some(schema), None) => format!("{schema}, no table"),
+        _ => String::from("nothing selected"),
+    };
+    html! {
+        <li class="list-group-item px-2 py-1">
+            <div class="d-flex justify-content-between gap-2">
+                <span class="fw-semibold">{format!("#{} {}", logged.sequence, logged.action.describe().text(Locale::EnUs))}</span>
+                <span class="text-muted">{String::from(time.to_locale_time_string("en-GB"))}</span>
+            </div>
+            <div class="text-muted" style="font-family: courier, monospace;" title={format!("{state:#?}")}>{selection}</div>
+        </li>
+    }
+}
@@ -1,88 +1,95 @@
-use crate::state::StyewardState;
+use crate::components::locale::use_locale;
+use crate::define_xml::{store_uploaded, use_define, Define};
+use crate::i18n::Locale;
+use crate::state::StoreHandle;
+use gloo_file::futures::read_as_text;
+use gloo_file::File;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{Event, HtmlInputElement};
 use yew::functional::use_context;
-use yew::prelude::{
-    function_component, html, use_effect_with_deps, use_state, Html, UseStateHandle,
-};
+use yew::prelude::{function_component, html, use_state, Callback, Html, TargetCast};
 use yew::virtual_dom::VNode;
-#[derive(PartialEq, Clone)]
-pub struct PreviousSelection {
-    pub previous_schema: Option<String>,
-    pub previous_table: Option<String>,
-}
 
 #[function_component()]
 pub fn TableDescription() -> Html {
-    let current_state = use_context::<UseStateHandle<StyewardState>>();
-    let previous_state = use_state(|| PreviousSelection {
-        previous_schema: None,
-        previous_table: None,
-    });
-    let _previous_state = previous_state.clone();
-    let current_schema = match current_state.clone() {
-        Some(handle) => handle.current_schema.clone(),
-        None => None,
+    let store = use_context::<StoreHandle>();
+    let shown = store.as_ref().and_then(|store| store.shown_selection());
+    let define_schema = store
+        .and_then(|store| store.current_schema.clone())
+        .or_else(|| shown.as_ref().map(|selection| selection.schema.clone()));
+    let locale = use_locale();
+    let define = use_define(define_schema.clone());
+    let upload_status = use_state(|| None::<String>);
+    let on_upload = {
+        let upload_status = upload_status.clone();
+        Callback::from(move |event: Event| {
+            let input = match event.target_dyn_into::<HtmlInputElement>() {
+                Some(input) => input,
+                None => return,
+            };
+            let (schema, file) = match (
+                define_schema.clone(),
+                input.files().and_then(|files| files.get(0)),
+            ) {
+                (Some(schema), Some(file)) => (schema, File::from(file)),
+                _ => return,
+            };
+            input.set_value("");
+            let upload_status = upload_status.clone();
+            spawn_local(async move {
+                match read_as_text(&file).await {
+                    Ok(xml) => match store_uploaded(&schema, &xml) {
+                        Ok(define) => upload_status.set(Some(locale.tr_args(
+                            "define-loaded",
+                            &[
+                                ("datasets", define.datasets.len().into()),
+                                ("schema", schema.into()),
+                            ],
+                        ))),
+                        Err(error) => upload_status.set(Some(error.message().clone())),
+                    },
+                    Err(error) => upload_status.set(Some(
+                        locale.tr_args("file-unreadable", &[("error", error.to_string().into())]),
+                    )),
+                }
+            });
+        })
     };
-    let current_table = match current_state.clone() {
-        Some(handle) => handle.current_table.clone(),
-        None => None,
+    let upload = html! {
+        <div class="mt-2">
+            <label class="btn btn-sm btn-outline-dark mb-0">
+                {locale.tr("define-load")}
+                <input type="file" accept=".xml,application/xml,text/xml" class="visually-hidden" onchange={on_upload}/>
+            </label>
+            if let Some(status) = &*upload_status {
+                <div class="text-muted mt-1">{status.clone()}</div>
+            }
+        </div>
     };
-    let _current_schema = current_schema.clone();
-    let _current_table = current_table.clone();
-    log::info!("1{:?}{:?}", _current_schema, _current_table);
-    use_effect_with_deps(
-        move |_| {
-            let previous_state = previous_state.clone();
-            if current_schema.clone().is_some() && current_table.clone().is_some() {
-                previous_state.set(PreviousSelection {
-                    previous_schema: current_schema.clone(),
-                    previous_table: current_table.clone(),
-                });
-            };
-            || ()
-        },
-        current_state.clone(),
-    );
-    if _current_schema.is_some() && _current_table.is_some() {
-        let text = format!(
-            "Description for table {}.{} is currently not available",
-            _current_schema.unwrap(),
-            _current_table.unwrap()
-        );
-        let v_node = style_the_text(text);
-        style_current_selection_card(v_node)
-    } else if _current_schema.is_some() && _current_table.is_none() {
-        if _previous_state.previous_schema.clone().is_some()
-            && _previous_state.previous_table.clone().is_some()
-        {
-            let text = format!(
-                "Description for table {}.{} is currently not available",
-                _previous_state.previous_schema.clone().unwrap(),
-                _previous_state.previous_table.clone().unwrap()
+    match shown {
+        Some(selection) => {
+            let v_node = describe_table(
+                locale,
+                define.as_deref(),
+                &selection.schema,
+                &selection.table,
             );
-            let v_node = style_the_text(text);
-            style_current_selection_card(v_node)
-        } else {
-            let text = "No table is selected".to_string();
-            let v_node = style_the_text(text);
-            style_current_selection_card(v_node)
+            style_current_selection_card(locale, html! { <>{v_node}{upload}</> })
         }
-    } else if _current_schema.is_none() && _current_table.is_some() {
-        html! {
-            <div>{"ERROR: You cant select a table without selecting a schema"}</div>
+        None => {
+            let text = locale.tr("no-table-selected");
+            let v_node = style_the_text(text);
+            style_current_selection_card(locale, v_node)
         }
-    } else {
-        let text = "No table is selected".to_string();
-        let v_node = style_the_text(text);
-        style_current_selection_card(v_node)
     }
 }
 
-fn style_current_selection_card(node: VNode) -> Html {
+fn style_current_selection_card(locale: Locale, node: VNode) -> Html {
     html! {
         <div class="h-auto d-inline-block">
             <div class="card" style="max_width; margin-top:10px; font-size:12px">
                 <div class="card-header" style="text-align: center;">
-                        {"Table Description"}
+                        {locale.tr("table-description")}
                 </div>
                 <div class="card-body">
                     {node}
@@ -92,6 +99,56 @@ fn style_current_selection_card(node: VNode) -> Html {
     }
 }
 
+/// The dataset's documentation from the define.xml, when it has one.
+fn describe_table(locale: Locale, define: Option<&Define>, schema: &str, table: &str) -> Html {
+    let dataset = match define.and_then(|define| define.dataset(table)) {
+        Some(dataset) => dataset,
+        None => {
+            return style_the_text(locale.tr_args(
+                "table-description-missing",
+                &[("table", format!("{schema}.{table}").into())],
+            ))
+        }
+    };
+    let keys: Vec<&str> = dataset
+        .keys()
+        .iter()
+        .map(|variable| variable.name.as_str())
+        .collect();
+    let rows = [
+        (
+            "define-dataset",
+            format!("{}: {}", dataset.name, dataset.label),
+        ),
+        ("define-class", dataset.class.clone().unwrap_or_default()),
+        (
+            "define-structure",
+            dataset.structure.clone().unwrap_or_default(),
+        ),
+        ("define-keys", keys.join(&locale.tr("list-separator"))),
+        (
+            "define-variables",
+            locale
+                .format_number(&dataset.variables.len().to_string())
+                .unwrap_or_default(),
+        ),
+        (
+            "define-comment",
+            dataset.comment.clone().unwrap_or_default(),
+        ),
+    ];
+    html! {
+        <dl class="row mb-0">
+            {for rows.into_iter().filter(|(_, value)| !value.is_empty()).map(|(term, value)| html! {
+                <>
+                    <dt class="col-4">{locale.tr(term)}</dt>
+                    <dd class="col-8 mb-1">{value}</dd>
+                </>
+            })}
+        </dl>
+    }
+}
+
 fn style_the_text(text: String) -> Html {
     html! {
         <div class="card-text" style="font-family: courier, monospace;  white-space: nowrap;">{text}</div>
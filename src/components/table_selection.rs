@@ -1,4 +1,9 @@
-use crate::state::StyewardState;
+use crate::a11y::Announcement;
+use crate::components::live_region::use_announcement;
+use crate::components::locale::use_locale;
+use crate::i18n::Locale;
+use crate::offline::{self, pinned_in_schema, use_online};
+use crate::state::{StoreHandle, StyewardAction};
 use crate::StyewardConfig;
 
 use gloo_net;
@@ -9,9 +14,7 @@ use wasm_bindgen::{JsCast, JsValue};
 use web_sys::HtmlSelectElement;
 use web_sys::{console::error_1, Event};
 use yew::functional::use_context;
-use yew::prelude::{
-    function_component, html, Callback, Children, Html, Properties, UseStateHandle,
-};
+use yew::prelude::{function_component, html, Callback, Children, Html, Properties};
 use yew::suspense::use_future_with_deps;
 use yew::virtual_dom::{VList, VNode, VTag, VText};
 
@@ -22,6 +25,17 @@ pub enum TableSelectionError {
     DeserializeError(String),
 }
 
+impl TableSelectionError {
+    pub fn message(&self) -> &String {
+        match self {
+            TableSelectionError::EndpointError(msg)
+            | TableSelectionError::GlooError(msg)
+            | TableSelectionError::HttpStatusError(msg)
+            | TableSelectionError::DeserializeError(msg) => msg,
+        }
+    }
+}
+
 fn style_the_select(node: VNode) -> Html {
     match node {
         VNode::VTag(v_tag) => {
@@ -33,9 +47,10 @@ fn style_the_select(node: VNode) -> Html {
     }
 }
 
-fn get_error_select(label: String) -> Html {
+fn get_error_select(locale: Locale, label_id: &str) -> Html {
+    let label = locale.tr(label_id);
     let output_node = html! {
-        <select disabled={true}>
+        <select disabled={true} aria-label={locale.tr("table-label")}>
             <option selected={true} value={label.clone()} disabled={true}>
                 {label.clone()}
             </option>
@@ -46,11 +61,12 @@ fn get_error_select(label: String) -> Html {
 
 #[function_component]
 pub fn TableSelection() -> Html {
+    let locale = use_locale();
     match use_context::<StyewardConfig>() {
         Some(config) => {
             html! {<RemoteSelection config={config}/>}
         }
-        None => get_error_select(format!("error!")),
+        None => get_error_select(locale, "select-error"),
     }
 }
 
@@ -66,13 +82,19 @@ struct ConfigProp {
 
 #[function_component]
 fn HandledSelection(prop: &PropSelectCallback) -> Html {
+    let locale = use_locale();
     let onchange = prop.cb.clone();
     let output_vnode = html! {
         <select {onchange} id={"table-select"}>
             {for prop.children.iter()}
         </select>
     };
-    style_the_select(output_vnode)
+    html! {
+        <>
+            <label for="table-select" class="visually-hidden">{locale.tr("table-label")}</label>
+            {style_the_select(output_vnode)}
+        </>
+    }
 }
 
 #[derive(Properties, PartialEq)]
@@ -81,16 +103,11 @@ struct PropSelectCallback {
     children: Children,
 }
 
-fn update_current_table(handle: UseStateHandle<StyewardState>, event: Event) {
+fn update_current_table(store: StoreHandle, event: Event) {
     match event.target() {
         Some(target) => match target.dyn_into::<HtmlSelectElement>() {
             Ok(select_elem) => {
-                let table = select_elem.value();
-                let old_state = (*handle).clone();
-                handle.set(StyewardState {
-                    current_table: Some(table),
-                    ..old_state
-                });
+                store.dispatch(StyewardAction::SelectTable(select_elem.value()));
             }
             Err(_) => {
                 error_1(&JsValue::from(
@@ -105,27 +122,47 @@ fn update_current_table(handle: UseStateHandle<StyewardState>, event: Event) {
 }
 
 #[function_component]
+#[allow(clippy::useless_format)]
 fn RemoteSelection(prop: &ConfigProp) -> Html {
+    let locale = use_locale();
     let config = prop.config.clone();
-    let state_handle = use_context::<UseStateHandle<StyewardState>>();
-    let current_schema = match state_handle {
-        Some(handle) => handle.current_schema.clone(),
-        None => None,
-    };
+    let current_schema =
+        use_context::<StoreHandle>().and_then(|store| store.current_schema.clone());
+    let online = use_online();
     let remote_schemas = use_future_with_deps(
-        |current_schema| async move {
-            match &*current_schema {
+        |deps| async move {
+            let (current_schema, online) = &*deps;
+            match current_schema {
                 Some(schema) => {
                     let schema_path = format!("{}", schema.clone());
-                    get_tables(&config.scheme, &config.host, config.port, &schema_path)
-                        .await
-                        .map(get_table_options)
+                    let tables = if *online {
+                        get_tables(&config.scheme, &config.host, config.port, &schema_path).await
+                    } else {
+                        Err(TableSelectionError::GlooError(String::from(
+                            "table select error: the browser is offline.",
+                        )))
+                    };
+                    match tables {
+                        Err(TableSelectionError::GlooError(msg)) => {
+                            pinned_tables_or(schema, msg).await
+                        }
+                        tables => tables,
+                    }
+                    .map(get_table_options)
                 }
                 None => Ok(VList::new()),
             }
         },
-        current_schema.clone(),
+        (current_schema.clone(), online),
     );
+    let announcement = match &remote_schemas {
+        Ok(result_ref) => (*result_ref)
+            .as_ref()
+            .err()
+            .map(|_| Announcement::alert(locale.tr("tables-not-loaded"))),
+        Err(_) => None,
+    };
+    use_announcement(announcement);
     match remote_schemas {
         Ok(result_ref) => match &*result_ref {
             Ok(v_list) => {
@@ -143,27 +180,29 @@ fn RemoteSelection(prop: &ConfigProp) -> Html {
                     | TableSelectionError::HttpStatusError(m)
                     | TableSelectionError::DeserializeError(m) => error_1(&m.into()),
                 };
-                get_error_select(format!("error!"))
+                get_error_select(locale, "select-error")
             }
         },
-        Err(_) => get_error_select(format!("loading...")),
+        Err(_) => get_error_select(locale, "select-loading"),
     }
 }
 
 #[function_component]
 fn FilledSelection(prop: &PropOptionsList) -> Html {
-    match use_context::<UseStateHandle<StyewardState>>() {
-        Some(handle) => {
+    let locale = use_locale();
+    match use_context::<StoreHandle>() {
+        Some(store) => {
+            let current_table = store.current_table.clone();
             let cb = Callback::from(move |event: Event| {
-                let handle = handle.clone();
-                update_current_table(handle, event);
+                let store = store.clone();
+                update_current_table(store, event);
             });
             html! {
                 <HandledSelection cb={cb}>
-                    <option selected={true} value={"NA"} disabled={true}>
-                        {"select table"}
+                    <option selected={current_table.is_none()} value={"NA"} disabled={true}>
+                        {locale.tr("select-table")}
                     </option>
-                    {for prop.children.iter()}
+                    {for prop.children.iter().map(|child| mark_selected_option(child, &current_table))}
                 </HandledSelection>
             }
         }
@@ -171,7 +210,7 @@ fn FilledSelection(prop: &PropOptionsList) -> Html {
             error_1(&JsValue::from_str(
                 "table select error: state context not provided",
             ));
-            get_error_select(format!("ERROR: Contact Support."))
+            get_error_select(locale, "select-contact-support")
         }
     }
 }
@@ -183,6 +222,31 @@ fn get_table_options(option_strings: Vec<String>) -> VList {
     v_list
 }
 
+fn mark_selected_option(node: VNode, selected: &Option<String>) -> VNode {
+    match (node, selected) {
+        (VNode::VList(v_list), Some(selected)) => {
+            let children = v_list
+                .iter()
+                .cloned()
+                .map(|child| match child {
+                    VNode::VTag(mut tag)
+                        if tag
+                            .attributes
+                            .iter()
+                            .any(|(key, value)| key == "value" && value == selected) =>
+                    {
+                        tag.add_attribute("selected", "selected");
+                        VNode::VTag(tag)
+                    }
+                    other => other,
+                })
+                .collect();
+            VNode::VList(VList::with_children(children, None))
+        }
+        (node, _) => node,
+    }
+}
+
 fn _get_table_option(option_text: &String) -> VNode {
     let text_node = VNode::VText(VText::new(String::from(option_text)));
     let mut option_node = VTag::new("option");
@@ -199,7 +263,21 @@ fn handle_gloo_error(gloo_error: gloo_net::Error) -> TableSelectionError {
     }
 }
 
-async fn get_tables(
+/// The tables of `schema` pinned to the device when the API cannot be reached, `msg` when none are.
+async fn pinned_tables_or(schema: &str, msg: String) -> Result<Vec<String>, TableSelectionError> {
+    let tables = offline::pinned_tables()
+        .await
+        .map(|pinned| pinned_in_schema(&pinned, schema))
+        .unwrap_or_default();
+    if tables.is_empty() {
+        Err(TableSelectionError::GlooError(msg))
+    } else {
+        Ok(tables)
+    }
+}
+
+/// Names of the tables in `schema`.
+pub async fn get_tables(
     scheme: &String,
     host: &String,
     port: u16,
@@ -231,6 +309,7 @@ async fn get_tables_with_gloo(url: Url) -> Result<Vec<String>, TableSelectionErr
     }
 }
 
+#[allow(clippy::useless_format)]
 async fn get_tables_from_response(response: Response) -> Result<Vec<String>, TableSelectionError> {
     match response.text().await {
         Ok(text) => match serde_json::from_str::<Vec<String>>(&text) {
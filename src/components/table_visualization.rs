@@ -1,23 +1,63 @@
-use crate::state::StyewardState;
+use crate::a11y::Announcement;
+use crate::ag_grid::{row_transaction, GridHandle, ROW_INDEX_FIELD};
+use crate::cdisc::{detect_domain, Domain, VariableIssue};
+use crate::codelists::{use_served_codelists, CodelistRegistry};
+use crate::commands::Command;
+use crate::components::checks_panel::ChecksPanel;
+use crate::components::codelists_panel::CodelistsPanel;
+use crate::components::command_palette::use_command;
+use crate::components::formatting_panel::FormattingPanel;
+use crate::components::live_region::use_announcement;
+use crate::components::locale::use_locale;
+#[cfg(feature = "native-grid")]
+use crate::components::native_grid::{native_grid_state, NativeGrid};
+use crate::components::offline_panel::{OfflinePanel, UnlockForm};
+use crate::components::pivot_builder::PivotBuilder;
+use crate::components::profile_panel::ProfilePanel;
+use crate::components::row_cards::RowCards;
+use crate::components::row_detail::RowDetail;
+use crate::components::snapshot_compare::SnapshotCompare;
+use crate::components::views_panel::ViewsPanel;
+use crate::csv::CsvOptions;
+use crate::data_location::{DataLocation, Lapse, TableLocation};
+use crate::define_xml::{use_define, DatasetDef, Define};
+use crate::formatting::{apply_rules, FormatRule};
+use crate::grid::filters_from_model;
+use crate::i18n::{self, Locale};
+use crate::layout::{prefers_reduced_motion, use_layout_class, GridOptions, LayoutClass};
+use crate::offline::{self, pinned_age, use_online};
+use crate::payload::PayloadFormat;
+use crate::row_stream::LoadProgress;
+use crate::state::{StoreHandle, StyewardAction};
+use crate::table::Table;
+use crate::table_cache;
+use crate::views::{find_view, ColumnSort, GridState, NamedView};
+use crate::worker::{JobId, WorkerError, WorkerRequest, WorkerResponse};
+use crate::worker_client::WorkerJob;
+use crate::workspace::Workspace;
 use crate::StyewardConfig;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 
 use gloo_net;
 use gloo_net::http::{Request, Response};
 use gloo_utils::document;
+use js_sys::{Reflect, Uint8Array};
 use serde::{Deserialize, Serialize};
 use serde_json::{self, Value};
 use url::Url;
-use wasm_bindgen::prelude::*;
-use wasm_bindgen::JsValue;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
 use web_sys::console::error_1;
+use web_sys::{HtmlElement, ReadableStreamDefaultReader};
 use yew::functional::use_context;
 use yew::prelude::*;
-use yew::prelude::{function_component, html, Html, UseStateHandle};
-use yew::suspense::use_future_with_deps;
+use yew::prelude::{function_component, html, Html};
 
 const AG_GRID_DIV_ID: &str = "grid-div";
+/// Row keys with this prefix are bookkeeping for the grid and never shown as columns.
+pub const HIDDEN_FIELD_PREFIX: &str = "__";
 
 pub enum TableVisualizationError {
     EndpointError(String),
@@ -27,6 +67,30 @@ pub enum TableVisualizationError {
     TablePropsError(String),
     TableDataFormatError(String),
     TableVisualizationError(String),
+    /// The table is pinned for offline use but the offline store is locked.
+    OfflineLockedError(String),
+    /// Object storage refused a presigned data url, which it does once the url has expired. The
+    /// message is the lapse's in English, for the console.
+    ExpiredLocationError(String, Lapse),
+    /// The worker decoding the table data stopped answering.
+    WorkerError(String),
+}
+
+impl TableVisualizationError {
+    pub fn message(&self) -> &String {
+        match self {
+            TableVisualizationError::EndpointError(msg)
+            | TableVisualizationError::GlooError(msg)
+            | TableVisualizationError::HttpStatusError(msg)
+            | TableVisualizationError::DeserializeError(msg)
+            | TableVisualizationError::TablePropsError(msg)
+            | TableVisualizationError::TableDataFormatError(msg)
+            | TableVisualizationError::TableVisualizationError(msg)
+            | TableVisualizationError::OfflineLockedError(msg)
+            | TableVisualizationError::ExpiredLocationError(msg, _)
+            | TableVisualizationError::WorkerError(msg) => msg,
+        }
+    }
 }
 
 fn get_error_div(label: String) -> Html {
@@ -39,15 +103,11 @@ fn get_error_div(label: String) -> Html {
 
 #[function_component]
 pub fn TableVisualization() -> Html {
-    let state_handle = use_context::<UseStateHandle<StyewardState>>();
-    let current_schema = match state_handle {
-        Some(ref handle) => handle.current_schema.clone(),
-        None => None,
-    };
-    let current_table = match state_handle {
-        Some(ref handle) => handle.current_table.clone(),
-        None => None,
-    };
+    let store = use_context::<StoreHandle>();
+    let current_schema = store
+        .as_ref()
+        .and_then(|store| store.current_schema.clone());
+    let current_table = store.and_then(|store| store.current_table.clone());
     html! {<ValidateLocalDeps schema={current_schema} table={current_table}/>}
 }
 
@@ -66,11 +126,8 @@ fn ValidateLocalDeps(prop: &ValidateLocalDepsProp) -> Html {
         log::info!("Trying to visualize the table");
         html! {<ValidateConfig schema={schema.unwrap()} table={table.unwrap()}/>}
     } else if schema.is_some() && table.is_none() {
-        log::info!("Trying to re-visualize the table");
-        match document().get_element_by_id(AG_GRID_DIV_ID) {
-            Some(element) => html! { <div class="p-0 m-0">{Html::VRef(element.into())}</div>},
-            None => html! {},
-        }
+        log::info!("Waiting for a table to be selected");
+        html! {}
     } else {
         html! {}
     }
@@ -84,13 +141,14 @@ struct ValidateConfigProp {
 
 #[function_component]
 fn ValidateConfig(prop: &ValidateConfigProp) -> Html {
+    let locale = use_locale();
     match use_context::<StyewardConfig>() {
         Some(config) => {
             html! {
                 <FetchRemoteDeps
                  scheme={config.scheme.clone()}
                  host={config.host.clone()}
-                 port={config.port.clone()}
+                 port={config.port}
                  schema={prop.schema.clone()}
                  table={prop.table.clone()} />
             }
@@ -98,7 +156,7 @@ fn ValidateConfig(prop: &ValidateConfigProp) -> Html {
         None => {
             let msg = String::from("The config object is None.");
             error_1(&msg.into());
-            get_error_div(format!("error!"))
+            get_error_div(locale.tr("select-error"))
         }
     }
 }
@@ -114,151 +172,1415 @@ struct FetchRemoteDepsProp {
 
 #[function_component]
 fn FetchRemoteDeps(prop: &FetchRemoteDepsProp) -> Html {
+    let locale = use_locale();
     let scheme = prop.scheme.clone();
     let host = prop.host.clone();
-    let port = prop.port.clone();
+    let port = prop.port;
     let schema = prop.schema.clone();
     let table = prop.table.clone();
 
-    let remote_table_data = use_future_with_deps(
-        |table| async move {
-            match get_table_url(
-                &scheme.clone(),
-                &host.clone(),
-                port.clone(),
-                &schema,
-                &table,
-            )
-            .await
-            {
-                Ok(data_url) => match get_table_data(&scheme, &host, port, &data_url).await {
-                    Ok(table_data) => Ok(table_data),
-                    Err(error) => Err(error),
-                },
-                Err(error) => Err(error),
-            }
-        },
-        table.clone(),
-    );
-    match remote_table_data {
-        Ok(result_ref) => match &*result_ref {
-            Ok(row_data) => html! {<VisualizeTable row_data={row_data.clone()} />},
-            Err(error) => match error {
-                TableVisualizationError::EndpointError(msg)
-                | TableVisualizationError::GlooError(msg)
-                | TableVisualizationError::HttpStatusError(msg)
-                | TableVisualizationError::DeserializeError(msg)
-                | TableVisualizationError::TableDataFormatError(msg)
-                | TableVisualizationError::TableVisualizationError(msg) => {
-                    error_1(&msg.into());
-                    get_error_div(format!("error!"))
-                }
-                TableVisualizationError::TablePropsError(msg) => {
-                    error_1(&msg.into());
-                    html! {}
-                }
+    let refresh_count = use_state(|| 0_u32);
+    let on_refresh = {
+        let refresh_count = refresh_count.clone();
+        Callback::from(move |_| refresh_count.set(*refresh_count + 1))
+    };
+
+    let online = use_online();
+
+    let load = use_state(|| TableLoad::Loading {
+        progress: LoadProgress::default(),
+        partial: None,
+    });
+    // Bumped by every load, a load that finds it changed was superseded and stops reporting.
+    let generation = use_mut_ref(|| 0_u32);
+    {
+        let load = load.clone();
+        use_effect_with_deps(
+            move |(table, _refresh_count, online)| {
+                *generation.borrow_mut() += 1;
+                let run = *generation.borrow();
+                load.set(TableLoad::Loading {
+                    progress: LoadProgress::default(),
+                    partial: None,
+                });
+                let (table, online) = (table.clone(), *online);
+                spawn_local(async move {
+                    let current = || *generation.borrow() == run;
+                    let loaded = if online {
+                        let progress_load = load.clone();
+                        let mut on_progress =
+                            |progress: LoadProgress, partial: Option<TableData>| {
+                                if current() {
+                                    progress_load.set(TableLoad::Loading { progress, partial });
+                                }
+                            };
+                        load_table_with_progress(
+                            &scheme,
+                            &host,
+                            port,
+                            &schema,
+                            &table,
+                            None,
+                            &mut on_progress,
+                        )
+                        .await
+                    } else {
+                        Err(TableVisualizationError::GlooError(String::from(
+                            "table error: the browser is offline.",
+                        )))
+                    };
+                    let loaded = match loaded {
+                        Ok(table_data) => {
+                            table_cache::record(&schema, &table, None, table_data.clone());
+                            Ok((table_data, None))
+                        }
+                        // The API is out of reach, fall back to the copy pinned to the device.
+                        Err(TableVisualizationError::GlooError(msg)) => {
+                            load_offline_copy(&schema, &table, msg).await
+                        }
+                        Err(error) => Err(error),
+                    };
+                    if current() {
+                        load.set(match loaded {
+                            Ok((data, pinned_at)) => TableLoad::Loaded { data, pinned_at },
+                            Err(error) => TableLoad::Failed(Rc::new(error)),
+                        });
+                    }
+                });
+                || ()
             },
+            (table, *refresh_count, online),
+        );
+    }
+    use_announcement(Some(load_announcement(
+        locale,
+        &prop.schema,
+        &prop.table,
+        &load,
+    )));
+    match &*load {
+        TableLoad::Loaded { data, pinned_at } => html! {
+            <VisualizeTable
+             schema={prop.schema.clone()}
+             table={prop.table.clone()}
+             data={data.table.clone()}
+             data_id={data.id}
+             format={data.format}
+             pinned_at={pinned_at.clone()}
+             on_refresh={on_refresh} />
         },
-        Err(_) => {
-            html! {
-                <div id="spinner" class="visible text-center position-absolute top-50 start-0" hidden=false>
-                    <div class="spinner-grow text-secondary" style="width: 10em; height: 10em;" role="status">
-                        <span class="visually-hidden">{"Loading..."}</span>
-                    </div>
+        // The first rows show while the rest downloads.
+        TableLoad::Loading {
+            progress,
+            partial: Some(data),
+        } => html! {
+            <VisualizeTable
+             schema={prop.schema.clone()}
+             table={prop.table.clone()}
+             data={data.table.clone()}
+             data_id={data.id}
+             format={data.format}
+             loading={Some(*progress)}
+             on_refresh={on_refresh} />
+        },
+        TableLoad::Loading {
+            progress,
+            partial: None,
+        } => html! {
+            <div class="w-100 pt-5 px-3" style="max-width: 480px;">
+                {progress_bar(locale, progress)}
+            </div>
+        },
+        TableLoad::Failed(error) => match &**error {
+            TableVisualizationError::OfflineLockedError(_) => html! {
+                <div class="card p-3 mt-3" style="max-width: 420px; font-size: 12px;">
+                    <p class="mb-2">{offline_locked_text(locale, &prop.schema, &prop.table)}</p>
+                    <UnlockForm on_unlocked={on_refresh.clone()}/>
                 </div>
+            },
+            TableVisualizationError::TablePropsError(msg) => {
+                error_1(&msg.into());
+                html! {}
+            }
+            TableVisualizationError::ExpiredLocationError(msg, lapse) => {
+                error_1(&msg.into());
+                get_error_div(lapse.message().text(locale))
+            }
+            error => {
+                error_1(&error.message().into());
+                get_error_div(locale.tr("select-error"))
             }
+        },
+    }
+}
+
+/// A table download: its progress and the rows read so far, then the table or why it failed.
+enum TableLoad {
+    Loading {
+        progress: LoadProgress,
+        partial: Option<TableData>,
+    },
+    Loaded {
+        data: TableData,
+        pinned_at: Option<String>,
+    },
+    Failed(Rc<TableVisualizationError>),
+}
+
+/// What screen readers announce of a table download.
+fn load_announcement(locale: Locale, schema: &str, table: &str, load: &TableLoad) -> Announcement {
+    let name = format!("{schema}.{table}");
+    match load {
+        TableLoad::Loading { .. } => {
+            Announcement::status(locale.tr_args("table-loading", &[("table", name.into())]))
         }
+        TableLoad::Loaded {
+            data,
+            pinned_at: None,
+        } => Announcement::status(locale.tr_args(
+            "table-loaded",
+            &[("table", name.into()), ("rows", data.table.len().into())],
+        )),
+        TableLoad::Loaded {
+            data,
+            pinned_at: Some(_),
+        } => Announcement::status(locale.tr_args(
+            "table-loaded-offline",
+            &[("table", name.into()), ("rows", data.table.len().into())],
+        )),
+        TableLoad::Failed(error) => match &**error {
+            TableVisualizationError::OfflineLockedError(_) => {
+                Announcement::alert(offline_locked_text(locale, schema, table))
+            }
+            _ => Announcement::alert(locale.tr_args("table-not-loaded", &[("table", name.into())])),
+        },
+    }
+}
+
+/// Why a table pinned to the device does not open while the offline store is locked.
+fn offline_locked_text(locale: Locale, schema: &str, table: &str) -> String {
+    locale.tr_args(
+        "table-offline-locked",
+        &[("table", format!("{schema}.{table}").into())],
+    )
+}
+
+fn progress_bar(locale: Locale, progress: &LoadProgress) -> Html {
+    let (width, bar_class) = match progress.percent() {
+        Some(percent) => (percent, "progress-bar bg-secondary"),
+        // Without a known size the bar only shows activity.
+        None => (
+            100.0,
+            "progress-bar bg-secondary progress-bar-striped progress-bar-animated",
+        ),
+    };
+    // An indeterminate progress bar has no current value.
+    let value = progress.percent().map(|percent| format!("{percent:.0}"));
+    let described = locale.tr_args(
+        "table-loading-progress",
+        &[("progress", progress.describe().text(locale).into())],
+    );
+    html! {
+        <div>
+            <div class="progress" role="progressbar" aria-label={locale.tr("table-download")}
+             aria-valuenow={value} aria-valuemin="0" aria-valuemax="100" style="height: 4px;">
+                <div class={bar_class} style={format!("width: {width:.1}%;")}></div>
+            </div>
+            <small class="text-muted">{described}</small>
+        </div>
+    }
+}
+
+/// The copy of the table pinned to the device and when it was pinned, for when the API cannot be
+/// reached. `network_error` is returned when the table is not pinned.
+async fn load_offline_copy(
+    schema: &str,
+    table: &str,
+    network_error: String,
+) -> Result<(TableData, Option<String>), TableVisualizationError> {
+    let pinned = offline::pinned_tables()
+        .await
+        .map(|pinned| {
+            pinned
+                .iter()
+                .any(|entry| entry.schema == schema && entry.table == table)
+        })
+        .unwrap_or(false);
+    if !pinned {
+        return Err(TableVisualizationError::GlooError(network_error));
+    }
+    if !offline::is_unlocked() {
+        return Err(TableVisualizationError::OfflineLockedError(format!(
+            "{schema}.{table} cannot be loaded from the API, unlock the offline store to open the copy pinned to this device."
+        )));
+    }
+    match offline::load_pinned(schema, table).await {
+        Ok(Some((table_data, pinned_at))) => Ok((table_data, Some(pinned_at))),
+        Ok(None) => Err(TableVisualizationError::GlooError(network_error)),
+        Err(error) => Err(TableVisualizationError::TableVisualizationError(
+            error.message().clone(),
+        )),
     }
 }
 
 #[derive(Properties, PartialEq)]
 struct VisualizeTableProp {
-    row_data: Vec<HashMap<String, String>>,
+    schema: String,
+    table: String,
+    data: Rc<Table>,
+    /// The id the worker keeps `data` under.
+    data_id: JobId,
+    /// The format the table was downloaded in, `None` when not known.
+    #[prop_or_default]
+    format: Option<PayloadFormat>,
+    /// When the rows come from the offline store, the time they were pinned.
+    #[prop_or_default]
+    pinned_at: Option<String>,
+    /// Set while the rest of the table is still downloading.
+    #[prop_or_default]
+    loading: Option<LoadProgress>,
+    on_refresh: Callback<()>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum VisualizationMode {
+    Grid,
+    Pivot,
+    Compare,
+}
+
+/// A loaded table, shared by the views of it and the table cache.
+#[derive(Clone, PartialEq)]
+pub struct TableData {
+    pub table: Rc<Table>,
+    /// The format the payload was decoded from.
+    pub format: Option<PayloadFormat>,
+    /// The id the worker keeps the table under, for the jobs run on it.
+    pub id: JobId,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct AGGridData {
-    pub row_data: Vec<HashMap<String, String>>,
+    pub row_data: Rc<Vec<HashMap<String, String>>>,
     pub col_defs: Vec<HashMap<String, String>>,
 }
 
 #[derive(Properties, PartialEq)]
-struct CallJavaScriptCodeProp {
-    ag_grid_data: AGGridData,
+pub struct CallJavaScriptCodeProp {
+    pub ag_grid_data: AGGridData,
+    #[prop_or(AttrValue::Static(AG_GRID_DIV_ID))]
+    pub grid_div_id: AttrValue,
+    /// Receives the `ROW_INDEX_FIELD` of the row the user clicked.
+    #[prop_or_default]
+    pub on_row_clicked: Option<Callback<usize>>,
+    /// Filters, sorts and column layout to restore once the grid is built.
+    #[prop_or_default]
+    pub grid_state: Option<GridState>,
+    /// Receives the `ROW_INDEX_FIELD` of the selected rows.
+    #[prop_or_default]
+    pub on_selection_changed: Option<Callback<Vec<usize>>>,
+    #[prop_or_default]
+    pub on_sort_changed: Option<Callback<Vec<ColumnSort>>>,
+    /// Receives the new filter model.
+    #[prop_or_default]
+    pub on_filter_changed: Option<Callback<Value>>,
+    /// Receives the grid's state whenever the user filters, sorts, hides or moves columns.
+    #[prop_or_default]
+    pub on_state_changed: Option<Callback<GridState>>,
 }
 
 #[function_component]
 fn VisualizeTable(prop: &VisualizeTableProp) -> Html {
+    let locale = use_locale();
+    // Number and date columns are written the locale's way, their type is found once per table.
+    let column_types = use_memo(
+        |table| {
+            table
+                .column_names()
+                .iter()
+                .enumerate()
+                .filter_map(|(index, name)| {
+                    Some((name.clone(), String::from(i18n::column_type(table, index)?)))
+                })
+                .collect::<HashMap<String, String>>()
+        },
+        prop.data.clone(),
+    );
     // Get the column names
     let mut col_defs = Vec::<HashMap<String, String>>::new();
-    match prop.row_data.first() {
-        Some(first_row) => {
-            for (k, _v) in first_row.clone() {
-                let mut field = HashMap::<String, String>::new();
-                field.insert(String::from("field"), k.clone());
-                if k.eq("subject") {
-                    field.insert(String::from("pinned"), String::from("left"));
+    for k in prop.data.column_names() {
+        if k.starts_with(HIDDEN_FIELD_PREFIX) {
+            continue;
+        }
+        let mut field = HashMap::<String, String>::new();
+        field.insert(String::from("field"), k.clone());
+        if k.eq("subject") {
+            field.insert(String::from("pinned"), String::from("left"));
+        }
+        if let Some(column_type) = column_types.get(&k) {
+            field.insert(String::from("type"), column_type.clone());
+        }
+        col_defs.push(field);
+    }
+    let fields: Vec<String> = col_defs
+        .iter()
+        .filter_map(|col_def| col_def.get("field").cloned())
+        .collect();
+    let define = use_define(Some(prop.schema.clone()));
+    let dataset = define
+        .as_ref()
+        .and_then(|define| Some((define.clone(), define.dataset(&prop.table)?.clone())));
+    if let Some((define, dataset)) = &dataset {
+        apply_define(locale, define, dataset, &mut col_defs);
+    }
+    let domain = detect_domain(&prop.table, &fields);
+    let variable_issues = match &domain {
+        Some(domain) => {
+            let issues = domain.variable_issues(&fields);
+            apply_cdisc(locale, domain, &issues, &mut col_defs);
+            issues
+        }
+        None => Vec::new(),
+    };
+
+    let format_rules = use_state(|| {
+        Workspace::load()
+            .table(&prop.schema, &prop.table)
+            .format_rules
+    });
+    {
+        let format_rules = format_rules.clone();
+        use_effect_with_deps(
+            move |(schema, table)| {
+                format_rules.set(Workspace::load().table(schema, table).format_rules);
+                || ()
+            },
+            (prop.schema.clone(), prop.table.clone()),
+        );
+    }
+    let on_format_rules = {
+        let format_rules = format_rules.clone();
+        let schema = prop.schema.clone();
+        let table = prop.table.clone();
+        Callback::from(move |rules: Vec<FormatRule>| {
+            let saved_rules = rules.clone();
+            Workspace::update_table(&schema, &table, move |table_workspace| {
+                table_workspace.format_rules = saved_rules;
+            });
+            format_rules.set(rules);
+        })
+    };
+
+    let imported_codelists = use_state(|| Workspace::load().codelists);
+    let show_raw_codes = use_state(|| {
+        Workspace::load()
+            .table(&prop.schema, &prop.table)
+            .show_raw_codes
+    });
+    {
+        let show_raw_codes = show_raw_codes.clone();
+        use_effect_with_deps(
+            move |(schema, table)| {
+                show_raw_codes.set(Workspace::load().table(schema, table).show_raw_codes);
+                || ()
+            },
+            (prop.schema.clone(), prop.table.clone()),
+        );
+    }
+    let on_import_codelists = {
+        let imported_codelists = imported_codelists.clone();
+        Callback::from(move |registry: CodelistRegistry| {
+            let mut workspace = Workspace::load();
+            workspace.codelists = registry.clone();
+            workspace.save();
+            imported_codelists.set(registry);
+        })
+    };
+    let on_show_raw_codes = {
+        let show_raw_codes = show_raw_codes.clone();
+        let schema = prop.schema.clone();
+        let table = prop.table.clone();
+        Callback::from(move |show: bool| {
+            Workspace::update_table(&schema, &table, move |table_workspace| {
+                table_workspace.show_raw_codes = show;
+            });
+            show_raw_codes.set(show);
+        })
+    };
+    let served_codelists = use_served_codelists(prop.schema.clone());
+    let codelists = imported_codelists
+        .with_fallback(&served_codelists)
+        .with_fallback(
+            &dataset
+                .as_ref()
+                .map(|(define, dataset)| define.codelist_registry(dataset))
+                .unwrap_or_default(),
+        );
+    apply_codelists(locale, &codelists, &prop.table, &mut col_defs);
+    let decoded_columns = codelists.columns(&prop.table, &fields).len();
+
+    // Rows failing the check picked in the checks panel, the grid shows only these.
+    let check_filter = use_state(|| None::<(String, Rc<HashSet<usize>>)>);
+    {
+        let check_filter = check_filter.clone();
+        use_effect_with_deps(
+            move |_| {
+                check_filter.set(None);
+                || ()
+            },
+            (prop.schema.clone(), prop.table.clone()),
+        );
+    }
+
+    // The rows handed to the grid, built from the table once and rebuilt only when the table or
+    // what decorates it changes.
+    let row_data = use_memo(
+        |(data, table, fields, codelists, show_raw_codes, format_rules, failing, locale)| {
+            let mut row_data: Vec<HashMap<String, String>> = (0..data.len())
+                .filter(|index| {
+                    failing
+                        .as_ref()
+                        .is_none_or(|failing| failing.contains(index))
+                })
+                .map(|index| {
+                    let mut row = data.display_row(index);
+                    row.insert(String::from(ROW_INDEX_FIELD), index.to_string());
+                    row
+                })
+                .collect();
+            if !show_raw_codes {
+                codelists.decode_rows(table, fields, &mut row_data);
+            }
+            apply_rules(format_rules, *locale, &mut row_data);
+            row_data
+        },
+        (
+            prop.data.clone(),
+            prop.table.clone(),
+            fields.clone(),
+            codelists.clone(),
+            *show_raw_codes,
+            (*format_rules).clone(),
+            check_filter.as_ref().map(|(_, failing)| failing.clone()),
+            locale,
+        ),
+    );
+
+    let store = use_context::<StoreHandle>();
+    let requested_view = store.as_ref().and_then(|store| store.current_view.clone());
+    let config = use_context::<StyewardConfig>();
+    let applied_view = use_state(|| None::<NamedView>);
+    let on_apply_view = {
+        let applied_view = applied_view.clone();
+        let store = store.clone();
+        Callback::from(move |view: NamedView| {
+            if let Some(pivot) = view.pivot.clone() {
+                Workspace::update_table(&view.schema, &view.table, move |table_workspace| {
+                    table_workspace.pivot = Some(pivot);
+                });
+            }
+            if let Some(store) = &store {
+                store.dispatch(StyewardAction::ApplyView {
+                    name: view.name.clone(),
+                    grid: view.grid.clone(),
+                });
+            }
+            applied_view.set(Some(view));
+        })
+    };
+    {
+        let schema = prop.schema.clone();
+        let table = prop.table.clone();
+        let on_apply_view = on_apply_view.clone();
+        use_effect_with_deps(
+            move |requested_view| {
+                if let (Some(id), Some(config)) = (requested_view.clone(), config) {
+                    spawn_local(async move {
+                        match find_view(&config, &schema, &table, &id).await {
+                            Ok(Some(view)) => on_apply_view.emit(view),
+                            Ok(None) => error_1(&JsValue::from(format!(
+                                "view error: no view {id} for table {schema}.{table}."
+                            ))),
+                            Err(error) => error_1(&JsValue::from(error.message())),
+                        }
+                    });
+                }
+                || ()
+            },
+            requested_view,
+        );
+    }
+    let show_views = use_state(|| false);
+    let on_toggle_views = {
+        let show_views = show_views.clone();
+        Callback::from(move |_: MouseEvent| show_views.set(!*show_views))
+    };
+    let views_panel = if *show_views {
+        html! {
+            <ViewsPanel
+             schema={prop.schema.clone()}
+             table={prop.table.clone()}
+             applied={(*applied_view).clone()}
+             on_apply={on_apply_view} />
+        }
+    } else {
+        html! {}
+    };
+
+    let cdisc_badge = match &domain {
+        Some(domain) => {
+            let mut title = vec![domain.describe().text(locale)];
+            title.extend(
+                variable_issues
+                    .iter()
+                    .map(|issue| issue.describe().text(locale)),
+            );
+            let label = match variable_issues.len() {
+                0 => domain.code.clone(),
+                count => format!("{} \u{26a0} {count}", domain.code),
+            };
+            html! {
+                <span class="badge bg-light text-dark border align-self-center ms-2" title={title.join("\n")}>{label}</span>
+            }
+        }
+        None => html! {},
+    };
+    let show_checks = use_state(|| false);
+    let on_toggle_checks = {
+        let show_checks = show_checks.clone();
+        Callback::from(move |_: MouseEvent| show_checks.set(!*show_checks))
+    };
+    let on_select_check = {
+        let check_filter = check_filter.clone();
+        let show_checks = show_checks.clone();
+        Callback::from(move |(description, rows): (String, Vec<usize>)| {
+            check_filter.set(Some((description, Rc::new(rows.into_iter().collect()))));
+            show_checks.set(false);
+        })
+    };
+    let check_banner = match &*check_filter {
+        Some((description, failing)) => {
+            let on_clear = {
+                let check_filter = check_filter.clone();
+                Callback::from(move |_: MouseEvent| check_filter.set(None))
+            };
+            html! {
+                <div class="alert alert-warning py-1 px-2 my-1 d-flex align-items-center" style="font-size: 12px;">
+                    {locale.tr_args("checks-showing-failing", &[("rows", failing.len().into()), ("check", description.clone().into())])}
+                    <button type="button" class="btn btn-link btn-sm py-0 ms-auto" onclick={on_clear}>{locale.tr("checks-show-all-rows")}</button>
+                </div>
+            }
+        }
+        None => html! {},
+    };
+
+    let show_offline = use_state(|| false);
+    let on_toggle_offline = {
+        let show_offline = show_offline.clone();
+        Callback::from(move |_: MouseEvent| show_offline.set(!*show_offline))
+    };
+    let offline_panel = if *show_offline {
+        html! {
+            <OfflinePanel
+             schema={prop.schema.clone()}
+             table={prop.table.clone()}
+             data={prop.data.clone()} />
+        }
+    } else {
+        html! {}
+    };
+    let stale_banner = match &prop.pinned_at {
+        Some(pinned_at) => html! {
+            <div class="alert alert-secondary py-1 px-2 my-1" role="status" style="font-size: 12px;" title={pinned_at.clone()}>
+                {locale.tr_args("table-offline-copy", &[
+                    ("table", format!("{}.{}", prop.schema, prop.table).into()),
+                    ("age", pinned_age(pinned_at, js_sys::Date::now()).text(locale).into()),
+                ])}
+            </div>
+        },
+        None => html! {},
+    };
+
+    let show_profile = use_state(|| false);
+    let on_toggle_profile = {
+        let show_profile = show_profile.clone();
+        Callback::from(move |_: MouseEvent| show_profile.set(!*show_profile))
+    };
+    let profile_panel = if *show_profile {
+        html! { <ProfilePanel data={prop.data.clone()} data_id={prop.data_id} /> }
+    } else {
+        html! {}
+    };
+
+    let show_formatting = use_state(|| false);
+    let on_toggle_formatting = {
+        let show_formatting = show_formatting.clone();
+        Callback::from(move |_: MouseEvent| show_formatting.set(!*show_formatting))
+    };
+
+    let selected_row = use_state(|| None::<usize>);
+    let on_row_clicked = {
+        let selected_row = selected_row.clone();
+        Callback::from(move |index: usize| selected_row.set(Some(index)))
+    };
+    let on_close_detail = {
+        let selected_row = selected_row.clone();
+        Callback::from(move |_| selected_row.set(None))
+    };
+    let row_detail = match (*selected_row).filter(|index| *index < prop.data.len()) {
+        Some(index) => html! {
+            <RowDetail
+                record={prop.data.record(index)}
+                on_close={on_close_detail}
+                documentation={dataset.as_ref().map(|(_, dataset)| dataset.clone())}/>
+        },
+        None => html! {},
+    };
+
+    let selected_rows = use_state(|| 0_usize);
+    let displayed_rows = use_state(|| None::<usize>);
+    let sorted_by = use_state(Vec::<ColumnSort>::new);
+    let grid_events = GridEvents {
+        on_row_clicked,
+        on_selection_changed: {
+            let selected_rows = selected_rows.clone();
+            Callback::from(move |indices: Vec<usize>| selected_rows.set(indices.len()))
+        },
+        on_sort_changed: {
+            let sorted_by = sorted_by.clone();
+            Callback::from(move |sort: Vec<ColumnSort>| sorted_by.set(sort))
+        },
+        on_state_changed: {
+            let store = store.clone();
+            let schema = prop.schema.clone();
+            let table = prop.table.clone();
+            Callback::from(move |grid: GridState| {
+                if let Some(store) = &store {
+                    store.dispatch(StyewardAction::ChangeGrid {
+                        schema: schema.clone(),
+                        table: table.clone(),
+                        grid,
+                    });
+                }
+            })
+        },
+        on_filter_changed: {
+            let displayed_rows = displayed_rows.clone();
+            Callback::from(move |filter_model: Value| {
+                let filtered = filter_model
+                    .as_object()
+                    .is_some_and(|filters| !filters.is_empty());
+                displayed_rows.set(match GridHandle::find(AG_GRID_DIV_ID) {
+                    Some(grid) if filtered => Some(grid.displayed_row_count()),
+                    _ => None,
+                });
+            })
+        },
+    };
+    let grid_status = grid_status(
+        locale,
+        prop.data.len(),
+        *displayed_rows,
+        *selected_rows,
+        &sorted_by,
+    );
+    let export_button = if cfg!(feature = "native-grid") {
+        html! {}
+    } else {
+        let file_name = format!("{}.{}.csv", prop.schema, prop.table);
+        let onclick = Callback::from(move |_: MouseEvent| {
+            if let Some(grid) = GridHandle::find(AG_GRID_DIV_ID) {
+                grid.export_csv(&file_name);
+            }
+        });
+        html! {
+            <button type="button" class="btn btn-sm btn-outline-secondary ms-2" {onclick}>{locale.tr("table-export-csv")}</button>
+        }
+    };
+
+    #[cfg(not(feature = "native-grid"))]
+    {
+        let file_name = format!("{}.{}.csv", prop.schema, prop.table);
+        use_command(
+            Command::new("table.export_csv", &locale.tr("table-export-csv"))
+                .with_keys("Ctrl+Shift+E"),
+            Callback::from(move |_| {
+                if let Some(grid) = GridHandle::find(AG_GRID_DIV_ID) {
+                    grid.export_csv(&file_name);
+                }
+            }),
+        );
+    }
+
+    let layout = use_layout_class();
+    let mode = use_state(|| VisualizationMode::Grid);
+    {
+        let mode = mode.clone();
+        use_command(
+            Command::new("table.toggle_pivot", &locale.tr("command-toggle-pivot")),
+            Callback::from(move |_| {
+                mode.set(match *mode {
+                    VisualizationMode::Pivot => VisualizationMode::Grid,
+                    _ => VisualizationMode::Pivot,
+                })
+            }),
+        );
+    }
+    {
+        let on_refresh = prop.on_refresh.clone();
+        use_command(
+            Command::new("table.refresh", &locale.tr("command-refresh-table")),
+            Callback::from(move |_| on_refresh.emit(())),
+        );
+    }
+    let mode_button = |target: VisualizationMode, label: String| {
+        let mode = mode.clone();
+        let pressed = *mode == target;
+        let class = if pressed {
+            "btn btn-sm btn-dark"
+        } else {
+            "btn btn-sm btn-outline-dark"
+        };
+        let onclick = Callback::from(move |_: MouseEvent| mode.set(target));
+        html! { <button type="button" {class} aria-pressed={pressed.to_string()} {onclick}>{label}</button> }
+    };
+    let checks_panel = if *show_checks {
+        html! {
+            <ChecksPanel
+             schema={prop.schema.clone()}
+             table={prop.table.clone()}
+             fields={fields.clone()}
+             data={prop.data.clone()}
+             on_select={on_select_check} />
+        }
+    } else {
+        html! {}
+    };
+    let show_codelists = use_state(|| false);
+    let on_toggle_codelists = {
+        let show_codelists = show_codelists.clone();
+        Callback::from(move |_: MouseEvent| show_codelists.set(!*show_codelists))
+    };
+    let codelists_panel = if *show_codelists {
+        html! {
+            <CodelistsPanel
+             table={prop.table.clone()}
+             fields={fields.clone()}
+             registry={codelists.clone()}
+             imported={imported_codelists.codelists.len()}
+             on_import={on_import_codelists}
+             show_raw_codes={*show_raw_codes}
+             on_show_raw_codes={on_show_raw_codes} />
+        }
+    } else {
+        html! {}
+    };
+    let formatting_panel = if *show_formatting {
+        html! {
+            <FormattingPanel
+             key={Workspace::table_key(&prop.schema, &prop.table)}
+             fields={fields.clone()}
+             rules={(*format_rules).clone()}
+             on_change={on_format_rules} />
+        }
+    } else {
+        html! {}
+    };
+
+    // The store keeps the grid as the user left it, undoing a change restores the state before.
+    let grid_state = match &store {
+        Some(store) => Some(store.grid.clone().unwrap_or_default()),
+        None => applied_view.as_ref().map(|view| view.grid.clone()),
+    };
+    let content = match *mode {
+        VisualizationMode::Grid if layout == LayoutClass::Phone => {
+            let query = grid_state
+                .as_ref()
+                .and_then(|state| filters_from_model(&state.filter_model).into_values().next());
+            html! {
+                <RowCards
+                 row_data={row_data}
+                 fields={fields.clone()}
+                 title_field={"subject"}
+                 column_types={(*column_types).clone()}
+                 query={query}
+                 on_row_clicked={grid_events.on_row_clicked} />
+            }
+        }
+        VisualizationMode::Grid => main_grid(row_data, col_defs, grid_events, grid_state),
+        VisualizationMode::Pivot => html! {
+            <PivotBuilder
+             key={Workspace::table_key(&prop.schema, &prop.table)}
+             schema={prop.schema.clone()}
+             table={prop.table.clone()}
+             fields={fields.clone()}
+             data={prop.data.clone()}
+             data_id={prop.data_id} />
+        },
+        VisualizationMode::Compare => html! {
+            <SnapshotCompare
+             key={Workspace::table_key(&prop.schema, &prop.table)}
+             schema={prop.schema.clone()}
+             table={prop.table.clone()}
+             fields={fields}
+             data={prop.data.clone()} />
+        },
+    };
+    let on_refresh = {
+        let on_refresh = prop.on_refresh.clone();
+        Callback::from(move |_: MouseEvent| on_refresh.emit(()))
+    };
+    // Keyboard and screen reader users land on the table once it has loaded.
+    let region = use_node_ref();
+    {
+        let region = region.clone();
+        use_effect_with_deps(
+            move |(_, _, loaded)| {
+                if let (true, Some(region)) = (*loaded, region.cast::<HtmlElement>()) {
+                    let _ = region.focus();
                 }
-                col_defs.push(field.clone());
+                || ()
+            },
+            (
+                prop.schema.clone(),
+                prop.table.clone(),
+                prop.loading.is_none(),
+            ),
+        );
+    }
+    // Types are inferred while the CSV is read, so changing the setting loads the table again.
+    let csv_types = if prop.format == Some(PayloadFormat::Csv) {
+        let csv_as_text = Workspace::load()
+            .table(&prop.schema, &prop.table)
+            .csv_as_text;
+        let on_csv_types = {
+            let on_refresh = prop.on_refresh.clone();
+            let schema = prop.schema.clone();
+            let table = prop.table.clone();
+            Callback::from(move |_: Event| {
+                Workspace::update_table(&schema, &table, move |table_workspace| {
+                    table_workspace.csv_as_text = !csv_as_text;
+                });
+                on_refresh.emit(());
+            })
+        };
+        html! {
+            <div class="form-check form-switch ms-2 mb-0 align-self-center" style="font-size: 12px;">
+                <input class="form-check-input" type="checkbox" id="csv-infer-types"
+                 checked={!csv_as_text} onchange={on_csv_types}/>
+                <label class="form-check-label" for="csv-infer-types">{locale.tr("table-infer-csv-types")}</label>
+            </div>
+        }
+    } else {
+        html! {}
+    };
+
+    html! {
+        <section ref={region} class="p-0 m-0 d-flex flex-column" style="height: 100%;" tabindex="-1"
+         aria-label={locale.tr_args("table-region", &[("table", format!("{}.{}", prop.schema, prop.table).into())])} aria-busy={prop.loading.is_some().to_string()}>
+            <div class="d-flex" style="margin-top:10px;">
+                <div class="btn-group" role="group" aria-label={locale.tr("table-view-mode")}>
+                    {mode_button(VisualizationMode::Grid, locale.tr("table-mode-grid"))}
+                    {mode_button(VisualizationMode::Pivot, locale.tr("table-mode-pivot"))}
+                    {mode_button(VisualizationMode::Compare, locale.tr("table-mode-compare"))}
+                </div>
+                <button type="button" class="btn btn-sm btn-outline-secondary ms-2" onclick={on_refresh}>{locale.tr("table-refresh")}</button>
+                <div class="position-relative ms-2">
+                    <button type="button" class="btn btn-sm btn-outline-secondary" aria-expanded={show_views.to_string()} onclick={on_toggle_views}>
+                        {match applied_view.as_ref() {
+                            Some(view) => locale.tr_args("table-view", &[("view", view.name.clone().into())]),
+                            None => locale.tr("table-views"),
+                        }}
+                    </button>
+                    {views_panel}
+                </div>
+                <div class="position-relative ms-2">
+                    <button type="button" class="btn btn-sm btn-outline-secondary" aria-expanded={show_checks.to_string()} onclick={on_toggle_checks}>{locale.tr("table-checks")}</button>
+                    {checks_panel}
+                </div>
+                <div class="position-relative ms-2">
+                    <button type="button" class="btn btn-sm btn-outline-secondary" aria-expanded={show_profile.to_string()} onclick={on_toggle_profile}>{locale.tr("table-stats")}</button>
+                    {profile_panel}
+                </div>
+                <div class="position-relative ms-2">
+                    <button type="button" class="btn btn-sm btn-outline-secondary" aria-expanded={show_formatting.to_string()} onclick={on_toggle_formatting}>
+                        {locale.tr_args("table-formatting", &[("rules", format_rules.len().into())])}
+                    </button>
+                    {formatting_panel}
+                </div>
+                <div class="position-relative ms-2">
+                    <button type="button" class="btn btn-sm btn-outline-secondary" aria-expanded={show_codelists.to_string()} onclick={on_toggle_codelists}>
+                        {locale.tr_args("table-codelists", &[("columns", decoded_columns.into())])}
+                    </button>
+                    {codelists_panel}
+                </div>
+                <div class="position-relative ms-2">
+                    <button type="button" class="btn btn-sm btn-outline-secondary" aria-expanded={show_offline.to_string()} onclick={on_toggle_offline}>
+                        {locale.tr(if prop.pinned_at.is_some() { "table-offline-pinned" } else { "table-offline" })}
+                    </button>
+                    {offline_panel}
+                </div>
+                {csv_types}
+                {export_button}
+                {cdisc_badge}
+                <small class="text-muted ms-auto align-self-center">{grid_status}</small>
+            </div>
+            {prop.loading.as_ref().map(|progress| progress_bar(locale, progress)).unwrap_or_default()}
+            {stale_banner}
+            {check_banner}
+            <div class="flex-grow-1 p-0 m-0" style="min-height: 0;">{content}</div>
+            {row_detail}
+        </section>
+    }
+}
+
+/// Header tooltips from the define.xml: label, origin, derivation and code list.
+fn apply_define(
+    locale: Locale,
+    define: &Define,
+    dataset: &DatasetDef,
+    col_defs: &mut [HashMap<String, String>],
+) {
+    for col_def in col_defs.iter_mut() {
+        if let Some(variable) = col_def
+            .get("field")
+            .and_then(|field| dataset.variable(field))
+        {
+            let lines: Vec<String> = variable
+                .describe(define)
+                .iter()
+                .map(|line| line.text(locale))
+                .collect();
+            col_def.insert(String::from("headerTooltip"), lines.join("\n"));
+        }
+    }
+}
+
+/// Names the code list of each decoded column in its header tooltip.
+fn apply_codelists(
+    locale: Locale,
+    registry: &CodelistRegistry,
+    table: &str,
+    col_defs: &mut [HashMap<String, String>],
+) {
+    for col_def in col_defs.iter_mut() {
+        let codelist = match col_def
+            .get("field")
+            .and_then(|field| registry.for_column(table, field))
+        {
+            Some(codelist) => codelist,
+            None => continue,
+        };
+        let line = locale.tr_args(
+            "column-codelist",
+            &[("codelist", codelist.name.clone().into())],
+        );
+        match col_def.get_mut("headerTooltip") {
+            Some(tooltip) if tooltip.contains(&line) => (),
+            Some(tooltip) => {
+                tooltip.push('\n');
+                tooltip.push_str(&line);
             }
+            None => {
+                col_def.insert(String::from("headerTooltip"), line);
+            }
+        }
+    }
+}
+
+/// Standard labels as header tooltips unless the define.xml documents the column, key variables
+/// pinned and non-conformant names flagged.
+fn apply_cdisc(
+    locale: Locale,
+    domain: &Domain,
+    issues: &[VariableIssue],
+    col_defs: &mut [HashMap<String, String>],
+) {
+    let columns: Vec<String> = col_defs
+        .iter()
+        .filter_map(|col_def| col_def.get("field").cloned())
+        .collect();
+    let keys = domain.key_variables(&columns);
+    for col_def in col_defs.iter_mut() {
+        let field = match col_def.get("field") {
+            Some(field) => field.clone(),
+            None => continue,
+        };
+        if keys.contains(&field) {
+            col_def.insert(String::from("pinned"), String::from("left"));
         }
-        None => (),
+        let mut tooltip: Vec<String> = match col_def.get("headerTooltip") {
+            Some(documented) => vec![documented.clone()],
+            None => domain.label(&field).map(String::from).into_iter().collect(),
+        };
+        if let Some(issue) = issues.iter().find(|issue| issue.variable == field) {
+            tooltip.push(locale.tr_args(
+                "column-nonconformant",
+                &[("issue", issue.message.text(locale).into())],
+            ));
+            col_def.insert(
+                String::from("headerClass"),
+                String::from("cdisc-nonconformant"),
+            );
+        }
+        if !tooltip.is_empty() {
+            col_def.insert(String::from("headerTooltip"), tooltip.join("\n"));
+        }
+    }
+}
+
+/// Row counts and sort order shown next to the toolbar, e.g. "12 of 340 rows, 2 selected".
+fn grid_status(
+    locale: Locale,
+    total_rows: usize,
+    displayed_rows: Option<usize>,
+    selected_rows: usize,
+    sorted_by: &[ColumnSort],
+) -> String {
+    let mut parts = vec![match displayed_rows {
+        Some(displayed) => locale.tr_args(
+            "grid-rows-shown",
+            &[("shown", displayed.into()), ("rows", total_rows.into())],
+        ),
+        None => locale.tr_args("grid-rows", &[("rows", total_rows.into())]),
+    }];
+    if selected_rows > 0 {
+        parts.push(locale.tr_args("grid-selected", &[("rows", selected_rows.into())]));
     }
+    if !sorted_by.is_empty() {
+        let columns: Vec<String> = sorted_by
+            .iter()
+            .map(|column_sort| {
+                let id = if column_sort.descending {
+                    "grid-sort-descending"
+                } else {
+                    "grid-sort-ascending"
+                };
+                locale.tr_args(id, &[("column", column_sort.column.clone().into())])
+            })
+            .collect();
+        parts.push(locale.tr_args(
+            "grid-sorted-by",
+            &[("columns", columns.join(&locale.tr("list-separator")).into())],
+        ));
+    }
+    parts.join(&locale.tr("list-separator"))
+}
+
+/// Callbacks for the events of the main grid.
+// The native grid only reports row clicks.
+#[cfg_attr(feature = "native-grid", allow(dead_code))]
+struct GridEvents {
+    on_row_clicked: Callback<usize>,
+    on_selection_changed: Callback<Vec<usize>>,
+    on_sort_changed: Callback<Vec<ColumnSort>>,
+    on_filter_changed: Callback<Value>,
+    on_state_changed: Callback<GridState>,
+}
+
+/// The main table grid, drawn by AG Grid.
+#[cfg(not(feature = "native-grid"))]
+fn main_grid(
+    row_data: Rc<Vec<HashMap<String, String>>>,
+    col_defs: Vec<HashMap<String, String>>,
+    events: GridEvents,
+    grid_state: Option<GridState>,
+) -> Html {
+    html! {
+        <CallJavaScriptCode
+         ag_grid_data={AGGridData { row_data, col_defs }}
+         on_row_clicked={events.on_row_clicked}
+         on_selection_changed={events.on_selection_changed}
+         on_sort_changed={events.on_sort_changed}
+         on_filter_changed={events.on_filter_changed}
+         on_state_changed={events.on_state_changed}
+         grid_state={grid_state}/>
+    }
+}
 
-    let ag_grid_data = AGGridData {
-        row_data: prop.row_data.clone(),
-        col_defs: col_defs.clone(),
+/// The main table grid, drawn by the Yew grid.
+#[cfg(feature = "native-grid")]
+fn main_grid(
+    row_data: Rc<Vec<HashMap<String, String>>>,
+    col_defs: Vec<HashMap<String, String>>,
+    events: GridEvents,
+    grid_state: Option<GridState>,
+) -> Html {
+    let fields: Vec<String> = col_defs
+        .iter()
+        .filter_map(|col_def| col_def.get("field").cloned())
+        .collect();
+    let pinned: Vec<String> = col_defs
+        .iter()
+        .filter(|col_def| col_def.get("pinned").map(String::as_str) == Some("left"))
+        .filter_map(|col_def| col_def.get("field").cloned())
+        .collect();
+    let col_def_values = |key: &str| -> HashMap<String, String> {
+        col_defs
+            .iter()
+            .filter_map(|col_def| Some((col_def.get("field")?.clone(), col_def.get(key)?.clone())))
+            .collect()
     };
+    let header_tooltips = col_def_values("headerTooltip");
+    let column_types = col_def_values("type");
+    html! {
+        <NativeGrid
+         row_data={row_data}
+         fields={fields}
+         pinned={pinned}
+         header_tooltips={header_tooltips}
+         column_types={column_types}
+         on_row_clicked={events.on_row_clicked}
+         on_state_changed={events.on_state_changed}
+         grid_state={grid_state}/>
+    }
+}
+
+/// Filters, sorts and column layout of the main grid, empty when no grid is shown.
+#[cfg(feature = "native-grid")]
+pub fn current_grid_state() -> GridState {
+    native_grid_state()
+}
 
-    html! { <CallJavaScriptCode ag_grid_data={ag_grid_data}/>}
+/// Filters, sorts and column layout of the main grid, empty when no grid is shown.
+#[cfg(not(feature = "native-grid"))]
+pub fn current_grid_state() -> GridState {
+    match GridHandle::find(AG_GRID_DIV_ID) {
+        Some(grid) => grid.grid_state(),
+        None => GridState::default(),
+    }
+}
+
+/// Callbacks of the grid, read when a grid event fires so the grid outlives prop changes.
+#[derive(Clone, Default)]
+struct GridListeners {
+    on_row_clicked: Option<Callback<usize>>,
+    on_selection_changed: Option<Callback<Vec<usize>>>,
+    on_sort_changed: Option<Callback<Vec<ColumnSort>>>,
+    on_filter_changed: Option<Callback<Value>>,
+    on_state_changed: Option<Callback<GridState>>,
 }
 
-#[wasm_bindgen(module = "/js/ag_grid.js")]
-extern "C" {
-    fn create_grid(ag_grid_data: JsValue, grid_div_id: String) -> bool;
+/// Numbers the rows that come without a `ROW_INDEX_FIELD`, AG Grid uses it as the row id. Rows
+/// already numbered are shared, not copied.
+fn with_row_index(ag_grid_data: &AGGridData) -> AGGridData {
+    if ag_grid_data
+        .row_data
+        .iter()
+        .all(|row| row.contains_key(ROW_INDEX_FIELD))
+    {
+        return ag_grid_data.clone();
+    }
+    let row_data = ag_grid_data
+        .row_data
+        .iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let mut row = row.clone();
+            row.entry(String::from(ROW_INDEX_FIELD))
+                .or_insert_with(|| index.to_string());
+            row
+        })
+        .collect();
+    AGGridData {
+        row_data: Rc::new(row_data),
+        col_defs: ag_grid_data.col_defs.clone(),
+    }
 }
 
 #[function_component]
-fn CallJavaScriptCode(prop: &CallJavaScriptCodeProp) -> Html {
+pub fn CallJavaScriptCode(prop: &CallJavaScriptCodeProp) -> Html {
+    let grid = use_mut_ref(|| None::<GridHandle>);
+    let shown_data = use_mut_ref(|| None::<Rc<AGGridData>>);
+    let listeners = use_mut_ref(GridListeners::default);
+    *listeners.borrow_mut() = GridListeners {
+        on_row_clicked: prop.on_row_clicked.clone(),
+        on_selection_changed: prop.on_selection_changed.clone(),
+        on_sort_changed: prop.on_sort_changed.clone(),
+        on_filter_changed: prop.on_filter_changed.clone(),
+        on_state_changed: prop.on_state_changed.clone(),
+    };
+
+    let layout = use_layout_class();
+    let locale = use_locale();
+    // Filters and sorts of a grid rebuilt for a new layout or locale.
+    let carried_state = use_mut_ref(|| None::<GridState>);
+    // Pivot and diff rows are numbered once per data, not on every render.
+    let ag_grid_data = use_memo(with_row_index, prop.ag_grid_data.clone());
+
+    // The grid lives as long as its div and layout, data and state changes go through the handle.
+    {
+        let grid = grid.clone();
+        let shown_data = shown_data.clone();
+        let ag_grid_data = ag_grid_data.clone();
+        use_effect_with_deps(
+            move |(grid_div_id, layout, locale)| {
+                if document().get_element_by_id(grid_div_id.as_str()).is_some() {
+                    let mut handle = GridHandle::create(
+                        &ag_grid_data,
+                        grid_div_id,
+                        &GridOptions::for_layout(*layout)
+                            .with_reduced_motion(prefers_reduced_motion())
+                            .with_locale(*locale),
+                    );
+                    if let Some(state) = carried_state.borrow_mut().take() {
+                        handle.apply_grid_state(&state);
+                    }
+                    let on_row_clicked = listeners.clone();
+                    handle.on_row_clicked(move |index| {
+                        if let Some(callback) = &on_row_clicked.borrow().on_row_clicked {
+                            callback.emit(index);
+                        }
+                    });
+                    let on_selection_changed = listeners.clone();
+                    handle.on_selection_changed(move |indices| {
+                        if let Some(callback) = &on_selection_changed.borrow().on_selection_changed
+                        {
+                            callback.emit(indices);
+                        }
+                    });
+                    let on_sort_changed = listeners.clone();
+                    handle.on_sort_changed(move |sort| {
+                        if let Some(callback) = &on_sort_changed.borrow().on_sort_changed {
+                            callback.emit(sort);
+                        }
+                    });
+                    let on_filter_changed = listeners.clone();
+                    handle.on_filter_changed(move |filter_model| {
+                        if let Some(callback) = &on_filter_changed.borrow().on_filter_changed {
+                            callback.emit(filter_model);
+                        }
+                    });
+                    let on_state_changed = listeners;
+                    handle.on_state_changed(move |state| {
+                        if let Some(callback) = &on_state_changed.borrow().on_state_changed {
+                            callback.emit(state);
+                        }
+                    });
+                    *shown_data.borrow_mut() = Some(ag_grid_data);
+                    *grid.borrow_mut() = Some(handle);
+                }
+                move || {
+                    shown_data.borrow_mut().take();
+                    if let Some(handle) = grid.borrow_mut().take() {
+                        *carried_state.borrow_mut() = Some(handle.grid_state());
+                        handle.destroy();
+                    }
+                }
+            },
+            (prop.grid_div_id.clone(), layout, locale),
+        );
+    }
+    {
+        let grid = grid.clone();
+        use_effect_with_deps(
+            move |ag_grid_data| {
+                if let Some(handle) = grid.borrow().as_ref() {
+                    let mut shown_data = shown_data.borrow_mut();
+                    match shown_data.as_ref() {
+                        Some(shown) if *shown == *ag_grid_data => {}
+                        Some(shown) if shown.col_defs == ag_grid_data.col_defs => handle
+                            .apply_transaction(&row_transaction(
+                                &shown.row_data,
+                                &ag_grid_data.row_data,
+                            )),
+                        _ => {
+                            handle.set_column_defs(&ag_grid_data.col_defs);
+                            handle.set_row_data(&ag_grid_data.row_data);
+                        }
+                    }
+                    *shown_data = Some(ag_grid_data.clone());
+                }
+                || ()
+            },
+            ag_grid_data,
+        );
+    }
     use_effect_with_deps(
-        |ag_grid_data| {
-            let ag_grid_data = ag_grid_data.clone();
-            let grid_div_id = String::from(AG_GRID_DIV_ID);
-            match document().get_element_by_id(grid_div_id.as_str()) {
-                Some(_div) => {
-                    let ag_grid_data_js_value = JsValue::from_serde(&ag_grid_data).unwrap();
-                    create_grid(ag_grid_data_js_value, grid_div_id);
+        move |grid_state| {
+            if let (Some(handle), Some(grid_state)) = (grid.borrow().as_ref(), grid_state) {
+                // The state may be the one the grid just reported.
+                if handle.grid_state() != *grid_state {
+                    handle.apply_grid_state(grid_state);
                 }
-                None => (),
-            };
-            || {}
+            }
+            || ()
         },
-        prop.ag_grid_data.clone(),
+        prop.grid_state.clone(),
     );
 
-    html! { <div id={AG_GRID_DIV_ID} class="ag-theme-alpine px-0" style="width: 100%; height: 100%; margin-top:10px;"></div>}
+    html! { <div id={prop.grid_div_id.clone()} class="ag-theme-alpine px-0" style="width: 100%; height: 100%; margin-top:10px;"></div>}
 }
 
-async fn get_table_data(
+/// Resolves the data url of `schema.table` and loads the table it points to.
+pub async fn load_table(
     scheme: &String,
     host: &String,
     port: u16,
-    data_url: &String,
-) -> Result<Vec<HashMap<String, String>>, TableVisualizationError> {
-    let endpoint = format!("{scheme}://{host}:{port}/{data_url}");
-    match Url::parse(&endpoint) {
-        Ok(url) => get_table_data_with_gloo(url).await,
-        Err(error) => Err(TableVisualizationError::EndpointError(format!("{error}"))),
+    schema: &String,
+    table: &String,
+    version: Option<&String>,
+) -> Result<TableData, TableVisualizationError> {
+    load_table_with_progress(scheme, host, port, schema, table, version, &mut |_, _| ()).await
+}
+
+/// Like `load_table`, reporting the download's progress and, now and then, the rows read so far.
+pub async fn load_table_with_progress(
+    scheme: &String,
+    host: &String,
+    port: u16,
+    schema: &String,
+    table: &String,
+    version: Option<&String>,
+    on_progress: &mut dyn FnMut(LoadProgress, Option<TableData>),
+) -> Result<TableData, TableVisualizationError> {
+    let csv = CsvOptions {
+        infer_types: !Workspace::load().table(schema, table).csv_as_text,
+        ..CsvOptions::default()
+    };
+    let location = get_table_location(scheme, host, port, schema, table, version).await?;
+    match get_table_data(&location, csv, on_progress).await {
+        // A presigned url lapsed before the download started, a new one is asked for once.
+        Err(TableVisualizationError::ExpiredLocationError(..)) => {
+            let location = get_table_location(scheme, host, port, schema, table, version).await?;
+            get_table_data(&location, csv, on_progress).await
+        }
+        result => result,
     }
 }
 
-async fn get_table_data_with_gloo(
-    url: Url,
-) -> Result<Vec<HashMap<String, String>>, TableVisualizationError> {
-    match Request::get(url.as_str()).send().await {
+/// Where `/uri` says the data of `schema.table` is, resolved against the API's root url.
+async fn get_table_location(
+    scheme: &String,
+    host: &String,
+    port: u16,
+    schema: &String,
+    table: &String,
+    version: Option<&String>,
+) -> Result<DataLocation, TableVisualizationError> {
+    let table_location = get_table_url(scheme, host, port, schema, table, version).await?;
+    let api = Url::parse(&format!("{scheme}://{host}:{port}/"))
+        .map_err(|error| TableVisualizationError::EndpointError(format!("{error}")))?;
+    DataLocation::resolve(&table_location, &api)
+        .map_err(|error| TableVisualizationError::EndpointError(error.message().clone()))
+}
+
+async fn get_table_data(
+    location: &DataLocation,
+    csv: CsvOptions,
+    on_progress: &mut dyn FnMut(LoadProgress, Option<TableData>),
+) -> Result<TableData, TableVisualizationError> {
+    // A url already past its expiry is asked for again without trying it.
+    if location.is_expired(js_sys::Date::now()) {
+        return Err(expired_location_error(Lapse::Expired));
+    }
+    match location.fetch().await {
         Ok(response) => {
             if response.ok() {
-                get_table_data_from_response(response).await
+                get_table_data_from_response(response, csv, on_progress).await
+            } else if let Some(lapse) = location.lapse(js_sys::Date::now(), Some(response.status()))
+            {
+                Err(expired_location_error(lapse))
             } else {
                 match response.text().await {
                     Ok(body_string) => Err(TableVisualizationError::HttpStatusError(body_string)),
@@ -269,95 +1591,168 @@ async fn get_table_data_with_gloo(
                 }
             }
         }
-        Err(error) => Err(handle_gloo_error(error)),
+        Err(error) => {
+            let error = handle_gloo_error(error);
+            match location.lapse(js_sys::Date::now(), None) {
+                Some(lapse) => {
+                    // The browser hides why, a missing CORS setup looks the same.
+                    error_1(&JsValue::from(error.message()));
+                    Err(expired_location_error(lapse))
+                }
+                None => Err(error),
+            }
+        }
     }
 }
 
+fn expired_location_error(lapse: Lapse) -> TableVisualizationError {
+    TableVisualizationError::ExpiredLocationError(lapse.message().text(Locale::EnUs), lapse)
+}
+
+/// Builds the rows as the body's chunks arrive, in the format the response's content type or
+/// url names, recognized from the first bytes when neither does. Gzipped bodies are decompressed
+/// as they arrive. The chunks are decoded in the worker, see src/worker.rs.
 async fn get_table_data_from_response(
     response: Response,
-) -> Result<Vec<HashMap<String, String>>, TableVisualizationError> {
-    match response.text().await {
-        Ok(text) => {
-            match serde_json::from_str::<serde_json::Value>(&text) {
-                Ok(data) => {
-                    // Deconvolute the table data.
-                    match build_table_data_struct(&data) {
-                        Ok(table_data) => Ok(table_data),
-                        Err(error) => Err(error),
-                    }
-                }
-                Err(_) => Err(TableVisualizationError::DeserializeError(format!(
-                    "Unable to deserialize server response to list of schemas"
-                ))),
-            }
+    csv: CsvOptions,
+    on_progress: &mut dyn FnMut(LoadProgress, Option<TableData>),
+) -> Result<TableData, TableVisualizationError> {
+    let headers = response.headers();
+    let mut load = DecodeJob::start(
+        headers
+            .get("content-length")
+            .and_then(|length| length.parse::<u64>().ok()),
+        PayloadFormat::negotiate(headers.get("content-type").as_deref(), &response.url()),
+        csv,
+    );
+    let body = match response.body() {
+        Some(body) => body,
+        None => {
+            let bytes = response.binary().await.map_err(handle_gloo_error)?;
+            load.read(bytes, on_progress)?;
+            return load.finish(on_progress).await;
         }
-        Err(error) => Err(handle_gloo_error(error)),
+    };
+    let reader = body
+        .get_reader()
+        .unchecked_into::<ReadableStreamDefaultReader>();
+    loop {
+        let result = JsFuture::from(reader.read()).await.map_err(|error| {
+            TableVisualizationError::GlooError(format!("Unable to read the table data: {error:?}"))
+        })?;
+        let done = Reflect::get(&result, &JsValue::from_str("done"))
+            .ok()
+            .and_then(|done| done.as_bool())
+            .unwrap_or(true);
+        if done {
+            break;
+        }
+        let chunk = Reflect::get(&result, &JsValue::from_str("value")).map_err(|error| {
+            TableVisualizationError::GlooError(format!("Unable to read the table data: {error:?}"))
+        })?;
+        load.read(Uint8Array::new(&chunk).to_vec(), on_progress)?;
     }
+    load.finish(on_progress).await
 }
 
-fn build_table_data_struct(
-    data: &Value,
-) -> Result<Vec<HashMap<String, String>>, TableVisualizationError> {
-    if !data.is_array() {
-        return Err(TableVisualizationError::TableDataFormatError(
-            "Data requested is not an array.".to_owned(),
-        ));
+/// The table being decoded by the worker, which keeps it under the load job's id afterwards.
+/// Dropping the load, e.g. when the download fails, cancels the job.
+struct DecodeJob {
+    job: WorkerJob,
+}
+
+impl DecodeJob {
+    fn start(
+        total_bytes: Option<u64>,
+        format: Option<PayloadFormat>,
+        csv: CsvOptions,
+    ) -> DecodeJob {
+        DecodeJob {
+            job: WorkerJob::start(|job| WorkerRequest::TableStart {
+                job,
+                format,
+                csv,
+                total_bytes,
+            }),
+        }
     }
 
-    // Map the data to a datasource to load in AG-grid
-    let mut datasource = Vec::<HashMap<String, String>>::new();
-    match data.as_array() {
-        Some(table_array) => {
-            for item in table_array {
-                let mut row = HashMap::<String, String>::new();
-                match item.as_object() {
-                    Some(row_data_map) => {
-                        for (k, v) in row_data_map {
-                            if v.is_string() {
-                                let string_value = v.as_str().unwrap().to_owned();
-                                row.insert(k.to_string(), string_value);
-                            } else if v.is_null() {
-                                let string_value = "N/A".to_owned();
-                                row.insert(k.to_string(), string_value);
-                            }
-                            // look into converting f64 ints to have only
-                            else if v.is_f64() {
-                                let string_value = format!("{:.5}", v.as_f64().unwrap());
-                                row.insert(k.to_string(), string_value);
-                            } else if v.is_u64() {
-                                let string_value = v.as_u64().unwrap().to_string();
-                                row.insert(k.to_string(), string_value);
-                            } else if v.is_boolean() {
-                                let string_value = v.as_bool().unwrap().to_string();
-                                row.insert(k.to_string(), string_value);
-                            } else if v.is_i64() {
-                                let string_value = v.as_i64().unwrap().to_string();
-                                row.insert(k.to_string(), string_value);
-                            } else if v.is_number() {
-                                let string_value = v.as_f64().unwrap().to_string();
-                                row.insert(k.to_string(), string_value);
-                            } else {
-                                // Don't support anything but null and string values, ideally no null values should exist after 'cleansing'
-                                return Err(TableVisualizationError::TableDataFormatError("A non-null or non-string value encountered in the returned data.".to_owned()));
-                            }
-                        }
-                    }
-                    None => {
-                        return Err(TableVisualizationError::TableDataFormatError(
-                            "non-row type data encountered in the data received.".to_owned(),
-                        ))
-                    }
+    /// Hands a chunk to the worker and reports the progress it has answered so far.
+    fn read(
+        &mut self,
+        bytes: Vec<u8>,
+        on_progress: &mut dyn FnMut(LoadProgress, Option<TableData>),
+    ) -> Result<(), TableVisualizationError> {
+        let job = self.job.id();
+        self.job.send(WorkerRequest::TableChunk { job, bytes });
+        while let Some(response) = self.job.try_next() {
+            self.report(response, on_progress)?;
+        }
+        Ok(())
+    }
+
+    async fn finish(
+        mut self,
+        on_progress: &mut dyn FnMut(LoadProgress, Option<TableData>),
+    ) -> Result<TableData, TableVisualizationError> {
+        let id = self.job.id();
+        self.job.send(WorkerRequest::TableEnd { job: id });
+        while let Some(response) = self.job.next().await {
+            match response {
+                WorkerResponse::Table { table, format, .. } => {
+                    return Ok(TableData { table, format, id })
                 }
-                datasource.push(row.clone());
+                response => self.report(response, on_progress)?,
             }
         }
-        None => {
-            return Err(TableVisualizationError::TableDataFormatError(
-                "No series of rows found in data received.".to_owned(),
-            ))
+        Err(TableVisualizationError::WorkerError(String::from(
+            "The worker stopped before the table data was read.",
+        )))
+    }
+
+    fn report(
+        &self,
+        response: WorkerResponse,
+        on_progress: &mut dyn FnMut(LoadProgress, Option<TableData>),
+    ) -> Result<(), TableVisualizationError> {
+        match response {
+            WorkerResponse::Progress {
+                progress,
+                partial,
+                format,
+                ..
+            } => {
+                let partial = partial.map(|table| TableData {
+                    table: Rc::new(table),
+                    format,
+                    id: self.job.id(),
+                });
+                on_progress(progress, partial);
+                Ok(())
+            }
+            WorkerResponse::Failed { error, .. } => Err(worker_error(error)),
+            _ => Ok(()),
         }
     }
-    Ok(datasource)
+}
+
+pub(crate) fn worker_error(error: WorkerError) -> TableVisualizationError {
+    match error {
+        WorkerError::FormatError(msg) => TableVisualizationError::TableDataFormatError(msg),
+        WorkerError::DeserializeError(msg) => TableVisualizationError::DeserializeError(msg),
+        WorkerError::TableNotLoadedError(msg) | WorkerError::WorkerUnavailableError(msg) => {
+            TableVisualizationError::WorkerError(msg)
+        }
+    }
+}
+
+/// Builds a table from a whole payload already in memory, e.g. a copy from the offline store.
+pub(crate) async fn table_data_from_bytes(
+    bytes: Vec<u8>,
+) -> Result<TableData, TableVisualizationError> {
+    let mut load = DecodeJob::start(None, Some(PayloadFormat::Json), CsvOptions::default());
+    load.read(bytes, &mut |_, _| ())?;
+    load.finish(&mut |_, _| ()).await
 }
 
 async fn get_table_url(
@@ -366,15 +1761,21 @@ async fn get_table_url(
     port: u16,
     schema: &String,
     table: &String,
-) -> Result<String, TableVisualizationError> {
+    version: Option<&String>,
+) -> Result<TableLocation, TableVisualizationError> {
     let endpoint = format!("{scheme}://{host}:{port}/api/v1/{schema}/{table}/uri");
     match Url::parse(&endpoint) {
-        Ok(url) => get_table_url_with_gloo(url).await,
+        Ok(mut url) => {
+            if let Some(version) = version {
+                url.query_pairs_mut().append_pair("version", version);
+            }
+            get_table_url_with_gloo(url).await
+        }
         Err(error) => Err(TableVisualizationError::EndpointError(format!("{error}"))),
     }
 }
 
-async fn get_table_url_with_gloo(url: Url) -> Result<String, TableVisualizationError> {
+async fn get_table_url_with_gloo(url: Url) -> Result<TableLocation, TableVisualizationError> {
     match Request::get(url.as_str()).send().await {
         Ok(response) => {
             if response.ok() {
@@ -403,20 +1804,15 @@ fn handle_gloo_error(gloo_error: gloo_net::Error) -> TableVisualizationError {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct TableLocation {
-    pub url: String,
-}
-
 async fn get_table_url_from_response(
     response: Response,
-) -> Result<String, TableVisualizationError> {
+) -> Result<TableLocation, TableVisualizationError> {
     match response.text().await {
         Ok(text) => match serde_json::from_str::<TableLocation>(&text) {
-            Ok(table_location) => Ok(table_location.url),
-            Err(_) => Err(TableVisualizationError::DeserializeError(format!(
-                "Unable to deserialize server response to list of schemas"
-            ))),
+            Ok(table_location) => Ok(table_location),
+            Err(_) => Err(TableVisualizationError::DeserializeError(
+                "Unable to deserialize server response to list of schemas".to_string(),
+            )),
         },
         Err(error) => Err(handle_gloo_error(error)),
     }
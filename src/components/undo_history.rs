@@ -0,0 +1,163 @@
+use crate::commands::Command;
+use crate::components::command_palette::{use_command, CommandRegistry};
+use crate::components::locale::use_locale;
+use crate::i18n::Message;
+use crate::state::{StoreHandle, StyewardAction};
+
+use web_sys::HtmlElement;
+use yew::prelude::*;
+
+/// Undo and redo buttons with the history of the steps, also run as commands bound to Ctrl+Z and
+/// Ctrl+Shift+Z.
+#[function_component]
+pub fn UndoHistory() -> Html {
+    let locale = use_locale();
+    let store = use_context::<StoreHandle>();
+    let registry = use_context::<CommandRegistry>();
+    let expanded = use_state(|| false);
+    let toggle = use_node_ref();
+    let dispatch_history = |action: StyewardAction| {
+        let store = store.clone();
+        Callback::from(move |_| {
+            if let Some(store) = &store {
+                store.dispatch(action.clone());
+            }
+        })
+    };
+    use_command(
+        Command::new("history.undo", &locale.tr("history-undo")).with_keys("Ctrl+Z"),
+        dispatch_history(StyewardAction::Undo(1)),
+    );
+    use_command(
+        Command::new("history.redo", &locale.tr("history-redo")).with_keys("Ctrl+Shift+Z"),
+        dispatch_history(StyewardAction::Redo(1)),
+    );
+    let store = match store {
+        Some(store) => store,
+        None => return html! {},
+    };
+
+    let history = store.history();
+    let title = |verb: &'static str, description: &Message, id: &str| {
+        let title = Message::new(verb)
+            .with("step", description.text(locale))
+            .text(locale);
+        match registry.as_ref().and_then(|registry| registry.keys(id)) {
+            Some(keys) => locale.tr_args(
+                "history-with-keys",
+                &[("title", title.into()), ("keys", keys.into())],
+            ),
+            None => title,
+        }
+    };
+    let undo_title = history
+        .undoable()
+        .next()
+        .map(|step| title("history-undo-step", &step.description, "history.undo"));
+    let redo_title = history
+        .redoable()
+        .next()
+        .map(|step| title("history-redo-step", &step.description, "history.redo"));
+    let dispatch = |action: StyewardAction| {
+        let store = store.clone();
+        let expanded = expanded.clone();
+        Callback::from(move |_: MouseEvent| {
+            store.dispatch(action.clone());
+            expanded.set(false);
+        })
+    };
+    let on_toggle = {
+        let expanded = expanded.clone();
+        Callback::from(move |_: MouseEvent| expanded.set(!*expanded))
+    };
+    // Escape closes the history and returns to its button.
+    let on_keydown = {
+        let expanded = expanded.clone();
+        let toggle = toggle.clone();
+        Callback::from(move |event: KeyboardEvent| {
+            if *expanded && event.key() == "Escape" {
+                event.prevent_default();
+                expanded.set(false);
+                if let Some(toggle) = toggle.cast::<HtmlElement>() {
+                    let _ = toggle.focus();
+                }
+            }
+        })
+    };
+
+    // Undone steps on top, furthest first, then the current state and the steps before it.
+    let steps = if *expanded {
+        let redoable: Vec<String> = history
+            .redoable()
+            .map(|step| step.description.text(locale))
+            .collect();
+        let undoable: Vec<String> = history
+            .undoable()
+            .map(|step| step.description.text(locale))
+            .collect();
+        let redo_items = redoable.iter().enumerate().rev().map(|(index, description)| {
+            html! {
+                <li>
+                    <button type="button" class="dropdown-item text-muted" onclick={dispatch(StyewardAction::Redo(index + 1))}>
+                        {description.clone()}
+                    </button>
+                </li>
+            }
+        });
+        let undo_items = undoable.iter().enumerate().map(|(index, description)| {
+            let class = if index == 0 {
+                "dropdown-item active"
+            } else {
+                "dropdown-item"
+            };
+            html! {
+                <li>
+                    <button type="button" {class} aria-current={(index == 0).then_some("step")} onclick={dispatch(StyewardAction::Undo(index))}>
+                        {description.clone()}
+                    </button>
+                </li>
+            }
+        });
+        let start_class = if undoable.is_empty() {
+            "dropdown-item fst-italic active"
+        } else {
+            "dropdown-item fst-italic"
+        };
+        html! {
+            <ul id="undo-history-steps" class="dropdown-menu show shadow" aria-label={locale.tr("history-steps")} style="top: 100%; right: 0; max-height: 40vh; overflow-y: auto; font-size: 12px;">
+                {for redo_items}
+                {for undo_items}
+                <li>
+                    <button type="button" class={start_class} onclick={dispatch(StyewardAction::Undo(undoable.len()))}>
+                        {locale.tr("history-start")}
+                    </button>
+                </li>
+            </ul>
+        }
+    } else {
+        html! {}
+    };
+
+    html! {
+        <div class="btn-group position-relative" role="group" aria-label={locale.tr("history-label")} style="margin-top:10px;"
+         onkeydown={on_keydown}>
+            <button type="button" class="btn btn-sm btn-outline-secondary"
+             disabled={undo_title.is_none()}
+             title={undo_title.clone().unwrap_or_default()}
+             onclick={dispatch(StyewardAction::Undo(1))}>
+                {format!("\u{21b6} {}", locale.tr("history-undo"))}
+            </button>
+            <button type="button" class="btn btn-sm btn-outline-secondary"
+             disabled={redo_title.is_none()}
+             title={redo_title.clone().unwrap_or_default()}
+             onclick={dispatch(StyewardAction::Redo(1))}>
+                {format!("{} \u{21b7}", locale.tr("history-redo"))}
+            </button>
+            <button ref={toggle} type="button" class="btn btn-sm btn-outline-secondary dropdown-toggle"
+             aria-expanded={expanded.to_string()} aria-controls="undo-history-steps" onclick={on_toggle}>
+                {locale.tr("history-steps")}
+            </button>
+            {steps}
+        </div>
+    }
+}
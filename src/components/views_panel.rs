@@ -0,0 +1,364 @@
+use crate::components::locale::use_locale;
+use crate::components::row_detail::copy_text;
+use crate::components::table_visualization::current_grid_state;
+use crate::i18n::Locale;
+use crate::views::{
+    delete_local_view, export_views, import_views, local_views, save_local_view, NamedView,
+    SharedViewStore,
+};
+use crate::workspace::Workspace;
+use crate::StyewardConfig;
+
+use gloo_file::futures::read_as_text;
+use gloo_file::File;
+use gloo_utils::window;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{console::error_1, Event, HtmlInputElement};
+use yew::prelude::*;
+
+#[wasm_bindgen(module = "/js/files.js")]
+extern "C" {
+    pub(crate) fn download_text(file_name: String, mime_type: String, text: String);
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ViewsPanelProp {
+    pub schema: String,
+    pub table: String,
+    pub applied: Option<NamedView>,
+    pub on_apply: Callback<NamedView>,
+}
+
+#[function_component]
+pub fn ViewsPanel(prop: &ViewsPanelProp) -> Html {
+    let locale = use_locale();
+    let schema = prop.schema.clone();
+    let table = prop.table.clone();
+    let local = use_state(|| local_views(&schema, &table));
+    let shared = use_state(Vec::<NamedView>::new);
+    let new_name = use_state(String::new);
+    let renaming = use_state(|| None::<(String, String)>);
+    let status = use_state(|| None::<String>);
+    let store = use_context::<StyewardConfig>().map(|config| SharedViewStore::from_config(&config));
+
+    let reload_local = {
+        let local = local.clone();
+        let schema = prop.schema.clone();
+        let table = prop.table.clone();
+        move || local.set(local_views(&schema, &table))
+    };
+    let reload_shared = {
+        let shared = shared.clone();
+        let status = status.clone();
+        let store = store.clone();
+        let schema = prop.schema.clone();
+        let table = prop.table.clone();
+        move || {
+            if let Some(store) = store.clone() {
+                let shared = shared.clone();
+                let status = status.clone();
+                let schema = schema.clone();
+                let table = table.clone();
+                spawn_local(async move {
+                    match store.list(&schema, &table).await {
+                        Ok(views) => shared.set(views),
+                        Err(error) => {
+                            error_1(&JsValue::from(error.message()));
+                            status.set(Some(error.message().clone()));
+                        }
+                    }
+                });
+            }
+        }
+    };
+    {
+        let reload_shared = reload_shared.clone();
+        use_effect_with_deps(
+            move |_| {
+                reload_shared();
+                || ()
+            },
+            (prop.schema.clone(), prop.table.clone()),
+        );
+    }
+
+    let on_name_input = {
+        let new_name = new_name.clone();
+        Callback::from(move |event: InputEvent| {
+            if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                new_name.set(input.value());
+            }
+        })
+    };
+    let on_save = {
+        let new_name = new_name.clone();
+        let schema = prop.schema.clone();
+        let table = prop.table.clone();
+        let reload_local = reload_local.clone();
+        Callback::from(move |_: MouseEvent| {
+            let name = new_name.trim().to_string();
+            if name.is_empty() {
+                return;
+            }
+            let mut view =
+                NamedView::new(name, schema.clone(), table.clone(), current_grid_state());
+            view.pivot = Workspace::load().table(&schema, &table).pivot;
+            save_local_view(view);
+            new_name.set(String::new());
+            reload_local();
+        })
+    };
+    let on_export = {
+        let local = local.clone();
+        let schema = prop.schema.clone();
+        let table = prop.table.clone();
+        Callback::from(move |_: MouseEvent| {
+            download_text(
+                format!("{schema}.{table}.views.json"),
+                String::from("application/json"),
+                export_views(&local),
+            );
+        })
+    };
+    let on_import = {
+        let status = status.clone();
+        let reload_local = reload_local.clone();
+        Callback::from(move |event: Event| {
+            let input = match event.target_dyn_into::<HtmlInputElement>() {
+                Some(input) => input,
+                None => return,
+            };
+            let file = match input.files().and_then(|files| files.get(0)) {
+                Some(file) => File::from(file),
+                None => return,
+            };
+            input.set_value("");
+            let status = status.clone();
+            let reload_local = reload_local.clone();
+            spawn_local(async move {
+                let text = match read_as_text(&file).await {
+                    Ok(text) => text,
+                    Err(error) => {
+                        status.set(Some(locale.tr_args(
+                            "file-unreadable",
+                            &[("error", error.to_string().into())],
+                        )));
+                        return;
+                    }
+                };
+                match import_views(&text) {
+                    Ok(views) => {
+                        let count = views.len();
+                        for view in views {
+                            save_local_view(view);
+                        }
+                        status.set(Some(
+                            locale.tr_args("views-imported", &[("views", count.into())]),
+                        ));
+                        reload_local();
+                    }
+                    Err(error) => status.set(Some(error.message().clone())),
+                }
+            });
+        })
+    };
+
+    let local_rows = local.iter().map(|view| {
+        let applied = prop.applied.as_ref().map(|applied| &applied.id) == Some(&view.id);
+        let on_apply = {
+            let on_apply = prop.on_apply.clone();
+            let view = view.clone();
+            Callback::from(move |_: MouseEvent| on_apply.emit(view.clone()))
+        };
+        let on_delete = {
+            let id = view.id.clone();
+            let reload_local = reload_local.clone();
+            Callback::from(move |_: MouseEvent| {
+                delete_local_view(&id);
+                reload_local();
+            })
+        };
+        let on_share = {
+            let view = view.clone();
+            let store = store.clone();
+            let status = status.clone();
+            let reload_shared = reload_shared.clone();
+            Callback::from(move |_: MouseEvent| {
+                if let Some(store) = store.clone() {
+                    let view = view.clone();
+                    let status = status.clone();
+                    let reload_shared = reload_shared.clone();
+                    spawn_local(async move {
+                        match store.publish(&view).await {
+                            Ok(()) => {
+                                status.set(Some(locale.tr_args(
+                                    "views-shared",
+                                    &[("name", view.name.clone().into())],
+                                )));
+                                reload_shared();
+                            }
+                            Err(error) => status.set(Some(error.message().clone())),
+                        }
+                    });
+                }
+            })
+        };
+        let on_copy_link = copy_link_callback(locale, view, status.clone());
+        let name = match &*renaming {
+            Some((id, name)) if id == &view.id => {
+                let on_rename_input = {
+                    let renaming = renaming.clone();
+                    let id = id.clone();
+                    Callback::from(move |event: InputEvent| {
+                        if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                            renaming.set(Some((id.clone(), input.value())));
+                        }
+                    })
+                };
+                let on_rename_done = {
+                    let renaming = renaming.clone();
+                    let view = view.clone();
+                    let name = name.clone();
+                    let reload_local = reload_local.clone();
+                    Callback::from(move |_: MouseEvent| {
+                        if !name.trim().is_empty() {
+                            save_local_view(NamedView {
+                                name: name.trim().to_string(),
+                                ..view.clone()
+                            });
+                        }
+                        renaming.set(None);
+                        reload_local();
+                    })
+                };
+                html! {
+                    <div class="input-group input-group-sm">
+                        <input type="text" class="form-control" aria-label={locale.tr("views-name")} value={name.clone()} oninput={on_rename_input}/>
+                        <button type="button" class="btn btn-outline-dark" onclick={on_rename_done}>{locale.tr("ok")}</button>
+                    </div>
+                }
+            }
+            _ => {
+                let on_rename = {
+                    let renaming = renaming.clone();
+                    let view = view.clone();
+                    Callback::from(move |_: MouseEvent| {
+                        renaming.set(Some((view.id.clone(), view.name.clone())))
+                    })
+                };
+                html! {
+                    <>
+                        <button type="button" class="btn btn-link btn-sm p-0 text-start" onclick={on_apply}>
+                            {if applied { locale.tr_args("views-applied", &[("name", view.name.clone().into())]) } else { view.name.clone() }}
+                        </button>
+                        <div>
+                            <button type="button" class="btn btn-link btn-sm p-0 me-2" onclick={on_rename}>{locale.tr("views-rename")}</button>
+                            <button type="button" class="btn btn-link btn-sm p-0 me-2" onclick={on_share}>{locale.tr("views-share")}</button>
+                            <button type="button" class="btn btn-link btn-sm p-0 me-2" onclick={on_copy_link}>{locale.tr("views-copy-link")}</button>
+                            <button type="button" class="btn btn-link btn-sm p-0 text-danger" onclick={on_delete}>{locale.tr("views-delete")}</button>
+                        </div>
+                    </>
+                }
+            }
+        };
+        html! { <li class="list-group-item px-2 py-1">{name}</li> }
+    });
+
+    let shared_rows = shared.iter().map(|view| {
+        let on_apply = {
+            let on_apply = prop.on_apply.clone();
+            let view = view.clone();
+            Callback::from(move |_: MouseEvent| on_apply.emit(view.clone()))
+        };
+        let on_unpublish = {
+            let id = view.id.clone();
+            let store = store.clone();
+            let status = status.clone();
+            let reload_shared = reload_shared.clone();
+            Callback::from(move |_: MouseEvent| {
+                if let Some(store) = store.clone() {
+                    let id = id.clone();
+                    let status = status.clone();
+                    let reload_shared = reload_shared.clone();
+                    spawn_local(async move {
+                        match store.unpublish(&id).await {
+                            Ok(()) => reload_shared(),
+                            Err(error) => status.set(Some(error.message().clone())),
+                        }
+                    });
+                }
+            })
+        };
+        let on_copy_link = copy_link_callback(locale, view, status.clone());
+        html! {
+            <li class="list-group-item px-2 py-1">
+                <button type="button" class="btn btn-link btn-sm p-0 text-start" onclick={on_apply}>{view.name.clone()}</button>
+                <div>
+                    <button type="button" class="btn btn-link btn-sm p-0 me-2" onclick={on_copy_link}>{locale.tr("views-copy-link")}</button>
+                    <button type="button" class="btn btn-link btn-sm p-0 text-danger" onclick={on_unpublish}>{locale.tr("views-unshare")}</button>
+                </div>
+            </li>
+        }
+    });
+
+    let status_line = match &*status {
+        Some(message) => {
+            html! { <div class="text-muted mt-2" role="status">{message.clone()}</div> }
+        }
+        None => html! {},
+    };
+    let shared_title = match store {
+        Some(SharedViewStore::Remote(_)) => locale.tr("views-shared-team"),
+        _ => locale.tr("views-shared-browser"),
+    };
+
+    html! {
+        <div class="card shadow position-absolute start-0 p-2" style="top: 100%; z-index: 1050; width: 320px; font-size: 12px;">
+            <div class="input-group input-group-sm mb-2">
+                <input type="text" class="form-control" placeholder={locale.tr("views-name-placeholder")} aria-label={locale.tr("views-name")} value={(*new_name).clone()} oninput={on_name_input}/>
+                <button type="button" class="btn btn-dark" onclick={on_save}>{locale.tr("save")}</button>
+            </div>
+            <div class="fw-semibold">{locale.tr("views-mine")}</div>
+            <ul class="list-group list-group-flush mb-2">{for local_rows}</ul>
+            <div class="fw-semibold">{shared_title}</div>
+            <ul class="list-group list-group-flush mb-2">{for shared_rows}</ul>
+            <div class="d-flex gap-2">
+                <button type="button" class="btn btn-sm btn-outline-dark" onclick={on_export}>{locale.tr("views-export")}</button>
+                <label class="btn btn-sm btn-outline-dark mb-0">
+                    {locale.tr("views-import")}
+                    <input type="file" accept="application/json,.json" class="visually-hidden" onchange={on_import}/>
+                </label>
+            </div>
+            {status_line}
+        </div>
+    }
+}
+
+fn copy_link_callback(
+    locale: Locale,
+    view: &NamedView,
+    status: UseStateHandle<Option<String>>,
+) -> Callback<MouseEvent> {
+    let view = view.clone();
+    Callback::from(move |_: MouseEvent| {
+        let origin = match window().location().origin() {
+            Ok(origin) => origin,
+            Err(_) => {
+                error_1(&JsValue::from(
+                    "view error: unable to read the page origin.",
+                ));
+                return;
+            }
+        };
+        let link = view.link(&origin);
+        let status = status.clone();
+        spawn_local(async move {
+            if copy_text(link).await.as_bool() == Some(true) {
+                status.set(Some(locale.tr("views-link-copied")));
+            } else {
+                status.set(Some(locale.tr("clipboard-failed")));
+            }
+        });
+    })
+}
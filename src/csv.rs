@@ -0,0 +1,391 @@
+#[cfg(test)]
+mod test_csv;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Delimiters a payload may use, preferred in this order when several split its lines alike.
+const DELIMITERS: [char; 4] = [',', ';', '\t', '|'];
+/// Lines read before choosing the delimiter, unless the payload ends first.
+const SNIFF_LINES: usize = 20;
+/// Bytes read before choosing the delimiter, for payloads with very long lines.
+const SNIFF_BYTES: usize = 64 * 1024;
+/// Rows the type of each column is inferred from, later rows are read as those types.
+const INFER_ROWS: usize = 1000;
+
+pub enum CsvError {
+    FormatError(String),
+    DeserializeError(String),
+}
+
+impl CsvError {
+    pub fn message(&self) -> &String {
+        match self {
+            CsvError::FormatError(msg) | CsvError::DeserializeError(msg) => msg,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct CsvOptions {
+    /// The field delimiter, recognized from the first lines when `None`.
+    pub delimiter: Option<char>,
+    /// Read numbers, booleans and empty fields as such, rather than every field as text.
+    pub infer_types: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> CsvOptions {
+        CsvOptions {
+            delimiter: None,
+            infer_types: true,
+        }
+    }
+}
+
+/// The type inferred for a column from its first rows.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum FieldType {
+    Integer,
+    Float,
+    Boolean,
+    Text,
+}
+
+impl FieldType {
+    /// The narrowest type every non-empty value is written as, text for a column of empty fields.
+    fn infer<'a>(values: impl Iterator<Item = &'a str>) -> FieldType {
+        let (mut integer, mut float, mut boolean) = (true, true, true);
+        let mut any = false;
+        for value in values.filter(|value| !value.is_empty()) {
+            any = true;
+            integer = integer && parse_integer(value).is_some();
+            float = float && parse_float(value).is_some();
+            boolean = boolean && parse_boolean(value).is_some();
+        }
+        match () {
+            _ if !any => FieldType::Text,
+            _ if integer => FieldType::Integer,
+            _ if float => FieldType::Float,
+            _ if boolean => FieldType::Boolean,
+            _ => FieldType::Text,
+        }
+    }
+
+    /// The value of a field, kept as text when it is not written as the column's type.
+    fn value(self, text: String) -> Value {
+        if text.is_empty() {
+            return Value::Null;
+        }
+        let value = match self {
+            FieldType::Integer => parse_integer(&text).map(Value::from),
+            FieldType::Float => parse_float(&text).map(Value::from),
+            FieldType::Boolean => parse_boolean(&text).map(Value::from),
+            FieldType::Text => None,
+        };
+        value.unwrap_or(Value::String(text))
+    }
+}
+
+/// An integer written the way it would be printed, so codes such as `007` stay text.
+fn parse_integer(text: &str) -> Option<i64> {
+    text.parse::<i64>()
+        .ok()
+        .filter(|number| number.to_string() == text)
+}
+
+fn parse_float(text: &str) -> Option<f64> {
+    // `inf`, `NaN` and the like parse as floats but are words in a table.
+    if !text.contains(|char: char| char.is_ascii_digit())
+        || text.contains(|char: char| !matches!(char, '0'..='9' | '.' | '-' | '+' | 'e' | 'E'))
+    {
+        return None;
+    }
+    // Leading zeros mark codes, as for integers.
+    let unsigned = text.trim_start_matches(['-', '+']);
+    if unsigned.starts_with('0') && unsigned[1..].starts_with(|char: char| char.is_ascii_digit()) {
+        return None;
+    }
+    text.parse::<f64>().ok().filter(|number| number.is_finite())
+}
+
+fn parse_boolean(text: &str) -> Option<bool> {
+    match text {
+        "true" | "TRUE" | "True" => Some(true),
+        "false" | "FALSE" | "False" => Some(false),
+        _ => None,
+    }
+}
+
+/// Turns CSV text into rows as its bytes arrive: the delimiter is recognized from the first
+/// lines, the first record names the columns, and the types of the columns are inferred from
+/// the first rows when `infer_types` is set.
+pub struct CsvReader {
+    options: CsvOptions,
+    /// Bytes read but not parsed, the record in progress or the lines the delimiter is chosen by.
+    pending: Vec<u8>,
+    delimiter: Option<char>,
+    header: Option<Vec<String>>,
+    /// Rows held back until the types of the columns are inferred.
+    sample: Vec<Vec<String>>,
+    types: Option<Vec<FieldType>>,
+    /// Rows read so far, for errors.
+    rows: usize,
+}
+
+impl CsvReader {
+    pub fn new(options: CsvOptions) -> CsvReader {
+        CsvReader {
+            options,
+            pending: Vec::new(),
+            delimiter: options.delimiter,
+            header: None,
+            sample: Vec::new(),
+            types: if options.infer_types {
+                None
+            } else {
+                Some(Vec::new())
+            },
+            rows: 0,
+        }
+    }
+
+    /// Reads the next chunk of the payload and returns the rows it completes.
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<Vec<Map<String, Value>>, CsvError> {
+        self.pending.extend_from_slice(chunk);
+        let delimiter = match self.delimiter {
+            Some(delimiter) => delimiter,
+            None => {
+                let lines = self.pending.iter().filter(|byte| **byte == b'\n').count();
+                if lines < SNIFF_LINES && self.pending.len() < SNIFF_BYTES {
+                    return Ok(Vec::new());
+                }
+                self.sniff_delimiter()
+            }
+        };
+        let end = record_end(&self.pending, delimiter);
+        let bytes: Vec<u8> = self.pending.drain(..end).collect();
+        self.read(&bytes)
+    }
+
+    /// Checks the payload is complete and returns the rows not returned by `feed`.
+    pub fn finish(&mut self) -> Result<Vec<Map<String, Value>>, CsvError> {
+        if self.delimiter.is_none() {
+            self.sniff_delimiter();
+        }
+        let bytes = std::mem::take(&mut self.pending);
+        let mut rows = self.read(&bytes)?;
+        rows.extend(self.infer_types());
+        Ok(rows)
+    }
+
+    fn sniff_delimiter(&mut self) -> char {
+        let delimiter = sniff_delimiter(&String::from_utf8_lossy(&self.pending));
+        self.delimiter = Some(delimiter);
+        delimiter
+    }
+
+    /// The rows of whole records.
+    fn read(&mut self, bytes: &[u8]) -> Result<Vec<Map<String, Value>>, CsvError> {
+        let bytes = match self.header {
+            None => bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes),
+            Some(_) => bytes,
+        };
+        let text = std::str::from_utf8(bytes).map_err(|error| {
+            CsvError::DeserializeError(format!("The CSV table data is not UTF-8: {error}"))
+        })?;
+        let mut rows = Vec::new();
+        for record in parse_csv(text, self.delimiter.unwrap_or(DELIMITERS[0]))? {
+            let columns = match &self.header {
+                Some(header) => header.len(),
+                None => {
+                    self.header = Some(header_names(record));
+                    continue;
+                }
+            };
+            self.rows += 1;
+            if record.len() != columns {
+                return Err(CsvError::FormatError(format!(
+                    "Row {} of the CSV table data has {} fields, its header has {columns}.",
+                    self.rows,
+                    record.len()
+                )));
+            }
+            if self.types.is_some() {
+                rows.push(self.row(record));
+            } else {
+                self.sample.push(record);
+                if self.sample.len() >= INFER_ROWS {
+                    rows.extend(self.infer_types());
+                }
+            }
+        }
+        Ok(rows)
+    }
+
+    /// Settles the types of the columns from the rows held back, and returns those rows.
+    fn infer_types(&mut self) -> Vec<Map<String, Value>> {
+        if self.types.is_some() {
+            return Vec::new();
+        }
+        let columns = self.header.as_ref().map_or(0, Vec::len);
+        let sample = std::mem::take(&mut self.sample);
+        self.types = Some(
+            (0..columns)
+                .map(|column| FieldType::infer(sample.iter().map(|record| record[column].as_str())))
+                .collect(),
+        );
+        sample.into_iter().map(|record| self.row(record)).collect()
+    }
+
+    fn row(&self, record: Vec<String>) -> Map<String, Value> {
+        let header = self.header.as_deref().unwrap_or_default();
+        let types = self.types.as_deref().unwrap_or_default();
+        header
+            .iter()
+            .zip(record)
+            .enumerate()
+            .map(|(column, (name, text))| {
+                let value = match types.get(column) {
+                    Some(field_type) if self.options.infer_types => field_type.value(text),
+                    _ => Value::String(text),
+                };
+                (name.clone(), value)
+            })
+            .collect()
+    }
+}
+
+/// The end of the last whole record in `bytes`, a record may span lines inside quotes.
+fn record_end(bytes: &[u8], delimiter: char) -> usize {
+    let mut end = 0;
+    let mut quoted = false;
+    let mut field_start = true;
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'"' if quoted => match bytes.get(index + 1) {
+                Some(b'"') => index += 1,
+                Some(_) => quoted = false,
+                // A closing quote or the first of a doubled one, the next chunk tells.
+                None => break,
+            },
+            b'"' if field_start => {
+                quoted = true;
+                field_start = false;
+            }
+            b'\n' if !quoted => {
+                end = index + 1;
+                field_start = true;
+            }
+            byte if !quoted && char::from(byte) == delimiter => field_start = true,
+            _ if !quoted => field_start = false,
+            _ => (),
+        }
+        index += 1;
+    }
+    end
+}
+
+/// The delimiter splitting the first lines of `text` into the same number of fields as its
+/// header, a comma when none splits the header.
+fn sniff_delimiter(text: &str) -> char {
+    let mut best = (DELIMITERS[0], 0);
+    for delimiter in DELIMITERS {
+        // Whole lines, unless the text is a single line without an end.
+        let lines = match record_end(text.as_bytes(), delimiter) {
+            0 => text,
+            end => &text[..end],
+        };
+        let records = match parse_csv(lines, delimiter) {
+            Ok(records) => records,
+            Err(_) => continue,
+        };
+        let columns = match records.first() {
+            Some(header) if header.len() > 1 => header.len(),
+            _ => continue,
+        };
+        let agreeing = records
+            .iter()
+            .filter(|record| record.len() == columns)
+            .count();
+        if agreeing > best.1 {
+            best = (delimiter, agreeing);
+        }
+    }
+    best.0
+}
+
+/// Column names from a header record: trimmed, blank ones numbered and repeated ones suffixed,
+/// so every column keeps its values.
+fn header_names(record: Vec<String>) -> Vec<String> {
+    let mut names: Vec<String> = Vec::with_capacity(record.len());
+    for (index, name) in record.into_iter().enumerate() {
+        let name = match name.trim() {
+            "" => format!("column_{}", index + 1),
+            name => name.to_string(),
+        };
+        let mut unique = name.clone();
+        let mut count = 1;
+        while names.contains(&unique) {
+            count += 1;
+            unique = format!("{name}_{count}");
+        }
+        names.push(unique);
+    }
+    names
+}
+
+/// Splits CSV text into records of fields, following RFC 4180: fields may be quoted, quotes in
+/// quoted fields are doubled, and quoted fields may span lines. Blank lines are skipped.
+pub fn parse_csv(text: &str, delimiter: char) -> Result<Vec<Vec<String>>, CsvError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    // Whether the field started with a quote, so `""` is an empty field, not a blank line.
+    let mut field_quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(char) = chars.next() {
+        if quoted {
+            match char {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                char => field.push(char),
+            }
+            continue;
+        }
+        match char {
+            '"' if field.is_empty() && !field_quoted => {
+                quoted = true;
+                field_quoted = true;
+            }
+            char if char == delimiter => {
+                record.push(std::mem::take(&mut field));
+                field_quoted = false;
+            }
+            '\r' if chars.peek() == Some(&'\n') => (),
+            '\n' => {
+                if !record.is_empty() || !field.is_empty() || field_quoted {
+                    record.push(std::mem::take(&mut field));
+                    records.push(std::mem::take(&mut record));
+                }
+                field_quoted = false;
+            }
+            char => field.push(char),
+        }
+    }
+    if quoted {
+        return Err(CsvError::FormatError(String::from(
+            "The CSV table data ends inside a quoted field.",
+        )));
+    }
+    if !record.is_empty() || !field.is_empty() || field_quoted {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
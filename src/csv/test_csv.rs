@@ -0,0 +1,125 @@
+use super::{parse_csv, CsvOptions, CsvReader};
+use serde_json::{json, Map, Value};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+/// Feeds the text in chunks of `size` bytes and collects the rows.
+fn read(text: &str, options: CsvOptions, size: usize) -> Result<Vec<Map<String, Value>>, String> {
+    let mut reader = CsvReader::new(options);
+    let mut rows = Vec::new();
+    for chunk in text.as_bytes().chunks(size) {
+        rows.extend(
+            reader
+                .feed(chunk)
+                .map_err(|error| error.message().clone())?,
+        );
+    }
+    rows.extend(reader.finish().map_err(|error| error.message().clone())?);
+    Ok(rows)
+}
+
+fn row(value: Value) -> Map<String, Value> {
+    value.as_object().unwrap().clone()
+}
+
+#[wasm_bindgen_test]
+fn test_sniffs_the_delimiter() {
+    let semicolons = "USUBJID;AETERM;AESEQ\n1023-004;\"Nausea; mild\";1\n1023-005;Rash;2\n";
+    let tabs = "USUBJID\tAETERM\tAESEQ\n1023-004\tNausea, mild\t1\n1023-005\tRash\t2\n";
+    let expected = vec![
+        row(json!({"USUBJID": "1023-004", "AETERM": "Nausea; mild", "AESEQ": 1})),
+        row(json!({"USUBJID": "1023-005", "AETERM": "Rash", "AESEQ": 2})),
+    ];
+    let expected_tabs = vec![
+        row(json!({"USUBJID": "1023-004", "AETERM": "Nausea, mild", "AESEQ": 1})),
+        expected[1].clone(),
+    ];
+    for size in [1, 7, 1024] {
+        assert_eq!(
+            read(semicolons, CsvOptions::default(), size).unwrap(),
+            expected
+        );
+        assert_eq!(
+            read(tabs, CsvOptions::default(), size).unwrap(),
+            expected_tabs
+        );
+    }
+    // A single column has no delimiter to find.
+    let single = read("USUBJID\n1023-004\n", CsvOptions::default(), 4).unwrap();
+    assert_eq!(single, vec![row(json!({"USUBJID": "1023-004"}))]);
+}
+
+#[wasm_bindgen_test]
+fn test_quoted_fields_across_chunks() {
+    let text = "\u{FEFF}subject,term\r\n1023-004,\"Headache, \"\"mild\"\"\"\r\n1023-005,\"Rash\nspreading\"\r\n";
+    for size in [1, 2, 5, 1024] {
+        let rows = read(text, CsvOptions::default(), size).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["subject"], "1023-004");
+        assert_eq!(rows[0]["term"], "Headache, \"mild\"");
+        assert_eq!(rows[1]["term"], "Rash\nspreading");
+    }
+    let error = read("subject,term\n1023-004\n", CsvOptions::default(), 5).unwrap_err();
+    assert!(error.contains("Row 1"));
+    assert!(read("subject,term\n1023-004,\"Rash\n", CsvOptions::default(), 5).is_err());
+    assert_eq!(
+        parse_csv("a;\"\"\n\nb;c", ';').ok().unwrap(),
+        vec![vec!["a", ""], vec!["b", "c"]]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_header_names_are_unique() {
+    let rows = read(
+        " VISIT ,,VISIT\nSCREENING,1,WEEK 2\n",
+        CsvOptions::default(),
+        64,
+    )
+    .unwrap();
+    assert_eq!(
+        rows,
+        vec![row(
+            json!({"VISIT": "SCREENING", "column_2": 1, "VISIT_2": "WEEK 2"})
+        )]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_infers_column_types() {
+    let text = "SITEID,LBSEQ,LBSTRESN,LBBLFL,LBORRES,LBSTAT\n\
+                007,1,41.5,true,12,\n\
+                012,2,,false,<5,NOT DONE\n\
+                015,3,3,TRUE,1e3,\n";
+    let rows = read(text, CsvOptions::default(), 16).unwrap();
+    assert_eq!(
+        rows[0],
+        row(
+            json!({"SITEID": "007", "LBSEQ": 1, "LBSTRESN": 41.5, "LBBLFL": true, "LBORRES": "12", "LBSTAT": null})
+        )
+    );
+    assert_eq!(rows[1]["LBSTRESN"], Value::Null);
+    assert_eq!(rows[1]["LBSTAT"], "NOT DONE");
+    assert_eq!(rows[2]["LBSTRESN"], 3.0);
+    assert_eq!(rows[2]["LBBLFL"], true);
+
+    let as_text = CsvOptions {
+        infer_types: false,
+        ..CsvOptions::default()
+    };
+    let rows = read(text, as_text, 16).unwrap();
+    assert_eq!(rows[0]["LBSEQ"], "1");
+    assert_eq!(rows[0]["LBSTAT"], "");
+}
+
+#[wasm_bindgen_test]
+fn test_types_follow_the_first_rows() {
+    let mut text = String::from("LBSEQ,LBORRES\n");
+    for index in 0..1500 {
+        text.push_str(&format!("{},{}\n", index + 1, index % 10));
+    }
+    text.push_str("1501,<5\n");
+    let rows = read(&text, CsvOptions::default(), 4096).unwrap();
+    assert_eq!(rows.len(), 1501);
+    assert_eq!(rows[1200]["LBORRES"], 0);
+    // A value the inferred type does not fit is kept as text.
+    assert_eq!(rows[1500]["LBORRES"], "<5");
+}
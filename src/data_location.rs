@@ -0,0 +1,165 @@
+#[cfg(test)]
+mod test_data_location;
+
+use crate::i18n::Message;
+use crate::payload;
+
+use gloo_net::http::{Request, Response};
+use js_sys::Date;
+use serde::{Deserialize, Serialize};
+use url::Url;
+use web_sys::{ReferrerPolicy, RequestCredentials};
+
+pub enum LocationError {
+    EndpointError(String),
+}
+
+impl LocationError {
+    pub fn message(&self) -> &String {
+        match self {
+            LocationError::EndpointError(msg) => msg,
+        }
+    }
+}
+
+/// The response of `/api/v1/{schema}/{table}/uri`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TableLocation {
+    /// A path on the API host, or an absolute url such as one presigned on object storage.
+    pub url: String,
+    /// When a presigned url stops working, as an RFC 3339 timestamp.
+    #[serde(default)]
+    pub expires_at: Option<String>,
+}
+
+/// How a url that may expire failed to give the table data.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Lapse {
+    /// It is past its expiry.
+    Expired,
+    /// It was refused before its expiry, or without one known.
+    Refused,
+    /// It could not be requested, as when object storage refuses a lapsed url without the CORS
+    /// headers the browser needs to show the refusal.
+    Unreachable,
+}
+
+impl Lapse {
+    pub fn message(&self) -> Message {
+        Message::new(match self {
+            Lapse::Expired => "data-link-expired",
+            Lapse::Refused => "data-link-refused",
+            Lapse::Unreachable => "data-link-unreachable",
+        })
+    }
+}
+
+/// Where the data of a table is downloaded from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataLocation {
+    pub url: Url,
+    /// Milliseconds since the epoch at which the url stops working, `None` when it does not expire.
+    pub expires_at: Option<f64>,
+    /// Set when the url is on another origin than the API's.
+    pub foreign: bool,
+}
+
+impl DataLocation {
+    /// The location `/uri` answered, its url resolved against the API's root url `api`. The
+    /// expiry is the one given, or failing that the one signed into an S3 presigned url.
+    pub fn resolve(location: &TableLocation, api: &Url) -> Result<DataLocation, LocationError> {
+        let url = api.join(&location.url).map_err(|error| {
+            LocationError::EndpointError(format!(
+                "The table data url \"{}\" is not valid: {error}",
+                location.url
+            ))
+        })?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(LocationError::EndpointError(format!(
+                "The table data url \"{}\" is not an http url.",
+                location.url
+            )));
+        }
+        let expires_at = location
+            .expires_at
+            .as_deref()
+            .map(Date::parse)
+            .filter(|expires_at| !expires_at.is_nan())
+            .or_else(|| presigned_expiry(&url));
+        Ok(DataLocation {
+            foreign: url.origin() != api.origin(),
+            url,
+            expires_at,
+        })
+    }
+
+    /// Whether a refusal of the url may only mean it lapsed, so that `/uri` is worth asking again:
+    /// presigned urls expire, and object storage answers 403 once they have.
+    pub fn may_expire(&self) -> bool {
+        self.foreign || self.expires_at.is_some()
+    }
+
+    pub fn is_expired(&self, now: f64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    /// Why the url may have lapsed after a download that answered `status`, or failed without an
+    /// answer when it is `None`, so that `/uri` is worth asking again. `None` when it did not.
+    pub fn lapse(&self, now: f64, status: Option<u16>) -> Option<Lapse> {
+        if !self.may_expire() {
+            return None;
+        }
+        match status {
+            _ if self.is_expired(now) => Some(Lapse::Expired),
+            Some(403) => Some(Lapse::Refused),
+            Some(_) => None,
+            None => Some(Lapse::Unreachable),
+        }
+    }
+
+    /// Requests the data. The API's cookies stay with the API: a url on another origin carries
+    /// its own authorization, and is sent no credentials and no referrer.
+    pub async fn fetch(&self) -> Result<Response, gloo_net::Error> {
+        let request = Request::get(self.url.as_str()).header("Accept", payload::ACCEPT);
+        let request = if self.foreign {
+            request
+                .credentials(RequestCredentials::Omit)
+                .referrer_policy(ReferrerPolicy::NoReferrer)
+        } else {
+            request
+        };
+        request.send().await
+    }
+}
+
+/// The expiry of an S3 presigned url, from its signing time and lifetime in seconds.
+fn presigned_expiry(url: &Url) -> Option<f64> {
+    let mut signed_at = None;
+    let mut lifetime = None;
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "X-Amz-Date" => signed_at = Some(value.into_owned()),
+            "X-Amz-Expires" => lifetime = value.parse::<f64>().ok(),
+            _ => (),
+        }
+    }
+    // Signed as `20241019T120000Z`.
+    let signed_at = signed_at?;
+    if signed_at.len() != 16 || !signed_at.is_ascii() {
+        return None;
+    }
+    let iso = format!(
+        "{}-{}-{}T{}:{}:{}Z",
+        &signed_at[0..4],
+        &signed_at[4..6],
+        &signed_at[6..8],
+        &signed_at[9..11],
+        &signed_at[11..13],
+        &signed_at[13..15]
+    );
+    let signed_at = Date::parse(&iso);
+    if signed_at.is_nan() {
+        return None;
+    }
+    Some(signed_at + lifetime? * 1000.0)
+}
@@ -0,0 +1,82 @@
+use super::{DataLocation, Lapse, TableLocation};
+use js_sys::Date;
+use url::Url;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn api() -> Url {
+    Url::parse("https://styeward.example:443/").unwrap()
+}
+
+fn resolve(url: &str, expires_at: Option<&str>) -> Option<DataLocation> {
+    let location = TableLocation {
+        url: String::from(url),
+        expires_at: expires_at.map(String::from),
+    };
+    DataLocation::resolve(&location, &api()).ok()
+}
+
+#[wasm_bindgen_test]
+fn test_paths_resolve_on_the_api_host() {
+    for path in ["data/study/lb.json", "/data/study/lb.json"] {
+        let location = resolve(path, None).unwrap();
+        assert_eq!(
+            location.url.as_str(),
+            "https://styeward.example/data/study/lb.json"
+        );
+        assert!(!location.foreign);
+        assert!(!location.may_expire());
+    }
+    let location = resolve("https://styeward.example/data/lb.json", None).unwrap();
+    assert!(!location.foreign);
+    assert!(resolve("javascript:alert(1)", None).is_none());
+    assert!(resolve("http://[::1", None).is_none());
+}
+
+#[wasm_bindgen_test]
+fn test_presigned_urls_expire() {
+    let presigned = "https://lake.s3.eu-west-1.amazonaws.com/study/lb.csv.gz\
+                     ?X-Amz-Algorithm=AWS4-HMAC-SHA256&X-Amz-Date=20241019T120000Z\
+                     &X-Amz-Expires=900&X-Amz-SignedHeaders=host&X-Amz-Signature=ab12";
+    let location = resolve(presigned, None).unwrap();
+    assert!(location.foreign);
+    assert!(location.may_expire());
+    let expires_at = Date::parse("2024-10-19T12:15:00Z");
+    assert_eq!(location.expires_at, Some(expires_at));
+    assert!(!location.is_expired(expires_at - 1.0));
+    assert!(location.is_expired(expires_at));
+
+    // The expiry `/uri` gives wins over the one in the url.
+    let location = resolve(presigned, Some("2024-10-19T13:00:00Z")).unwrap();
+    assert_eq!(
+        location.expires_at,
+        Some(Date::parse("2024-10-19T13:00:00Z"))
+    );
+    let location = resolve("https://lake.example/lb.parquet", Some("soon")).unwrap();
+    assert_eq!(location.expires_at, None);
+    assert!(location.may_expire());
+}
+
+#[wasm_bindgen_test]
+fn test_lapses_of_presigned_urls() {
+    let presigned = "https://lake.s3.eu-west-1.amazonaws.com/study/lb.csv.gz\
+                     ?X-Amz-Date=20241019T120000Z&X-Amz-Expires=900&X-Amz-Signature=ab12";
+    let location = resolve(presigned, None).unwrap();
+    let expires_at = Date::parse("2024-10-19T12:15:00Z");
+    // Past its expiry, the url is not worth requesting at all.
+    assert_eq!(location.lapse(expires_at, None), Some(Lapse::Expired));
+    assert_eq!(location.lapse(expires_at, Some(403)), Some(Lapse::Expired));
+    assert_eq!(
+        location.lapse(expires_at - 1.0, Some(403)),
+        Some(Lapse::Refused)
+    );
+    assert_eq!(
+        location.lapse(expires_at - 1.0, None),
+        Some(Lapse::Unreachable)
+    );
+    assert_eq!(location.lapse(expires_at - 1.0, Some(404)), None);
+
+    // Urls on the API host are not asked for again.
+    let location = resolve("data/study/lb.json", None).unwrap();
+    assert_eq!(location.lapse(expires_at, Some(403)), None);
+    assert_eq!(location.lapse(expires_at, None), None);
+}
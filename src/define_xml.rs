@@ -0,0 +1,515 @@
+#[cfg(test)]
+mod test_define_xml;
+
+use crate::codelists::{Codelist, CodelistRegistry};
+use crate::i18n::Message;
+use crate::StyewardConfig;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use gloo_net::http::Request;
+use gloo_storage::{LocalStorage, Storage};
+use gloo_utils::window;
+use roxmltree::{Document, Node};
+use url::Url;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::console::error_1;
+use web_sys::Event;
+use yew::prelude::*;
+
+/// Uploaded define.xml files are kept under this prefix and the schema name.
+const DEFINE_STORAGE_PREFIX: &str = "styeward.define.";
+/// Window event fired when a define.xml is uploaded, so every `use_define` picks it up.
+const DEFINE_LOADED_EVENT: &str = "styeward-define-loaded";
+
+pub enum DefineError {
+    EndpointError(String),
+    GlooError(String),
+    HttpStatusError(String),
+    ParseError(String),
+}
+
+impl DefineError {
+    pub fn message(&self) -> &String {
+        match self {
+            DefineError::EndpointError(msg)
+            | DefineError::GlooError(msg)
+            | DefineError::HttpStatusError(msg)
+            | DefineError::ParseError(msg) => msg,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Define {
+    pub study_name: String,
+    pub datasets: Vec<DatasetDef>,
+    /// Code lists by OID.
+    pub codelists: HashMap<String, CodeList>,
+}
+
+/// An `ItemGroupDef`: one dataset and its variables in `OrderNumber` order.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct DatasetDef {
+    pub name: String,
+    pub label: String,
+    pub class: Option<String>,
+    pub structure: Option<String>,
+    pub purpose: Option<String>,
+    pub comment: Option<String>,
+    pub variables: Vec<VariableDef>,
+}
+
+/// An `ItemDef` as referenced from one dataset.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct VariableDef {
+    pub name: String,
+    pub label: String,
+    pub data_type: String,
+    pub length: Option<u32>,
+    pub mandatory: bool,
+    /// Position among the dataset's keys, `None` for variables that are not keys.
+    pub key_sequence: Option<u32>,
+    /// OID of the code list the values come from.
+    pub codelist: Option<String>,
+    /// `def:Origin` type, e.g. Collected or Derived.
+    pub origin: Option<String>,
+    /// Description of the `MethodDef` deriving the variable.
+    pub derivation: Option<String>,
+    pub comment: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct CodeList {
+    pub name: String,
+    /// Coded values and their decodes, `None` for enumerated items without one.
+    pub items: Vec<(String, Option<String>)>,
+}
+
+impl Define {
+    /// The dataset documenting a table, matched on the name like `detect_domain`, so `sdtm_ae`
+    /// finds AE.
+    pub fn dataset(&self, table: &str) -> Option<&DatasetDef> {
+        let table = table.to_ascii_uppercase();
+        std::iter::once(table.as_str())
+            .chain(table.rsplit(['.', '_', '-']))
+            .find_map(|part| {
+                self.datasets
+                    .iter()
+                    .find(|dataset| dataset.name.eq_ignore_ascii_case(part))
+            })
+    }
+
+    pub fn codelist(&self, variable: &VariableDef) -> Option<&CodeList> {
+        variable
+            .codelist
+            .as_ref()
+            .and_then(|oid| self.codelists.get(oid))
+    }
+}
+
+impl DatasetDef {
+    pub fn variable(&self, name: &str) -> Option<&VariableDef> {
+        self.variables
+            .iter()
+            .find(|variable| variable.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Key variables in `KeySequence` order.
+    pub fn keys(&self) -> Vec<&VariableDef> {
+        let mut keys: Vec<&VariableDef> = self
+            .variables
+            .iter()
+            .filter(|variable| variable.key_sequence.is_some())
+            .collect();
+        keys.sort_by_key(|variable| variable.key_sequence);
+        keys
+    }
+}
+
+impl VariableDef {
+    /// Header tooltip lines: label, origin, derivation and code list.
+    pub fn describe(&self, define: &Define) -> Vec<Message> {
+        let mut lines = vec![Message::new("define-variable")
+            .with("name", self.name.clone())
+            .with("label", self.label.clone())];
+        if let Some(origin) = &self.origin {
+            lines.push(Message::new("define-origin").with("origin", origin.clone()));
+        }
+        if let Some(derivation) = &self.derivation {
+            lines.push(Message::new("define-derivation").with("derivation", derivation.clone()));
+        }
+        if let Some(codelist) = define.codelist(self) {
+            lines.push(Message::new("column-codelist").with("codelist", codelist.name.clone()));
+        }
+        lines
+    }
+}
+
+impl Define {
+    /// The dataset's code lists as a registry decoding its columns, codes decoding to themselves
+    /// are left out.
+    pub fn codelist_registry(&self, dataset: &DatasetDef) -> CodelistRegistry {
+        CodelistRegistry {
+            codelists: dataset
+                .variables
+                .iter()
+                .filter_map(|variable| {
+                    let codelist = self.codelist(variable)?;
+                    Some(Codelist {
+                        name: codelist.name.clone(),
+                        columns: vec![variable.name.clone()],
+                        terms: codelist
+                            .items
+                            .iter()
+                            .filter_map(|(coded, decode)| {
+                                let decode = decode.as_ref().filter(|decode| *decode != coded)?;
+                                Some((coded.clone(), decode.clone()))
+                            })
+                            .collect(),
+                    })
+                })
+                .filter(|codelist| !codelist.terms.is_empty())
+                .collect(),
+        }
+    }
+}
+
+/// Reads a Define-XML 2.x document.
+pub fn parse_define(xml: &str) -> Result<Define, DefineError> {
+    let document = Document::parse(xml)
+        .map_err(|error| DefineError::ParseError(format!("define error: invalid XML: {error}")))?;
+    let metadata = document
+        .descendants()
+        .find(|node| node.has_tag_name("MetaDataVersion"))
+        .ok_or_else(|| {
+            DefineError::ParseError(String::from(
+                "define error: the document has no MetaDataVersion.",
+            ))
+        })?;
+
+    let methods: HashMap<&str, String> = definitions(metadata, "MethodDef");
+    let comments: HashMap<&str, String> = definitions(metadata, "CommentDef");
+    let items: HashMap<&str, Node> = metadata
+        .children()
+        .filter(|node| node.has_tag_name("ItemDef"))
+        .filter_map(|node| Some((node.attribute("OID")?, node)))
+        .collect();
+
+    let datasets = metadata
+        .children()
+        .filter(|node| node.has_tag_name("ItemGroupDef"))
+        .map(|group| {
+            let mut references: Vec<(u32, VariableDef)> = group
+                .children()
+                .filter(|node| node.has_tag_name("ItemRef"))
+                .filter_map(|item_ref| {
+                    let item = items.get(item_ref.attribute("ItemOID")?)?;
+                    let order = attribute(item_ref, "OrderNumber")
+                        .and_then(|order| order.parse::<u32>().ok())
+                        .unwrap_or(u32::MAX);
+                    let variable = VariableDef {
+                        name: item.attribute("Name").unwrap_or_default().to_string(),
+                        label: description(*item).unwrap_or_default(),
+                        data_type: item.attribute("DataType").unwrap_or_default().to_string(),
+                        length: item
+                            .attribute("Length")
+                            .and_then(|length| length.parse::<u32>().ok()),
+                        mandatory: item_ref.attribute("Mandatory") == Some("Yes"),
+                        key_sequence: item_ref
+                            .attribute("KeySequence")
+                            .and_then(|key| key.parse::<u32>().ok()),
+                        codelist: item
+                            .children()
+                            .find(|node| node.has_tag_name("CodeListRef"))
+                            .and_then(|node| node.attribute("CodeListOID"))
+                            .map(String::from),
+                        origin: item
+                            .children()
+                            .find(|node| node.tag_name().name() == "Origin")
+                            .and_then(|node| node.attribute("Type"))
+                            .map(String::from),
+                        derivation: item_ref
+                            .attribute("MethodOID")
+                            .and_then(|oid| methods.get(oid))
+                            .cloned(),
+                        comment: attribute(*item, "CommentOID")
+                            .and_then(|oid| comments.get(oid))
+                            .cloned(),
+                    };
+                    Some((order, variable))
+                })
+                .collect();
+            references.sort_by_key(|(order, _)| *order);
+            DatasetDef {
+                name: group.attribute("Name").unwrap_or_default().to_string(),
+                label: description(group).unwrap_or_default(),
+                class: attribute(group, "Class").map(String::from).or_else(|| {
+                    group
+                        .children()
+                        .find(|node| node.tag_name().name() == "Class")
+                        .and_then(|node| node.attribute("Name"))
+                        .map(String::from)
+                }),
+                structure: attribute(group, "Structure").map(String::from),
+                purpose: group.attribute("Purpose").map(String::from),
+                comment: attribute(group, "CommentOID")
+                    .and_then(|oid| comments.get(oid))
+                    .cloned(),
+                variables: references
+                    .into_iter()
+                    .map(|(_, variable)| variable)
+                    .collect(),
+            }
+        })
+        .collect();
+
+    let codelists = metadata
+        .children()
+        .filter(|node| node.has_tag_name("CodeList"))
+        .filter_map(|list| {
+            let items = list
+                .children()
+                .filter(|node| {
+                    node.has_tag_name("CodeListItem") || node.has_tag_name("EnumeratedItem")
+                })
+                .filter_map(|item| {
+                    let decode = item
+                        .children()
+                        .find(|node| node.has_tag_name("Decode"))
+                        .and_then(translated_text);
+                    Some((item.attribute("CodedValue")?.to_string(), decode))
+                })
+                .collect();
+            Some((
+                list.attribute("OID")?.to_string(),
+                CodeList {
+                    name: list.attribute("Name").unwrap_or_default().to_string(),
+                    items,
+                },
+            ))
+        })
+        .collect();
+
+    let study_name = document
+        .descendants()
+        .find(|node| node.has_tag_name("StudyName"))
+        .and_then(|node| node.text())
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+
+    Ok(Define {
+        study_name,
+        datasets,
+        codelists,
+    })
+}
+
+/// The descriptions of the `tag` elements, e.g. `MethodDef`, by OID.
+fn definitions<'a>(metadata: Node<'a, '_>, tag: &str) -> HashMap<&'a str, String> {
+    metadata
+        .children()
+        .filter(|node| node.tag_name().name() == tag)
+        .filter_map(|node| Some((node.attribute("OID")?, description(node)?)))
+        .collect()
+}
+
+/// An attribute by local name, whatever its namespace, e.g. `def:Structure`.
+fn attribute<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    node.attributes()
+        .find(|attribute| attribute.name() == name)
+        .map(|attribute| attribute.value())
+}
+
+fn description(node: Node) -> Option<String> {
+    node.children()
+        .find(|child| child.has_tag_name("Description"))
+        .and_then(translated_text)
+}
+
+/// The English `TranslatedText` of an element, or its first one.
+fn translated_text(node: Node) -> Option<String> {
+    let texts: Vec<Node> = node
+        .children()
+        .filter(|child| child.has_tag_name("TranslatedText"))
+        .collect();
+    texts
+        .iter()
+        .find(|text| {
+            text.attributes()
+                .any(|attribute| attribute.name() == "lang" && attribute.value().starts_with("en"))
+        })
+        .or(texts.first())
+        .and_then(|text| text.text())
+        .map(|text| text.trim().to_string())
+}
+
+thread_local! {
+    static DEFINES: RefCell<HashMap<String, Rc<Define>>> = RefCell::new(HashMap::new());
+}
+
+fn cached(schema: &str) -> Option<Rc<Define>> {
+    DEFINES.with(|defines| defines.borrow().get(schema).cloned())
+}
+
+fn cache(schema: &str, define: Define) -> Rc<Define> {
+    let define = Rc::new(define);
+    DEFINES.with(|defines| {
+        defines
+            .borrow_mut()
+            .insert(String::from(schema), define.clone())
+    });
+    define
+}
+
+/// Parses an uploaded define.xml, keeps it for the schema and tells every `use_define` about it.
+pub fn store_uploaded(schema: &str, xml: &str) -> Result<Rc<Define>, DefineError> {
+    let define = cache(schema, parse_define(xml)?);
+    if let Err(error) = LocalStorage::set(format!("{DEFINE_STORAGE_PREFIX}{schema}"), xml) {
+        // Large files may not fit in local storage, they then only last until a reload.
+        error_1(&JsValue::from(format!(
+            "define error: unable to keep the define.xml: {error}"
+        )));
+    }
+    if let Ok(event) = Event::new(DEFINE_LOADED_EVENT) {
+        let _ = window().dispatch_event(&event);
+    }
+    Ok(define)
+}
+
+/// The define.xml of a schema: the one uploaded, else the one served at
+/// `/api/v1/{schema}/define.xml`, `None` when there is neither.
+pub(crate) async fn load_define(
+    config: &StyewardConfig,
+    schema: &str,
+) -> Result<Option<Rc<Define>>, DefineError> {
+    if let Some(define) = cached(schema) {
+        return Ok(Some(define));
+    }
+    if let Ok(xml) = LocalStorage::get::<String>(format!("{DEFINE_STORAGE_PREFIX}{schema}")) {
+        return parse_define(&xml).map(|define| Some(cache(schema, define)));
+    }
+    let endpoint = format!(
+        "{}://{}:{}/api/v1/{schema}/define.xml",
+        config.scheme, config.host, config.port
+    );
+    let url = Url::parse(&endpoint)
+        .map_err(|error| DefineError::EndpointError(format!("define error: {error}")))?;
+    let response = Request::get(url.as_str())
+        .send()
+        .await
+        .map_err(|error| DefineError::GlooError(format!("define error: {error}")))?;
+    if response.status() == 404 {
+        return Ok(None);
+    }
+    if !response.ok() {
+        return Err(DefineError::HttpStatusError(format!(
+            "define error: status {} loading {endpoint}",
+            response.status()
+        )));
+    }
+    let xml = response
+        .text()
+        .await
+        .map_err(|error| DefineError::GlooError(format!("define error: {error}")))?;
+    parse_define(&xml).map(|define| Some(cache(schema, define)))
+}
+
+/// The define.xml `use_define` shows and the schema it is for.
+#[derive(Clone, PartialEq, Default, Debug)]
+struct DefineState {
+    schema: Option<String>,
+    define: Option<Rc<Define>>,
+}
+
+enum DefineAction {
+    /// The schema changed, its define.xml is shown from the cache until it loads.
+    Select(Option<String>),
+    /// The define.xml of `schema` loaded, ignored when another schema is selected by then.
+    Loaded {
+        schema: String,
+        define: Option<Rc<Define>>,
+    },
+    /// A define.xml was uploaded, it may replace the one of the selected schema.
+    Uploaded,
+}
+
+impl Reducible for DefineState {
+    type Action = DefineAction;
+
+    fn reduce(self: Rc<Self>, action: DefineAction) -> Rc<Self> {
+        let state = match action {
+            DefineAction::Select(schema) => DefineState {
+                define: schema.as_deref().and_then(cached),
+                schema,
+            },
+            DefineAction::Loaded { schema, define } if self.schema.as_ref() == Some(&schema) => {
+                DefineState {
+                    schema: Some(schema),
+                    define,
+                }
+            }
+            DefineAction::Loaded { .. } => return self,
+            DefineAction::Uploaded => DefineState {
+                schema: self.schema.clone(),
+                define: self.schema.as_deref().and_then(cached),
+            },
+        };
+        Rc::new(state)
+    }
+}
+
+/// The define.xml of the schema, updated when one is uploaded.
+#[hook]
+pub fn use_define(schema: Option<String>) -> Option<Rc<Define>> {
+    let state = use_reducer(|| DefineState {
+        define: schema.as_deref().and_then(cached),
+        schema: schema.clone(),
+    });
+    let config = use_context::<StyewardConfig>();
+    {
+        let dispatcher = state.dispatcher();
+        use_effect_with_deps(
+            move |_| {
+                let listener =
+                    Closure::<dyn Fn()>::new(move || dispatcher.dispatch(DefineAction::Uploaded));
+                let _ = window().add_event_listener_with_callback(
+                    DEFINE_LOADED_EVENT,
+                    listener.as_ref().unchecked_ref(),
+                );
+                move || {
+                    let _ = window().remove_event_listener_with_callback(
+                        DEFINE_LOADED_EVENT,
+                        listener.as_ref().unchecked_ref(),
+                    );
+                }
+            },
+            (),
+        );
+    }
+    {
+        let dispatcher = state.dispatcher();
+        use_effect_with_deps(
+            move |schema| {
+                dispatcher.dispatch(DefineAction::Select(schema.clone()));
+                if let (Some(schema), Some(config)) = (schema.clone(), config) {
+                    spawn_local(async move {
+                        let define = load_define(&config, &schema).await.unwrap_or_else(|error| {
+                            error_1(&JsValue::from(error.message()));
+                            None
+                        });
+                        dispatcher.dispatch(DefineAction::Loaded { schema, define });
+                    });
+                }
+                || ()
+            },
+            schema,
+        );
+    }
+    state.define.clone()
+}
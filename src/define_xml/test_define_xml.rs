@@ -0,0 +1,145 @@
+use super::{cache, parse_define, DefineAction, DefineState};
+use crate::i18n::Locale;
+use std::rc::Rc;
+use wasm_bindgen_test::wasm_bindgen_test;
+use yew::functional::Reducible;
+
+const DEFINE_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ODM xmlns="http://www.cdisc.org/ns/odm/v1.3" xmlns:def="http://www.cdisc.org/ns/def/v2.1" xml:lang="en">
+  <Study OID="S.CDISC01">
+    <GlobalVariables>
+      <StudyName>CDISC01</StudyName>
+    </GlobalVariables>
+    <MetaDataVersion OID="MDV.1" Name="Study CDISC01">
+      <ItemGroupDef OID="IG.AE" Name="AE" Repeating="Yes" Purpose="Tabulation" def:Structure="One record per adverse event per subject">
+        <Description><TranslatedText xml:lang="en">Adverse Events</TranslatedText></Description>
+        <ItemRef ItemOID="IT.AE.AESEV" Mandatory="No" OrderNumber="3"/>
+        <ItemRef ItemOID="IT.AE.AESEQ" Mandatory="Yes" OrderNumber="2" KeySequence="2" MethodOID="MT.SEQ"/>
+        <ItemRef ItemOID="IT.USUBJID" Mandatory="Yes" OrderNumber="1" KeySequence="1"/>
+        <def:Class Name="EVENTS"/>
+      </ItemGroupDef>
+      <ItemDef OID="IT.USUBJID" Name="USUBJID" DataType="text" Length="20">
+        <Description><TranslatedText xml:lang="en">Unique Subject Identifier</TranslatedText></Description>
+        <def:Origin Type="Assigned"/>
+      </ItemDef>
+      <ItemDef OID="IT.AE.AESEQ" Name="AESEQ" DataType="integer" Length="8">
+        <Description><TranslatedText xml:lang="en">Sequence Number</TranslatedText></Description>
+        <def:Origin Type="Derived"/>
+      </ItemDef>
+      <ItemDef OID="IT.AE.AESEV" Name="AESEV" DataType="text" Length="8">
+        <Description><TranslatedText xml:lang="en">Severity/Intensity</TranslatedText></Description>
+        <CodeListRef CodeListOID="CL.AESEV"/>
+        <def:Origin Type="Collected"/>
+      </ItemDef>
+      <CodeList OID="CL.AESEV" Name="Severity" DataType="text">
+        <CodeListItem CodedValue="1"><Decode><TranslatedText xml:lang="en">MILD</TranslatedText></Decode></CodeListItem>
+        <CodeListItem CodedValue="3"><Decode><TranslatedText xml:lang="en">SEVERE</TranslatedText></Decode></CodeListItem>
+        <EnumeratedItem CodedValue="MODERATE"/>
+      </CodeList>
+      <MethodDef OID="MT.SEQ" Name="Sequence" Type="Computation">
+        <Description><TranslatedText xml:lang="en">Sequential number per subject ordered by AESTDTC.</TranslatedText></Description>
+      </MethodDef>
+    </MetaDataVersion>
+  </Study>
+</ODM>"#;
+
+#[wasm_bindgen_test]
+fn test_parse_define() {
+    let define = parse_define(DEFINE_XML).ok().unwrap();
+    assert_eq!(define.study_name, "CDISC01");
+    let ae = define.dataset("sdtm_ae").unwrap();
+    assert_eq!(ae.label, "Adverse Events");
+    assert_eq!(ae.class.as_deref(), Some("EVENTS"));
+    assert_eq!(
+        ae.structure.as_deref(),
+        Some("One record per adverse event per subject")
+    );
+    let names: Vec<&str> = ae
+        .variables
+        .iter()
+        .map(|variable| variable.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["USUBJID", "AESEQ", "AESEV"]);
+    let keys: Vec<&str> = ae
+        .keys()
+        .iter()
+        .map(|variable| variable.name.as_str())
+        .collect();
+    assert_eq!(keys, vec!["USUBJID", "AESEQ"]);
+
+    let aeseq = ae.variable("aeseq").unwrap();
+    assert_eq!(aeseq.origin.as_deref(), Some("Derived"));
+    assert_eq!(
+        aeseq.derivation.as_deref(),
+        Some("Sequential number per subject ordered by AESTDTC.")
+    );
+    let describe = |locale: Locale| -> Vec<String> {
+        aeseq
+            .describe(&define)
+            .iter()
+            .map(|line| line.text(locale))
+            .collect()
+    };
+    assert_eq!(
+        describe(Locale::EnUs),
+        vec![
+            "AESEQ: Sequence Number",
+            "Origin: Derived",
+            "Derivation: Sequential number per subject ordered by AESTDTC."
+        ]
+    );
+    assert_eq!(describe(Locale::DeDe)[1], "Herkunft: Derived");
+    let severity = define.codelist(ae.variable("AESEV").unwrap()).unwrap();
+    assert_eq!(severity.name, "Severity");
+    assert_eq!(severity.items.len(), 3);
+    assert!(define.dataset("dm").is_none());
+
+    assert!(parse_define("<ODM>").is_err());
+    assert!(parse_define("<ODM/>").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_codelist_registry() {
+    let define = parse_define(DEFINE_XML).ok().unwrap();
+    let registry = define.codelist_registry(define.dataset("ae").unwrap());
+    let severity = registry.for_column("ae", "aesev").unwrap();
+    assert_eq!(severity.render("3").as_deref(), Some("3 \u{2014} SEVERE"));
+    // MODERATE has no decode, so it is not a term.
+    assert_eq!(severity.render("MODERATE"), None);
+    assert!(registry.for_column("ae", "AESEQ").is_none());
+}
+
+#[wasm_bindgen_test]
+fn test_uploads_replace_the_shown_define() {
+    let state = Rc::new(DefineState::default())
+        .reduce(DefineAction::Select(Some(String::from("upload-test"))));
+    assert_eq!(state.define, None);
+
+    let first = cache("upload-test", parse_define(DEFINE_XML).ok().unwrap());
+    let state = state.reduce(DefineAction::Uploaded);
+    assert_eq!(state.define, Some(first));
+
+    // A corrected define.xml uploaded for the same schema replaces the first one.
+    let corrected = DEFINE_XML.replace("Adverse Events", "Adverse Events (corrected)");
+    let second = cache("upload-test", parse_define(&corrected).ok().unwrap());
+    let state = state.reduce(DefineAction::Uploaded);
+    assert_eq!(state.define, Some(second));
+}
+
+#[wasm_bindgen_test]
+fn test_define_of_a_previous_schema_is_ignored() {
+    let define = Rc::new(parse_define(DEFINE_XML).ok().unwrap());
+    let state = Rc::new(DefineState::default())
+        .reduce(DefineAction::Select(Some(String::from("sdtm"))))
+        .reduce(DefineAction::Select(Some(String::from("adam"))));
+    let state = state.reduce(DefineAction::Loaded {
+        schema: String::from("sdtm"),
+        define: Some(define.clone()),
+    });
+    assert_eq!(state.define, None);
+    let state = state.reduce(DefineAction::Loaded {
+        schema: String::from("adam"),
+        define: Some(define.clone()),
+    });
+    assert_eq!(state.define, Some(define));
+}
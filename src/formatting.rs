@@ -0,0 +1,467 @@
+#[cfg(test)]
+mod test_formatting;
+
+use crate::cdisc::IsoDate;
+use crate::i18n::{Locale, Message, MessageArg};
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+/// Row key prefix of a cell's formatted text, e.g. `__text.LBORRES`, read by ag_grid.js.
+pub const FORMATTED_TEXT_PREFIX: &str = "__text.";
+/// Row key prefix of a cell's CSS classes, e.g. `__class.LBORRES`, read by ag_grid.js.
+pub const CELL_CLASS_PREFIX: &str = "__class.";
+
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = RegExp)]
+    type Pattern;
+
+    #[wasm_bindgen(constructor, catch, js_class = "RegExp")]
+    fn new(pattern: &str, flags: &str) -> Result<Pattern, JsValue>;
+    #[wasm_bindgen(method)]
+    fn test(this: &Pattern, text: &str) -> bool;
+}
+
+pub enum FormattingError {
+    PatternError(String),
+    RuleError(String),
+}
+
+impl FormattingError {
+    pub fn message(&self) -> &String {
+        match self {
+            FormattingError::PatternError(msg) | FormattingError::RuleError(msg) => msg,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+}
+
+impl Comparison {
+    pub const ALL: [Comparison; 5] = [
+        Comparison::Less,
+        Comparison::LessOrEqual,
+        Comparison::Greater,
+        Comparison::GreaterOrEqual,
+        Comparison::Equal,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "\u{2264}",
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => "\u{2265}",
+            Comparison::Equal => "=",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Comparison> {
+        Comparison::ALL
+            .iter()
+            .find(|comparison| comparison.label() == label)
+            .copied()
+    }
+
+    fn holds(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Less => value < threshold,
+            Comparison::LessOrEqual => value <= threshold,
+            Comparison::Greater => value > threshold,
+            Comparison::GreaterOrEqual => value >= threshold,
+            Comparison::Equal => value == threshold,
+        }
+    }
+}
+
+/// When a rule applies to a cell.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum Condition {
+    Always,
+    /// The value is a number and compares to `threshold`, e.g. AETOXGR ≥ 3.
+    Threshold {
+        comparison: Comparison,
+        threshold: f64,
+    },
+    /// The value matches a JavaScript regular expression.
+    Matches {
+        pattern: String,
+    },
+    /// The value is one of `values`, ignoring case.
+    OneOf {
+        values: Vec<String>,
+    },
+    /// The value is a number below the row's `low_field` or above its `high_field`, e.g. a lab
+    /// result against LBORNRLO and LBORNRHI. A missing or non-numeric bound is not checked.
+    OutsideRange {
+        low_field: String,
+        high_field: String,
+    },
+}
+
+impl Condition {
+    pub fn describe(&self) -> Message {
+        match self {
+            Condition::Always => Message::new("condition-always"),
+            Condition::Threshold {
+                comparison,
+                threshold,
+            } => Message::new("condition-threshold")
+                .with("comparison", comparison.label())
+                .with("threshold", *threshold),
+            Condition::Matches { pattern } => {
+                Message::new("condition-matches").with("pattern", pattern.clone())
+            }
+            Condition::OneOf { values } => Message::new("condition-one-of")
+                .with_arg("values", MessageArg::list("list-separator", values.clone())),
+            Condition::OutsideRange {
+                low_field,
+                high_field,
+            } => Message::new("condition-outside-range")
+                .with("low", low_field.clone())
+                .with("high", high_field.clone()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Highlight {
+    Red,
+    Orange,
+    Green,
+    Blue,
+}
+
+impl Highlight {
+    pub const ALL: [Highlight; 4] = [
+        Highlight::Red,
+        Highlight::Orange,
+        Highlight::Green,
+        Highlight::Blue,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Highlight::Red => "red",
+            Highlight::Orange => "orange",
+            Highlight::Green => "green",
+            Highlight::Blue => "blue",
+        }
+    }
+
+    /// The color's name in the UI.
+    pub fn name(&self) -> Message {
+        Message::new(match self {
+            Highlight::Red => "color-red",
+            Highlight::Orange => "color-orange",
+            Highlight::Green => "color-green",
+            Highlight::Blue => "color-blue",
+        })
+    }
+
+    pub fn from_label(label: &str) -> Option<Highlight> {
+        Highlight::ALL
+            .iter()
+            .find(|highlight| highlight.label() == label)
+            .copied()
+    }
+}
+
+/// How a matching cell looks, as classes defined in style.css.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct CellStyle {
+    pub color: Option<Highlight>,
+    pub background: Option<Highlight>,
+    pub bold: bool,
+}
+
+impl CellStyle {
+    pub fn is_empty(&self) -> bool {
+        self.color.is_none() && self.background.is_none() && !self.bold
+    }
+
+    /// What the style does, e.g. "red text", one message per effect.
+    pub fn describe(&self) -> Vec<Message> {
+        let mut effects = Vec::new();
+        if let Some(color) = self.color {
+            effects.push(
+                Message::new("style-color").with_arg("color", MessageArg::Message(color.name())),
+            );
+        }
+        if let Some(background) = self.background {
+            effects.push(
+                Message::new("style-fill")
+                    .with_arg("color", MessageArg::Message(background.name())),
+            );
+        }
+        if self.bold {
+            effects.push(Message::new("style-bold"));
+        }
+        effects
+    }
+
+    pub fn classes(&self) -> Vec<String> {
+        let mut classes = Vec::new();
+        if let Some(color) = self.color {
+            classes.push(format!("fmt-text-{}", color.label()));
+        }
+        if let Some(background) = self.background {
+            classes.push(format!("fmt-bg-{}", background.label()));
+        }
+        if self.bold {
+            classes.push(String::from("fmt-bold"));
+        }
+        classes
+    }
+}
+
+/// How a matching cell's value is written.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ValueFormat {
+    #[default]
+    AsIs,
+    /// ISO 8601 dates as DD-MMM-YYYY, e.g. 2023-04-05 as 05-APR-2023, see `format_date`.
+    Date,
+    /// Numbers rounded to this many decimals, with the locale's separators.
+    Decimals(usize),
+}
+
+impl ValueFormat {
+    pub fn describe(&self) -> Option<Message> {
+        match self {
+            ValueFormat::AsIs => None,
+            ValueFormat::Date => Some(Message::new("format-date")),
+            ValueFormat::Decimals(decimals) => {
+                Some(Message::new("format-decimals").with("decimals", *decimals))
+            }
+        }
+    }
+
+    /// The value written in this format, `None` when it does not apply to the value.
+    pub fn apply(&self, locale: Locale, value: &str) -> Option<String> {
+        match self {
+            ValueFormat::AsIs => None,
+            ValueFormat::Date => format_date(value),
+            ValueFormat::Decimals(decimals) => value
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .and_then(|number| locale.format_number(&format!("{number:.decimals$}"))),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct FormatRule {
+    pub column: String,
+    pub condition: Condition,
+    #[serde(default)]
+    pub style: CellStyle,
+    #[serde(default)]
+    pub format: ValueFormat,
+}
+
+impl FormatRule {
+    pub fn validate(&self) -> Result<(), FormattingError> {
+        if self.column.is_empty() {
+            return Err(FormattingError::RuleError(String::from(
+                "formatting error: the rule has no column.",
+            )));
+        }
+        if self.style.is_empty() && self.format == ValueFormat::AsIs {
+            return Err(FormattingError::RuleError(String::from(
+                "formatting error: the rule neither styles nor formats the value.",
+            )));
+        }
+        match &self.condition {
+            Condition::Matches { pattern } => {
+                Pattern::new(pattern, "").map_err(|_| {
+                    FormattingError::PatternError(format!(
+                        "formatting error: /{pattern}/ is not a valid regular expression."
+                    ))
+                })?;
+            }
+            Condition::OutsideRange {
+                low_field,
+                high_field,
+            } if low_field.is_empty() && high_field.is_empty() => {
+                return Err(FormattingError::RuleError(String::from(
+                    "formatting error: the range needs a low or a high column.",
+                )));
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    /// E.g. "AETOXGR ≥ 3 → red text, bold".
+    pub fn describe(&self) -> Message {
+        let mut effects = self.style.describe();
+        effects.extend(self.format.describe());
+        Message::new("format-rule")
+            .with("column", self.column.clone())
+            .with_arg("condition", MessageArg::Message(self.condition.describe()))
+            .with_arg(
+                "effects",
+                MessageArg::List {
+                    separator: "list-separator",
+                    items: effects.into_iter().map(MessageArg::Message).collect(),
+                },
+            )
+    }
+}
+
+/// A rule with its pattern compiled once for all rows.
+struct CompiledRule<'a> {
+    rule: &'a FormatRule,
+    pattern: Option<Pattern>,
+}
+
+impl CompiledRule<'_> {
+    fn matches(&self, row: &HashMap<String, String>) -> bool {
+        let value = match row.get(&self.rule.column) {
+            Some(value) => value,
+            None => return false,
+        };
+        let number = || value.trim().parse::<f64>().ok();
+        match &self.rule.condition {
+            Condition::Always => true,
+            Condition::Threshold {
+                comparison,
+                threshold,
+            } => number().is_some_and(|number| comparison.holds(number, *threshold)),
+            Condition::Matches { .. } => self
+                .pattern
+                .as_ref()
+                .is_some_and(|pattern| pattern.test(value)),
+            Condition::OneOf { values } => values
+                .iter()
+                .any(|candidate| candidate.trim().eq_ignore_ascii_case(value.trim())),
+            Condition::OutsideRange {
+                low_field,
+                high_field,
+            } => {
+                let bound = |field: &String| {
+                    row.get(field)
+                        .and_then(|bound| bound.trim().parse::<f64>().ok())
+                };
+                number().is_some_and(|number| {
+                    bound(low_field).is_some_and(|low| number < low)
+                        || bound(high_field).is_some_and(|high| number > high)
+                })
+            }
+        }
+    }
+}
+
+/// Adds the `FORMATTED_TEXT_PREFIX` and `CELL_CLASS_PREFIX` keys of the cells matched by `rules`.
+///
+/// Rules apply in order, so a later rule's format replaces an earlier one's and their classes
+/// add up.
+pub fn apply_rules(rules: &[FormatRule], locale: Locale, rows: &mut [HashMap<String, String>]) {
+    if rules.is_empty() {
+        return;
+    }
+    let compiled: Vec<CompiledRule> = rules
+        .iter()
+        .map(|rule| CompiledRule {
+            rule,
+            pattern: match &rule.condition {
+                Condition::Matches { pattern } => Pattern::new(pattern, "").ok(),
+                _ => None,
+            },
+        })
+        .collect();
+    for row in rows.iter_mut() {
+        let mut texts = HashMap::<&String, String>::new();
+        let mut classes = HashMap::<&String, Vec<String>>::new();
+        for compiled_rule in compiled.iter().filter(|compiled| compiled.matches(row)) {
+            let column = &compiled_rule.rule.column;
+            if let Some(text) = row
+                .get(column)
+                .and_then(|value| compiled_rule.rule.format.apply(locale, value))
+            {
+                texts.insert(column, text);
+            }
+            classes
+                .entry(column)
+                .or_default()
+                .extend(compiled_rule.rule.style.classes());
+        }
+        let texts: Vec<(String, String)> = texts
+            .into_iter()
+            .map(|(column, text)| (format!("{FORMATTED_TEXT_PREFIX}{column}"), text))
+            .collect();
+        let classes: Vec<(String, String)> = classes
+            .into_iter()
+            .filter(|(_, classes)| !classes.is_empty())
+            .map(|(column, classes)| (format!("{CELL_CLASS_PREFIX}{column}"), classes.join(" ")))
+            .collect();
+        row.extend(texts);
+        row.extend(classes);
+    }
+}
+
+/// The value of a cell as shown, its formatted text when a rule set one.
+pub fn display_value<'a>(row: &'a HashMap<String, String>, column: &str) -> Option<&'a String> {
+    row.get(&format!("{FORMATTED_TEXT_PREFIX}{column}"))
+        .or_else(|| row.get(column))
+}
+
+/// The value of a cell as shown to a user of `locale`: its formatted text when a rule set one,
+/// and otherwise written the locale's way when `column_types` has the column's type, see
+/// `i18n::column_type`.
+pub fn localized_value(
+    row: &HashMap<String, String>,
+    column: &str,
+    column_types: &HashMap<String, String>,
+    locale: Locale,
+) -> Option<String> {
+    if let Some(text) = row.get(&format!("{FORMATTED_TEXT_PREFIX}{column}")) {
+        return Some(text.clone());
+    }
+    let value = row.get(column)?;
+    Some(
+        column_types
+            .get(column)
+            .and_then(|column_type| locale.format_cell(column_type, value))
+            .unwrap_or_else(|| value.clone()),
+    )
+}
+
+/// The CSS classes the rules gave a cell, empty without any.
+pub fn cell_classes<'a>(row: &'a HashMap<String, String>, column: &str) -> &'a str {
+    row.get(&format!("{CELL_CLASS_PREFIX}{column}"))
+        .map(String::as_str)
+        .unwrap_or("")
+}
+
+/// An ISO 8601 date or date time as DD-MMM-YYYY, with UN for an unknown day and UNK for an
+/// unknown month, e.g. 2023-04 as UN-APR-2023. Dates without a year are left alone.
+pub fn format_date(value: &str) -> Option<String> {
+    let date = IsoDate::parse(value)?;
+    let year = date.year?;
+    let day = match date.day {
+        Some(day) => format!("{day:02}"),
+        None => String::from("UN"),
+    };
+    let month = date
+        .month
+        .and_then(|month| MONTHS.get(month as usize - 1))
+        .unwrap_or(&"UNK");
+    Some(format!("{day}-{month}-{year:04}"))
+}
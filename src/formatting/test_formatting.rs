@@ -0,0 +1,171 @@
+use super::{
+    apply_rules, cell_classes, display_value, format_date, CellStyle, Comparison, Condition,
+    FormatRule, Highlight, ValueFormat,
+};
+use crate::i18n::Locale;
+use std::collections::HashMap;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn row(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(key, value)| (String::from(*key), String::from(*value)))
+        .collect()
+}
+
+fn rule(column: &str, condition: Condition, style: CellStyle, format: ValueFormat) -> FormatRule {
+    FormatRule {
+        column: String::from(column),
+        condition,
+        style,
+        format,
+    }
+}
+
+fn red() -> CellStyle {
+    CellStyle {
+        color: Some(Highlight::Red),
+        ..CellStyle::default()
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_format_date() {
+    assert_eq!(format_date("2023-04-05"), Some(String::from("05-APR-2023")));
+    assert_eq!(
+        format_date("2021-12-31T08:15:00"),
+        Some(String::from("31-DEC-2021"))
+    );
+    assert_eq!(format_date("2023-04"), Some(String::from("UN-APR-2023")));
+    assert_eq!(format_date("2023---15"), Some(String::from("15-UNK-2023")));
+    assert_eq!(format_date("--04-15"), None);
+    assert_eq!(format_date("2023-13-01"), None);
+    assert_eq!(format_date("not a date"), None);
+}
+
+#[wasm_bindgen_test]
+fn test_apply_rules_outside_normal_range() {
+    let mut rows = vec![
+        row(&[("LBORRES", "7.9"), ("LBORNRLO", "3.5"), ("LBORNRHI", "5.1")]),
+        row(&[("LBORRES", "4.2"), ("LBORNRLO", "3.5"), ("LBORNRHI", "5.1")]),
+        row(&[("LBORRES", "2.0"), ("LBORNRLO", "3.5"), ("LBORNRHI", "")]),
+    ];
+    let rules = vec![rule(
+        "LBORRES",
+        Condition::OutsideRange {
+            low_field: String::from("LBORNRLO"),
+            high_field: String::from("LBORNRHI"),
+        },
+        red(),
+        ValueFormat::AsIs,
+    )];
+    apply_rules(&rules, Locale::EnUs, &mut rows);
+    assert_eq!(cell_classes(&rows[0], "LBORRES"), "fmt-text-red");
+    assert_eq!(cell_classes(&rows[1], "LBORRES"), "");
+    assert_eq!(cell_classes(&rows[2], "LBORRES"), "fmt-text-red");
+}
+
+#[wasm_bindgen_test]
+fn test_apply_rules_combines_matching_rules() {
+    let mut rows = vec![
+        row(&[
+            ("AETOXGR", "3"),
+            ("AESTDTC", "2023-04-05"),
+            ("AETERM", "Rash"),
+        ]),
+        row(&[("AETOXGR", "1"), ("AESTDTC", "2023-04"), ("AETERM", "rash")]),
+    ];
+    let bold = CellStyle {
+        bold: true,
+        ..CellStyle::default()
+    };
+    let rules = vec![
+        rule(
+            "AETOXGR",
+            Condition::Threshold {
+                comparison: Comparison::GreaterOrEqual,
+                threshold: 3.0,
+            },
+            bold,
+            ValueFormat::AsIs,
+        ),
+        rule(
+            "AESTDTC",
+            Condition::Always,
+            CellStyle::default(),
+            ValueFormat::Date,
+        ),
+        rule(
+            "AETERM",
+            Condition::OneOf {
+                values: vec![String::from("RASH")],
+            },
+            red(),
+            ValueFormat::AsIs,
+        ),
+        rule(
+            "AETERM",
+            Condition::Matches {
+                pattern: String::from("^R"),
+            },
+            CellStyle {
+                background: Some(Highlight::Orange),
+                ..CellStyle::default()
+            },
+            ValueFormat::AsIs,
+        ),
+    ];
+    apply_rules(&rules, Locale::EnUs, &mut rows);
+    assert_eq!(cell_classes(&rows[0], "AETOXGR"), "fmt-bold");
+    assert_eq!(cell_classes(&rows[1], "AETOXGR"), "");
+    assert_eq!(
+        display_value(&rows[0], "AESTDTC"),
+        Some(&String::from("05-APR-2023"))
+    );
+    assert_eq!(
+        display_value(&rows[1], "AESTDTC"),
+        Some(&String::from("UN-APR-2023"))
+    );
+    assert_eq!(
+        cell_classes(&rows[0], "AETERM"),
+        "fmt-text-red fmt-bg-orange"
+    );
+    assert_eq!(cell_classes(&rows[1], "AETERM"), "fmt-text-red");
+    // The raw values are kept for sorting and filtering.
+    assert_eq!(rows[0]["AESTDTC"], "2023-04-05");
+}
+
+#[wasm_bindgen_test]
+fn test_validate_rejects_bad_rules() {
+    let bad_pattern = rule(
+        "AETERM",
+        Condition::Matches {
+            pattern: String::from("(unclosed"),
+        },
+        red(),
+        ValueFormat::AsIs,
+    );
+    assert!(bad_pattern.validate().is_err());
+    let no_effect = rule(
+        "AETERM",
+        Condition::Always,
+        CellStyle::default(),
+        ValueFormat::AsIs,
+    );
+    assert!(no_effect.validate().is_err());
+    let decimals = rule(
+        "LBSTRESN",
+        Condition::Always,
+        CellStyle::default(),
+        ValueFormat::Decimals(1),
+    );
+    assert!(decimals.validate().is_ok());
+    assert_eq!(
+        ValueFormat::Decimals(1).apply(Locale::EnUs, "3.14159"),
+        Some(String::from("3.1"))
+    );
+    assert_eq!(
+        ValueFormat::Decimals(2).apply(Locale::DeDe, "12345.678"),
+        Some(String::from("12.345,68"))
+    );
+}
@@ -0,0 +1,290 @@
+#[cfg(test)]
+mod test_grid;
+
+use crate::codelists::FILTER_TEXT_PREFIX;
+use crate::pivot::compare_values;
+use crate::views::{ColumnSort, GridState};
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+
+use serde_json::{json, Map, Value};
+
+pub const DEFAULT_COLUMN_WIDTH: f64 = 150.0;
+pub const MIN_COLUMN_WIDTH: f64 = 40.0;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct GridColumn {
+    pub field: String,
+    pub width: f64,
+    /// Pinned columns stay on the left while the grid scrolls horizontally.
+    pub pinned: bool,
+    pub hidden: bool,
+}
+
+impl GridColumn {
+    pub fn new(field: &str) -> GridColumn {
+        GridColumn {
+            field: field.to_owned(),
+            width: DEFAULT_COLUMN_WIDTH,
+            pinned: false,
+            hidden: false,
+        }
+    }
+}
+
+/// Indices of the rows passing every filter, in sort order.
+///
+/// A filter keeps the rows whose column contains its text, ignoring case. Rows that compare
+/// equal keep their original order.
+pub fn visible_rows(
+    rows: &[HashMap<String, String>],
+    filters: &BTreeMap<String, String>,
+    sort: &[ColumnSort],
+) -> Vec<usize> {
+    let needles: Vec<(&String, String)> = filters
+        .iter()
+        .filter(|(_, needle)| !needle.trim().is_empty())
+        .map(|(column, needle)| (column, needle.trim().to_lowercase()))
+        .collect();
+    let mut indices: Vec<usize> = rows
+        .iter()
+        .enumerate()
+        .filter(|(_, row)| {
+            needles.iter().all(|(column, needle)| {
+                match row
+                    .get(&format!("{FILTER_TEXT_PREFIX}{column}"))
+                    .or_else(|| row.get(*column))
+                {
+                    Some(value) => value.to_lowercase().contains(needle),
+                    None => false,
+                }
+            })
+        })
+        .map(|(index, _)| index)
+        .collect();
+    if !sort.is_empty() {
+        indices.sort_by(|left, right| compare_rows(&rows[*left], &rows[*right], sort));
+    }
+    indices
+}
+
+fn compare_rows(
+    left: &HashMap<String, String>,
+    right: &HashMap<String, String>,
+    sort: &[ColumnSort],
+) -> Ordering {
+    for column_sort in sort {
+        let empty = String::new();
+        let ordering = compare_values(
+            left.get(&column_sort.column).unwrap_or(&empty),
+            right.get(&column_sort.column).unwrap_or(&empty),
+        );
+        let ordering = if column_sort.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+/// The rows to render for a viewport, with `overscan` extra rows above and below.
+pub fn row_window(
+    scroll_top: f64,
+    viewport_height: f64,
+    row_height: f64,
+    row_count: usize,
+    overscan: usize,
+) -> Range<usize> {
+    if row_count == 0 || row_height <= 0.0 {
+        return 0..0;
+    }
+    let first = (scroll_top.max(0.0) / row_height).floor() as usize;
+    let last = ((scroll_top.max(0.0) + viewport_height.max(0.0)) / row_height).ceil() as usize;
+    first.saturating_sub(overscan).min(row_count)..(last + overscan).min(row_count)
+}
+
+/// Shown columns, pinned columns first, each with its left offset.
+pub fn column_layout(columns: &[GridColumn]) -> Vec<(f64, &GridColumn)> {
+    let mut left = 0.0;
+    let pinned = columns.iter().filter(|column| column.pinned);
+    let unpinned = columns.iter().filter(|column| !column.pinned);
+    pinned
+        .chain(unpinned)
+        .filter(|column| !column.hidden)
+        .map(|column| {
+            let offset = left;
+            left += column.width;
+            (offset, column)
+        })
+        .collect()
+}
+
+/// Whether the unpinned column at `offset` is inside the viewport.
+///
+/// Pinned columns cover the left `pinned_width` of the viewport, so a scrolled column is only
+/// visible once it clears them.
+pub fn column_in_view(
+    offset: f64,
+    width: f64,
+    pinned_width: f64,
+    scroll_left: f64,
+    viewport_width: f64,
+) -> bool {
+    let view_start = scroll_left + pinned_width;
+    let view_end = scroll_left + viewport_width;
+    offset + width > view_start && offset < view_end
+}
+
+/// Moves the column named `field` in front of `before`, or to the end when `before` is `None`.
+pub fn move_column(columns: &mut Vec<GridColumn>, field: &str, before: Option<&str>) {
+    if before == Some(field) {
+        return;
+    }
+    let from = match columns.iter().position(|column| column.field == field) {
+        Some(from) => from,
+        None => return,
+    };
+    let column = columns.remove(from);
+    let to = before
+        .and_then(|before| columns.iter().position(|column| column.field == before))
+        .unwrap_or(columns.len());
+    columns.insert(to, column);
+}
+
+/// Moves the column named `field` one place left or right among the shown columns, for moving
+/// columns with the keyboard. Pinned and unpinned columns only trade places among themselves.
+pub fn step_column(columns: &mut Vec<GridColumn>, field: &str, right: bool) {
+    let order: Vec<String> = {
+        let layout = column_layout(columns);
+        let pinned = match layout.iter().find(|(_, column)| column.field == field) {
+            Some((_, column)) => column.pinned,
+            None => return,
+        };
+        layout
+            .iter()
+            .filter(|(_, column)| column.pinned == pinned)
+            .map(|(_, column)| column.field.clone())
+            .collect()
+    };
+    let index = match order.iter().position(|shown| shown == field) {
+        Some(index) => index,
+        None => return,
+    };
+    let before = if right {
+        if index + 1 >= order.len() {
+            return;
+        }
+        order.get(index + 2)
+    } else {
+        if index == 0 {
+            return;
+        }
+        order.get(index - 1)
+    };
+    // The end of the columns is the end of either group in the layout.
+    move_column(columns, field, before.map(String::as_str));
+}
+
+/// The `aria-sort` of the column named `field`.
+pub fn aria_sort(sort: &[ColumnSort], field: &str) -> &'static str {
+    match sort.iter().find(|column_sort| column_sort.column == field) {
+        Some(column_sort) if column_sort.descending => "descending",
+        Some(_) => "ascending",
+        None => "none",
+    }
+}
+
+/// Cycles a header click through ascending, descending and unsorted.
+///
+/// With `add` the column joins the existing sort, otherwise it replaces it.
+pub fn toggle_sort(sort: &[ColumnSort], field: &str, add: bool) -> Vec<ColumnSort> {
+    let current = sort.iter().find(|column_sort| column_sort.column == field);
+    let next = match current {
+        None => Some(false),
+        Some(column_sort) if !column_sort.descending => Some(true),
+        Some(_) => None,
+    };
+    let mut sort: Vec<ColumnSort> = if add {
+        sort.iter()
+            .filter(|column_sort| column_sort.column != field)
+            .cloned()
+            .collect()
+    } else {
+        Vec::new()
+    };
+    if let Some(descending) = next {
+        sort.push(ColumnSort {
+            column: field.to_owned(),
+            descending,
+        });
+    }
+    sort
+}
+
+/// Reads the text filters of an AG Grid filter model.
+pub fn filters_from_model(filter_model: &Value) -> BTreeMap<String, String> {
+    filter_model
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(column, filter)| {
+            filter
+                .get("filter")
+                .and_then(Value::as_str)
+                .map(|text| (column.clone(), text.to_owned()))
+        })
+        .collect()
+}
+
+/// Writes text filters as an AG Grid filter model, so views work with either grid.
+pub fn filters_to_model(filters: &BTreeMap<String, String>) -> Value {
+    let model: Map<String, Value> = filters
+        .iter()
+        .filter(|(_, text)| !text.trim().is_empty())
+        .map(|(column, text)| {
+            (
+                column.clone(),
+                json!({"filterType": "text", "type": "contains", "filter": text}),
+            )
+        })
+        .collect();
+    Value::Object(model)
+}
+
+/// Applies the column order and visibility of a view to `columns`.
+pub fn apply_grid_state(columns: &mut [GridColumn], state: &GridState) {
+    columns.sort_by_key(|column| {
+        state
+            .column_order
+            .iter()
+            .position(|field| field == &column.field)
+            .unwrap_or(usize::MAX)
+    });
+    for column in columns.iter_mut() {
+        column.hidden = state.hidden_columns.contains(&column.field);
+    }
+}
+
+/// The grid's filters, sorts and layout as a view stores them.
+pub fn grid_state(
+    columns: &[GridColumn],
+    filters: &BTreeMap<String, String>,
+    sort: &[ColumnSort],
+) -> GridState {
+    GridState {
+        filter_model: filters_to_model(filters),
+        sort: sort.to_vec(),
+        hidden_columns: columns
+            .iter()
+            .filter(|column| column.hidden)
+            .map(|column| column.field.clone())
+            .collect(),
+        column_order: columns.iter().map(|column| column.field.clone()).collect(),
+    }
+}
@@ -0,0 +1,201 @@
+use super::{
+    apply_grid_state, aria_sort, column_in_view, column_layout, filters_from_model,
+    filters_to_model, grid_state, move_column, row_window, step_column, toggle_sort, visible_rows,
+    GridColumn,
+};
+use crate::views::{ColumnSort, GridState};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn row(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+fn labs() -> Vec<HashMap<String, String>> {
+    vec![
+        row(&[
+            ("subject", "1023-004"),
+            ("lbtest", "ALT"),
+            ("lbstresn", "41"),
+        ]),
+        row(&[
+            ("subject", "1023-005"),
+            ("lbtest", "AST"),
+            ("lbstresn", "9"),
+        ]),
+        row(&[
+            ("subject", "1023-006"),
+            ("lbtest", "ALT"),
+            ("lbstresn", "120"),
+        ]),
+    ]
+}
+
+fn sort(column: &str, descending: bool) -> ColumnSort {
+    ColumnSort {
+        column: String::from(column),
+        descending,
+    }
+}
+
+fn columns(fields: &[&str]) -> Vec<GridColumn> {
+    fields.iter().map(|field| GridColumn::new(field)).collect()
+}
+
+fn fields(columns: &[GridColumn]) -> Vec<&str> {
+    columns.iter().map(|column| column.field.as_str()).collect()
+}
+
+#[wasm_bindgen_test]
+fn test_visible_rows_filters_ignoring_case() {
+    let filters = BTreeMap::from([(String::from("lbtest"), String::from("alt"))]);
+    assert_eq!(visible_rows(&labs(), &filters, &[]), vec![0, 2]);
+}
+
+#[wasm_bindgen_test]
+fn test_visible_rows_filters_decoded_codes() {
+    let rows = vec![
+        row(&[("sex", "1"), ("__filter.sex", "1 \u{2014} Male")]),
+        row(&[("sex", "2"), ("__filter.sex", "2 \u{2014} Female")]),
+    ];
+    let filter = |needle: &str| BTreeMap::from([(String::from("sex"), String::from(needle))]);
+    assert_eq!(visible_rows(&rows, &filter("female"), &[]), vec![1]);
+    assert_eq!(visible_rows(&rows, &filter("1"), &[]), vec![0]);
+}
+
+#[wasm_bindgen_test]
+fn test_visible_rows_sorts_numbers_numerically() {
+    let rows = labs();
+    assert_eq!(
+        visible_rows(&rows, &BTreeMap::new(), &[sort("lbstresn", false)]),
+        vec![1, 0, 2]
+    );
+    assert_eq!(
+        visible_rows(
+            &rows,
+            &BTreeMap::new(),
+            &[sort("lbtest", false), sort("lbstresn", true)]
+        ),
+        vec![2, 0, 1]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_row_window_adds_overscan_and_clamps() {
+    assert_eq!(row_window(0.0, 300.0, 30.0, 1000, 5), 0..15);
+    assert_eq!(row_window(3000.0, 300.0, 30.0, 1000, 5), 95..115);
+    assert_eq!(row_window(29_900.0, 300.0, 30.0, 1000, 5), 991..1000);
+    assert_eq!(row_window(0.0, 300.0, 30.0, 0, 5), 0..0);
+}
+
+#[wasm_bindgen_test]
+fn test_column_layout_puts_pinned_columns_first() {
+    let mut grid_columns = columns(&["lbtest", "lbstresn", "subject"]);
+    grid_columns[2].pinned = true;
+    grid_columns[1].hidden = true;
+    let layout: Vec<(f64, &str)> = column_layout(&grid_columns)
+        .into_iter()
+        .map(|(offset, column)| (offset, column.field.as_str()))
+        .collect();
+    assert_eq!(layout, vec![(0.0, "subject"), (150.0, "lbtest")]);
+}
+
+#[wasm_bindgen_test]
+fn test_column_in_view_accounts_for_pinned_columns() {
+    assert!(column_in_view(300.0, 150.0, 150.0, 0.0, 500.0));
+    assert!(!column_in_view(600.0, 150.0, 150.0, 0.0, 500.0));
+    assert!(!column_in_view(150.0, 150.0, 150.0, 200.0, 500.0));
+    assert!(column_in_view(450.0, 150.0, 150.0, 200.0, 500.0));
+}
+
+#[wasm_bindgen_test]
+fn test_move_column() {
+    let mut grid_columns = columns(&["subject", "lbtest", "lbstresn"]);
+    move_column(&mut grid_columns, "lbstresn", Some("subject"));
+    assert_eq!(fields(&grid_columns), vec!["lbstresn", "subject", "lbtest"]);
+    move_column(&mut grid_columns, "lbstresn", None);
+    assert_eq!(fields(&grid_columns), vec!["subject", "lbtest", "lbstresn"]);
+}
+
+#[wasm_bindgen_test]
+fn test_step_column_moves_within_its_group() {
+    let mut grid_columns = columns(&["lbtest", "subject", "lbstresn", "lbdtc"]);
+    grid_columns[1].pinned = true;
+    grid_columns[2].hidden = true;
+    step_column(&mut grid_columns, "lbdtc", false);
+    assert_eq!(
+        fields(&grid_columns),
+        vec!["lbdtc", "lbtest", "subject", "lbstresn"]
+    );
+    // Already first of the unpinned columns, the pinned subject stays in front.
+    step_column(&mut grid_columns, "lbdtc", false);
+    assert_eq!(
+        fields(&grid_columns),
+        vec!["lbdtc", "lbtest", "subject", "lbstresn"]
+    );
+    step_column(&mut grid_columns, "lbdtc", true);
+    let shown: Vec<&str> = column_layout(&grid_columns)
+        .iter()
+        .map(|(_, column)| column.field.as_str())
+        .collect();
+    assert_eq!(shown, vec!["subject", "lbtest", "lbdtc"]);
+    // The only pinned column has nowhere to move.
+    step_column(&mut grid_columns, "subject", true);
+    assert_eq!(
+        fields(&grid_columns),
+        vec!["lbtest", "subject", "lbstresn", "lbdtc"]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_aria_sort() {
+    let sorted = vec![sort("lbtest", true), sort("subject", false)];
+    assert_eq!(aria_sort(&sorted, "lbtest"), "descending");
+    assert_eq!(aria_sort(&sorted, "subject"), "ascending");
+    assert_eq!(aria_sort(&sorted, "lbdtc"), "none");
+}
+
+#[wasm_bindgen_test]
+fn test_toggle_sort_cycles_directions() {
+    let ascending = toggle_sort(&[], "lbtest", false);
+    assert_eq!(ascending, vec![sort("lbtest", false)]);
+    let descending = toggle_sort(&ascending, "lbtest", false);
+    assert_eq!(descending, vec![sort("lbtest", true)]);
+    assert!(toggle_sort(&descending, "lbtest", false).is_empty());
+    assert_eq!(
+        toggle_sort(&descending, "subject", true),
+        vec![sort("lbtest", true), sort("subject", false)]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_filters_round_trip_through_the_filter_model() {
+    let filters = BTreeMap::from([(String::from("lbtest"), String::from("ALT"))]);
+    let model = filters_to_model(&filters);
+    assert_eq!(
+        model,
+        json!({"lbtest": {"filterType": "text", "type": "contains", "filter": "ALT"}})
+    );
+    assert_eq!(filters_from_model(&model), filters);
+}
+
+#[wasm_bindgen_test]
+fn test_apply_grid_state_restores_order_and_visibility() {
+    let mut grid_columns = columns(&["subject", "lbtest", "lbstresn", "visit"]);
+    let state = GridState {
+        hidden_columns: vec![String::from("visit")],
+        column_order: vec![String::from("lbstresn"), String::from("subject")],
+        ..GridState::default()
+    };
+    apply_grid_state(&mut grid_columns, &state);
+    assert_eq!(
+        fields(&grid_columns),
+        vec!["lbstresn", "subject", "lbtest", "visit"]
+    );
+    let restored = grid_state(&grid_columns, &BTreeMap::new(), &[]);
+    assert_eq!(restored.hidden_columns, vec![String::from("visit")]);
+}
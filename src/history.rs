@@ -0,0 +1,242 @@
+#[cfg(test)]
+mod test_history;
+
+use crate::i18n::{Message, MessageArg};
+use crate::views::{ColumnSort, GridState};
+
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use serde_json::{Map, Value};
+
+/// Steps kept for undo, the oldest are dropped past this.
+const HISTORY_LENGTH: usize = 100;
+
+/// A reversible change: the state before and after it.
+#[derive(PartialEq, Debug)]
+pub struct HistoryStep<T> {
+    /// What the step did, e.g. "Filter AETERM".
+    pub description: Message,
+    pub before: Rc<T>,
+    pub after: Rc<T>,
+}
+
+impl<T> Clone for HistoryStep<T> {
+    fn clone(&self) -> Self {
+        HistoryStep {
+            description: self.description.clone(),
+            before: self.before.clone(),
+            after: self.after.clone(),
+        }
+    }
+}
+
+/// The steps that can be undone and those undone since the last change, which can be redone.
+#[derive(PartialEq, Debug)]
+pub struct History<T> {
+    past: Vec<HistoryStep<T>>,
+    future: Vec<HistoryStep<T>>,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        History {
+            past: Vec::new(),
+            future: Vec::new(),
+        }
+    }
+}
+
+impl<T> Clone for History<T> {
+    fn clone(&self) -> Self {
+        History {
+            past: self.past.clone(),
+            future: self.future.clone(),
+        }
+    }
+}
+
+impl<T> History<T> {
+    /// Records a change, which drops the steps undone before it. A change described as the last
+    /// step extends that step instead, so typing a filter is undone in one go.
+    pub fn record(&mut self, description: Message, before: Rc<T>, after: Rc<T>) {
+        self.future.clear();
+        if let Some(last) = self.past.last_mut() {
+            if last.description == description {
+                last.after = after;
+                return;
+            }
+        }
+        if self.past.len() >= HISTORY_LENGTH {
+            self.past.remove(0);
+        }
+        self.past.push(HistoryStep {
+            description,
+            before,
+            after,
+        });
+    }
+
+    /// Undoes up to `steps` steps from `current`, returning the state before the earliest of
+    /// them. Redoing the first of them returns to `current`.
+    pub fn undo(&mut self, steps: usize, current: &Rc<T>) -> Option<Rc<T>> {
+        let mut state = None;
+        for _ in 0..steps {
+            let Some(mut step) = self.past.pop() else {
+                break;
+            };
+            if state.is_none() {
+                step.after = current.clone();
+            }
+            state = Some(step.before.clone());
+            self.future.push(step);
+        }
+        state
+    }
+
+    /// Redoes up to `steps` undone steps, returning the state after the last of them.
+    pub fn redo(&mut self, steps: usize) -> Option<Rc<T>> {
+        let mut state = None;
+        for _ in 0..steps {
+            let Some(step) = self.future.pop() else {
+                break;
+            };
+            state = Some(step.after.clone());
+            self.past.push(step);
+        }
+        state
+    }
+
+    /// The steps that can be undone, the last one first.
+    pub fn undoable(&self) -> impl Iterator<Item = &HistoryStep<T>> {
+        self.past.iter().rev()
+    }
+
+    /// The steps that can be redone, the next one first.
+    pub fn redoable(&self) -> impl Iterator<Item = &HistoryStep<T>> {
+        self.future.iter().rev()
+    }
+}
+
+/// Describes what changed between two states of a grid, `None` when nothing a user would call a
+/// change did, e.g. when the grid only reports the column order of columns it was not given.
+pub fn describe_grid_change(before: &GridState, after: &GridState) -> Option<Message> {
+    let mut changes: Vec<Message> = [
+        describe_filter_change(&before.filter_model, &after.filter_model),
+        describe_sort_change(&before.sort, &after.sort),
+        describe_visibility_change(&before.hidden_columns, &after.hidden_columns),
+        describe_order_change(&before.column_order, &after.column_order),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    match changes.len() {
+        0 => None,
+        1 => changes.pop(),
+        _ => Some(Message::new("history-changes").with_arg(
+            "changes",
+            MessageArg::List {
+                separator: "change-separator",
+                items: changes.into_iter().map(MessageArg::Message).collect(),
+            },
+        )),
+    }
+}
+
+fn filters(filter_model: &Value) -> Map<String, Value> {
+    filter_model.as_object().cloned().unwrap_or_default()
+}
+
+fn describe_filter_change(before: &Value, after: &Value) -> Option<Message> {
+    let before = filters(before);
+    let after = filters(after);
+    if after.is_empty() && !before.is_empty() {
+        return Some(Message::new("history-clear-filters"));
+    }
+    let set: Vec<&String> = after
+        .iter()
+        .filter(|(column, filter)| before.get(*column) != Some(filter))
+        .map(|(column, _)| column)
+        .collect();
+    let cleared: Vec<&String> = before
+        .keys()
+        .filter(|column| !after.contains_key(*column))
+        .collect();
+    match (set.as_slice(), cleared.as_slice()) {
+        ([], []) => None,
+        ([column], []) => Some(Message::new("history-filter").with("column", (*column).clone())),
+        ([], [column]) => {
+            Some(Message::new("history-clear-filter").with("column", (*column).clone()))
+        }
+        _ => Some(Message::new("history-change-filters").with_arg("columns", join(set, cleared))),
+    }
+}
+
+fn describe_sort_change(before: &[ColumnSort], after: &[ColumnSort]) -> Option<Message> {
+    if before == after {
+        return None;
+    }
+    if after.is_empty() {
+        return Some(Message::new("history-clear-sort"));
+    }
+    let columns: Vec<MessageArg> = after
+        .iter()
+        .map(|column_sort| {
+            let id = match column_sort.descending {
+                true => "history-sort-descending",
+                false => "history-sort-ascending",
+            };
+            MessageArg::Message(Message::new(id).with("column", column_sort.column.clone()))
+        })
+        .collect();
+    Some(Message::new("history-sort").with_arg(
+        "columns",
+        MessageArg::List {
+            separator: "list-separator",
+            items: columns,
+        },
+    ))
+}
+
+fn describe_visibility_change(before: &[String], after: &[String]) -> Option<Message> {
+    let hidden: Vec<&String> = after.iter().filter(|c| !before.contains(c)).collect();
+    let shown: Vec<&String> = before.iter().filter(|c| !after.contains(c)).collect();
+    let (id, columns) = match (hidden.as_slice(), shown.as_slice()) {
+        ([], []) => return None,
+        (hidden, []) => ("history-hide", join(hidden.to_vec(), Vec::new())),
+        ([], shown) => ("history-show", join(shown.to_vec(), Vec::new())),
+        _ => ("history-show-and-hide", join(hidden, shown)),
+    };
+    Some(Message::new(id).with_arg("columns", columns))
+}
+
+/// Only the order of the columns both states list counts, a state listing no columns keeps
+/// the grid's order.
+fn describe_order_change(before: &[String], after: &[String]) -> Option<Message> {
+    let in_before: HashSet<&String> = before.iter().collect();
+    let in_after: HashSet<&String> = after.iter().collect();
+    let before: Vec<&String> = before.iter().filter(|c| in_after.contains(c)).collect();
+    let after: Vec<&String> = after.iter().filter(|c| in_before.contains(c)).collect();
+    if before == after {
+        return None;
+    }
+    // A single moved column is the one whose removal leaves both orders the same.
+    let moved = before.iter().find(|column| {
+        let without = |order: &[&String]| -> Vec<String> {
+            order
+                .iter()
+                .filter(|c| c != column)
+                .map(|c| (*c).clone())
+                .collect()
+        };
+        without(&before) == without(&after)
+    });
+    match moved {
+        Some(column) => Some(Message::new("history-move").with("column", (*column).clone())),
+        None => Some(Message::new("history-reorder")),
+    }
+}
+
+fn join(first: Vec<&String>, second: Vec<&String>) -> MessageArg {
+    MessageArg::list("list-separator", first.into_iter().chain(second).cloned())
+}
@@ -0,0 +1,153 @@
+use super::{describe_grid_change, History};
+use crate::i18n::{Locale, Message};
+use crate::views::{ColumnSort, GridState};
+use serde_json::json;
+use std::rc::Rc;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn columns(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| String::from(*name)).collect()
+}
+
+fn grid() -> GridState {
+    GridState {
+        filter_model: json!({}),
+        sort: Vec::new(),
+        hidden_columns: Vec::new(),
+        column_order: columns(&["subject", "aeterm", "aesev"]),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_undo_and_redo() {
+    let mut history = History::default();
+    let states: Vec<Rc<u32>> = (0..4).map(Rc::new).collect();
+    history.record(Message::new("one"), states[0].clone(), states[1].clone());
+    history.record(Message::new("two"), states[1].clone(), states[2].clone());
+    history.record(Message::new("three"), states[2].clone(), states[3].clone());
+
+    assert_eq!(history.undo(2, &states[3]), Some(states[1].clone()));
+    let undoable: Vec<&str> = history.undoable().map(|step| step.description.id).collect();
+    let redoable: Vec<&str> = history.redoable().map(|step| step.description.id).collect();
+    assert_eq!(undoable, vec!["one"]);
+    assert_eq!(redoable, vec!["two", "three"]);
+
+    assert_eq!(history.redo(1), Some(states[2].clone()));
+    assert_eq!(history.redo(5), Some(states[3].clone()));
+    assert_eq!(history.redo(1), None);
+    assert_eq!(history.undo(5, &states[3]), Some(states[0].clone()));
+    assert_eq!(history.undo(1, &states[0]), None);
+}
+
+#[wasm_bindgen_test]
+fn test_record_drops_undone_steps() {
+    let mut history = History::default();
+    history.record(Message::new("one"), Rc::new(0), Rc::new(1));
+    history.record(Message::new("two"), Rc::new(1), Rc::new(2));
+    history.undo(1, &Rc::new(2));
+    history.record(Message::new("other"), Rc::new(1), Rc::new(5));
+    assert_eq!(history.redoable().count(), 0);
+    assert_eq!(history.undoable().count(), 2);
+}
+
+#[wasm_bindgen_test]
+fn test_record_extends_the_same_step() {
+    let mut history = History::default();
+    history.record(
+        Message::new("history-filter").with("column", "aeterm"),
+        Rc::new(0),
+        Rc::new(1),
+    );
+    history.record(
+        Message::new("history-filter").with("column", "aeterm"),
+        Rc::new(1),
+        Rc::new(2),
+    );
+    assert_eq!(history.undoable().count(), 1);
+    // Undoing returns to before the first keystroke, redoing to the current state.
+    assert_eq!(history.undo(1, &Rc::new(3)), Some(Rc::new(0)));
+    assert_eq!(history.redo(1), Some(Rc::new(3)));
+}
+
+#[wasm_bindgen_test]
+fn test_describe_filter_changes() {
+    let filtered = GridState {
+        filter_model: json!({"aeterm": {"filterType": "text", "type": "contains", "filter": "head"}}),
+        ..grid()
+    };
+    assert_eq!(
+        describe_grid_change(&grid(), &filtered)
+            .map(|change| change.text(Locale::EnUs))
+            .as_deref(),
+        Some("Filter aeterm")
+    );
+    assert_eq!(
+        describe_grid_change(&filtered, &grid())
+            .map(|change| change.text(Locale::EnUs))
+            .as_deref(),
+        Some("Clear filters")
+    );
+    // A grid reporting no filters is the same as a state without a filter model.
+    assert_eq!(describe_grid_change(&GridState::default(), &grid()), None);
+}
+
+#[wasm_bindgen_test]
+fn test_describe_sort_and_columns() {
+    let sorted = GridState {
+        sort: vec![ColumnSort {
+            column: String::from("aesev"),
+            descending: true,
+        }],
+        ..grid()
+    };
+    assert_eq!(
+        describe_grid_change(&grid(), &sorted)
+            .map(|change| change.text(Locale::EnUs))
+            .as_deref(),
+        Some("Sort by aesev descending")
+    );
+    assert_eq!(
+        describe_grid_change(&sorted, &grid())
+            .map(|change| change.text(Locale::EnUs))
+            .as_deref(),
+        Some("Clear sort")
+    );
+
+    let hidden = GridState {
+        hidden_columns: columns(&["aeterm"]),
+        ..grid()
+    };
+    assert_eq!(
+        describe_grid_change(&grid(), &hidden)
+            .map(|change| change.text(Locale::EnUs))
+            .as_deref(),
+        Some("Hide aeterm")
+    );
+    assert_eq!(
+        describe_grid_change(&hidden, &grid())
+            .map(|change| change.text(Locale::EnUs))
+            .as_deref(),
+        Some("Show aeterm")
+    );
+
+    let moved = GridState {
+        column_order: columns(&["subject", "aesev", "aeterm", "aeser"]),
+        ..grid()
+    };
+    assert_eq!(
+        describe_grid_change(&grid(), &moved)
+            .map(|change| change.text(Locale::EnUs))
+            .as_deref(),
+        Some("Move aeterm")
+    );
+    let reversed = GridState {
+        column_order: columns(&["aesev", "aeterm", "subject"]),
+        ..grid()
+    };
+    assert_eq!(
+        describe_grid_change(&grid(), &reversed)
+            .map(|change| change.text(Locale::EnUs))
+            .as_deref(),
+        Some("Reorder columns")
+    );
+}
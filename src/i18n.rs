@@ -0,0 +1,404 @@
+#[cfg(test)]
+mod test_i18n;
+
+use crate::cdisc::IsoDate;
+use crate::table::{ColumnValues, Table};
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use gloo_utils::window;
+use serde::{Deserialize, Serialize};
+use unic_langid::LanguageIdentifier;
+use wasm_bindgen::JsValue;
+use web_sys::console::error_1;
+
+/// Column type of numeric columns, written with the locale's separators. See `column_type`.
+pub const NUMBER_COLUMN: &str = "localeNumber";
+/// Column type of numbers without codes to decode, such as the cells of a pivot: written like
+/// `NUMBER_COLUMN` ones, sorted and filtered by value.
+pub const NUMBER_VALUE_COLUMN: &str = "localeNumberValue";
+/// Column type of ISO 8601 date columns, written in the locale's date order.
+pub const DATE_COLUMN: &str = "localeDate";
+
+/// A language and region the UI is translated to, with its catalog in locales/<tag>.ftl.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum Locale {
+    #[default]
+    #[serde(rename = "en-US")]
+    EnUs,
+    #[serde(rename = "de-DE")]
+    DeDe,
+    #[serde(rename = "fr-FR")]
+    FrFr,
+    #[serde(rename = "ja-JP")]
+    JaJp,
+}
+
+/// How a locale writes numbers and dates.
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NumberFormat {
+    pub decimal_separator: &'static str,
+    pub group_separator: &'static str,
+    /// Complete dates with `YYYY`, `MM` and `DD` replaced, e.g. `DD.MM.YYYY`.
+    pub date_pattern: &'static str,
+    /// Separates the fields of exported CSV files, a semicolon where the comma is the decimal
+    /// separator.
+    pub csv_separator: &'static str,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 4] = [Locale::EnUs, Locale::DeDe, Locale::FrFr, Locale::JaJp];
+
+    /// The BCP 47 language tag, e.g. `de-DE`.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Locale::EnUs => "en-US",
+            Locale::DeDe => "de-DE",
+            Locale::FrFr => "fr-FR",
+            Locale::JaJp => "ja-JP",
+        }
+    }
+
+    /// The name of the language in that language, as the locale switcher lists it.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Locale::EnUs => "English",
+            Locale::DeDe => "Deutsch",
+            Locale::FrFr => "Français",
+            Locale::JaJp => "日本語",
+        }
+    }
+
+    /// The locale of a language tag, or of its language when the region differs, e.g. `de-AT`
+    /// is German.
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let language = |tag: &str| {
+            tag.split(['-', '_'])
+                .next()
+                .unwrap_or_default()
+                .to_lowercase()
+        };
+        Locale::ALL
+            .into_iter()
+            .find(|locale| locale.tag().eq_ignore_ascii_case(tag))
+            .or_else(|| {
+                Locale::ALL
+                    .into_iter()
+                    .find(|locale| language(locale.tag()) == language(tag))
+            })
+    }
+
+    /// The first of the preferred language tags with a catalog, English without any.
+    pub fn negotiate(preferred: &[String]) -> Locale {
+        preferred
+            .iter()
+            .find_map(|tag| Locale::from_tag(tag))
+            .unwrap_or_default()
+    }
+
+    /// The locale the browser prefers, from `navigator.languages`.
+    pub fn detect() -> Locale {
+        let preferred: Vec<String> = window()
+            .navigator()
+            .languages()
+            .iter()
+            .filter_map(|language| language.as_string())
+            .collect();
+        Locale::negotiate(&preferred)
+    }
+
+    pub fn number_format(&self) -> NumberFormat {
+        match self {
+            Locale::EnUs => NumberFormat {
+                decimal_separator: ".",
+                group_separator: ",",
+                date_pattern: "MM/DD/YYYY",
+                csv_separator: ",",
+            },
+            Locale::DeDe => NumberFormat {
+                decimal_separator: ",",
+                group_separator: ".",
+                date_pattern: "DD.MM.YYYY",
+                csv_separator: ";",
+            },
+            Locale::FrFr => NumberFormat {
+                decimal_separator: ",",
+                group_separator: "\u{202f}",
+                date_pattern: "DD/MM/YYYY",
+                csv_separator: ";",
+            },
+            Locale::JaJp => NumberFormat {
+                decimal_separator: ".",
+                group_separator: ",",
+                date_pattern: "YYYY/MM/DD",
+                csv_separator: ",",
+            },
+        }
+    }
+
+    /// A decimal number written with the locale's separators, e.g. `-1234.5` as `-1.234,5` in
+    /// German. `None` for anything else, such as exponents or text.
+    pub fn format_number(&self, value: &str) -> Option<String> {
+        let value = value.trim();
+        let (sign, digits) = match value.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", value),
+        };
+        let (integer, fraction) = match digits.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (digits, None),
+        };
+        let is_digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+        if !is_digits(integer) || fraction.is_some_and(|fraction| !is_digits(fraction)) {
+            return None;
+        }
+        let format = self.number_format();
+        let mut written = String::from(sign);
+        for (index, digit) in integer.chars().enumerate() {
+            if index > 0 && (integer.len() - index) % 3 == 0 {
+                written.push_str(format.group_separator);
+            }
+            written.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            written.push_str(format.decimal_separator);
+            written.push_str(fraction);
+        }
+        Some(written)
+    }
+
+    /// A complete ISO 8601 date in the locale's date order, keeping the time as written, e.g.
+    /// `2023-04-05T08:15` as `05.04.2023 08:15` in German. `None` for partial dates.
+    pub fn format_date(&self, value: &str) -> Option<String> {
+        let date = IsoDate::parse(value)?;
+        let written = self
+            .number_format()
+            .date_pattern
+            .replace("YYYY", &format!("{:04}", date.year?))
+            .replace("MM", &format!("{:02}", date.month?))
+            .replace("DD", &format!("{:02}", date.day?));
+        Some(match date.time {
+            Some(time) => format!("{written} {time}"),
+            None => written,
+        })
+    }
+
+    /// A cell of a column of `column_type` as the locale writes it, `None` when it is written as
+    /// stored.
+    pub fn format_cell(&self, column_type: &str, value: &str) -> Option<String> {
+        match column_type {
+            NUMBER_COLUMN | NUMBER_VALUE_COLUMN => self.format_number(value),
+            DATE_COLUMN => self.format_date(value),
+            _ => None,
+        }
+    }
+
+    /// The message `id` of the locale's catalog, see `translate`.
+    pub fn tr(&self, id: &str) -> String {
+        self.translate(id, None)
+    }
+
+    /// The message `id` with its variables set to `args`, e.g.
+    /// `locale.tr_args("grid-rows-shown", &[("shown", 12.into()), ("rows", 340.into())])`.
+    pub fn tr_args(&self, id: &str, args: &[(&str, FluentValue)]) -> String {
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, value.clone());
+        }
+        self.translate(id, Some(&fluent_args))
+    }
+
+    /// Messages missing from the catalog come from the English one, and the id itself is shown
+    /// when English lacks it too.
+    fn translate(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        for locale in [*self, Locale::EnUs] {
+            let bundle = bundle(locale);
+            let pattern = match bundle.get_message(id).and_then(|message| message.value()) {
+                Some(pattern) => pattern,
+                None => continue,
+            };
+            let mut errors = Vec::new();
+            let text = bundle.format_pattern(pattern, args, &mut errors);
+            if !errors.is_empty() {
+                error_1(&JsValue::from(format!(
+                    "i18n error: the message {id} of {} did not format: {errors:?}",
+                    locale.tag()
+                )));
+            }
+            return text.into_owned();
+        }
+        error_1(&JsValue::from(format!("i18n error: no message {id}")));
+        String::from(id)
+    }
+
+    fn catalog(&self) -> &'static str {
+        match self {
+            Locale::EnUs => include_str!("../locales/en-US.ftl"),
+            Locale::DeDe => include_str!("../locales/de-DE.ftl"),
+            Locale::FrFr => include_str!("../locales/fr-FR.ftl"),
+            Locale::JaJp => include_str!("../locales/ja-JP.ftl"),
+        }
+    }
+}
+
+/// A message of the catalogs with its arguments, for text made before the locale is known, such
+/// as the steps of the undo history.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Message {
+    pub id: &'static str,
+    pub args: Vec<(&'static str, MessageArg)>,
+}
+
+/// An argument of a `Message`.
+#[derive(Clone, PartialEq, Debug)]
+pub enum MessageArg {
+    Value(FluentValue<'static>),
+    Message(Message),
+    /// Arguments written one after another, separated by the message `separator`, e.g. the
+    /// columns of a sort.
+    List {
+        separator: &'static str,
+        items: Vec<MessageArg>,
+    },
+}
+
+impl Message {
+    pub fn new(id: &'static str) -> Message {
+        Message {
+            id,
+            args: Vec::new(),
+        }
+    }
+
+    pub fn with(self, name: &'static str, value: impl Into<FluentValue<'static>>) -> Message {
+        self.with_arg(name, MessageArg::Value(value.into()))
+    }
+
+    pub fn with_arg(mut self, name: &'static str, arg: MessageArg) -> Message {
+        self.args.push((name, arg));
+        self
+    }
+
+    pub fn text(&self, locale: Locale) -> String {
+        let args: Vec<(&str, FluentValue)> = self
+            .args
+            .iter()
+            .map(|(name, arg)| (*name, arg.value(locale)))
+            .collect();
+        locale.tr_args(self.id, &args)
+    }
+}
+
+impl MessageArg {
+    /// Plain values, such as column names, separated by the message `separator`.
+    pub fn list<T: Into<FluentValue<'static>>>(
+        separator: &'static str,
+        values: impl IntoIterator<Item = T>,
+    ) -> MessageArg {
+        MessageArg::List {
+            separator,
+            items: values
+                .into_iter()
+                .map(|value| MessageArg::Value(value.into()))
+                .collect(),
+        }
+    }
+
+    fn value(&self, locale: Locale) -> FluentValue<'static> {
+        match self {
+            MessageArg::Value(value) => value.clone(),
+            MessageArg::Message(message) => message.text(locale).into(),
+            MessageArg::List { separator, items } => items
+                .iter()
+                .map(|item| match item.value(locale) {
+                    FluentValue::String(text) => text.into_owned(),
+                    FluentValue::Number(number) => locale
+                        .format_number(&number.as_string())
+                        .unwrap_or_else(|| number.as_string().into_owned()),
+                    _ => String::new(),
+                })
+                .collect::<Vec<String>>()
+                .join(&locale.tr(separator))
+                .into(),
+        }
+    }
+}
+
+type Bundle = FluentBundle<FluentResource>;
+
+thread_local! {
+    static BUNDLES: RefCell<HashMap<Locale, Rc<Bundle>>> = RefCell::new(HashMap::new());
+}
+
+/// The message bundle of a locale, parsed the first time it is asked for.
+fn bundle(locale: Locale) -> Rc<Bundle> {
+    BUNDLES.with(|bundles| {
+        bundles
+            .borrow_mut()
+            .entry(locale)
+            .or_insert_with(|| Rc::new(build_bundle(locale)))
+            .clone()
+    })
+}
+
+fn build_bundle(locale: Locale) -> Bundle {
+    let language: LanguageIdentifier = locale.tag().parse().unwrap_or_default();
+    let mut bundle = FluentBundle::new(vec![language]);
+    // The UI is written left to right only, the isolation marks would end up in copied text.
+    bundle.set_use_isolating(false);
+    bundle.set_formatter(Some(match locale {
+        Locale::EnUs => |value, _| format_fluent_number(Locale::EnUs, value),
+        Locale::DeDe => |value, _| format_fluent_number(Locale::DeDe, value),
+        Locale::FrFr => |value, _| format_fluent_number(Locale::FrFr, value),
+        Locale::JaJp => |value, _| format_fluent_number(Locale::JaJp, value),
+    }));
+    let resource = match FluentResource::try_new(locale.catalog().to_owned()) {
+        Ok(resource) => resource,
+        Err((resource, errors)) => {
+            error_1(&JsValue::from(format!(
+                "i18n error: the catalog of {} has errors: {errors:?}",
+                locale.tag()
+            )));
+            resource
+        }
+    };
+    if let Err(errors) = bundle.add_resource(resource) {
+        error_1(&JsValue::from(format!(
+            "i18n error: the catalog of {} repeats messages: {errors:?}",
+            locale.tag()
+        )));
+    }
+    bundle
+}
+
+/// Numbers placed in messages, such as row counts, with the locale's separators.
+fn format_fluent_number(locale: Locale, value: &FluentValue) -> Option<String> {
+    match value {
+        FluentValue::Number(number) => locale.format_number(&number.as_string()),
+        _ => None,
+    }
+}
+
+/// The column type of a table column whose cells the locale writes its own way: numbers, and
+/// text where every value is an ISO 8601 date and some are complete.
+pub fn column_type(table: &Table, column: usize) -> Option<&'static str> {
+    match table.columns().get(column)?.values() {
+        ColumnValues::Integer(_) | ColumnValues::Float(_) => Some(NUMBER_COLUMN),
+        ColumnValues::Text(_) => {
+            let texts = table.distinct_texts(column);
+            let dates: Vec<IsoDate> = texts
+                .iter()
+                .filter(|text| !text.trim().is_empty())
+                .map(|text| IsoDate::parse(text).filter(|date| date.year.is_some()))
+                .collect::<Option<_>>()?;
+            dates
+                .iter()
+                .any(|date| date.month.is_some() && date.day.is_some())
+                .then_some(DATE_COLUMN)
+        }
+        _ => None,
+    }
+}
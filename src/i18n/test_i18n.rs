@@ -0,0 +1,191 @@
+use super::{
+    column_type, Locale, Message, MessageArg, DATE_COLUMN, NUMBER_COLUMN, NUMBER_VALUE_COLUMN,
+};
+use crate::a11y::audit::{assert_accessible, render_component};
+use crate::components::locale::LocaleSwitcher;
+use crate::table::Table;
+
+use std::collections::BTreeSet;
+
+use fluent_bundle::FluentResource;
+use serde_json::{json, Map, Value};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+/// The ids of the messages of a locale's catalog, which must parse.
+fn message_ids(locale: Locale) -> BTreeSet<&'static str> {
+    if let Err((_, errors)) = FluentResource::try_new(locale.catalog().to_owned()) {
+        panic!("the catalog of {} has errors: {errors:?}", locale.tag());
+    }
+    locale
+        .catalog()
+        .lines()
+        .filter(|line| line.starts_with(|c: char| c.is_ascii_lowercase()))
+        .filter_map(|line| line.split_once(" ="))
+        .map(|(id, _)| id)
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn test_catalogs_translate_every_message() {
+    let english = message_ids(Locale::EnUs);
+    for locale in Locale::ALL {
+        let ids = message_ids(locale);
+        let missing: Vec<&&str> = english.difference(&ids).collect();
+        let unknown: Vec<&&str> = ids.difference(&english).collect();
+        assert!(missing.is_empty(), "{} lacks {missing:?}", locale.tag());
+        assert!(
+            unknown.is_empty(),
+            "{} has unknown {unknown:?}",
+            locale.tag()
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_messages_and_plurals() {
+    assert_eq!(Locale::EnUs.tr("no-table-selected"), "No table is selected");
+    assert_eq!(Locale::DeDe.tr("select-schema"), "Schema wählen");
+    let rows = |locale: Locale, rows: usize| locale.tr_args("grid-rows", &[("rows", rows.into())]);
+    assert_eq!(rows(Locale::EnUs, 1), "1 row");
+    assert_eq!(rows(Locale::EnUs, 12345), "12,345 rows");
+    assert_eq!(rows(Locale::DeDe, 12345), "12.345 Zeilen");
+    assert_eq!(rows(Locale::FrFr, 12345), "12\u{202f}345 lignes");
+    assert_eq!(rows(Locale::JaJp, 2), "2行");
+    // Unknown ids show the id itself rather than nothing.
+    assert_eq!(Locale::FrFr.tr("no-such-message"), "no-such-message");
+}
+
+#[wasm_bindgen_test]
+fn test_format_number() {
+    assert_eq!(
+        Locale::EnUs.format_number("-1234567.5").as_deref(),
+        Some("-1,234,567.5")
+    );
+    assert_eq!(
+        Locale::DeDe.format_number("-1234567.5").as_deref(),
+        Some("-1.234.567,5")
+    );
+    assert_eq!(
+        Locale::FrFr.format_number("1234.5").as_deref(),
+        Some("1\u{202f}234,5")
+    );
+    assert_eq!(Locale::JaJp.format_number("999").as_deref(), Some("999"));
+    assert_eq!(Locale::DeDe.format_number("1e5"), None);
+    assert_eq!(Locale::DeDe.format_number("12.x"), None);
+    assert_eq!(Locale::DeDe.format_number(""), None);
+}
+
+#[wasm_bindgen_test]
+fn test_format_date() {
+    assert_eq!(
+        Locale::EnUs.format_date("2023-04-05").as_deref(),
+        Some("04/05/2023")
+    );
+    assert_eq!(
+        Locale::DeDe.format_date("2023-04-05T08:15").as_deref(),
+        Some("05.04.2023 08:15")
+    );
+    assert_eq!(
+        Locale::FrFr.format_date("2023-04-05").as_deref(),
+        Some("05/04/2023")
+    );
+    assert_eq!(
+        Locale::JaJp.format_date("2023-04-05").as_deref(),
+        Some("2023/04/05")
+    );
+    // Partial dates are kept as written.
+    assert_eq!(Locale::DeDe.format_date("2023-04"), None);
+    assert_eq!(Locale::DeDe.format_cell(DATE_COLUMN, "2023"), None);
+    assert_eq!(
+        Locale::DeDe.format_cell(NUMBER_COLUMN, "1500").as_deref(),
+        Some("1.500")
+    );
+    assert_eq!(
+        Locale::DeDe
+            .format_cell(NUMBER_VALUE_COLUMN, "1500")
+            .as_deref(),
+        Some("1.500")
+    );
+    assert_eq!(Locale::DeDe.format_cell("text", "1500"), None);
+}
+
+#[wasm_bindgen_test]
+fn test_locale_negotiation() {
+    assert_eq!(Locale::from_tag("de-DE"), Some(Locale::DeDe));
+    assert_eq!(Locale::from_tag("de-AT"), Some(Locale::DeDe));
+    assert_eq!(Locale::from_tag("ja"), Some(Locale::JaJp));
+    assert_eq!(Locale::from_tag("fr_CA"), Some(Locale::FrFr));
+    assert_eq!(Locale::from_tag("es-ES"), None);
+    let preferred =
+        |tags: &[&str]| -> Vec<String> { tags.iter().map(|tag| tag.to_string()).collect() };
+    assert_eq!(
+        Locale::negotiate(&preferred(&["es-ES", "fr-BE", "en"])),
+        Locale::FrFr
+    );
+    assert_eq!(Locale::negotiate(&preferred(&["es-ES"])), Locale::EnUs);
+    assert_eq!(Locale::negotiate(&[]), Locale::EnUs);
+}
+
+#[wasm_bindgen_test]
+fn test_message_renders_in_any_locale() {
+    let sort = Message::new("history-sort").with_arg(
+        "columns",
+        MessageArg::List {
+            separator: "list-separator",
+            items: vec![
+                MessageArg::Message(
+                    Message::new("history-sort-ascending").with("column", "AETERM"),
+                ),
+                MessageArg::Message(
+                    Message::new("history-sort-descending").with("column", "AESEQ"),
+                ),
+            ],
+        },
+    );
+    assert_eq!(
+        sort.text(Locale::EnUs),
+        "Sort by AETERM ascending, AESEQ descending"
+    );
+    assert_eq!(
+        sort.text(Locale::DeDe),
+        "Nach AETERM aufsteigend, AESEQ absteigend sortieren"
+    );
+    assert_eq!(
+        sort.text(Locale::JaJp),
+        "AETERMの昇順、AESEQの降順で並べ替え"
+    );
+    let values = Message::new("condition-one-of")
+        .with_arg("values", MessageArg::list("list-separator", [1500, 2]));
+    assert_eq!(values.text(Locale::DeDe), "in 1.500, 2");
+}
+
+#[wasm_bindgen_test]
+fn test_column_type() {
+    let records: Vec<Map<String, Value>> = [
+        json!({"AESEQ": 1, "AESTDTC": "2023-04-05", "AEENDTC": "2023", "AETERM": "HEADACHE"}),
+        json!({"AESEQ": 2, "AESTDTC": "2023-04", "AEENDTC": "", "AETERM": "NAUSEA"}),
+    ]
+    .iter()
+    .map(|record| record.as_object().unwrap().clone())
+    .collect();
+    let table = Table::from_records(&records).ok().unwrap();
+    let column_type = |name: &str| column_type(&table, table.column_index(name).unwrap());
+    assert_eq!(column_type("AESEQ"), Some(NUMBER_COLUMN));
+    // Dates are localized when some are complete, partial ones are written as stored.
+    assert_eq!(column_type("AESTDTC"), Some(DATE_COLUMN));
+    assert_eq!(column_type("AEENDTC"), None);
+    assert_eq!(column_type("AETERM"), None);
+}
+
+#[wasm_bindgen_test]
+async fn test_locale_switcher_lists_every_locale() {
+    let markup = render_component::<LocaleSwitcher>(()).await;
+    for locale in Locale::ALL {
+        assert!(
+            markup.contains(&format!("lang=\"{}\"", locale.tag())),
+            "{markup}"
+        );
+    }
+    assert!(markup.contains("aria-label=\"Language\""), "{markup}");
+    assert_accessible(&markup);
+}
@@ -0,0 +1,224 @@
+#[cfg(test)]
+mod test_layout;
+
+use crate::i18n::{Locale, NumberFormat};
+
+use gloo_utils::window;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use yew::prelude::*;
+
+/// Below this width, in CSS pixels, the viewport is a phone (Bootstrap's `sm` breakpoint).
+pub const PHONE_MAX_WIDTH: f64 = 576.0;
+/// Below this width the viewport is treated like a tablet (Bootstrap's `lg` breakpoint).
+pub const TABLET_MAX_WIDTH: f64 = 992.0;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Viewport {
+    pub width: f64,
+    /// Whether the primary pointer is a finger rather than a mouse, `(pointer: coarse)`.
+    pub coarse_pointer: bool,
+}
+
+impl Viewport {
+    pub fn current() -> Viewport {
+        let window = window();
+        let width = window
+            .inner_width()
+            .ok()
+            .and_then(|width| width.as_f64())
+            .unwrap_or(TABLET_MAX_WIDTH);
+        let coarse_pointer = window
+            .match_media("(pointer: coarse)")
+            .ok()
+            .flatten()
+            .map(|query| query.matches())
+            .unwrap_or(false);
+        Viewport {
+            width,
+            coarse_pointer,
+        }
+    }
+}
+
+/// Whether the user asked the system for less motion, `(prefers-reduced-motion: reduce)`.
+pub fn prefers_reduced_motion() -> bool {
+    window()
+        .match_media("(prefers-reduced-motion: reduce)")
+        .ok()
+        .flatten()
+        .map(|query| query.matches())
+        .unwrap_or(false)
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LayoutClass {
+    Phone,
+    Tablet,
+    Desktop,
+}
+
+impl LayoutClass {
+    /// Narrow viewports are phones, and touch screens or narrow windows get the tablet layout.
+    pub fn from_viewport(viewport: &Viewport) -> LayoutClass {
+        if viewport.width < PHONE_MAX_WIDTH {
+            LayoutClass::Phone
+        } else if viewport.width < TABLET_MAX_WIDTH || viewport.coarse_pointer {
+            LayoutClass::Tablet
+        } else {
+            LayoutClass::Desktop
+        }
+    }
+}
+
+/// The layout class of the window, updated as the window is resized.
+#[hook]
+pub fn use_layout_class() -> LayoutClass {
+    let layout = use_state_eq(|| LayoutClass::from_viewport(&Viewport::current()));
+    {
+        let layout = layout.clone();
+        use_effect_with_deps(
+            move |_| {
+                let listener = Closure::<dyn Fn()>::new(move || {
+                    layout.set(LayoutClass::from_viewport(&Viewport::current()))
+                });
+                let _ = window()
+                    .add_event_listener_with_callback("resize", listener.as_ref().unchecked_ref());
+                move || {
+                    let _ = window().remove_event_listener_with_callback(
+                        "resize",
+                        listener.as_ref().unchecked_ref(),
+                    );
+                }
+            },
+            (),
+        );
+    }
+    *layout
+}
+
+#[derive(Serialize, Clone, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DefaultColDef {
+    pub flex: u32,
+    pub min_width: u32,
+    pub filter: bool,
+    pub resizable: bool,
+    pub wrap_text: bool,
+    pub sortable: bool,
+    pub enable_row_group: bool,
+}
+
+/// The AG Grid `gridOptions` that depend on the layout, `create_grid` in ag_grid.js adds the
+/// data and the callbacks.
+#[derive(Serialize, Clone, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GridOptions {
+    pub dom_layout: &'static str,
+    pub animate_rows: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row_selection: Option<&'static str>,
+    pub default_col_def: DefaultColDef,
+    pub row_height: u32,
+    pub header_height: u32,
+    pub always_show_horizontal_scroll: bool,
+    pub always_show_vertical_scroll: bool,
+    pub enable_cell_text_selection: bool,
+    pub column_hover_highlight: bool,
+    pub row_drag_managed: bool,
+    pub row_drag_entire_row: bool,
+    pub row_drag_multi_row: bool,
+    pub row_group_panel_show: &'static str,
+    pub group_display_type: &'static str,
+    pub suppress_drag_leave_hides_columns: bool,
+    pub suppress_column_move_animation: bool,
+    /// How the UI's locale writes numbers and dates, read by the `localeNumber` and
+    /// `localeDate` column types of ag_grid.js.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<NumberFormat>,
+}
+
+impl GridOptions {
+    pub fn for_layout(layout: LayoutClass) -> GridOptions {
+        let desktop = GridOptions {
+            dom_layout: "normal",
+            animate_rows: true,
+            row_selection: Some("multiple"),
+            default_col_def: DefaultColDef {
+                flex: 1,
+                min_width: 100,
+                filter: true,
+                resizable: true,
+                wrap_text: true,
+                sortable: true,
+                enable_row_group: true,
+            },
+            row_height: 30,
+            header_height: 40,
+            always_show_horizontal_scroll: true,
+            always_show_vertical_scroll: false,
+            enable_cell_text_selection: true,
+            column_hover_highlight: true,
+            row_drag_managed: true,
+            row_drag_entire_row: true,
+            row_drag_multi_row: true,
+            row_group_panel_show: "always",
+            group_display_type: "groupRows",
+            suppress_drag_leave_hides_columns: true,
+            suppress_column_move_animation: false,
+            context: None,
+        };
+        match layout {
+            LayoutClass::Desktop => desktop,
+            // Dragging whole rows fights with scrolling on touch screens, and fingers need
+            // taller rows.
+            LayoutClass::Tablet => GridOptions {
+                default_col_def: DefaultColDef {
+                    min_width: 80,
+                    ..desktop.default_col_def
+                },
+                row_height: 36,
+                enable_cell_text_selection: false,
+                row_drag_managed: false,
+                row_drag_entire_row: false,
+                row_drag_multi_row: false,
+                ..desktop
+            },
+            LayoutClass::Phone => GridOptions {
+                dom_layout: "autoHeight",
+                row_selection: None,
+                default_col_def: DefaultColDef {
+                    min_width: 40,
+                    ..desktop.default_col_def
+                },
+                row_height: 20,
+                header_height: 30,
+                always_show_vertical_scroll: true,
+                enable_cell_text_selection: false,
+                column_hover_highlight: false,
+                row_drag_managed: false,
+                row_drag_entire_row: false,
+                row_drag_multi_row: false,
+                ..desktop
+            },
+        }
+    }
+
+    /// Rows and columns jump to their new place instead of sliding there when `reduced_motion`.
+    pub fn with_reduced_motion(self, reduced_motion: bool) -> GridOptions {
+        GridOptions {
+            animate_rows: self.animate_rows && !reduced_motion,
+            suppress_column_move_animation: self.suppress_column_move_animation || reduced_motion,
+            ..self
+        }
+    }
+
+    /// Number and date columns, and the CSV export, written the way `locale` writes them.
+    pub fn with_locale(self, locale: Locale) -> GridOptions {
+        GridOptions {
+            context: Some(locale.number_format()),
+            ..self
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use super::{GridOptions, LayoutClass, Viewport};
+use crate::i18n::Locale;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn viewport(width: f64, coarse_pointer: bool) -> Viewport {
+    Viewport {
+        width,
+        coarse_pointer,
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_layout_class_from_viewport() {
+    assert_eq!(
+        LayoutClass::from_viewport(&viewport(390.0, true)),
+        LayoutClass::Phone
+    );
+    assert_eq!(
+        LayoutClass::from_viewport(&viewport(820.0, true)),
+        LayoutClass::Tablet
+    );
+    // A desktop browser in a narrow window.
+    assert_eq!(
+        LayoutClass::from_viewport(&viewport(700.0, false)),
+        LayoutClass::Tablet
+    );
+    // A large touch screen.
+    assert_eq!(
+        LayoutClass::from_viewport(&viewport(1366.0, true)),
+        LayoutClass::Tablet
+    );
+    assert_eq!(
+        LayoutClass::from_viewport(&viewport(1440.0, false)),
+        LayoutClass::Desktop
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_grid_options_serialize_as_ag_grid_options() {
+    let options = serde_json::to_value(GridOptions::for_layout(LayoutClass::Desktop)).unwrap();
+    assert_eq!(options["domLayout"], "normal");
+    assert_eq!(options["rowSelection"], "multiple");
+    assert_eq!(options["defaultColDef"]["minWidth"], 100);
+    assert_eq!(options["rowDragManaged"], true);
+
+    let phone = serde_json::to_value(GridOptions::for_layout(LayoutClass::Phone)).unwrap();
+    assert_eq!(phone["domLayout"], "autoHeight");
+    assert!(phone.get("rowSelection").is_none());
+}
+
+#[wasm_bindgen_test]
+fn test_touch_layouts_do_not_drag_rows() {
+    for layout in [LayoutClass::Tablet, LayoutClass::Phone] {
+        let options = GridOptions::for_layout(layout);
+        assert!(!options.row_drag_managed);
+        assert!(!options.row_drag_entire_row);
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_reduced_motion_turns_off_grid_animations() {
+    let options = GridOptions::for_layout(LayoutClass::Desktop);
+    assert!(options.animate_rows);
+    let reduced = options.clone().with_reduced_motion(true);
+    assert!(!reduced.animate_rows);
+    let serialized = serde_json::to_value(&reduced).unwrap();
+    assert_eq!(serialized["suppressColumnMoveAnimation"], true);
+    assert_eq!(options.clone().with_reduced_motion(false), options);
+}
+
+#[wasm_bindgen_test]
+fn test_locale_reaches_the_grid_context() {
+    let options = GridOptions::for_layout(LayoutClass::Desktop);
+    assert!(serde_json::to_value(&options)
+        .unwrap()
+        .get("context")
+        .is_none());
+    let german = serde_json::to_value(options.with_locale(Locale::DeDe)).unwrap();
+    assert_eq!(german["context"]["decimal_separator"], ",");
+    assert_eq!(german["context"]["group_separator"], ".");
+    assert_eq!(german["context"]["date_pattern"], "DD.MM.YYYY");
+    assert_eq!(german["context"]["csv_separator"], ";");
+}
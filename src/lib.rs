@@ -0,0 +1,130 @@
+pub mod a11y;
+pub mod ag_grid;
+pub mod cdisc;
+pub mod checks;
+pub mod codelists;
+pub mod commands;
+pub mod components;
+pub mod csv;
+pub mod data_location;
+pub mod define_xml;
+pub mod formatting;
+pub mod grid;
+pub mod history;
+pub mod i18n;
+pub mod layout;
+pub mod offline;
+pub mod payload;
+pub mod pivot;
+pub mod profile;
+pub mod row_stream;
+pub mod search;
+pub mod snapshot_diff;
+pub mod state;
+pub mod table;
+pub mod table_cache;
+pub mod views;
+pub mod worker;
+pub mod worker_client;
+pub mod workspace;
+use crate::components::command_palette::CommandPalette;
+use crate::components::live_region::LiveRegion;
+use crate::components::locale::{use_locale, LocaleProvider};
+use crate::components::search_page::SearchPage;
+use crate::components::{Home, StyewardDataTables};
+use wasm_bindgen::prelude::*;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+#[wasm_bindgen(module = "/js/config.js")]
+extern "C" {
+    fn get_host() -> String;
+}
+
+#[wasm_bindgen(module = "/js/features.js")]
+extern "C" {
+    fn shared_views_enabled() -> bool;
+}
+
+#[derive(Clone, Routable, PartialEq)]
+pub enum MainRoute {
+    #[at("/site")]
+    Home,
+    #[at("/site/datatable")]
+    DataTable,
+    #[at("/site/datatable/:schema/:table")]
+    DataTableView { schema: String, table: String },
+    #[at("/site/search")]
+    Search,
+    #[not_found]
+    #[at("/404")]
+    NotFound,
+}
+
+fn switch_main(route: MainRoute) -> Html {
+    match route {
+        MainRoute::Home => html! {<Home/>},
+        MainRoute::DataTable => html! {<StyewardDataTables/>},
+        MainRoute::DataTableView { schema, table } => html! {
+            <StyewardDataTables schema={Some(decode_segment(schema))} table={Some(decode_segment(table))}/>
+        },
+        MainRoute::Search => html! {<SearchPage/>},
+        MainRoute::NotFound => html! {<NotFound/>},
+    }
+}
+
+/// A path segment of the route as written, e.g. `sdtm raw` for `sdtm%20raw` in a shared link.
+fn decode_segment(segment: String) -> String {
+    js_sys::decode_uri_component(&segment)
+        .ok()
+        .and_then(|decoded| decoded.as_string())
+        .unwrap_or(segment)
+}
+
+#[function_component]
+fn NotFound() -> Html {
+    let locale = use_locale();
+    html! {<h1>{locale.tr("not-found")}</h1>}
+}
+
+#[derive(PartialEq, Clone)]
+pub struct StyewardConfig {
+    scheme: String,
+    host: String,
+    port: u16,
+    /// Set in the deployment's config.js, see js/features.js.
+    shared_views: bool,
+}
+
+#[function_component]
+pub fn App() -> Html {
+    let config = StyewardConfig {
+        scheme: "https".to_string(),
+        host: get_host(),
+        port: 443,
+        shared_views: shared_views_enabled(),
+    };
+    html! {
+        <main>
+            <ContextProvider<StyewardConfig> context={config}>
+                <LocaleProvider>
+                    <LiveRegion>
+                        <BrowserRouter>
+                            <CommandPalette>
+                                <Switch<MainRoute> render={switch_main} />
+                            </CommandPalette>
+                        </BrowserRouter>
+                    </LiveRegion>
+                </LocaleProvider>
+            </ContextProvider<StyewardConfig>>
+        </main>
+    }
+}
+
+/// Starts the app, the entry point of the `styeward` binary. Heavy work runs in the worker
+/// started from the `worker` binary, see src/worker.rs.
+pub fn start() {
+    wasm_logger::init(wasm_logger::Config::default());
+    offline::register();
+    yew::Renderer::<App>::new().render();
+}
@@ -0,0 +1,270 @@
+#[cfg(test)]
+mod test_offline;
+
+use crate::components::table_visualization::{table_data_from_bytes, TableData};
+use crate::i18n::Message;
+use crate::table::Table;
+use crate::worker_client;
+
+use std::collections::BTreeSet;
+use std::rc::Rc;
+
+use gloo_utils::window;
+use js_sys::Date;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use yew::prelude::*;
+
+/// Where trunk copies js/service_worker.js, its scope is the app under `/site/`.
+const SERVICE_WORKER_URL: &str = "/site/service_worker.js";
+
+#[wasm_bindgen(module = "/js/offline_store.js")]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    async fn offline_unlock(passphrase: String) -> Result<JsValue, JsValue>;
+    fn offline_unlocked() -> bool;
+    fn offline_lock();
+    #[wasm_bindgen(catch)]
+    async fn offline_put(
+        schema: String,
+        table: String,
+        pinned_at: String,
+        rows: usize,
+        json: String,
+    ) -> Result<JsValue, JsValue>;
+    #[wasm_bindgen(catch)]
+    async fn offline_get(schema: String, table: String) -> Result<JsValue, JsValue>;
+    #[wasm_bindgen(catch)]
+    async fn offline_list() -> Result<JsValue, JsValue>;
+    #[wasm_bindgen(catch)]
+    async fn offline_delete(schema: String, table: String) -> Result<JsValue, JsValue>;
+    #[wasm_bindgen(catch)]
+    async fn offline_reset() -> Result<JsValue, JsValue>;
+    fn register_service_worker(script_url: &str);
+}
+
+pub enum OfflineError {
+    StoreError(String),
+    LockedError(String),
+    DeserializeError(String),
+}
+
+impl OfflineError {
+    pub fn message(&self) -> &String {
+        match self {
+            OfflineError::StoreError(msg)
+            | OfflineError::LockedError(msg)
+            | OfflineError::DeserializeError(msg) => msg,
+        }
+    }
+}
+
+fn store_error(error: JsValue) -> OfflineError {
+    let reason = error
+        .dyn_ref::<js_sys::Error>()
+        .map(|error| String::from(error.message()))
+        .unwrap_or_else(|| format!("{error:?}"));
+    OfflineError::StoreError(format!("offline error: {reason}"))
+}
+
+/// A table pinned to the device, as listed without unlocking the store.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PinnedTable {
+    pub schema: String,
+    pub table: String,
+    /// ISO 8601 timestamp of when the table was pinned, the age of the offline copy.
+    pub pinned_at: String,
+    pub rows: usize,
+}
+
+#[derive(Deserialize)]
+struct StoredTable {
+    pinned_at: String,
+    json: String,
+}
+
+pub fn register() {
+    register_service_worker(SERVICE_WORKER_URL);
+}
+
+pub fn is_online() -> bool {
+    window().navigator().on_line()
+}
+
+/// Whether the browser is online, updated on its `online` and `offline` events.
+#[hook]
+pub fn use_online() -> bool {
+    let online = use_state_eq(is_online);
+    {
+        let online = online.clone();
+        use_effect_with_deps(
+            move |_| {
+                let listener = Closure::<dyn Fn()>::new(move || online.set(is_online()));
+                for event in ["online", "offline"] {
+                    let _ = window()
+                        .add_event_listener_with_callback(event, listener.as_ref().unchecked_ref());
+                }
+                move || {
+                    for event in ["online", "offline"] {
+                        let _ = window().remove_event_listener_with_callback(
+                            event,
+                            listener.as_ref().unchecked_ref(),
+                        );
+                    }
+                }
+            },
+            (),
+        );
+    }
+    *online
+}
+
+/// Derives the store's key from the passphrase, the first passphrase given creates the store.
+pub async fn unlock(passphrase: &str) -> Result<(), OfflineError> {
+    match offline_unlock(String::from(passphrase)).await {
+        Ok(opened) if opened.as_bool() == Some(true) => Ok(()),
+        Ok(_) => Err(OfflineError::LockedError(String::from(
+            "offline error: the passphrase does not open the offline store.",
+        ))),
+        Err(error) => Err(store_error(error)),
+    }
+}
+
+pub fn is_unlocked() -> bool {
+    offline_unlocked()
+}
+
+pub fn lock() {
+    offline_lock();
+}
+
+/// Encrypts the table into the store, replacing an older copy.
+pub async fn pin(schema: &str, table: &str, data: &Table) -> Result<PinnedTable, OfflineError> {
+    if !is_unlocked() {
+        return Err(OfflineError::LockedError(String::from(
+            "offline error: unlock the offline store to pin tables.",
+        )));
+    }
+    let json = pinned_json(data)?;
+    let pinned = PinnedTable {
+        schema: String::from(schema),
+        table: String::from(table),
+        pinned_at: String::from(Date::new_0().to_iso_string()),
+        rows: data.len(),
+    };
+    offline_put(
+        pinned.schema.clone(),
+        pinned.table.clone(),
+        pinned.pinned_at.clone(),
+        pinned.rows,
+        json,
+    )
+    .await
+    .map_err(store_error)?;
+    Ok(pinned)
+}
+
+pub async fn unpin(schema: &str, table: &str) -> Result<(), OfflineError> {
+    offline_delete(String::from(schema), String::from(table))
+        .await
+        .map(|_| ())
+        .map_err(store_error)
+}
+
+/// Drops every pinned table, the way out of a forgotten passphrase.
+pub async fn forget_all() -> Result<(), OfflineError> {
+    offline_reset().await.map(|_| ()).map_err(store_error)
+}
+
+pub async fn pinned_tables() -> Result<Vec<PinnedTable>, OfflineError> {
+    let list = offline_list().await.map_err(store_error)?;
+    serde_wasm_bindgen::from_value::<Vec<PinnedTable>>(list).map_err(|error| {
+        OfflineError::DeserializeError(format!("offline error: invalid pinned tables: {error}"))
+    })
+}
+
+/// The pinned copy of the table and when it was pinned, `None` when the table is not pinned.
+pub async fn load_pinned(
+    schema: &str,
+    table: &str,
+) -> Result<Option<(TableData, String)>, OfflineError> {
+    if !is_unlocked() {
+        return Err(OfflineError::LockedError(String::from(
+            "offline error: unlock the offline store to read pinned tables.",
+        )));
+    }
+    let stored = offline_get(String::from(schema), String::from(table))
+        .await
+        .map_err(store_error)?;
+    if stored.is_null() {
+        return Ok(None);
+    }
+    let stored = serde_wasm_bindgen::from_value::<StoredTable>(stored).map_err(|error| {
+        OfflineError::DeserializeError(format!("offline error: invalid pinned table: {error}"))
+    })?;
+    // Copies pinned before the table was stored by column are arrays of records.
+    if stored.json.starts_with('[') {
+        return table_data_from_bytes(stored.json.into_bytes())
+            .await
+            .map(|data| Some((data, stored.pinned_at)))
+            .map_err(|error| OfflineError::DeserializeError(error.message().clone()));
+    }
+    let data = TableData {
+        table: Rc::new(pinned_table(&stored.json)?),
+        format: None,
+        id: worker_client::table_id(),
+    };
+    Ok(Some((data, stored.pinned_at)))
+}
+
+/// The columnar table as stored, so that it comes back with its columns in order and its missing
+/// cells apart from its nulls.
+fn pinned_json(data: &Table) -> Result<String, OfflineError> {
+    serde_json::to_string(data).map_err(|error| {
+        OfflineError::DeserializeError(format!("offline error: unable to serialize: {error}"))
+    })
+}
+
+fn pinned_table(json: &str) -> Result<Table, OfflineError> {
+    serde_json::from_str::<Table>(json).map_err(|error| {
+        OfflineError::DeserializeError(format!("offline error: invalid pinned table: {error}"))
+    })
+}
+
+/// The schemas with pinned tables, sorted.
+pub fn pinned_schemas(pinned: &[PinnedTable]) -> Vec<String> {
+    pinned
+        .iter()
+        .map(|pinned| pinned.schema.clone())
+        .collect::<BTreeSet<String>>()
+        .into_iter()
+        .collect()
+}
+
+/// The pinned tables of a schema, sorted.
+pub fn pinned_in_schema(pinned: &[PinnedTable], schema: &str) -> Vec<String> {
+    pinned
+        .iter()
+        .filter(|pinned| pinned.schema == schema)
+        .map(|pinned| pinned.table.clone())
+        .collect::<BTreeSet<String>>()
+        .into_iter()
+        .collect()
+}
+
+/// How old an offline copy is, e.g. "pinned 3 hours ago".
+pub fn pinned_age(pinned_at: &str, now_ms: f64) -> Message {
+    let pinned_ms = Date::parse(pinned_at);
+    if pinned_ms.is_nan() {
+        return Message::new("pinned-at").with("time", pinned_at.to_owned());
+    }
+    let minutes = ((now_ms - pinned_ms) / 60_000.0).max(0.0) as u64;
+    let (count, id) = match minutes {
+        0 => return Message::new("pinned-just-now"),
+        1..=59 => (minutes, "pinned-minutes-ago"),
+        60..=1439 => (minutes / 60, "pinned-hours-ago"),
+        _ => (minutes / 1440, "pinned-days-ago"),
+    };
+    Message::new(id).with("count", count)
+}
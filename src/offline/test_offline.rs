@@ -0,0 +1,79 @@
+use super::{pinned_age, pinned_in_schema, pinned_json, pinned_schemas, pinned_table, PinnedTable};
+use crate::i18n::Locale;
+use crate::table::{Table, NULL_TEXT};
+use js_sys::Date;
+use serde_json::{json, Map, Value};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn pinned(schema: &str, table: &str) -> PinnedTable {
+    PinnedTable {
+        schema: String::from(schema),
+        table: String::from(table),
+        pinned_at: String::from("2023-04-10T08:00:00.000Z"),
+        rows: 10,
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_pinned_schemas_and_tables() {
+    let tables = vec![
+        pinned("study_b", "ae"),
+        pinned("study_a", "lb"),
+        pinned("study_b", "dm"),
+    ];
+    assert_eq!(pinned_schemas(&tables), vec!["study_a", "study_b"]);
+    assert_eq!(pinned_in_schema(&tables, "study_b"), vec!["ae", "dm"]);
+    assert!(pinned_in_schema(&tables, "study_c").is_empty());
+}
+
+#[wasm_bindgen_test]
+fn test_pinned_age() {
+    let pinned_at = "2023-04-10T08:00:00.000Z";
+    let at = |time: &str| Date::parse(time);
+    assert_eq!(
+        pinned_age(pinned_at, at("2023-04-10T08:00:30.000Z")).text(Locale::EnUs),
+        "pinned just now"
+    );
+    assert_eq!(
+        pinned_age(pinned_at, at("2023-04-10T08:01:00.000Z")).text(Locale::EnUs),
+        "pinned 1 minute ago"
+    );
+    assert_eq!(
+        pinned_age(pinned_at, at("2023-04-10T11:30:00.000Z")).text(Locale::EnUs),
+        "pinned 3 hours ago"
+    );
+    assert_eq!(
+        pinned_age(pinned_at, at("2023-04-12T09:00:00.000Z")).text(Locale::EnUs),
+        "pinned 2 days ago"
+    );
+    assert_eq!(
+        pinned_age("yesterday", 0.0).text(Locale::EnUs),
+        "pinned yesterday"
+    );
+    assert_eq!(
+        pinned_age(pinned_at, at("2023-04-10T11:30:00.000Z")).text(Locale::JaJp),
+        "3時間前に保存"
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_pinned_tables_keep_their_columns() {
+    let records: Vec<Map<String, Value>> = [
+        json!({"USUBJID": "01-001", "AETERM": "HEADACHE"}),
+        json!({"USUBJID": "01-002", "AESEV": null, "AETERM": "NAUSEA"}),
+    ]
+    .iter()
+    .map(|record| record.as_object().unwrap().clone())
+    .collect();
+    let table = Table::from_records(&records).ok().unwrap();
+    let pinned = pinned_table(&pinned_json(&table).ok().unwrap())
+        .ok()
+        .unwrap();
+    assert_eq!(pinned.column_names(), vec!["USUBJID", "AETERM", "AESEV"]);
+    assert_eq!(pinned, table);
+    // The first row has no AESEV, the second a null one.
+    let aesev = pinned.column_index("AESEV").unwrap();
+    assert_eq!(pinned.text(0, aesev), None);
+    assert_eq!(pinned.text(1, aesev).as_deref(), Some(NULL_TEXT));
+    assert!(pinned_table("[{").is_err());
+}
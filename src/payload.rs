@@ -0,0 +1,509 @@
+#[cfg(test)]
+mod test_payload;
+
+use crate::csv::{CsvError, CsvOptions, CsvReader};
+use crate::row_stream::{RowStream, RowStreamError};
+
+use std::io::{Cursor, Write};
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Float64Type, Int64Type, UInt64Type};
+use arrow_array::{Array, ArrayRef, RecordBatch};
+use arrow_buffer::Buffer;
+use arrow_cast::cast;
+use arrow_cast::display::{ArrayFormatter, FormatOptions};
+use arrow_ipc::reader::{FileReader, StreamDecoder};
+use arrow_schema::{ArrowError, DataType};
+use bytes::Bytes;
+use flate2::write::MultiGzDecoder;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Sent as the `Accept` header of table data requests. Kept a CORS-safelisted value, under 128
+/// bytes, so requests to object storage need no preflight.
+pub const ACCEPT: &str = "application/vnd.apache.arrow.stream, application/vnd.apache.parquet, \
+                          application/x-ndjson, text/csv, application/json";
+
+/// How many bytes are read before giving up on recognizing a payload without a known type.
+const SNIFF_LIMIT: usize = 64 * 1024;
+/// The first bytes of a gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+
+pub enum PayloadError {
+    FormatError(String),
+    DeserializeError(String),
+    UnsupportedError(String),
+}
+
+impl PayloadError {
+    pub fn message(&self) -> &String {
+        match self {
+            PayloadError::FormatError(msg)
+            | PayloadError::DeserializeError(msg)
+            | PayloadError::UnsupportedError(msg) => msg,
+        }
+    }
+}
+
+impl From<RowStreamError> for PayloadError {
+    fn from(error: RowStreamError) -> PayloadError {
+        match error {
+            RowStreamError::FormatError(msg) => PayloadError::FormatError(msg),
+            RowStreamError::DeserializeError(msg) => PayloadError::DeserializeError(msg),
+        }
+    }
+}
+
+impl From<CsvError> for PayloadError {
+    fn from(error: CsvError) -> PayloadError {
+        match error {
+            CsvError::FormatError(msg) => PayloadError::FormatError(msg),
+            CsvError::DeserializeError(msg) => PayloadError::DeserializeError(msg),
+        }
+    }
+}
+
+fn gzip_error(error: std::io::Error) -> PayloadError {
+    PayloadError::DeserializeError(format!("Unable to decompress the gzip table data: {error}"))
+}
+
+fn arrow_error(format: PayloadFormat, error: impl std::fmt::Display) -> PayloadError {
+    PayloadError::DeserializeError(format!(
+        "Unable to read the {} table data: {error}",
+        format.describe()
+    ))
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum PayloadFormat {
+    /// A JSON array of rows, or one row per line; told apart by the first byte.
+    Json,
+    Csv,
+    /// The Arrow IPC streaming format, decoded a record batch at a time.
+    ArrowStream,
+    /// The Arrow IPC file format, its footer needs the whole payload.
+    ArrowFile,
+    Parquet,
+}
+
+impl PayloadFormat {
+    /// The format named by the response's content type, or failing that by the extension of the
+    /// url's path, the one before `.gz` for gzipped files. `None` for generic types such as
+    /// `application/octet-stream` or `application/gzip`.
+    pub fn negotiate(content_type: Option<&str>, url: &str) -> Option<PayloadFormat> {
+        let media_type = content_type
+            .and_then(|content_type| content_type.split(';').next())
+            .map(|media_type| media_type.trim().to_lowercase());
+        let from_type = match media_type.as_deref() {
+            Some("application/json" | "text/json") => Some(PayloadFormat::Json),
+            Some(
+                "application/x-ndjson"
+                | "application/ndjson"
+                | "application/jsonl"
+                | "application/x-jsonlines"
+                | "application/jsonlines",
+            ) => Some(PayloadFormat::Json),
+            Some("text/csv" | "application/csv") => Some(PayloadFormat::Csv),
+            Some("application/vnd.apache.arrow.stream") => Some(PayloadFormat::ArrowStream),
+            Some("application/vnd.apache.arrow.file") => Some(PayloadFormat::ArrowFile),
+            Some("application/vnd.apache.parquet" | "application/x-parquet") => {
+                Some(PayloadFormat::Parquet)
+            }
+            _ => None,
+        };
+        from_type.or_else(|| {
+            let path = url.split(['?', '#']).next().unwrap_or_default();
+            let file_name = path.rsplit('/').next().unwrap_or_default().to_lowercase();
+            let file_name = file_name.strip_suffix(".gz").unwrap_or(&file_name);
+            match file_name.rsplit_once('.').map(|(_, extension)| extension) {
+                Some("json" | "ndjson" | "jsonl") => Some(PayloadFormat::Json),
+                Some("csv") => Some(PayloadFormat::Csv),
+                Some("arrows") => Some(PayloadFormat::ArrowStream),
+                Some("arrow" | "feather" | "ipc") => Some(PayloadFormat::ArrowFile),
+                Some("parquet" | "parq") => Some(PayloadFormat::Parquet),
+                _ => None,
+            }
+        })
+    }
+
+    /// The format of a payload from its first bytes, `None` while more bytes are needed to tell.
+    /// `complete` is set once the payload has ended.
+    pub fn sniff(bytes: &[u8], complete: bool) -> Result<Option<PayloadFormat>, PayloadError> {
+        if bytes.starts_with(b"PAR1") {
+            return Ok(Some(PayloadFormat::Parquet));
+        }
+        if bytes.starts_with(b"ARROW1") {
+            return Ok(Some(PayloadFormat::ArrowFile));
+        }
+        if bytes.starts_with(&[0xFF, 0xFF, 0xFF, 0xFF]) {
+            return Ok(Some(PayloadFormat::ArrowStream));
+        }
+        let text = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+        let decided = complete || text.contains(&b'\n') || bytes.len() >= SNIFF_LIMIT;
+        match text.iter().find(|byte| !byte.is_ascii_whitespace()) {
+            Some(b'[' | b'{') => return Ok(Some(PayloadFormat::Json)),
+            // Until the first line has ended, the bytes may be the start of a magic number.
+            _ if !decided => return Ok(None),
+            // An empty payload, an empty table.
+            None => return Ok(Some(PayloadFormat::Json)),
+            Some(_) => (),
+        }
+        let first_line = text.split(|byte| *byte == b'\n').next().unwrap_or_default();
+        match std::str::from_utf8(first_line) {
+            Ok(line) if line.contains([',', ';', '\t', '|']) => Ok(Some(PayloadFormat::Csv)),
+            _ => Err(PayloadError::UnsupportedError(String::from(
+                "The table data is in a format this viewer does not read, expected JSON, NDJSON, CSV, Arrow or Parquet.",
+            ))),
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            PayloadFormat::Json => "JSON",
+            PayloadFormat::Csv => "CSV",
+            PayloadFormat::ArrowStream => "Arrow stream",
+            PayloadFormat::ArrowFile => "Arrow file",
+            PayloadFormat::Parquet => "Parquet",
+        }
+    }
+}
+
+enum DecoderState {
+    /// The format is not known yet, the bytes so far. Arrow payloads are named alike in both
+    /// formats, `hint` is set when the payload is only known to be Arrow.
+    Sniffing {
+        hint: Option<PayloadFormat>,
+        bytes: Vec<u8>,
+    },
+    Rows(RowStream),
+    Csv(CsvReader),
+    ArrowStream(StreamDecoder),
+    /// Formats read once the whole payload is in.
+    Buffered(PayloadFormat, Vec<u8>),
+}
+
+/// Whether the payload is gzipped, told by its first bytes whatever its type or name says.
+enum Compression {
+    /// Fewer bytes than the gzip magic number have been read.
+    Unknown(Vec<u8>),
+    Identity,
+    /// The decompressed bytes collect in the decoder's `Vec` until they are decoded.
+    Gzip(Box<MultiGzDecoder<Vec<u8>>>),
+}
+
+/// Turns a table payload into rows as its bytes arrive, decompressing gzipped payloads on the
+/// way. JSON, NDJSON, CSV and Arrow streams yield rows chunk by chunk, Arrow files and Parquet
+/// when the payload ends.
+pub struct PayloadDecoder {
+    state: DecoderState,
+    compression: Compression,
+    csv: CsvOptions,
+}
+
+impl PayloadDecoder {
+    /// A decoder for `format`, or one recognizing the format from the first bytes when `None`.
+    /// CSV payloads are read with `csv`.
+    pub fn new(format: Option<PayloadFormat>, csv: CsvOptions) -> PayloadDecoder {
+        PayloadDecoder {
+            state: match format {
+                Some(PayloadFormat::ArrowStream | PayloadFormat::ArrowFile) | None => {
+                    DecoderState::Sniffing {
+                        hint: format,
+                        bytes: Vec::new(),
+                    }
+                }
+                Some(format) => PayloadDecoder::state_for(format, csv),
+            },
+            compression: Compression::Unknown(Vec::new()),
+            csv,
+        }
+    }
+
+    fn state_for(format: PayloadFormat, csv: CsvOptions) -> DecoderState {
+        match format {
+            PayloadFormat::Json => DecoderState::Rows(RowStream::new()),
+            PayloadFormat::Csv => DecoderState::Csv(CsvReader::new(csv)),
+            PayloadFormat::ArrowStream => DecoderState::ArrowStream(StreamDecoder::new()),
+            format => DecoderState::Buffered(format, Vec::new()),
+        }
+    }
+
+    /// The format of the payload, `None` until it is recognized.
+    pub fn format(&self) -> Option<PayloadFormat> {
+        match &self.state {
+            DecoderState::Sniffing { .. } => None,
+            DecoderState::Rows(_) => Some(PayloadFormat::Json),
+            DecoderState::Csv(_) => Some(PayloadFormat::Csv),
+            DecoderState::ArrowStream(_) => Some(PayloadFormat::ArrowStream),
+            DecoderState::Buffered(format, _) => Some(*format),
+        }
+    }
+
+    /// Reads the next chunk of the payload and returns the rows it completes.
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<Vec<Map<String, Value>>, PayloadError> {
+        match &mut self.compression {
+            Compression::Unknown(bytes) => {
+                bytes.extend_from_slice(chunk);
+                if bytes.len() < GZIP_MAGIC.len() {
+                    return Ok(Vec::new());
+                }
+                let bytes = std::mem::take(bytes);
+                self.compression = if bytes.starts_with(&GZIP_MAGIC) {
+                    Compression::Gzip(Box::new(MultiGzDecoder::new(Vec::new())))
+                } else {
+                    Compression::Identity
+                };
+                self.feed(&bytes)
+            }
+            Compression::Identity => self.decode(chunk),
+            Compression::Gzip(decoder) => {
+                decoder.write_all(chunk).map_err(gzip_error)?;
+                let bytes = std::mem::take(decoder.get_mut());
+                self.decode(&bytes)
+            }
+        }
+    }
+
+    /// Checks the payload is complete and returns the rows not returned by `feed`.
+    pub fn finish(&mut self) -> Result<Vec<Map<String, Value>>, PayloadError> {
+        let mut rows = match std::mem::replace(&mut self.compression, Compression::Identity) {
+            Compression::Unknown(bytes) => self.decode(&bytes)?,
+            Compression::Identity => Vec::new(),
+            Compression::Gzip(decoder) => {
+                let bytes = decoder.finish().map_err(gzip_error)?;
+                self.decode(&bytes)?
+            }
+        };
+        rows.extend(self.decode_finish()?);
+        Ok(rows)
+    }
+
+    fn decode(&mut self, chunk: &[u8]) -> Result<Vec<Map<String, Value>>, PayloadError> {
+        match &mut self.state {
+            DecoderState::Sniffing { hint, bytes } => {
+                bytes.extend_from_slice(chunk);
+                match recognize(*hint, bytes, false)? {
+                    Some(format) => {
+                        let bytes = std::mem::take(bytes);
+                        self.state = PayloadDecoder::state_for(format, self.csv);
+                        self.decode(&bytes)
+                    }
+                    None => Ok(Vec::new()),
+                }
+            }
+            DecoderState::Rows(stream) => Ok(stream.feed(chunk)?),
+            DecoderState::Csv(reader) => Ok(reader.feed(chunk)?),
+            DecoderState::ArrowStream(decoder) => {
+                let mut buffer = Buffer::from(chunk);
+                let mut rows = Vec::new();
+                while !buffer.is_empty() {
+                    match decoder.decode(&mut buffer) {
+                        Ok(Some(batch)) => rows.extend(batch_records(&batch)?),
+                        Ok(None) => (),
+                        Err(error) => return Err(arrow_error(PayloadFormat::ArrowStream, error)),
+                    }
+                }
+                Ok(rows)
+            }
+            DecoderState::Buffered(_, bytes) => {
+                bytes.extend_from_slice(chunk);
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    fn decode_finish(&mut self) -> Result<Vec<Map<String, Value>>, PayloadError> {
+        match &mut self.state {
+            DecoderState::Sniffing { hint, bytes } => {
+                let bytes = std::mem::take(bytes);
+                match recognize(*hint, &bytes, true)? {
+                    Some(format) => {
+                        self.state = PayloadDecoder::state_for(format, self.csv);
+                        let mut rows = self.decode(&bytes)?;
+                        rows.extend(self.decode_finish()?);
+                        Ok(rows)
+                    }
+                    None => Ok(Vec::new()),
+                }
+            }
+            DecoderState::Rows(stream) => {
+                stream.finish()?;
+                Ok(Vec::new())
+            }
+            DecoderState::Csv(reader) => Ok(reader.finish()?),
+            DecoderState::ArrowStream(decoder) => {
+                decoder
+                    .finish()
+                    .map_err(|error| arrow_error(PayloadFormat::ArrowStream, error))?;
+                Ok(Vec::new())
+            }
+            DecoderState::Buffered(format, bytes) => {
+                let bytes = std::mem::take(bytes);
+                match format {
+                    PayloadFormat::ArrowFile => arrow_file_records(bytes),
+                    PayloadFormat::Parquet => parquet_records(bytes),
+                    PayloadFormat::Json | PayloadFormat::Csv | PayloadFormat::ArrowStream => {
+                        Ok(Vec::new())
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The format of a payload known to be `hint`, or recognized from its first bytes.
+fn recognize(
+    hint: Option<PayloadFormat>,
+    bytes: &[u8],
+    complete: bool,
+) -> Result<Option<PayloadFormat>, PayloadError> {
+    match hint {
+        Some(PayloadFormat::ArrowStream | PayloadFormat::ArrowFile) => {
+            if bytes.starts_with(b"ARROW1") {
+                Ok(Some(PayloadFormat::ArrowFile))
+            } else if bytes.len() >= 6 || complete {
+                Ok(Some(PayloadFormat::ArrowStream))
+            } else {
+                Ok(None)
+            }
+        }
+        Some(format) => Ok(Some(format)),
+        None => PayloadFormat::sniff(bytes, complete),
+    }
+}
+
+fn arrow_file_records(bytes: Vec<u8>) -> Result<Vec<Map<String, Value>>, PayloadError> {
+    let format = PayloadFormat::ArrowFile;
+    let reader = FileReader::try_new(Cursor::new(bytes), None)
+        .map_err(|error| arrow_error(format, error))?;
+    let mut rows = Vec::new();
+    for batch in reader {
+        rows.extend(batch_records(
+            &batch.map_err(|error| arrow_error(format, error))?,
+        )?);
+    }
+    Ok(rows)
+}
+
+fn parquet_records(bytes: Vec<u8>) -> Result<Vec<Map<String, Value>>, PayloadError> {
+    let format = PayloadFormat::Parquet;
+    let reader = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(bytes))
+        .and_then(|builder| builder.build())
+        .map_err(|error| arrow_error(format, error))?;
+    let mut rows = Vec::new();
+    for batch in reader {
+        rows.extend(batch_records(
+            &batch.map_err(|error| arrow_error(format, error))?,
+        )?);
+    }
+    Ok(rows)
+}
+
+/// A column of a record batch, cast to the few types rows are made of.
+enum BatchColumn {
+    Null,
+    Boolean(ArrayRef),
+    Integer(ArrayRef),
+    Unsigned(ArrayRef),
+    Float(ArrayRef),
+    Text(ArrayRef),
+    /// Dates, times, decimals and anything else, shown as Arrow displays them.
+    Formatted(ArrayRef),
+}
+
+impl BatchColumn {
+    fn new(array: &ArrayRef) -> Result<BatchColumn, ArrowError> {
+        Ok(match array.data_type() {
+            DataType::Null => BatchColumn::Null,
+            DataType::Boolean => BatchColumn::Boolean(array.clone()),
+            DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32 => BatchColumn::Integer(cast(array, &DataType::Int64)?),
+            DataType::UInt64 => BatchColumn::Unsigned(array.clone()),
+            DataType::Float16 | DataType::Float32 | DataType::Float64 => {
+                BatchColumn::Float(cast(array, &DataType::Float64)?)
+            }
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => {
+                BatchColumn::Text(cast(array, &DataType::Utf8)?)
+            }
+            DataType::Dictionary(_, value_type)
+                if matches!(
+                    **value_type,
+                    DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+                ) =>
+            {
+                BatchColumn::Text(cast(array, &DataType::Utf8)?)
+            }
+            _ => BatchColumn::Formatted(array.clone()),
+        })
+    }
+}
+
+/// The rows of a record batch, keyed by column name.
+fn batch_records(batch: &RecordBatch) -> Result<Vec<Map<String, Value>>, PayloadError> {
+    let to_error = |error: ArrowError| {
+        PayloadError::DeserializeError(format!("Unable to read a record batch: {error}"))
+    };
+    let schema = batch.schema();
+    let columns = batch
+        .columns()
+        .iter()
+        .map(BatchColumn::new)
+        .collect::<Result<Vec<BatchColumn>, ArrowError>>()
+        .map_err(to_error)?;
+    let options = FormatOptions::default();
+    let formatters = columns
+        .iter()
+        .map(|column| match column {
+            BatchColumn::Formatted(array) => ArrayFormatter::try_new(array, &options).map(Some),
+            _ => Ok(None),
+        })
+        .collect::<Result<Vec<Option<ArrayFormatter>>, ArrowError>>()
+        .map_err(to_error)?;
+    let names: Vec<&String> = schema.fields().iter().map(|field| field.name()).collect();
+    let rows = (0..batch.num_rows())
+        .map(|row| {
+            columns
+                .iter()
+                .zip(&formatters)
+                .zip(&names)
+                .map(|((column, formatter), name)| {
+                    ((*name).clone(), batch_value(column, formatter, row))
+                })
+                .collect()
+        })
+        .collect();
+    Ok(rows)
+}
+
+fn batch_value(column: &BatchColumn, formatter: &Option<ArrayFormatter>, row: usize) -> Value {
+    let array = match column {
+        BatchColumn::Null => return Value::Null,
+        BatchColumn::Boolean(array)
+        | BatchColumn::Integer(array)
+        | BatchColumn::Unsigned(array)
+        | BatchColumn::Float(array)
+        | BatchColumn::Text(array)
+        | BatchColumn::Formatted(array) => array,
+    };
+    if array.is_null(row) {
+        return Value::Null;
+    }
+    match column {
+        BatchColumn::Null => Value::Null,
+        BatchColumn::Boolean(array) => Value::from(array.as_boolean().value(row)),
+        BatchColumn::Integer(array) => Value::from(array.as_primitive::<Int64Type>().value(row)),
+        BatchColumn::Unsigned(array) => Value::from(array.as_primitive::<UInt64Type>().value(row)),
+        BatchColumn::Float(array) => Value::from(array.as_primitive::<Float64Type>().value(row)),
+        BatchColumn::Text(array) => Value::from(array.as_string::<i32>().value(row)),
+        BatchColumn::Formatted(_) => match formatter {
+            Some(formatter) => Value::from(formatter.value(row).to_string()),
+            None => Value::Null,
+        },
+    }
+}
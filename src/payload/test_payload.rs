@@ -0,0 +1,188 @@
+use super::{PayloadDecoder, PayloadFormat};
+use crate::csv::CsvOptions;
+use arrow_array::{
+    ArrayRef, BooleanArray, Date32Array, Float64Array, Int32Array, RecordBatch, StringArray,
+};
+use arrow_ipc::writer::{FileWriter, StreamWriter};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use parquet::arrow::ArrowWriter;
+use serde_json::{json, Map, Value};
+use std::io::Write;
+use std::sync::Arc;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn lab_batch() -> RecordBatch {
+    let columns: Vec<(&str, ArrayRef)> = vec![
+        (
+            "USUBJID",
+            Arc::new(StringArray::from(vec!["1023-004", "1023-005"])),
+        ),
+        ("LBSEQ", Arc::new(Int32Array::from(vec![1, 2]))),
+        (
+            "LBSTRESN",
+            Arc::new(Float64Array::from(vec![Some(41.5), None])),
+        ),
+        ("LBBLFL", Arc::new(BooleanArray::from(vec![true, false]))),
+        ("LBDT", Arc::new(Date32Array::from(vec![19723, 19730]))),
+    ];
+    RecordBatch::try_from_iter(columns).unwrap()
+}
+
+fn lab_rows() -> Vec<Map<String, Value>> {
+    vec![
+        json!({"USUBJID": "1023-004", "LBSEQ": 1, "LBSTRESN": 41.5, "LBBLFL": true, "LBDT": "2024-01-01"}),
+        json!({"USUBJID": "1023-005", "LBSEQ": 2, "LBSTRESN": null, "LBBLFL": false, "LBDT": "2024-01-08"}),
+    ]
+    .into_iter()
+    .map(|row| row.as_object().unwrap().clone())
+    .collect()
+}
+
+/// Feeds the payload in chunks of `size` bytes and collects the rows.
+fn decode(
+    format: Option<PayloadFormat>,
+    payload: &[u8],
+    size: usize,
+) -> Result<Vec<Map<String, Value>>, String> {
+    let mut decoder = PayloadDecoder::new(format, CsvOptions::default());
+    let mut rows = Vec::new();
+    for chunk in payload.chunks(size) {
+        rows.extend(
+            decoder
+                .feed(chunk)
+                .map_err(|error| error.message().clone())?,
+        );
+    }
+    rows.extend(decoder.finish().map_err(|error| error.message().clone())?);
+    Ok(rows)
+}
+
+#[wasm_bindgen_test]
+fn test_negotiate() {
+    let negotiate = PayloadFormat::negotiate;
+    assert_eq!(
+        negotiate(Some("application/vnd.apache.arrow.stream"), "/data/lb"),
+        Some(PayloadFormat::ArrowStream)
+    );
+    assert_eq!(
+        negotiate(Some("text/csv; charset=utf-8"), "/data/lb.json"),
+        Some(PayloadFormat::Csv)
+    );
+    assert_eq!(
+        negotiate(
+            Some("application/octet-stream"),
+            "https://lake.example/lb.parquet?X-Amz-Signature=1"
+        ),
+        Some(PayloadFormat::Parquet)
+    );
+    assert_eq!(
+        negotiate(None, "/data/lb.ndjson"),
+        Some(PayloadFormat::Json)
+    );
+    assert_eq!(
+        negotiate(
+            Some("application/gzip"),
+            "/extracts/lb.csv.gz?X-Amz-Signature=1"
+        ),
+        Some(PayloadFormat::Csv)
+    );
+    assert_eq!(negotiate(Some("binary/octet-stream"), "/data/lb"), None);
+}
+
+#[wasm_bindgen_test]
+fn test_sniff() {
+    let sniff = |bytes: &[u8], complete| PayloadFormat::sniff(bytes, complete).ok().unwrap();
+    assert_eq!(sniff(b"PAR1\x15\x04", false), Some(PayloadFormat::Parquet));
+    assert_eq!(sniff(b"ARROW1\0\0", false), Some(PayloadFormat::ArrowFile));
+    assert_eq!(
+        sniff(&[0xFF, 0xFF, 0xFF, 0xFF, 0x78], false),
+        Some(PayloadFormat::ArrowStream)
+    );
+    assert_eq!(sniff(b"\xEF\xBB\xBF [{", false), Some(PayloadFormat::Json));
+    assert_eq!(sniff(b"PA", false), None);
+    assert_eq!(sniff(b"subject,term", false), None);
+    assert_eq!(sniff(b"subject,term\n", false), Some(PayloadFormat::Csv));
+    assert!(PayloadFormat::sniff(b"<html>\n", false).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_decode_arrow_stream_in_chunks() {
+    let mut payload = Vec::new();
+    let mut writer = StreamWriter::try_new(&mut payload, &lab_batch().schema()).unwrap();
+    writer.write(&lab_batch()).unwrap();
+    writer.finish().unwrap();
+    drop(writer);
+    for size in [7, payload.len()] {
+        assert_eq!(decode(None, &payload, size).unwrap(), lab_rows());
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_decode_arrow_file_named_as_stream() {
+    let mut payload = Vec::new();
+    let mut writer = FileWriter::try_new(&mut payload, &lab_batch().schema()).unwrap();
+    writer.write(&lab_batch()).unwrap();
+    writer.finish().unwrap();
+    drop(writer);
+    let rows = decode(Some(PayloadFormat::ArrowStream), &payload, 64).unwrap();
+    assert_eq!(rows, lab_rows());
+}
+
+#[wasm_bindgen_test]
+fn test_decode_parquet() {
+    let mut payload = Vec::new();
+    let mut writer = ArrowWriter::try_new(&mut payload, lab_batch().schema(), None).unwrap();
+    writer.write(&lab_batch()).unwrap();
+    writer.close().unwrap();
+    assert_eq!(decode(None, &payload, 100).unwrap(), lab_rows());
+}
+
+#[wasm_bindgen_test]
+fn test_decode_csv() {
+    let payload = "subject;term;grade\r\n1023-004;\"Headache; mild\";1\r\n1023-005;Rash;2\r\n";
+    let rows = decode(Some(PayloadFormat::Csv), payload.as_bytes(), 5).unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            json!({"subject": "1023-004", "term": "Headache; mild", "grade": 1}),
+            json!({"subject": "1023-005", "term": "Rash", "grade": 2}),
+        ]
+        .into_iter()
+        .map(|row| row.as_object().unwrap().clone())
+        .collect::<Vec<_>>()
+    );
+    assert!(decode(Some(PayloadFormat::Csv), b"subject,term\n1023-004\n", 5).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_decode_gzip_in_chunks() {
+    let gzip = |payload: &[u8]| {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(payload).unwrap();
+        encoder.finish().unwrap()
+    };
+    let csv = gzip(b"subject,term\n1023-004,Headache\n1023-005,Rash\n");
+    for (format, size) in [(Some(PayloadFormat::Csv), 1), (None, 9), (None, csv.len())] {
+        let rows = decode(format, &csv, size).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1]["term"], "Rash");
+    }
+    let mut payload = Vec::new();
+    let mut writer = StreamWriter::try_new(&mut payload, &lab_batch().schema()).unwrap();
+    writer.write(&lab_batch()).unwrap();
+    writer.finish().unwrap();
+    drop(writer);
+    assert_eq!(decode(None, &gzip(&payload), 13).unwrap(), lab_rows());
+
+    let mut truncated = csv.clone();
+    truncated.truncate(csv.len() / 2);
+    truncated[8] ^= 0xFF;
+    assert!(decode(None, &truncated, 4).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_unknown_payload_fails_plainly() {
+    let error = decode(None, b"<html><body>Access denied</body></html>\n", 8).unwrap_err();
+    assert!(error.contains("expected JSON, NDJSON, CSV, Arrow or Parquet"));
+}
@@ -0,0 +1,357 @@
+#[cfg(test)]
+mod test_pivot;
+
+use crate::i18n::{Locale, Message};
+use crate::table::{Table, NULL_TEXT};
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+/// Row label of the grand total, the UI shows it translated.
+pub const TOTAL_LABEL: &str = "Total";
+/// Row label of a subtotal, the UI shows it translated.
+pub const SUBTOTAL_LABEL: &str = "Subtotal";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Aggregation {
+    Count,
+    DistinctCount,
+    Sum,
+    Mean,
+    Min,
+    Max,
+}
+
+impl Aggregation {
+    pub const ALL: [Aggregation; 6] = [
+        Aggregation::Count,
+        Aggregation::DistinctCount,
+        Aggregation::Sum,
+        Aggregation::Mean,
+        Aggregation::Min,
+        Aggregation::Max,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Aggregation::Count => "count",
+            Aggregation::DistinctCount => "distinct count",
+            Aggregation::Sum => "sum",
+            Aggregation::Mean => "mean",
+            Aggregation::Min => "min",
+            Aggregation::Max => "max",
+        }
+    }
+
+    /// The aggregation's name in the UI.
+    pub fn name(&self) -> Message {
+        Message::new(match self {
+            Aggregation::Count => "aggregation-count",
+            Aggregation::DistinctCount => "aggregation-distinct-count",
+            Aggregation::Sum => "aggregation-sum",
+            Aggregation::Mean => "aggregation-mean",
+            Aggregation::Min => "aggregation-min",
+            Aggregation::Max => "aggregation-max",
+        })
+    }
+
+    pub fn from_label(label: &str) -> Option<Aggregation> {
+        Aggregation::ALL
+            .iter()
+            .find(|aggregation| aggregation.label() == label)
+            .copied()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PivotValue {
+    pub field: String,
+    pub aggregation: Aggregation,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct PivotDefinition {
+    pub rows: Vec<String>,
+    pub columns: Vec<String>,
+    pub values: Vec<PivotValue>,
+}
+
+impl PivotDefinition {
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty() && self.columns.is_empty() && self.values.is_empty()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PivotRowKind {
+    Data,
+    Subtotal,
+    GrandTotal,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PivotRow {
+    pub kind: PivotRowKind,
+    /// One label per row field; levels below a subtotal are left empty.
+    pub labels: Vec<String>,
+    /// One cell per entry of `PivotResult::columns`, `None` when there is nothing to aggregate.
+    pub cells: Vec<Option<f64>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PivotColumn {
+    /// Values of the column fields, `None` for the row total column.
+    pub key: Option<Vec<String>>,
+    pub value: PivotValue,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct PivotResult {
+    pub row_fields: Vec<String>,
+    pub column_fields: Vec<String>,
+    pub columns: Vec<PivotColumn>,
+    pub rows: Vec<PivotRow>,
+}
+
+/// A row key prefix and a column key, `None` standing for the row total column.
+type CellKey = (Vec<String>, Option<Vec<String>>);
+
+#[derive(Default, Clone)]
+struct Accumulator {
+    count: usize,
+    distinct: HashSet<String>,
+    numeric: usize,
+    sum: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl Accumulator {
+    fn add(&mut self, value: &str) {
+        if value == NULL_TEXT {
+            return;
+        }
+        self.count += 1;
+        self.distinct.insert(value.to_owned());
+        if let Ok(number) = value.trim().parse::<f64>() {
+            self.numeric += 1;
+            self.sum += number;
+            self.min = Some(self.min.map_or(number, |min| min.min(number)));
+            self.max = Some(self.max.map_or(number, |max| max.max(number)));
+        }
+    }
+
+    fn finish(&self, aggregation: Aggregation) -> Option<f64> {
+        match aggregation {
+            Aggregation::Count => Some(self.count as f64),
+            Aggregation::DistinctCount => Some(self.distinct.len() as f64),
+            Aggregation::Sum if self.numeric > 0 => Some(self.sum),
+            Aggregation::Mean if self.numeric > 0 => Some(self.sum / self.numeric as f64),
+            Aggregation::Min => self.min,
+            Aggregation::Max => self.max,
+            _ => None,
+        }
+    }
+}
+
+/// Compares cell values numerically when both sides are numbers, lexically otherwise.
+pub fn compare_values(left: &str, right: &str) -> Ordering {
+    match (left.trim().parse::<f64>(), right.trim().parse::<f64>()) {
+        (Ok(l), Ok(r)) => l.partial_cmp(&r).unwrap_or(Ordering::Equal),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => left.cmp(right),
+    }
+}
+
+fn compare_keys(left: &[String], right: &[String]) -> Ordering {
+    for (l, r) in left.iter().zip(right.iter()) {
+        match compare_values(l, r) {
+            Ordering::Equal => continue,
+            ordering => return ordering,
+        }
+    }
+    left.len().cmp(&right.len())
+}
+
+fn key_of(table: &Table, row: usize, fields: &[Option<usize>]) -> Vec<String> {
+    fields
+        .iter()
+        .map(|field| {
+            field
+                .and_then(|field| table.text(row, field))
+                .map(|text| text.into_owned())
+                .unwrap_or_else(|| NULL_TEXT.to_owned())
+        })
+        .collect()
+}
+
+/// Builds the crosstab described by `definition`.
+///
+/// Every prefix of the row key is accumulated from the raw values, so subtotals and
+/// totals of distinct counts and means are exact rather than aggregates of aggregates.
+pub fn compute_pivot(definition: &PivotDefinition, table: &Table) -> PivotResult {
+    let values = if definition.values.is_empty() {
+        vec![PivotValue {
+            field: String::new(),
+            aggregation: Aggregation::Count,
+        }]
+    } else {
+        definition.values.clone()
+    };
+
+    let mut accumulators: HashMap<CellKey, Vec<Accumulator>> = HashMap::new();
+    let mut row_keys: HashSet<Vec<String>> = HashSet::new();
+    let mut column_keys: HashSet<Vec<String>> = HashSet::new();
+
+    let indices = |fields: &[String]| -> Vec<Option<usize>> {
+        fields
+            .iter()
+            .map(|field| table.column_index(field))
+            .collect()
+    };
+    let row_fields = indices(&definition.rows);
+    let column_fields = indices(&definition.columns);
+    let value_fields: Vec<Option<usize>> = values
+        .iter()
+        .map(|value| table.column_index(&value.field))
+        .collect();
+
+    for row in 0..table.len() {
+        let row_key = key_of(table, row, &row_fields);
+        let column_key = key_of(table, row, &column_fields);
+        for depth in 0..=row_key.len() {
+            let prefix = row_key[..depth].to_vec();
+            for column in [Some(column_key.clone()), None] {
+                let cells = accumulators
+                    .entry((prefix.clone(), column))
+                    .or_insert_with(|| vec![Accumulator::default(); values.len()]);
+                for ((cell, value), field) in
+                    cells.iter_mut().zip(values.iter()).zip(value_fields.iter())
+                {
+                    if value.field.is_empty() {
+                        cell.add("");
+                    } else if let Some(text) = field.and_then(|field| table.text(row, field)) {
+                        cell.add(&text);
+                    }
+                }
+            }
+        }
+        row_keys.insert(row_key);
+        column_keys.insert(column_key);
+    }
+
+    let mut column_keys: Vec<Vec<String>> = column_keys.into_iter().collect();
+    column_keys.sort_by(|l, r| compare_keys(l, r));
+    let mut columns = Vec::<PivotColumn>::new();
+    if !definition.columns.is_empty() {
+        for key in column_keys.iter() {
+            for value in values.iter() {
+                columns.push(PivotColumn {
+                    key: Some(key.clone()),
+                    value: value.clone(),
+                });
+            }
+        }
+    }
+    for value in values.iter() {
+        columns.push(PivotColumn {
+            key: None,
+            value: value.clone(),
+        });
+    }
+
+    let cells_for = |prefix: &[String]| -> Vec<Option<f64>> {
+        columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let value_index = i % values.len();
+                accumulators
+                    .get(&(prefix.to_vec(), column.key.clone()))
+                    .and_then(|cells| cells[value_index].finish(column.value.aggregation))
+            })
+            .collect()
+    };
+
+    let mut row_keys: Vec<Vec<String>> = row_keys.into_iter().collect();
+    row_keys.sort_by(|l, r| compare_keys(l, r));
+    let depth = definition.rows.len();
+    let mut rows = Vec::<PivotRow>::new();
+    for (i, key) in row_keys.iter().enumerate().filter(|_| depth > 0) {
+        rows.push(PivotRow {
+            kind: PivotRowKind::Data,
+            labels: key.clone(),
+            cells: cells_for(key),
+        });
+        // Close every group whose prefix changes with the next row, innermost first.
+        let next = row_keys.get(i + 1);
+        for level in (1..depth).rev() {
+            let closes = match next {
+                Some(next_key) => next_key[..level] != key[..level],
+                None => true,
+            };
+            if closes {
+                let mut labels = key[..level].to_vec();
+                labels.push(String::from(SUBTOTAL_LABEL));
+                labels.resize(depth, String::new());
+                rows.push(PivotRow {
+                    kind: PivotRowKind::Subtotal,
+                    labels,
+                    cells: cells_for(&key[..level]),
+                });
+            }
+        }
+    }
+    if !table.is_empty() {
+        let mut labels = vec![String::new(); depth.max(1)];
+        labels[0] = String::from(TOTAL_LABEL);
+        rows.push(PivotRow {
+            kind: PivotRowKind::GrandTotal,
+            labels,
+            cells: cells_for(&[]),
+        });
+    }
+
+    PivotResult {
+        row_fields: definition.rows.clone(),
+        column_fields: definition.columns.clone(),
+        columns,
+        rows,
+    }
+}
+
+pub fn format_cell(value: Option<f64>) -> String {
+    match value {
+        Some(number) if number.fract() == 0.0 && number.abs() < 1e15 => {
+            format!("{}", number as i64)
+        }
+        Some(number) => format!("{:.5}", number),
+        None => String::new(),
+    }
+}
+
+pub fn column_header(locale: Locale, column: &PivotColumn, column_fields: &[String]) -> String {
+    let value_label = if column.value.field.is_empty() {
+        Aggregation::Count.name().text(locale)
+    } else {
+        format!(
+            "{}({})",
+            column.value.aggregation.name().text(locale),
+            column.value.field
+        )
+    };
+    match &column.key {
+        Some(key) => {
+            let key_label: Vec<String> = column_fields
+                .iter()
+                .zip(key.iter())
+                .map(|(field, value)| format!("{field}={value}"))
+                .collect();
+            format!("{} {}", key_label.join(" / "), value_label)
+        }
+        None => format!("{} {}", locale.tr("pivot-total"), value_label),
+    }
+}
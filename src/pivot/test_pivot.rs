@@ -0,0 +1,124 @@
+use super::{
+    column_header, compute_pivot, format_cell, Aggregation, PivotDefinition, PivotRowKind,
+    PivotValue,
+};
+use crate::i18n::Locale;
+use crate::table::Table;
+use serde_json::{json, Map, Value};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn visits() -> Table {
+    let rows = json!([
+        {"subject": "001", "site": "10", "visit": "1", "value": 2.0},
+        {"subject": "001", "site": "10", "visit": "2", "value": 4.0},
+        {"subject": "002", "site": "10", "visit": "1", "value": null},
+        {"subject": "003", "site": "9", "visit": "1", "value": 6.0},
+    ]);
+    let records: Vec<Map<String, Value>> = serde_json::from_value(rows).unwrap();
+    Table::from_records(&records).ok().unwrap()
+}
+
+#[wasm_bindgen_test]
+fn test_compute_pivot_counts_subjects_by_site_and_visit() {
+    let definition = PivotDefinition {
+        rows: vec![String::from("site")],
+        columns: vec![String::from("visit")],
+        values: vec![PivotValue {
+            field: String::from("subject"),
+            aggregation: Aggregation::DistinctCount,
+        }],
+    };
+    let result = compute_pivot(&definition, &visits());
+    // visit=1, visit=2, Total
+    assert_eq!(result.columns.len(), 3);
+    assert_eq!(result.rows.len(), 3);
+    // numeric ordering puts site 9 before site 10
+    assert_eq!(result.rows[0].labels, vec![String::from("9")]);
+    assert_eq!(result.rows[0].cells, vec![Some(1.0), None, Some(1.0)]);
+    assert_eq!(result.rows[1].cells, vec![Some(2.0), Some(1.0), Some(2.0)]);
+    assert_eq!(result.rows[2].kind, PivotRowKind::GrandTotal);
+    assert_eq!(result.rows[2].cells, vec![Some(3.0), Some(1.0), Some(3.0)]);
+}
+
+#[wasm_bindgen_test]
+fn test_compute_pivot_adds_subtotals_for_nested_rows() {
+    let definition = PivotDefinition {
+        rows: vec![String::from("site"), String::from("subject")],
+        columns: vec![],
+        values: vec![PivotValue {
+            field: String::from("value"),
+            aggregation: Aggregation::Mean,
+        }],
+    };
+    let result = compute_pivot(&definition, &visits());
+    let kinds: Vec<PivotRowKind> = result.rows.iter().map(|r| r.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            PivotRowKind::Data,
+            PivotRowKind::Subtotal,
+            PivotRowKind::Data,
+            PivotRowKind::Data,
+            PivotRowKind::Subtotal,
+            PivotRowKind::GrandTotal,
+        ]
+    );
+    // subject 002 only has a null value
+    assert_eq!(result.rows[3].cells, vec![None]);
+    // the site 10 subtotal is the mean of the raw values, not of the subject means
+    assert_eq!(result.rows[4].cells, vec![Some(3.0)]);
+    assert_eq!(result.rows[5].cells, vec![Some(4.0)]);
+}
+
+#[wasm_bindgen_test]
+fn test_compute_pivot_without_values_counts_rows() {
+    let definition = PivotDefinition {
+        rows: vec![],
+        columns: vec![String::from("site")],
+        values: vec![],
+    };
+    let result = compute_pivot(&definition, &visits());
+    assert_eq!(result.rows.len(), 1);
+    assert_eq!(result.rows[0].cells, vec![Some(1.0), Some(3.0), Some(4.0)]);
+    assert_eq!(
+        column_header(Locale::EnUs, &result.columns[0], &result.column_fields),
+        String::from("site=9 count")
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_compute_pivot_sum_min_max() {
+    let definition = PivotDefinition {
+        rows: vec![],
+        columns: vec![],
+        values: vec![
+            PivotValue {
+                field: String::from("value"),
+                aggregation: Aggregation::Sum,
+            },
+            PivotValue {
+                field: String::from("value"),
+                aggregation: Aggregation::Min,
+            },
+            PivotValue {
+                field: String::from("value"),
+                aggregation: Aggregation::Max,
+            },
+        ],
+    };
+    let result = compute_pivot(&definition, &visits());
+    assert_eq!(result.rows[0].cells, vec![Some(12.0), Some(2.0), Some(6.0)]);
+}
+
+#[wasm_bindgen_test]
+fn test_compute_pivot_with_empty_data() {
+    let result = compute_pivot(&PivotDefinition::default(), &Table::new());
+    assert!(result.rows.is_empty());
+}
+
+#[wasm_bindgen_test]
+fn test_format_cell() {
+    assert_eq!(format_cell(Some(3.0)), String::from("3"));
+    assert_eq!(format_cell(Some(0.25)), String::from("0.25000"));
+    assert_eq!(format_cell(None), String::new());
+}
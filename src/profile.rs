@@ -0,0 +1,134 @@
+#[cfg(test)]
+mod test_profile;
+
+use crate::i18n::Message;
+use crate::table::{ColumnValues, Table};
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The most frequent values listed for each column.
+const TOP_VALUES: usize = 5;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ColumnKind {
+    Empty,
+    Text,
+    Integer,
+    Float,
+    Boolean,
+    Mixed,
+}
+
+impl ColumnKind {
+    fn of(values: &ColumnValues) -> ColumnKind {
+        match values {
+            ColumnValues::Empty => ColumnKind::Empty,
+            ColumnValues::Text(_) => ColumnKind::Text,
+            ColumnValues::Integer(_) => ColumnKind::Integer,
+            ColumnValues::Float(_) => ColumnKind::Float,
+            ColumnValues::Boolean(_) => ColumnKind::Boolean,
+            ColumnValues::Mixed(_) => ColumnKind::Mixed,
+        }
+    }
+
+    pub fn describe(&self) -> Message {
+        Message::new(match self {
+            ColumnKind::Empty => "kind-empty",
+            ColumnKind::Text => "kind-text",
+            ColumnKind::Integer => "kind-integer",
+            ColumnKind::Float => "kind-decimal",
+            ColumnKind::Boolean => "kind-boolean",
+            ColumnKind::Mixed => "kind-mixed",
+        })
+    }
+}
+
+/// Summary statistics of a column.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ColumnProfile {
+    pub name: String,
+    pub kind: ColumnKind,
+    /// Cells holding a value.
+    pub filled: usize,
+    /// Null cells and rows without the column.
+    pub empty: usize,
+    pub distinct: usize,
+    /// The smallest and largest value, by number in number columns and as text in the others.
+    pub min: Option<String>,
+    pub max: Option<String>,
+    /// The mean of a number column.
+    pub mean: Option<f64>,
+    /// The most frequent values with their counts, most frequent first.
+    pub top: Vec<(String, usize)>,
+}
+
+/// Statistics of every column of `table`, in column order.
+pub fn profile_table(table: &Table) -> Vec<ColumnProfile> {
+    (0..table.columns().len())
+        .map(|column| profile_column(table, column))
+        .collect()
+}
+
+fn profile_column(table: &Table, index: usize) -> ColumnProfile {
+    let column = &table.columns()[index];
+    let filled_rows: Vec<usize> = (0..table.len())
+        .filter(|row| !column.is_null(*row))
+        .collect();
+    let mut counts: HashMap<Cow<str>, usize> = HashMap::new();
+    for row in &filled_rows {
+        if let Some(text) = table.text(*row, index) {
+            *counts.entry(text).or_default() += 1;
+        }
+    }
+    let numbers: Vec<(usize, f64)> = match column.values() {
+        ColumnValues::Integer(values) => filled_rows
+            .iter()
+            .map(|row| (*row, values[*row] as f64))
+            .collect(),
+        ColumnValues::Float(values) => filled_rows.iter().map(|row| (*row, values[*row])).collect(),
+        _ => Vec::new(),
+    };
+    let text = |row: usize| table.text(row, index).map(String::from);
+    let (min, max, mean) = if numbers.is_empty() {
+        (
+            counts.keys().min().map(|text| text.to_string()),
+            counts.keys().max().map(|text| text.to_string()),
+            None,
+        )
+    } else {
+        let by_value = |left: &&(usize, f64), right: &&(usize, f64)| left.1.total_cmp(&right.1);
+        let sum: f64 = numbers.iter().map(|(_, number)| number).sum();
+        (
+            numbers
+                .iter()
+                .min_by(by_value)
+                .and_then(|(row, _)| text(*row)),
+            numbers
+                .iter()
+                .max_by(by_value)
+                .and_then(|(row, _)| text(*row)),
+            Some(sum / numbers.len() as f64),
+        )
+    };
+    let distinct = counts.len();
+    let mut top: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(text, count)| (text.into_owned(), count))
+        .collect();
+    top.sort_by(|left, right| right.1.cmp(&left.1).then_with(|| left.0.cmp(&right.0)));
+    top.truncate(TOP_VALUES);
+    ColumnProfile {
+        name: column.name().to_string(),
+        kind: ColumnKind::of(column.values()),
+        filled: filled_rows.len(),
+        empty: table.len() - filled_rows.len(),
+        distinct,
+        min,
+        max,
+        mean,
+        top,
+    }
+}
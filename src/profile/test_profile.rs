@@ -0,0 +1,58 @@
+use super::{profile_table, ColumnKind};
+use crate::table::Table;
+use serde_json::{json, Map, Value};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn table(value: Value) -> Table {
+    let records: Vec<Map<String, Value>> = value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|record| record.as_object().unwrap().clone())
+        .collect();
+    Table::from_records(&records).ok().unwrap()
+}
+
+#[wasm_bindgen_test]
+fn test_profile_table() {
+    let table = table(json!([
+        {"USUBJID": "1023-004", "AESEV": "MILD", "AESEQ": 1, "dose": 2.5},
+        {"USUBJID": "1023-004", "AESEV": "SEVERE", "AESEQ": 2, "dose": null},
+        {"USUBJID": "1023-005", "AESEV": "MILD", "AESEQ": 10},
+        {"USUBJID": "1023-006", "AESEV": null, "AESEQ": -3, "dose": 10.0},
+    ]));
+    let profiles = profile_table(&table);
+    let profile = |name: &str| {
+        profiles
+            .iter()
+            .find(|profile| profile.name == name)
+            .unwrap()
+    };
+
+    let severity = profile("AESEV");
+    assert_eq!(severity.kind, ColumnKind::Text);
+    assert_eq!(
+        (severity.filled, severity.empty, severity.distinct),
+        (3, 1, 2)
+    );
+    assert_eq!(severity.min.as_deref(), Some("MILD"));
+    assert_eq!(severity.max.as_deref(), Some("SEVERE"));
+    assert_eq!(
+        severity.top,
+        vec![(String::from("MILD"), 2), (String::from("SEVERE"), 1)]
+    );
+    assert_eq!(severity.mean, None);
+
+    // Numbers order as numbers, not as text.
+    let sequence = profile("AESEQ");
+    assert_eq!(sequence.kind, ColumnKind::Integer);
+    assert_eq!(sequence.min.as_deref(), Some("-3"));
+    assert_eq!(sequence.max.as_deref(), Some("10"));
+    assert_eq!(sequence.mean, Some(2.5));
+
+    // The row without a dose counts as empty, like the null one.
+    let dose = profile("dose");
+    assert_eq!((dose.filled, dose.empty), (2, 2));
+    assert_eq!(dose.max.as_deref(), Some("10.00000"));
+    assert_eq!(dose.mean, Some(6.25));
+}
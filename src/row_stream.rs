@@ -0,0 +1,187 @@
+#[cfg(test)]
+mod test_row_stream;
+
+use crate::i18n::{Message, MessageArg};
+
+use std::str::FromStr;
+
+use fluent_bundle::types::FluentNumber;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+pub enum RowStreamError {
+    FormatError(String),
+    DeserializeError(String),
+}
+
+impl RowStreamError {
+    pub fn message(&self) -> &String {
+        match self {
+            RowStreamError::FormatError(msg) | RowStreamError::DeserializeError(msg) => msg,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StreamFormat {
+    /// A JSON array of row objects, `[{...}, {...}]`.
+    JsonArray,
+    /// One row object per line.
+    Ndjson,
+}
+
+/// Splits a table payload into row objects as its bytes arrive, so rows are built without
+/// holding the whole body. Only the bytes of the row being read are buffered.
+#[derive(Default)]
+pub struct RowStream {
+    format: Option<StreamFormat>,
+    /// Nesting of the row object being read, 0 between rows.
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    row: Vec<u8>,
+    array_closed: bool,
+}
+
+impl RowStream {
+    pub fn new() -> RowStream {
+        RowStream::default()
+    }
+
+    /// Reads the next chunk of the payload and returns the rows it completes.
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<Vec<Map<String, Value>>, RowStreamError> {
+        let mut rows = Vec::new();
+        for &byte in chunk {
+            if self.depth > 0 {
+                self.row.push(byte);
+                if self.in_string {
+                    match (self.escaped, byte) {
+                        (true, _) => self.escaped = false,
+                        (false, b'\\') => self.escaped = true,
+                        (false, b'"') => self.in_string = false,
+                        _ => (),
+                    }
+                    continue;
+                }
+                match byte {
+                    b'"' => self.in_string = true,
+                    b'{' | b'[' => self.depth += 1,
+                    b'}' | b']' => {
+                        self.depth -= 1;
+                        if self.depth == 0 {
+                            rows.push(self.take_row()?);
+                        }
+                    }
+                    _ => (),
+                }
+                continue;
+            }
+            if byte.is_ascii_whitespace() {
+                continue;
+            }
+            match (self.format, byte) {
+                // A byte order mark may precede the payload.
+                (None, 0xEF | 0xBB | 0xBF) => (),
+                (None, b'[') => self.format = Some(StreamFormat::JsonArray),
+                (None, b'{') => {
+                    self.format = Some(StreamFormat::Ndjson);
+                    self.start_row();
+                }
+                (None, _) => {
+                    return Err(RowStreamError::FormatError(String::from(
+                        "Data requested is not an array.",
+                    )))
+                }
+                (Some(StreamFormat::JsonArray), _) if self.array_closed => {
+                    return Err(RowStreamError::FormatError(String::from(
+                        "Unexpected data after the end of the array.",
+                    )))
+                }
+                (Some(StreamFormat::JsonArray), b',') => (),
+                (Some(StreamFormat::JsonArray), b']') => self.array_closed = true,
+                (Some(_), b'{') => self.start_row(),
+                (Some(_), _) => {
+                    return Err(RowStreamError::FormatError(String::from(
+                        "non-row type data encountered in the data received.",
+                    )))
+                }
+            }
+        }
+        Ok(rows)
+    }
+
+    /// Checks the payload ended between rows, and that a JSON array was closed.
+    pub fn finish(&self) -> Result<(), RowStreamError> {
+        if self.depth > 0 || (self.format == Some(StreamFormat::JsonArray) && !self.array_closed) {
+            return Err(RowStreamError::FormatError(String::from(
+                "The data received ended in the middle of a row.",
+            )));
+        }
+        Ok(())
+    }
+
+    fn start_row(&mut self) {
+        self.depth = 1;
+        self.row.clear();
+        self.row.push(b'{');
+    }
+
+    fn take_row(&mut self) -> Result<Map<String, Value>, RowStreamError> {
+        let row = serde_json::from_slice::<Map<String, Value>>(&self.row).map_err(|error| {
+            RowStreamError::DeserializeError(format!("Unable to deserialize a row: {error}"))
+        });
+        self.row.clear();
+        row
+    }
+}
+
+/// How far a table download is.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub struct LoadProgress {
+    pub bytes: u64,
+    /// From the `Content-Length` header, `None` when the server does not send it.
+    pub total_bytes: Option<u64>,
+    pub rows: usize,
+}
+
+impl LoadProgress {
+    /// Percentage downloaded, `None` when the size is unknown.
+    pub fn percent(&self) -> Option<f64> {
+        self.total_bytes
+            .filter(|total| *total > 0)
+            .map(|total| (self.bytes as f64 * 100.0 / total as f64).min(100.0))
+    }
+
+    /// E.g. "1.5 KB of 3.0 KB, 12 rows".
+    pub fn describe(&self) -> Message {
+        let bytes = match self.total_bytes {
+            Some(total) => Message::new("progress-bytes-of")
+                .with_arg("bytes", MessageArg::Message(format_bytes(self.bytes)))
+                .with_arg("total", MessageArg::Message(format_bytes(total))),
+            None => format_bytes(self.bytes),
+        };
+        Message::new("progress")
+            .with_arg("bytes", MessageArg::Message(bytes))
+            .with("rows", self.rows)
+    }
+}
+
+/// A size in bytes, in kilobytes, megabytes or gigabytes with one decimal past a kilobyte.
+pub fn format_bytes(bytes: u64) -> Message {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    let size = if unit == 0 {
+        FluentNumber::from(bytes)
+    } else {
+        // Parsed from its text, the number keeps the decimal of e.g. "3.0".
+        FluentNumber::from_str(&format!("{value:.1}")).unwrap_or_else(|_| value.into())
+    };
+    Message::new("file-size")
+        .with("size", size)
+        .with("unit", UNITS[unit])
+}
@@ -0,0 +1,83 @@
+use super::{format_bytes, LoadProgress, RowStream};
+use crate::i18n::Locale;
+use serde_json::{Map, Value};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+/// Feeds the payload in chunks of `size` bytes and collects the rows.
+fn read_in_chunks(payload: &str, size: usize) -> Result<Vec<Map<String, Value>>, String> {
+    let mut stream = RowStream::new();
+    let mut rows = Vec::new();
+    for chunk in payload.as_bytes().chunks(size) {
+        rows.extend(
+            stream
+                .feed(chunk)
+                .map_err(|error| error.message().clone())?,
+        );
+    }
+    stream.finish().map_err(|error| error.message().clone())?;
+    Ok(rows)
+}
+
+#[wasm_bindgen_test]
+fn test_json_array_split_anywhere() {
+    let payload = r#"[{"subject": "001", "term": "Head{ache}, \"mild\""}, {"subject": "002", "dose": [1, 2], "site": {"id": 3}}]"#;
+    for size in [1, 2, 7, payload.len()] {
+        let rows = read_in_chunks(payload, size).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["term"], "Head{ache}, \"mild\"");
+        assert_eq!(rows[1]["site"]["id"], 3);
+    }
+    assert_eq!(read_in_chunks("\u{feff}[ ]", 1).unwrap().len(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_ndjson() {
+    let payload = "{\"subject\": \"001\", \"term\": \"caf\u{e9}\"}\n{\"subject\": \"002\"}\n";
+    let rows = read_in_chunks(payload, 3).unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["term"], "caf\u{e9}");
+}
+
+#[wasm_bindgen_test]
+fn test_rows_arrive_before_the_end() {
+    let mut stream = RowStream::new();
+    assert_eq!(stream.feed(br#"[{"a": 1}, {"a""#).ok().unwrap().len(), 1);
+    assert_eq!(stream.feed(br#": 2}]"#).ok().unwrap().len(), 1);
+    assert!(stream.finish().is_ok());
+}
+
+#[wasm_bindgen_test]
+fn test_invalid_payloads() {
+    assert!(read_in_chunks(r#"{"rows": 1"#, 4).is_err());
+    assert!(read_in_chunks(r#"[{"a": 1}, 2]"#, 4).is_err());
+    assert!(read_in_chunks(r#"[{"a": 1}"#, 4).is_err());
+    assert!(read_in_chunks(r#"[{"a": 1}] {"b": 2}"#, 4).is_err());
+    assert!(read_in_chunks(r#""text""#, 4).is_err());
+    assert!(read_in_chunks(r#"[{"a": }]"#, 4).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_load_progress() {
+    let progress = LoadProgress {
+        bytes: 1536,
+        total_bytes: Some(3072),
+        rows: 1,
+    };
+    assert_eq!(progress.percent(), Some(50.0));
+    assert_eq!(
+        progress.describe().text(Locale::EnUs),
+        "1.5 KB of 3.0 KB, 1 row"
+    );
+    assert_eq!(
+        progress.describe().text(Locale::DeDe),
+        "1,5 KB von 3,0 KB, 1 Zeile"
+    );
+    let unknown = LoadProgress {
+        bytes: 512,
+        total_bytes: None,
+        rows: 20,
+    };
+    assert_eq!(unknown.percent(), None);
+    assert_eq!(unknown.describe().text(Locale::EnUs), "512 B, 20 rows");
+    assert_eq!(format_bytes(5 * 1024 * 1024).text(Locale::EnUs), "5.0 MB");
+}
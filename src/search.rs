@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod test_search;
+
+use crate::table::Table;
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct SearchHit {
+    pub table: String,
+    pub column: String,
+    /// Number of rows whose `column` contains the query.
+    pub row_count: usize,
+    /// The first matching rows, at most `max_samples` of them.
+    pub sample_rows: Vec<HashMap<String, String>>,
+}
+
+/// Finds the columns of `data` containing `query`, ignoring case and null cells.
+///
+/// Hits are reported per column, in column name order, so a subject id that shows up in
+/// both `subject` and `usubjid` yields two hits.
+pub fn search_rows(table: &str, data: &Table, query: &str, max_samples: usize) -> Vec<SearchHit> {
+    let needle = query.trim().to_lowercase();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let mut hits = BTreeMap::<String, SearchHit>::new();
+    for (index, column) in data.columns().iter().enumerate() {
+        let mut rows = data.matching_rows(index, |value| value.to_lowercase().contains(&needle));
+        // Nulls are shown as `NULL_TEXT`, which is no value to find.
+        rows.retain(|row| !column.is_null(*row));
+        if rows.is_empty() {
+            continue;
+        }
+        hits.insert(
+            String::from(column.name()),
+            SearchHit {
+                table: table.to_owned(),
+                column: String::from(column.name()),
+                row_count: rows.len(),
+                sample_rows: rows
+                    .iter()
+                    .take(max_samples)
+                    .map(|row| data.display_row(*row))
+                    .collect(),
+            },
+        );
+    }
+    hits.into_values().collect()
+}
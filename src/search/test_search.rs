@@ -0,0 +1,76 @@
+use super::search_rows;
+use crate::table::Table;
+use serde_json::{Map, Value};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn row(pairs: &[(&str, &str)]) -> Map<String, Value> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), Value::from(*v)))
+        .collect()
+}
+
+fn table(rows: Vec<Map<String, Value>>) -> Table {
+    Table::from_records(&rows).ok().unwrap()
+}
+
+fn adverse_events() -> Table {
+    table(vec![
+        row(&[("subject", "1023-004"), ("aeterm", "Pneumonitis")]),
+        row(&[("subject", "1023-004"), ("aeterm", "Fatigue")]),
+        row(&[
+            ("subject", "1023-005"),
+            ("aeterm", "Immune-mediated pneumonitis"),
+        ]),
+    ])
+}
+
+#[wasm_bindgen_test]
+fn test_search_rows_ignores_case_and_counts_rows() {
+    let hits = search_rows("ae", &adverse_events(), "PNEUMONITIS", 5);
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].table, "ae");
+    assert_eq!(hits[0].column, "aeterm");
+    assert_eq!(hits[0].row_count, 2);
+    assert_eq!(hits[0].sample_rows.len(), 2);
+}
+
+#[wasm_bindgen_test]
+fn test_search_rows_limits_samples() {
+    let hits = search_rows("ae", &adverse_events(), "1023-004", 1);
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].row_count, 2);
+    assert_eq!(hits[0].sample_rows.len(), 1);
+}
+
+#[wasm_bindgen_test]
+fn test_search_rows_reports_each_matching_column() {
+    let rows = table(vec![row(&[
+        ("subject", "1023-004"),
+        ("usubjid", "STUDY-1023-004"),
+    ])]);
+    let hits = search_rows("dm", &rows, "1023-004", 3);
+    let columns: Vec<&str> = hits.iter().map(|hit| hit.column.as_str()).collect();
+    assert_eq!(columns, vec!["subject", "usubjid"]);
+}
+
+#[wasm_bindgen_test]
+fn test_search_rows_with_blank_query() {
+    assert!(search_rows("ae", &adverse_events(), "  ", 3).is_empty());
+}
+
+#[wasm_bindgen_test]
+fn test_search_rows_skips_nulls() {
+    let mut with_null = row(&[("subject", "1023-006")]);
+    with_null.insert(String::from("aeterm"), Value::Null);
+    let mut rows = vec![
+        row(&[("subject", "1023-004"), ("aeterm", "n/a")]),
+        with_null,
+    ];
+    rows.push(row(&[("subject", "1023-007"), ("aeterm", "Fatigue")]));
+    let hits = search_rows("ae", &table(rows), "N/A", 3);
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].column, "aeterm");
+    assert_eq!(hits[0].row_count, 1);
+    assert_eq!(hits[0].sample_rows[0]["subject"], "1023-004");
+}
@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod test_snapshot_diff;
+
+use crate::table::{Table, NULL_TEXT};
+
+use std::collections::HashMap;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ModifiedRow {
+    pub baseline_index: usize,
+    pub current_index: usize,
+    /// Columns whose value differs, in the order of `SnapshotDiff::columns`.
+    pub changed_columns: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct SnapshotDiff {
+    /// Union of the columns of both versions, the baseline's in order and then the current ones
+    /// it lacks.
+    pub columns: Vec<String>,
+    /// Indices into the current rows.
+    pub added: Vec<usize>,
+    /// Indices into the baseline rows.
+    pub removed: Vec<usize>,
+    pub modified: Vec<ModifiedRow>,
+    pub unchanged: usize,
+    /// Keys that appear more than once in either version; only their first occurrence is compared.
+    pub duplicate_keys: Vec<RowKey>,
+}
+
+/// The values of the key columns of a row, one per column.
+pub type RowKey = Vec<String>;
+
+pub fn row_key(table: &Table, row: usize, key_columns: &[Option<usize>]) -> RowKey {
+    key_columns
+        .iter()
+        .map(|column| {
+            column
+                .and_then(|column| table.text(row, column))
+                .map(|text| text.into_owned())
+                .unwrap_or_else(|| String::from(NULL_TEXT))
+        })
+        .collect()
+}
+
+fn index_by_key(
+    table: &Table,
+    key_columns: &[String],
+    duplicate_keys: &mut Vec<RowKey>,
+) -> (Vec<RowKey>, HashMap<RowKey, usize>) {
+    let key_columns: Vec<Option<usize>> = key_columns
+        .iter()
+        .map(|column| table.column_index(column))
+        .collect();
+    let mut order = Vec::<RowKey>::new();
+    let mut index = HashMap::<RowKey, usize>::new();
+    for row in 0..table.len() {
+        let key = row_key(table, row, &key_columns);
+        if index.contains_key(&key) {
+            if !duplicate_keys.contains(&key) {
+                duplicate_keys.push(key);
+            }
+        } else {
+            index.insert(key.clone(), row);
+            order.push(key);
+        }
+    }
+    (order, index)
+}
+
+/// Matches the rows of two versions of a table by `key_columns` and reports what changed.
+pub fn diff_snapshots(baseline: &Table, current: &Table, key_columns: &[String]) -> SnapshotDiff {
+    let mut columns = baseline.column_names();
+    for column in current.column_names() {
+        if !columns.contains(&column) {
+            columns.push(column);
+        }
+    }
+    // each column's index in both versions, `None` where a version lacks it
+    let indices: Vec<(Option<usize>, Option<usize>)> = columns
+        .iter()
+        .map(|column| (baseline.column_index(column), current.column_index(column)))
+        .collect();
+
+    let mut duplicate_keys = Vec::<RowKey>::new();
+    let (baseline_order, baseline_index) = index_by_key(baseline, key_columns, &mut duplicate_keys);
+    let (current_order, current_index) = index_by_key(current, key_columns, &mut duplicate_keys);
+
+    let mut diff = SnapshotDiff {
+        columns,
+        ..SnapshotDiff::default()
+    };
+    for key in current_order.iter() {
+        let current_row_index = current_index[key];
+        match baseline_index.get(key) {
+            Some(&baseline_row_index) => {
+                let changed_columns: Vec<String> = diff
+                    .columns
+                    .iter()
+                    .zip(indices.iter())
+                    .filter(|(_, (old, new))| {
+                        let old = old.and_then(|column| baseline.text(baseline_row_index, column));
+                        let new = new.and_then(|column| current.text(current_row_index, column));
+                        old != new
+                    })
+                    .map(|(column, _)| column.clone())
+                    .collect();
+                if changed_columns.is_empty() {
+                    diff.unchanged += 1;
+                } else {
+                    diff.modified.push(ModifiedRow {
+                        baseline_index: baseline_row_index,
+                        current_index: current_row_index,
+                        changed_columns,
+                    });
+                }
+            }
+            None => diff.added.push(current_row_index),
+        }
+    }
+    for key in baseline_order.iter() {
+        if !current_index.contains_key(key) {
+            diff.removed.push(baseline_index[key]);
+        }
+    }
+    diff.duplicate_keys = duplicate_keys;
+    diff
+}
@@ -0,0 +1,99 @@
+use super::{diff_snapshots, row_key};
+use crate::table::Table;
+use serde_json::{Map, Value};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn row(pairs: &[(&str, &str)]) -> Map<String, Value> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), Value::from(*v)))
+        .collect()
+}
+
+fn table(rows: Vec<Map<String, Value>>) -> Table {
+    Table::from_records(&rows).ok().unwrap()
+}
+
+fn keys() -> Vec<String> {
+    vec![String::from("subject"), String::from("aeseq")]
+}
+
+fn columns(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| String::from(*name)).collect()
+}
+
+#[wasm_bindgen_test]
+fn test_diff_snapshots_classifies_rows() {
+    let baseline = table(vec![
+        row(&[("subject", "001"), ("aeseq", "1"), ("aetoxgr", "2")]),
+        row(&[("subject", "001"), ("aeseq", "2"), ("aetoxgr", "1")]),
+        row(&[("subject", "002"), ("aeseq", "1"), ("aetoxgr", "1")]),
+    ]);
+    let current = table(vec![
+        row(&[("subject", "001"), ("aeseq", "1"), ("aetoxgr", "3")]),
+        row(&[("subject", "002"), ("aeseq", "1"), ("aetoxgr", "1")]),
+        row(&[("subject", "003"), ("aeseq", "1"), ("aetoxgr", "2")]),
+    ]);
+    let diff = diff_snapshots(&baseline, &current, &keys());
+    assert_eq!(diff.added, vec![2]);
+    assert_eq!(diff.removed, vec![1]);
+    assert_eq!(diff.unchanged, 1);
+    assert_eq!(diff.modified.len(), 1);
+    assert_eq!(diff.modified[0].baseline_index, 0);
+    assert_eq!(diff.modified[0].current_index, 0);
+    assert_eq!(
+        diff.modified[0].changed_columns,
+        vec![String::from("aetoxgr")]
+    );
+    assert!(diff.duplicate_keys.is_empty());
+}
+
+#[wasm_bindgen_test]
+fn test_diff_snapshots_reports_added_columns_as_changes() {
+    let baseline = table(vec![row(&[("subject", "001"), ("aeseq", "1")])]);
+    let current = table(vec![row(&[
+        ("subject", "001"),
+        ("aeout", "RECOVERED"),
+        ("aeseq", "1"),
+    ])]);
+    let diff = diff_snapshots(&baseline, &current, &keys());
+    // In table order, not sorted.
+    assert_eq!(diff.columns, columns(&["subject", "aeseq", "aeout"]));
+    assert_eq!(
+        diff.modified[0].changed_columns,
+        vec![String::from("aeout")]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_diff_snapshots_flags_duplicate_keys() {
+    let baseline = table(vec![
+        row(&[("subject", "001"), ("aeseq", "1")]),
+        row(&[("subject", "001"), ("aeseq", "1")]),
+    ]);
+    let diff = diff_snapshots(&baseline, &baseline, &keys());
+    assert_eq!(diff.duplicate_keys, vec![columns(&["001", "1"])]);
+    assert_eq!(diff.unchanged, 1);
+}
+
+#[wasm_bindgen_test]
+fn test_diff_snapshots_keeps_key_parts_apart() {
+    // Joined with " / " both keys would read "a / b / c".
+    let baseline = table(vec![row(&[("subject", "a / b"), ("aeseq", "c")])]);
+    let current = table(vec![row(&[("subject", "a"), ("aeseq", "b / c")])]);
+    let diff = diff_snapshots(&baseline, &current, &keys());
+    assert_eq!(diff.added, vec![0]);
+    assert_eq!(diff.removed, vec![0]);
+    assert!(diff.modified.is_empty());
+}
+
+#[wasm_bindgen_test]
+fn test_row_key_uses_null_for_missing_columns() {
+    let ae = table(vec![row(&[("subject", "001")])]);
+    let key = row_key(
+        &ae,
+        0,
+        &[ae.column_index("subject"), ae.column_index("aeseq")],
+    );
+    assert_eq!(key, columns(&["001", "N/A"]));
+}
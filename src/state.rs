@@ -1,5 +1,381 @@
-#[derive(PartialEq, Clone)]
+#[cfg(test)]
+mod test_state;
+
+use crate::history::{describe_grid_change, History};
+use crate::i18n::{Message, MessageArg};
+use crate::views::GridState;
+
+use std::collections::VecDeque;
+use std::ops::Deref;
+use std::rc::Rc;
+
+use gloo_storage::{LocalStorage, Storage};
+use js_sys::Date;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsValue;
+use web_sys::console::error_1;
+use yew::prelude::{Reducible, UseReducerHandle};
+
+/// Where the last selection is kept, so the data tables page opens on it again.
+const SELECTION_STORAGE_KEY: &str = "styeward.selection";
+/// Set to `true` in local storage to show the action log, see src/components/store.rs.
+pub const DEBUG_STORAGE_KEY: &str = "styeward.debug";
+/// Actions kept in the log, the oldest are dropped past this.
+const LOG_LENGTH: usize = 200;
+
+#[derive(PartialEq, Clone, Debug, Default)]
 pub struct StyewardState {
     pub current_schema: Option<String>,
     pub current_table: Option<String>,
+    /// Id of the named view to apply once the current table is loaded.
+    pub current_view: Option<String>,
+    /// Filters, sorts and column layout of the current table's grid, `None` for the grid as the
+    /// table first shows it.
+    pub grid: Option<GridState>,
+    /// The table selected last, still described while a newly selected schema has no table.
+    pub last_selection: Option<Selection>,
+}
+
+/// A "contains" filter on a single column.
+#[derive(PartialEq, Clone, Debug)]
+pub struct ColumnFilter {
+    pub column: String,
+    pub value: String,
+}
+
+/// A table of a schema.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Selection {
+    pub schema: String,
+    pub table: String,
+}
+
+/// Every change of the state, see `StyewardState::reduce`.
+#[derive(PartialEq, Clone, Debug)]
+pub enum StyewardAction {
+    /// Selects a schema, its table is selected next.
+    SelectSchema(String),
+    /// Selects a table of the selected schema.
+    SelectTable(String),
+    ClearSelection,
+    /// Opens the table a route names, with the view and filter of its query.
+    OpenTable {
+        schema: String,
+        table: String,
+        view: Option<String>,
+        filter: Option<ColumnFilter>,
+    },
+    /// Keeps the grid of `schema.table` as the user left it.
+    ChangeGrid {
+        schema: String,
+        table: String,
+        grid: GridState,
+    },
+    /// Applies the grid of a named view to the current table.
+    ApplyView {
+        name: String,
+        grid: GridState,
+    },
+    /// Undoes this many steps of the history, see `Store`.
+    Undo(usize),
+    /// Redoes this many undone steps.
+    Redo(usize),
+}
+
+impl StyewardAction {
+    pub fn describe(&self) -> Message {
+        match self {
+            StyewardAction::SelectSchema(schema) => {
+                Message::new("action-select-schema").with("schema", schema.clone())
+            }
+            StyewardAction::SelectTable(table) => {
+                Message::new("action-select-table").with("table", table.clone())
+            }
+            StyewardAction::ClearSelection => Message::new("action-clear-selection"),
+            StyewardAction::OpenTable {
+                schema,
+                table,
+                view,
+                filter,
+            } => {
+                let mut parts =
+                    vec![Message::new("action-open-table")
+                        .with("table", format!("{schema}.{table}"))];
+                if let Some(view) = view {
+                    parts.push(Message::new("action-with-view").with("view", view.clone()));
+                }
+                if let Some(filter) = filter {
+                    parts.push(
+                        Message::new("action-with-filter")
+                            .with("column", filter.column.clone())
+                            .with("value", filter.value.clone()),
+                    );
+                }
+                match parts.len() {
+                    1 => parts.remove(0),
+                    _ => Message::new("action-parts").with_arg(
+                        "parts",
+                        MessageArg::List {
+                            separator: "list-separator",
+                            items: parts.into_iter().map(MessageArg::Message).collect(),
+                        },
+                    ),
+                }
+            }
+            StyewardAction::ChangeGrid { schema, table, .. } => {
+                Message::new("action-change-grid").with("table", format!("{schema}.{table}"))
+            }
+            StyewardAction::ApplyView { name, .. } => {
+                Message::new("action-apply-view").with("view", name.clone())
+            }
+            StyewardAction::Undo(steps) => Message::new("action-undo").with("steps", *steps),
+            StyewardAction::Redo(steps) => Message::new("action-redo").with("steps", *steps),
+        }
+    }
+}
+
+impl StyewardState {
+    /// The state once `action` is applied.
+    pub fn reduce(&self, action: &StyewardAction) -> StyewardState {
+        match action {
+            StyewardAction::SelectSchema(schema) => StyewardState {
+                current_schema: Some(schema.clone()),
+                last_selection: self.selection().or_else(|| self.last_selection.clone()),
+                ..StyewardState::default()
+            },
+            StyewardAction::SelectTable(table) => match &self.current_schema {
+                Some(schema) => StyewardState {
+                    current_schema: Some(schema.clone()),
+                    current_table: Some(table.clone()),
+                    last_selection: Some(Selection {
+                        schema: schema.clone(),
+                        table: table.clone(),
+                    }),
+                    ..StyewardState::default()
+                },
+                // A table is always one of the selected schema's.
+                None => self.clone(),
+            },
+            StyewardAction::ClearSelection => StyewardState::default(),
+            StyewardAction::OpenTable {
+                schema,
+                table,
+                view,
+                filter,
+            } => {
+                // The address bar follows the selection, the table it names is open already.
+                let selection = Some(Selection {
+                    schema: schema.clone(),
+                    table: table.clone(),
+                });
+                if view.is_none() && filter.is_none() && self.selection() == selection {
+                    return self.clone();
+                }
+                StyewardState {
+                    current_schema: Some(schema.clone()),
+                    current_table: Some(table.clone()),
+                    current_view: view.clone(),
+                    grid: filter
+                        .as_ref()
+                        .map(|filter| GridState::contains_filter(&filter.column, &filter.value)),
+                    last_selection: selection,
+                }
+            }
+            StyewardAction::ChangeGrid {
+                schema,
+                table,
+                grid,
+            } => {
+                // A grid still showing the table selected before reports nothing of this one.
+                if self.selection().as_ref().is_none_or(|selection| {
+                    &selection.schema != schema || &selection.table != table
+                }) {
+                    return self.clone();
+                }
+                StyewardState {
+                    grid: Some(grid.clone()),
+                    ..self.clone()
+                }
+            }
+            StyewardAction::ApplyView { grid, .. } if self.selection().is_some() => StyewardState {
+                grid: Some(grid.clone()),
+                ..self.clone()
+            },
+            // The history is the store's, see `Store`.
+            StyewardAction::ApplyView { .. }
+            | StyewardAction::Undo(_)
+            | StyewardAction::Redo(_) => self.clone(),
+        }
+    }
+
+    /// What `action` did to this state, `None` when it only records what the grid already shows.
+    fn describe_step(&self, action: &StyewardAction) -> Option<Message> {
+        match action {
+            StyewardAction::ChangeGrid { grid, .. } => {
+                describe_grid_change(&self.grid.clone().unwrap_or_default(), grid)
+            }
+            action => Some(action.describe()),
+        }
+    }
+
+    /// The selected table, once both a schema and a table are selected.
+    pub fn selection(&self) -> Option<Selection> {
+        match (&self.current_schema, &self.current_table) {
+            (Some(schema), Some(table)) => Some(Selection {
+                schema: schema.clone(),
+                table: table.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// The table to describe: the selected one, or the last one while a newly selected schema has
+    /// no table yet.
+    pub fn shown_selection(&self) -> Option<Selection> {
+        self.selection().or_else(|| self.last_selection.clone())
+    }
+
+    /// The state the last selection saved in local storage restores.
+    pub fn restored() -> StyewardState {
+        match LocalStorage::get::<Selection>(SELECTION_STORAGE_KEY) {
+            Ok(selection) => StyewardState {
+                current_schema: Some(selection.schema.clone()),
+                current_table: Some(selection.table.clone()),
+                last_selection: Some(selection),
+                ..StyewardState::default()
+            },
+            Err(_) => StyewardState::default(),
+        }
+    }
+}
+
+/// An action applied to the store and the state it led to.
+#[derive(PartialEq, Clone, Debug)]
+pub struct LoggedAction {
+    pub sequence: u64,
+    /// Milliseconds since the epoch.
+    pub at: f64,
+    pub action: StyewardAction,
+    pub state: Rc<StyewardState>,
+}
+
+/// The state with the log of the actions that changed it and the history of the changes that can
+/// be undone. Actions leaving the state as it is are not logged.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct Store {
+    state: Rc<StyewardState>,
+    log: VecDeque<LoggedAction>,
+    sequence: u64,
+    history: History<StyewardState>,
+}
+
+/// The store, as components find it in their context.
+pub type StoreHandle = UseReducerHandle<Store>;
+
+impl Store {
+    pub fn new(state: StyewardState) -> Store {
+        Store {
+            state: Rc::new(state),
+            ..Store::default()
+        }
+    }
+
+    pub fn state(&self) -> &Rc<StyewardState> {
+        &self.state
+    }
+
+    /// The logged actions, oldest first.
+    pub fn log(&self) -> impl DoubleEndedIterator<Item = &LoggedAction> {
+        self.log.iter()
+    }
+
+    /// The sequence number of the last logged action, 0 before any.
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    pub fn history(&self) -> &History<StyewardState> {
+        &self.history
+    }
+}
+
+impl Deref for Store {
+    type Target = StyewardState;
+
+    fn deref(&self) -> &StyewardState {
+        &self.state
+    }
+}
+
+impl Reducible for Store {
+    type Action = StyewardAction;
+
+    fn reduce(self: Rc<Self>, action: StyewardAction) -> Rc<Self> {
+        let mut history = self.history.clone();
+        let state = match &action {
+            StyewardAction::Undo(steps) => history.undo(*steps, &self.state),
+            StyewardAction::Redo(steps) => history.redo(*steps),
+            action => {
+                let state = self.state.reduce(action);
+                if state == *self.state {
+                    return self;
+                }
+                let state = Rc::new(state);
+                if let Some(description) = self.state.describe_step(action) {
+                    history.record(description, self.state.clone(), state.clone());
+                }
+                Some(state)
+            }
+        };
+        // Nothing to undo or redo.
+        let Some(state) = state else {
+            return self;
+        };
+        let mut store = (*self).clone();
+        store.history = history;
+        store.sequence += 1;
+        if store.log.len() >= LOG_LENGTH {
+            store.log.pop_front();
+        }
+        store.log.push_back(LoggedAction {
+            sequence: store.sequence,
+            at: Date::now(),
+            action,
+            state: state.clone(),
+        });
+        store.state = state;
+        Rc::new(store)
+    }
+}
+
+/// Side effects of actions, which the reducer must not have. Middleware runs once per logged
+/// action, after the render showing its state, with the state before it.
+pub trait Middleware {
+    fn after(&self, logged: &LoggedAction, before: &StyewardState);
+}
+
+/// Saves the selection to local storage, see `StyewardState::restored`.
+pub struct Persistence;
+
+impl Middleware for Persistence {
+    fn after(&self, logged: &LoggedAction, before: &StyewardState) {
+        let selection = logged.state.selection();
+        if selection == before.selection() {
+            return;
+        }
+        match selection {
+            Some(selection) => {
+                if let Err(error) = LocalStorage::set(SELECTION_STORAGE_KEY, &selection) {
+                    error_1(&JsValue::from(format!(
+                        "state error: unable to save the selection: {error}"
+                    )));
+                }
+            }
+            None if logged.action == StyewardAction::ClearSelection => {
+                LocalStorage::delete(SELECTION_STORAGE_KEY)
+            }
+            // A schema without a table yet, the last table stays the one to restore.
+            None => (),
+        }
+    }
 }
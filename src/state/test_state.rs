@@ -0,0 +1,175 @@
+use super::{ColumnFilter, Selection, Store, StyewardAction, StyewardState, LOG_LENGTH};
+use crate::i18n::Locale;
+use crate::views::GridState;
+use serde_json::json;
+use std::rc::Rc;
+use wasm_bindgen_test::wasm_bindgen_test;
+use yew::prelude::Reducible;
+
+fn open(schema: &str, table: &str) -> StyewardAction {
+    StyewardAction::OpenTable {
+        schema: String::from(schema),
+        table: String::from(table),
+        view: Some(String::from("by-site")),
+        filter: None,
+    }
+}
+
+fn selection(schema: &str, table: &str) -> Selection {
+    Selection {
+        schema: String::from(schema),
+        table: String::from(table),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_select_schema_then_table() {
+    let state = StyewardState::default()
+        .reduce(&open("sdtm", "ae"))
+        .reduce(&StyewardAction::SelectSchema(String::from("adam")));
+    assert_eq!(state.current_schema.as_deref(), Some("adam"));
+    assert_eq!(state.current_table, None);
+    assert_eq!(state.current_view, None);
+    assert_eq!(state.selection(), None);
+    // The last table is still shown until one of the new schema is selected.
+    assert_eq!(state.shown_selection(), Some(selection("sdtm", "ae")));
+
+    let state = state.reduce(&StyewardAction::SelectTable(String::from("adsl")));
+    assert_eq!(state.selection(), Some(selection("adam", "adsl")));
+    assert_eq!(state.shown_selection(), Some(selection("adam", "adsl")));
+}
+
+#[wasm_bindgen_test]
+fn test_select_table_without_schema() {
+    let state = StyewardState::default();
+    assert_eq!(
+        state.reduce(&StyewardAction::SelectTable(String::from("ae"))),
+        state
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_clear_selection() {
+    let state = StyewardState::default()
+        .reduce(&open("sdtm", "ae"))
+        .reduce(&StyewardAction::ClearSelection);
+    assert_eq!(state, StyewardState::default());
+    assert_eq!(state.shown_selection(), None);
+}
+
+#[wasm_bindgen_test]
+fn test_store_logs_changes() {
+    let store = Rc::new(Store::default());
+    let store = store.reduce(open("sdtm", "ae"));
+    let store = store.reduce(StyewardAction::SelectTable(String::from("dm")));
+    let store = store.reduce(StyewardAction::SelectTable(String::from("dm")));
+    // Selecting the selected table again changes nothing and is not logged.
+    assert_eq!(store.sequence(), 2);
+    let log: Vec<_> = store.log().collect();
+    assert_eq!(log.len(), 2);
+    assert_eq!(log[0].action, open("sdtm", "ae"));
+    assert_eq!(log[0].state.current_view.as_deref(), Some("by-site"));
+    assert_eq!(log[1].sequence, 2);
+    assert_eq!(&log[1].state, store.state());
+    assert_eq!(store.current_table.as_deref(), Some("dm"));
+}
+
+#[wasm_bindgen_test]
+fn test_store_log_length() {
+    let mut store = Rc::new(Store::default());
+    store = store.reduce(StyewardAction::SelectSchema(String::from("sdtm")));
+    for index in 0..LOG_LENGTH + 10 {
+        store = store.reduce(StyewardAction::SelectTable(format!("table{index}")));
+    }
+    assert_eq!(store.sequence(), LOG_LENGTH as u64 + 11);
+    assert_eq!(store.log().count(), LOG_LENGTH);
+    assert_eq!(store.log().next().map(|logged| logged.sequence), Some(12));
+}
+
+fn change_grid(table: &str, grid: GridState) -> StyewardAction {
+    StyewardAction::ChangeGrid {
+        schema: String::from("sdtm"),
+        table: String::from(table),
+        grid,
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_open_table_with_filter() {
+    let action = StyewardAction::OpenTable {
+        schema: String::from("sdtm"),
+        table: String::from("ae"),
+        view: None,
+        filter: Some(ColumnFilter {
+            column: String::from("aeterm"),
+            value: String::from("head"),
+        }),
+    };
+    let state = StyewardState::default().reduce(&action);
+    assert_eq!(
+        state.grid,
+        Some(GridState::contains_filter("aeterm", "head"))
+    );
+    // The address bar naming the open table leaves its grid as it is.
+    let same = StyewardAction::OpenTable {
+        schema: String::from("sdtm"),
+        table: String::from("ae"),
+        view: None,
+        filter: None,
+    };
+    assert_eq!(state.reduce(&same), state);
+}
+
+#[wasm_bindgen_test]
+fn test_undo_table_switch_restores_the_grid() {
+    let filtered = GridState::contains_filter("aeterm", "head");
+    let mut store = Rc::new(Store::default());
+    store = store.reduce(open("sdtm", "ae"));
+    store = store.reduce(change_grid("ae", filtered.clone()));
+    store = store.reduce(StyewardAction::SelectTable(String::from("dm")));
+    // The grid still showing ae reports nothing of dm.
+    store = store.reduce(change_grid("ae", GridState::default()));
+    assert_eq!(store.grid, None);
+
+    let steps: Vec<String> = store
+        .history()
+        .undoable()
+        .map(|step| step.description.text(Locale::EnUs))
+        .collect();
+    assert_eq!(
+        steps,
+        vec![
+            "Select table dm",
+            "Filter aeterm",
+            "Open sdtm.ae, view by-site"
+        ]
+    );
+
+    store = store.reduce(StyewardAction::Undo(1));
+    assert_eq!(store.selection(), Some(selection("sdtm", "ae")));
+    assert_eq!(store.grid, Some(filtered.clone()));
+    store = store.reduce(StyewardAction::Undo(1));
+    assert_eq!(store.grid, None);
+    store = store.reduce(StyewardAction::Redo(2));
+    assert_eq!(store.current_table.as_deref(), Some("dm"));
+    assert_eq!(store.history().redoable().count(), 0);
+    assert_eq!(
+        store.log().last().map(|logged| logged.action.clone()),
+        Some(StyewardAction::Redo(2))
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_grid_reports_without_a_change_are_not_steps() {
+    let mut store = Rc::new(Store::default());
+    store = store.reduce(open("sdtm", "ae"));
+    let reported = GridState {
+        filter_model: json!({}),
+        column_order: vec![String::from("subject"), String::from("aeterm")],
+        ..GridState::default()
+    };
+    store = store.reduce(change_grid("ae", reported.clone()));
+    assert_eq!(store.grid, Some(reported));
+    assert_eq!(store.history().undoable().count(), 1);
+    assert_eq!(store.sequence(), 2);
+}
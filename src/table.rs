@@ -0,0 +1,521 @@
+#[cfg(test)]
+mod bench_table;
+#[cfg(test)]
+mod test_table;
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::mem::size_of;
+use std::rc::Rc;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+/// Shown for null values, in the grid and everything fed from it.
+pub const NULL_TEXT: &str = "N/A";
+
+pub enum TableError {
+    FormatError(String),
+}
+
+impl TableError {
+    pub fn message(&self) -> &String {
+        match self {
+            TableError::FormatError(msg) => msg,
+        }
+    }
+}
+
+/// One bit per row.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
+pub struct Bitmap {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl Bitmap {
+    fn filled(len: usize, bit: bool) -> Bitmap {
+        let mut bitmap = Bitmap::default();
+        for _ in 0..len {
+            bitmap.push(bit);
+        }
+        bitmap
+    }
+
+    fn set(&mut self, index: usize, bit: bool) {
+        if bit {
+            self.words[index / 64] |= 1 << (index % 64);
+        } else {
+            self.words[index / 64] &= !(1 << (index % 64));
+        }
+    }
+
+    fn push(&mut self, bit: bool) {
+        if self.len.is_multiple_of(64) {
+            self.words.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, bit);
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        index < self.len && self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+}
+
+/// Each distinct string of a table stored once, text cells hold its id. Serialized as its
+/// strings, the ids are rebuilt when it is read back.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
+#[serde(from = "Vec<Rc<str>>", into = "Vec<Rc<str>>")]
+struct StringPool {
+    strings: Vec<Rc<str>>,
+    ids: HashMap<Rc<str>, u32>,
+}
+
+impl StringPool {
+    fn intern(&mut self, value: &str) -> u32 {
+        if let Some(id) = self.ids.get(value) {
+            return *id;
+        }
+        let id = self.strings.len() as u32;
+        let value: Rc<str> = Rc::from(value);
+        self.strings.push(value.clone());
+        self.ids.insert(value, id);
+        id
+    }
+
+    fn get(&self, id: u32) -> &str {
+        &self.strings[id as usize]
+    }
+}
+
+impl From<Vec<Rc<str>>> for StringPool {
+    fn from(strings: Vec<Rc<str>>) -> StringPool {
+        let ids = strings
+            .iter()
+            .enumerate()
+            .map(|(id, string)| (string.clone(), id as u32))
+            .collect();
+        StringPool { strings, ids }
+    }
+}
+
+impl From<StringPool> for Vec<Rc<str>> {
+    fn from(pool: StringPool) -> Vec<Rc<str>> {
+        pool.strings
+    }
+}
+
+/// The values of a column, typed by the first non-null value. A column whose values disagree on
+/// their type keeps them as JSON values.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum ColumnValues {
+    /// No non-null value yet.
+    Empty,
+    /// Ids in the table's string pool.
+    Text(Vec<u32>),
+    Integer(Vec<i64>),
+    Float(Vec<f64>),
+    Boolean(Bitmap),
+    #[serde(
+        serialize_with = "serialize_json",
+        deserialize_with = "deserialize_json"
+    )]
+    Mixed(Vec<Value>),
+}
+
+/// JSON values as JSON text, for formats such as bincode that cannot tell a value's type from
+/// the value itself.
+fn serialize_json<S: Serializer>(values: &[Value], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(values.iter().map(Value::to_string))
+}
+
+fn deserialize_json<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Value>, D::Error> {
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|text| serde_json::from_str(text).map_err(serde::de::Error::custom))
+        .collect()
+}
+
+/// A column of a table. Null and missing cells hold a placeholder in `values`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Column {
+    name: Rc<str>,
+    values: ColumnValues,
+    nulls: Bitmap,
+    /// Rows without the column at all, `None` while every row has it.
+    missing: Option<Bitmap>,
+}
+
+impl Column {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn values(&self) -> &ColumnValues {
+        &self.values
+    }
+
+    pub fn null_count(&self) -> usize {
+        self.nulls.count_ones()
+    }
+
+    /// Whether the cell of `row` is null, or the row does not have the column.
+    pub fn is_null(&self, row: usize) -> bool {
+        self.nulls.get(row) || self.is_missing(row)
+    }
+
+    fn is_missing(&self, row: usize) -> bool {
+        self.missing
+            .as_ref()
+            .is_some_and(|missing| missing.get(row))
+    }
+
+    fn new(name: &str, rows: usize) -> Column {
+        let mut column = Column {
+            name: Rc::from(name),
+            values: ColumnValues::Empty,
+            nulls: Bitmap::default(),
+            missing: None,
+        };
+        for _ in 0..rows {
+            column.push_missing();
+        }
+        column
+    }
+
+    fn push_missing(&mut self) {
+        let rows = self.nulls.len;
+        self.missing
+            .get_or_insert_with(|| Bitmap::filled(rows, false))
+            .push(true);
+        self.nulls.push(true);
+        self.values.push_placeholder();
+    }
+
+    fn push(&mut self, value: &Value, pool: &mut StringPool) {
+        if let Some(missing) = self.missing.as_mut() {
+            missing.push(false);
+        }
+        self.nulls.push(value.is_null());
+        self.values.push_placeholder();
+        if value.is_null() {
+            return;
+        }
+        let row = self.nulls.len - 1;
+        if !self.set_typed(row, value, pool) {
+            let mut values: Vec<Value> = (0..row).map(|row| self.value(row, pool)).collect();
+            values.push(value.clone());
+            self.values = ColumnValues::Mixed(values);
+        }
+    }
+
+    /// Stores `value` in the placeholder of `row`, `false` when the column's type does not fit it.
+    fn set_typed(&mut self, row: usize, value: &Value, pool: &mut StringPool) -> bool {
+        if matches!(self.values, ColumnValues::Empty) {
+            self.values = match value {
+                Value::String(_) => ColumnValues::Text(vec![0; row + 1]),
+                Value::Number(number) if number.is_f64() => ColumnValues::Float(vec![0.0; row + 1]),
+                Value::Number(number) if number.is_i64() => ColumnValues::Integer(vec![0; row + 1]),
+                Value::Bool(_) => ColumnValues::Boolean(Bitmap::filled(row + 1, false)),
+                _ => ColumnValues::Mixed(vec![Value::Null; row + 1]),
+            };
+        }
+        match (&mut self.values, value) {
+            (ColumnValues::Text(ids), Value::String(text)) => ids[row] = pool.intern(text),
+            (ColumnValues::Float(floats), Value::Number(number)) if number.is_f64() => {
+                floats[row] = number.as_f64().unwrap_or_default()
+            }
+            (ColumnValues::Integer(integers), Value::Number(number)) if number.is_i64() => {
+                integers[row] = number.as_i64().unwrap_or_default()
+            }
+            (ColumnValues::Boolean(bits), Value::Bool(flag)) => bits.set(row, *flag),
+            (ColumnValues::Mixed(values), value) => values[row] = value.clone(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn value(&self, row: usize, pool: &StringPool) -> Value {
+        if self.nulls.get(row) {
+            return Value::Null;
+        }
+        match &self.values {
+            ColumnValues::Empty => Value::Null,
+            ColumnValues::Text(ids) => Value::from(pool.get(ids[row])),
+            ColumnValues::Integer(integers) => Value::from(integers[row]),
+            ColumnValues::Float(floats) => Value::from(floats[row]),
+            ColumnValues::Boolean(bits) => Value::from(bits.get(row)),
+            ColumnValues::Mixed(values) => values[row].clone(),
+        }
+    }
+
+    fn number(&self, row: usize) -> Option<f64> {
+        if self.is_null(row) {
+            return None;
+        }
+        match &self.values {
+            ColumnValues::Integer(integers) => Some(integers[row] as f64),
+            ColumnValues::Float(floats) => Some(floats[row]),
+            ColumnValues::Mixed(values) => values[row].as_f64(),
+            _ => None,
+        }
+    }
+
+    fn text<'a>(&self, row: usize, pool: &'a StringPool) -> Cow<'a, str> {
+        if self.nulls.get(row) {
+            return Cow::Borrowed(NULL_TEXT);
+        }
+        match &self.values {
+            ColumnValues::Empty => Cow::Borrowed(NULL_TEXT),
+            ColumnValues::Text(ids) => Cow::Borrowed(pool.get(ids[row])),
+            ColumnValues::Integer(integers) => Cow::Owned(integers[row].to_string()),
+            ColumnValues::Float(floats) => Cow::Owned(format!("{:.5}", floats[row])),
+            ColumnValues::Boolean(bits) => {
+                Cow::Borrowed(if bits.get(row) { "true" } else { "false" })
+            }
+            ColumnValues::Mixed(values) => Cow::Owned(value_text(&values[row]).unwrap_or_default()),
+        }
+    }
+
+    fn heap_size(&self) -> usize {
+        let values = match &self.values {
+            ColumnValues::Empty => 0,
+            ColumnValues::Text(ids) => ids.capacity() * size_of::<u32>(),
+            ColumnValues::Integer(integers) => integers.capacity() * size_of::<i64>(),
+            ColumnValues::Float(floats) => floats.capacity() * size_of::<f64>(),
+            ColumnValues::Boolean(bits) => bits.words.capacity() * size_of::<u64>(),
+            ColumnValues::Mixed(values) => values.iter().map(value_heap_size).sum(),
+        };
+        let bitmaps = self.nulls.words.capacity()
+            + self
+                .missing
+                .as_ref()
+                .map_or(0, |missing| missing.words.capacity());
+        self.name.len() + values + bitmaps * size_of::<u64>()
+    }
+}
+
+impl ColumnValues {
+    fn push_placeholder(&mut self) {
+        match self {
+            ColumnValues::Empty => (),
+            ColumnValues::Text(ids) => ids.push(0),
+            ColumnValues::Integer(integers) => integers.push(0),
+            ColumnValues::Float(floats) => floats.push(0.0),
+            ColumnValues::Boolean(bits) => bits.push(false),
+            ColumnValues::Mixed(values) => values.push(Value::Null),
+        }
+    }
+}
+
+/// A table held column by column: typed value vectors, strings interned once per table and null
+/// bitmaps. Rows are positions, the same in every column.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
+pub struct Table {
+    columns: Vec<Column>,
+    rows: usize,
+    pool: StringPool,
+}
+
+impl Table {
+    pub fn new() -> Table {
+        Table::default()
+    }
+
+    /// Builds a table from records, see `push_record`.
+    pub fn from_records<'a, I>(records: I) -> Result<Table, TableError>
+    where
+        I: IntoIterator<Item = &'a Map<String, Value>>,
+    {
+        let mut table = Table::new();
+        for record in records {
+            table.push_record(record)?;
+        }
+        Ok(table)
+    }
+
+    /// Appends a row. Values must be strings, numbers, booleans or null.
+    pub fn push_record(&mut self, record: &Map<String, Value>) -> Result<(), TableError> {
+        if record
+            .values()
+            .any(|value| value.is_array() || value.is_object())
+        {
+            // Don't support anything but null and scalar values, ideally no null values should exist after 'cleansing'
+            return Err(TableError::FormatError(String::from(
+                "A non-null or non-string value encountered in the returned data.",
+            )));
+        }
+        for name in record.keys() {
+            if self.column_index(name).is_none() {
+                self.columns.push(Column::new(name, self.rows));
+            }
+        }
+        for column in self.columns.iter_mut() {
+            match record.get(column.name()) {
+                Some(value) => column.push(value, &mut self.pool),
+                None => column.push_missing(),
+            }
+        }
+        self.rows += 1;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows == 0
+    }
+
+    /// Columns in the order they first appear in the records.
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    pub fn column_names(&self) -> Vec<String> {
+        self.columns
+            .iter()
+            .map(|column| String::from(column.name()))
+            .collect()
+    }
+
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|column| column.name() == name)
+    }
+
+    /// The display text of a cell, `None` when the row has no such column.
+    pub fn text(&self, row: usize, column: usize) -> Option<Cow<'_, str>> {
+        let column = self.columns.get(column)?;
+        if row >= self.rows || column.is_missing(row) {
+            return None;
+        }
+        Some(column.text(row, &self.pool))
+    }
+
+    /// The number a cell holds, `None` for nulls, missing cells and values other than numbers.
+    pub fn number(&self, row: usize, column: usize) -> Option<f64> {
+        let column = self.columns.get(column)?;
+        if row >= self.rows {
+            return None;
+        }
+        column.number(row)
+    }
+
+    /// The display strings of a row keyed by column, the shape the grid reads.
+    pub fn display_row(&self, row: usize) -> HashMap<String, String> {
+        (0..self.columns.len())
+            .filter_map(|column| {
+                let text = self.text(row, column)?;
+                Some((String::from(self.columns[column].name()), text.into_owned()))
+            })
+            .collect()
+    }
+
+    /// A row as it was received.
+    pub fn record(&self, row: usize) -> Map<String, Value> {
+        self.columns
+            .iter()
+            .filter(|column| !column.is_missing(row))
+            .map(|column| (String::from(column.name()), column.value(row, &self.pool)))
+            .collect()
+    }
+
+    pub fn records(&self) -> Vec<Map<String, Value>> {
+        (0..self.rows).map(|row| self.record(row)).collect()
+    }
+
+    /// The distinct display texts of a column, without reading every row for text columns.
+    pub fn distinct_texts(&self, column: usize) -> Vec<Cow<'_, str>> {
+        let column = match self.columns.get(column) {
+            Some(column) => column,
+            None => return Vec::new(),
+        };
+        let mut seen = HashSet::<Cow<str>>::new();
+        (0..self.rows)
+            .filter(|row| !column.is_missing(*row))
+            .map(|row| column.text(row, &self.pool))
+            .filter(|text| seen.insert(text.clone()))
+            .collect()
+    }
+
+    /// Rows whose text in `column` satisfies `matches`. Text columns test each distinct string
+    /// once, however many rows share it.
+    pub fn matching_rows<F>(&self, column: usize, matches: F) -> Vec<usize>
+    where
+        F: Fn(&str) -> bool,
+    {
+        let column = match self.columns.get(column) {
+            Some(column) => column,
+            None => return Vec::new(),
+        };
+        let present = |row: &usize| !column.is_missing(*row);
+        match &column.values {
+            ColumnValues::Text(ids) => {
+                let mut tested = HashMap::<u32, bool>::new();
+                let null_matches = matches(NULL_TEXT);
+                (0..self.rows)
+                    .filter(present)
+                    .filter(|row| {
+                        if column.nulls.get(*row) {
+                            return null_matches;
+                        }
+                        *tested
+                            .entry(ids[*row])
+                            .or_insert_with(|| matches(self.pool.get(ids[*row])))
+                    })
+                    .collect()
+            }
+            _ => (0..self.rows)
+                .filter(present)
+                .filter(|row| matches(&column.text(*row, &self.pool)))
+                .collect(),
+        }
+    }
+
+    /// An estimate of the bytes the table holds on the heap.
+    pub fn heap_size(&self) -> usize {
+        let pool: usize = self
+            .pool
+            .strings
+            .iter()
+            .map(|string| string.len() + 2 * size_of::<usize>())
+            .sum::<usize>()
+            + self.pool.ids.capacity() * (size_of::<Rc<str>>() + size_of::<u32>());
+        pool + self
+            .columns
+            .iter()
+            .map(|column| size_of::<Column>() + column.heap_size())
+            .sum::<usize>()
+    }
+}
+
+/// The display text of a scalar JSON value, `None` for arrays and objects.
+pub fn value_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Null => Some(String::from(NULL_TEXT)),
+        Value::Number(number) if number.is_f64() => {
+            Some(format!("{:.5}", number.as_f64().unwrap_or_default()))
+        }
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        Value::Array(_) | Value::Object(_) => None,
+    }
+}
+
+fn value_heap_size(value: &Value) -> usize {
+    size_of::<Value>()
+        + match value {
+            Value::String(text) => text.capacity(),
+            _ => 0,
+        }
+}
@@ -0,0 +1,194 @@
+//! Compares the path from a payload to the rows handed to the grid, as the loader ran it before
+//! the columnar table and as it runs now, on a generated table the size of a large lab domain.
+//! The crate only builds for wasm32, so these run with the tests under `wasm-bindgen-test-runner`;
+//! the numbers are printed to the test output and only the memory comparison is asserted, timings
+//! depend on the machine.
+
+use super::{value_text, Table, NULL_TEXT};
+use crate::ag_grid::ROW_INDEX_FIELD;
+use crate::csv::CsvOptions;
+use crate::payload::{PayloadDecoder, PayloadFormat};
+use js_sys::Date;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::mem::size_of;
+use wasm_bindgen_test::{console_log, wasm_bindgen_test};
+
+const ROWS: usize = 20_000;
+
+type Row = HashMap<String, String>;
+
+fn lab_records() -> Vec<Map<String, Value>> {
+    let tests = ["ALT", "AST", "BILI", "CREAT", "GLUC", "HGB", "PLAT", "WBC"];
+    let visits = [
+        "SCREENING",
+        "WEEK 2",
+        "WEEK 4",
+        "WEEK 8",
+        "WEEK 12",
+        "END OF TREATMENT",
+    ];
+    (0..ROWS)
+        .map(|index| {
+            let mut record = Map::new();
+            record.insert("STUDYID".into(), Value::from("STYW-1023"));
+            record.insert("DOMAIN".into(), Value::from("LB"));
+            record.insert(
+                "USUBJID".into(),
+                Value::from(format!("STYW-1023-{:04}", index / 48)),
+            );
+            record.insert("LBSEQ".into(), Value::from(index % 48 + 1));
+            record.insert("LBTESTCD".into(), Value::from(tests[index % tests.len()]));
+            record.insert(
+                "VISIT".into(),
+                Value::from(visits[index / 8 % visits.len()]),
+            );
+            record.insert("LBSTRESN".into(), Value::from(index as f64 * 0.37 % 200.0));
+            record.insert("LBSTRESU".into(), Value::from("U/L"));
+            record.insert(
+                "LBNRIND".into(),
+                if index % 11 == 0 {
+                    Value::Null
+                } else {
+                    Value::from("NORMAL")
+                },
+            );
+            record.insert("LBBLFL".into(), Value::from(index / 8 % visits.len() == 0));
+            record.insert(
+                "LBDTC".into(),
+                Value::from(format!("2024-{:02}-{:02}", index % 12 + 1, index % 28 + 1)),
+            );
+            record
+        })
+        .collect()
+}
+
+fn payload() -> Vec<u8> {
+    serde_json::to_vec(&lab_records()).unwrap()
+}
+
+fn decode(payload: &[u8]) -> Vec<Map<String, Value>> {
+    let mut decoder = PayloadDecoder::new(Some(PayloadFormat::Json), CsvOptions::default());
+    let mut records = decoder.feed(payload).ok().unwrap();
+    records.extend(decoder.finish().ok().unwrap());
+    records
+}
+
+/// What the loader kept before the columnar table: the records and their display strings.
+fn load_rows(payload: &[u8]) -> (Vec<Map<String, Value>>, Vec<Row>) {
+    let records = decode(payload);
+    let rows = records
+        .iter()
+        .map(|record| {
+            record
+                .iter()
+                .map(|(key, value)| {
+                    let text = value_text(value).unwrap_or_else(|| String::from(NULL_TEXT));
+                    (key.clone(), text)
+                })
+                .collect()
+        })
+        .collect();
+    (records, rows)
+}
+
+/// The grid rows built from the row maps, copied and numbered on every render before.
+fn grid_rows_from_rows(rows: &[HashMap<String, String>]) -> Vec<HashMap<String, String>> {
+    rows.iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let mut row = row.clone();
+            row.insert(String::from(ROW_INDEX_FIELD), index.to_string());
+            row
+        })
+        .collect()
+}
+
+fn load_table(payload: &[u8]) -> Table {
+    Table::from_records(&decode(payload)).ok().unwrap()
+}
+
+/// The grid rows built from the table, as the table view does once per table.
+fn grid_rows_from_table(table: &Table) -> Vec<HashMap<String, String>> {
+    (0..table.len())
+        .map(|index| {
+            let mut row = table.display_row(index);
+            row.insert(String::from(ROW_INDEX_FIELD), index.to_string());
+            row
+        })
+        .collect()
+}
+
+fn row_maps_heap_size(rows: &[HashMap<String, String>]) -> usize {
+    rows.iter()
+        .map(|row| {
+            size_of::<HashMap<String, String>>()
+                + row.capacity() * (2 * size_of::<String>() + 1)
+                + row
+                    .iter()
+                    .map(|(key, value)| key.capacity() + value.capacity())
+                    .sum::<usize>()
+        })
+        .sum()
+}
+
+fn records_heap_size(records: &[Map<String, Value>]) -> usize {
+    records
+        .iter()
+        .map(|record| {
+            size_of::<Map<String, Value>>()
+                + record
+                    .iter()
+                    .map(|(key, value)| {
+                        size_of::<String>()
+                            + size_of::<Value>()
+                            + key.capacity()
+                            + value.as_str().map_or(0, str::len)
+                    })
+                    .sum::<usize>()
+        })
+        .sum()
+}
+
+fn elapsed_ms<T>(run: impl FnOnce() -> T) -> (T, f64) {
+    let start = Date::now();
+    let result = run();
+    (result, Date::now() - start)
+}
+
+#[wasm_bindgen_test]
+fn bench_table_memory() {
+    let payload = payload();
+    let (records, rows) = load_rows(&payload);
+    let before_grid_rows = grid_rows_from_rows(&rows);
+    let table = load_table(&payload);
+    let grid_rows = grid_rows_from_table(&table);
+    assert_eq!(grid_rows, before_grid_rows);
+
+    let grid = row_maps_heap_size(&grid_rows);
+    let before = records_heap_size(&records) + row_maps_heap_size(&rows) + grid;
+    let after = table.heap_size() + grid;
+    console_log!(
+        "{ROWS} rows held after loading: records, row maps and grid rows {} KB before, table and \
+         grid rows {} KB now, of which grid rows {} KB",
+        before / 1024,
+        after / 1024,
+        grid / 1024
+    );
+    assert!(after < before);
+}
+
+#[wasm_bindgen_test]
+fn bench_table_payload_to_grid_rows() {
+    let payload = payload();
+    let (before_rows, before_ms) = elapsed_ms(|| {
+        let (_records, rows) = load_rows(&payload);
+        grid_rows_from_rows(&rows)
+    });
+    let (rows, after_ms) = elapsed_ms(|| grid_rows_from_table(&load_table(&payload)));
+    assert_eq!(rows.len(), before_rows.len());
+    console_log!(
+        "{ROWS} rows from payload to grid rows: {before_ms} ms through row maps, {after_ms} ms \
+         through the table"
+    );
+}
@@ -0,0 +1,104 @@
+use super::{ColumnValues, Table};
+use serde_json::{json, Map, Value};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn records(value: Value) -> Vec<Map<String, Value>> {
+    value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|record| record.as_object().unwrap().clone())
+        .collect()
+}
+
+fn table(value: Value) -> Table {
+    Table::from_records(&records(value)).ok().unwrap()
+}
+
+#[wasm_bindgen_test]
+fn test_display_text_of_each_type() {
+    let table = table(json!([
+        {"subject": "001", "AESEQ": 1, "dose": 2.5, "serious": true, "outcome": null},
+        {"subject": "002", "AESEQ": -3, "dose": 10.0, "serious": false, "outcome": "recovered"},
+    ]));
+    assert_eq!(table.len(), 2);
+    let row = table.display_row(0);
+    assert_eq!(row["subject"], "001");
+    assert_eq!(row["AESEQ"], "1");
+    assert_eq!(row["dose"], "2.50000");
+    assert_eq!(row["serious"], "true");
+    assert_eq!(row["outcome"], "N/A");
+    let row = table.display_row(1);
+    assert_eq!(row["AESEQ"], "-3");
+    assert_eq!(row["serious"], "false");
+    assert_eq!(row["outcome"], "recovered");
+
+    let column = |name: &str| &table.columns()[table.column_index(name).unwrap()];
+    assert!(matches!(column("subject").values(), ColumnValues::Text(_)));
+    assert!(matches!(column("AESEQ").values(), ColumnValues::Integer(_)));
+    assert!(matches!(column("dose").values(), ColumnValues::Float(_)));
+    assert!(matches!(
+        column("serious").values(),
+        ColumnValues::Boolean(_)
+    ));
+    assert!(matches!(column("outcome").values(), ColumnValues::Text(_)));
+    assert_eq!(column("outcome").null_count(), 1);
+}
+
+#[wasm_bindgen_test]
+fn test_strings_are_interned_once() {
+    let table = table(json!([
+        {"subject": "001", "visit": "SCREENING"},
+        {"subject": "001", "visit": "WEEK 1"},
+        {"subject": "002", "visit": "SCREENING"},
+    ]));
+    assert_eq!(table.pool.strings.len(), 4);
+    let visit = table.column_index("visit").unwrap();
+    assert_eq!(table.distinct_texts(visit), vec!["SCREENING", "WEEK 1"]);
+}
+
+#[wasm_bindgen_test]
+fn test_records_round_trip() {
+    let received = records(json!([
+        {"subject": "001", "AESEQ": 1, "dose": 2.5},
+        {"subject": "002", "dose": "unknown", "serious": null},
+        {"subject": "003", "AESEQ": 18446744073709551615_u64, "serious": true},
+    ]));
+    let table = Table::from_records(&received).ok().unwrap();
+    assert_eq!(table.records(), received);
+    // A column whose types disagree keeps its values as received.
+    let dose = table.column_index("dose").unwrap();
+    assert!(matches!(
+        table.columns()[dose].values(),
+        ColumnValues::Mixed(_)
+    ));
+    assert_eq!(table.display_row(1)["dose"], "unknown");
+    // Columns a row does not have are left out of it.
+    assert!(!table.display_row(0).contains_key("serious"));
+    assert!(!table.display_row(1).contains_key("AESEQ"));
+    assert_eq!(table.display_row(2)["AESEQ"], "18446744073709551615");
+}
+
+#[wasm_bindgen_test]
+fn test_matching_rows() {
+    let table = table(json!([
+        {"subject": "001", "term": "Headache", "grade": 1},
+        {"subject": "002", "term": null, "grade": 3},
+        {"subject": "003", "term": "headache", "grade": 2},
+        {"subject": "004"},
+    ]));
+    let term = table.column_index("term").unwrap();
+    let grade = table.column_index("grade").unwrap();
+    assert_eq!(
+        table.matching_rows(term, |text| text.to_lowercase() == "headache"),
+        vec![0, 2]
+    );
+    assert_eq!(table.matching_rows(term, |text| text == "N/A"), vec![1]);
+    assert_eq!(table.matching_rows(grade, |text| text != "2"), vec![0, 1]);
+}
+
+#[wasm_bindgen_test]
+fn test_rejects_nested_values() {
+    let error = Table::from_records(&records(json!([{"subject": "001", "doses": [1, 2]}])));
+    assert!(error.is_err());
+}
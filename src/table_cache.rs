@@ -0,0 +1,110 @@
+use crate::components::table_visualization::{load_table, TableData};
+use crate::i18n::Locale;
+use crate::workspace::Workspace;
+use crate::StyewardConfig;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use js_sys::Date;
+use wasm_bindgen::JsValue;
+use web_sys::console::error_1;
+
+/// Older loads are dropped once a table has been loaded this many times.
+const MAX_LOADS_PER_TABLE: usize = 3;
+
+#[derive(Clone, PartialEq)]
+pub struct CachedLoad {
+    /// ISO 8601 timestamp of when the load finished.
+    pub loaded_at: String,
+    /// The version requested from the uri endpoint, `None` for the latest data.
+    pub version: Option<String>,
+    pub data: TableData,
+}
+
+impl CachedLoad {
+    /// E.g. "version 3 (loaded 04/05/2023 10:30:00.000Z)", the time written for `locale`.
+    pub fn label(&self, locale: Locale) -> String {
+        let loaded = locale
+            .format_date(&self.loaded_at)
+            .unwrap_or_else(|| self.loaded_at.clone());
+        match &self.version {
+            Some(version) => locale.tr_args(
+                "cached-load-version",
+                &[
+                    ("version", version.clone().into()),
+                    ("loaded", loaded.into()),
+                ],
+            ),
+            None => locale.tr_args("cached-load", &[("loaded", loaded.into())]),
+        }
+    }
+}
+
+thread_local! {
+    static TABLE_CACHE: RefCell<HashMap<String, Vec<CachedLoad>>> = RefCell::new(HashMap::new());
+}
+
+/// Remembers a finished load of `schema.table`, most recent last.
+pub fn record(schema: &str, table: &str, version: Option<String>, data: TableData) {
+    let load = CachedLoad {
+        loaded_at: String::from(Date::new_0().to_iso_string()),
+        version,
+        data,
+    };
+    TABLE_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let loads = cache
+            .entry(Workspace::table_key(schema, table))
+            .or_default();
+        loads.push(load);
+        if loads.len() > MAX_LOADS_PER_TABLE {
+            loads.remove(0);
+        }
+    });
+}
+
+/// Every cached load of `schema.table`, oldest first.
+pub fn loads(schema: &str, table: &str) -> Vec<CachedLoad> {
+    TABLE_CACHE.with(|cache| {
+        cache
+            .borrow()
+            .get(&Workspace::table_key(schema, table))
+            .cloned()
+            .unwrap_or_default()
+    })
+}
+
+/// The latest cached load of the table, loading and caching it when there is none.
+pub(crate) async fn latest_or_load(
+    config: &StyewardConfig,
+    schema: &String,
+    table: &String,
+) -> Option<TableData> {
+    if let Some(load) = loads(schema, table)
+        .into_iter()
+        .rev()
+        .find(|load| load.version.is_none())
+    {
+        return Some(load.data);
+    }
+    match load_table(
+        &config.scheme,
+        &config.host,
+        config.port,
+        schema,
+        table,
+        None,
+    )
+    .await
+    {
+        Ok(data) => {
+            record(schema, table, None, data.clone());
+            Some(data)
+        }
+        Err(error) => {
+            error_1(&JsValue::from(error.message()));
+            None
+        }
+    }
+}
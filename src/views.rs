@@ -0,0 +1,387 @@
+#[cfg(test)]
+mod test_views;
+
+use crate::pivot::PivotDefinition;
+use crate::workspace::Workspace;
+use crate::StyewardConfig;
+
+use gloo_net;
+use gloo_net::http::{Request, Response};
+use gloo_storage::{LocalStorage, Storage};
+use js_sys::{Date, Math};
+use serde::{Deserialize, Serialize};
+use serde_json::{self, json, Value};
+use url::Url;
+
+const SHARED_VIEWS_STORAGE_KEY: &str = "styeward.shared_views";
+
+pub enum ViewError {
+    EndpointError(String),
+    GlooError(String),
+    HttpStatusError(String),
+    DeserializeError(String),
+    ImportError(String),
+}
+
+impl ViewError {
+    pub fn message(&self) -> &String {
+        match self {
+            ViewError::EndpointError(msg)
+            | ViewError::GlooError(msg)
+            | ViewError::HttpStatusError(msg)
+            | ViewError::DeserializeError(msg)
+            | ViewError::ImportError(msg) => msg,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ColumnSort {
+    pub column: String,
+    pub descending: bool,
+}
+
+/// The parts of the grid a view restores: filters, sorts, column order and visibility.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct GridState {
+    /// AG Grid filter model, keyed by column.
+    pub filter_model: Value,
+    /// Sorted columns, highest priority first.
+    pub sort: Vec<ColumnSort>,
+    pub hidden_columns: Vec<String>,
+    pub column_order: Vec<String>,
+}
+
+impl GridState {
+    /// A state that only filters `column` to values containing `value`.
+    pub fn contains_filter(column: &str, value: &str) -> GridState {
+        GridState {
+            filter_model: json!({
+                column: {"filterType": "text", "type": "contains", "filter": value}
+            }),
+            ..GridState::default()
+        }
+    }
+
+    /// Reads the state returned by `get_grid_state` in ag_grid.js.
+    pub fn from_ag_grid(filter_model: Value, column_state: &Value) -> GridState {
+        let mut sort = Vec::<(i64, ColumnSort)>::new();
+        let mut hidden_columns = Vec::<String>::new();
+        let mut column_order = Vec::<String>::new();
+        for column in column_state.as_array().into_iter().flatten() {
+            let col_id = match column.get("colId").and_then(Value::as_str) {
+                Some(col_id) => col_id.to_owned(),
+                None => continue,
+            };
+            if let Some(direction) = column.get("sort").and_then(Value::as_str) {
+                let priority = column
+                    .get("sortIndex")
+                    .and_then(Value::as_i64)
+                    .unwrap_or(i64::MAX);
+                sort.push((
+                    priority,
+                    ColumnSort {
+                        column: col_id.clone(),
+                        descending: direction == "desc",
+                    },
+                ));
+            }
+            if column.get("hide").and_then(Value::as_bool) == Some(true) {
+                hidden_columns.push(col_id.clone());
+            }
+            column_order.push(col_id);
+        }
+        sort.sort_by_key(|(priority, _)| *priority);
+        let filter_model = if filter_model.is_object() {
+            filter_model
+        } else {
+            json!({})
+        };
+        GridState {
+            filter_model,
+            sort: sort
+                .into_iter()
+                .map(|(_, column_sort)| column_sort)
+                .collect(),
+            hidden_columns,
+            column_order,
+        }
+    }
+
+    /// Builds the `{filter_model, column_state}` object `apply_grid_state` in ag_grid.js expects.
+    pub fn to_ag_grid(&self) -> Value {
+        let column_state: Vec<Value> = self
+            .column_order
+            .iter()
+            .map(|column| {
+                let sort = self
+                    .sort
+                    .iter()
+                    .position(|column_sort| &column_sort.column == column);
+                json!({
+                    "colId": column,
+                    "hide": self.hidden_columns.contains(column),
+                    "sort": sort.map(|i| if self.sort[i].descending { "desc" } else { "asc" }),
+                    "sortIndex": sort,
+                })
+            })
+            .collect();
+        json!({
+            "filter_model": self.filter_model,
+            "column_state": column_state,
+        })
+    }
+}
+
+/// A saved view of a table: its filters, sorts and visible columns, and the pivot settings, which
+/// stand in for chart settings as the app has no charts.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct NamedView {
+    pub id: String,
+    pub name: String,
+    pub schema: String,
+    pub table: String,
+    pub grid: GridState,
+    /// Pivot settings shown in the Pivot tab when the view is opened.
+    #[serde(default)]
+    pub pivot: Option<PivotDefinition>,
+}
+
+impl NamedView {
+    pub fn new(name: String, schema: String, table: String, grid: GridState) -> NamedView {
+        NamedView {
+            id: new_view_id(),
+            name,
+            schema,
+            table,
+            grid,
+            pivot: None,
+        }
+    }
+
+    /// Absolute link that opens the table with this view applied, the schema and table names
+    /// percent-encoded as path segments.
+    pub fn link(&self, origin: &str) -> String {
+        let mut url = match Url::parse(origin) {
+            Ok(url) if !url.cannot_be_a_base() => url,
+            _ => {
+                return format!(
+                    "{origin}/site/datatable/{}/{}?view={}",
+                    self.schema, self.table, self.id
+                )
+            }
+        };
+        if let Ok(mut segments) = url.path_segments_mut() {
+            segments
+                .clear()
+                .extend(["site", "datatable", &self.schema, &self.table]);
+        }
+        url.query_pairs_mut().append_pair("view", &self.id);
+        url.to_string()
+    }
+}
+
+fn new_view_id() -> String {
+    format!(
+        "{:x}{:06x}",
+        Date::now() as u64,
+        (Math::random() * 16_777_216.0) as u32
+    )
+}
+
+/// Serializes views to the JSON document `import_views` reads back.
+pub fn export_views(views: &[NamedView]) -> String {
+    serde_json::to_string_pretty(views).unwrap_or_else(|_| String::from("[]"))
+}
+
+/// Reads either a single view or a list of views.
+pub fn import_views(text: &str) -> Result<Vec<NamedView>, ViewError> {
+    match serde_json::from_str::<Vec<NamedView>>(text) {
+        Ok(views) => Ok(views),
+        Err(list_error) => match serde_json::from_str::<NamedView>(text) {
+            Ok(view) => Ok(vec![view]),
+            Err(_) => Err(ViewError::ImportError(format!(
+                "Unable to read views from the file: {list_error}"
+            ))),
+        },
+    }
+}
+
+/// Inserts `views` into `existing`, replacing views that share an id.
+pub fn merge_views(existing: &mut Vec<NamedView>, views: Vec<NamedView>) {
+    for view in views {
+        match existing.iter_mut().find(|current| current.id == view.id) {
+            Some(current) => *current = view,
+            None => existing.push(view),
+        }
+    }
+}
+
+pub fn local_views(schema: &str, table: &str) -> Vec<NamedView> {
+    Workspace::load()
+        .views
+        .into_iter()
+        .filter(|view| view.schema == schema && view.table == table)
+        .collect()
+}
+
+pub fn save_local_view(view: NamedView) {
+    let mut workspace = Workspace::load();
+    merge_views(&mut workspace.views, vec![view]);
+    workspace.save();
+}
+
+pub fn delete_local_view(id: &str) {
+    let mut workspace = Workspace::load();
+    workspace.views.retain(|view| view.id != id);
+    workspace.save();
+}
+
+/// Where views are published for the rest of the team.
+#[derive(Clone, PartialEq)]
+pub(crate) enum SharedViewStore {
+    /// The `/api/v1/views` endpoint of the API.
+    Remote(StyewardConfig),
+    /// Stand-in for deployments without the endpoint, kept in local storage.
+    Local,
+}
+
+impl SharedViewStore {
+    pub fn from_config(config: &StyewardConfig) -> SharedViewStore {
+        if config.shared_views {
+            SharedViewStore::Remote(config.clone())
+        } else {
+            SharedViewStore::Local
+        }
+    }
+
+    pub async fn list(&self, schema: &str, table: &str) -> Result<Vec<NamedView>, ViewError> {
+        match self {
+            SharedViewStore::Remote(config) => {
+                let mut url = views_url(config, None)?;
+                url.query_pairs_mut()
+                    .append_pair("schema", schema)
+                    .append_pair("table", table);
+                match Request::get(url.as_str()).send().await {
+                    Ok(response) => views_from_response(checked(response).await?).await,
+                    Err(error) => Err(handle_gloo_error(error)),
+                }
+            }
+            SharedViewStore::Local => Ok(LocalStorage::get::<Vec<NamedView>>(
+                SHARED_VIEWS_STORAGE_KEY,
+            )
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|view| view.schema == schema && view.table == table)
+            .collect()),
+        }
+    }
+
+    pub async fn publish(&self, view: &NamedView) -> Result<(), ViewError> {
+        match self {
+            SharedViewStore::Remote(config) => {
+                let url = views_url(config, Some(&view.id))?;
+                match Request::put(url.as_str()).json(view) {
+                    Ok(request) => match request.send().await {
+                        Ok(response) => checked(response).await.map(|_| ()),
+                        Err(error) => Err(handle_gloo_error(error)),
+                    },
+                    Err(error) => Err(handle_gloo_error(error)),
+                }
+            }
+            SharedViewStore::Local => {
+                let mut views = LocalStorage::get::<Vec<NamedView>>(SHARED_VIEWS_STORAGE_KEY)
+                    .unwrap_or_default();
+                merge_views(&mut views, vec![view.clone()]);
+                LocalStorage::set(SHARED_VIEWS_STORAGE_KEY, views)
+                    .map_err(|error| ViewError::GlooError(format!("{error}")))
+            }
+        }
+    }
+
+    pub async fn unpublish(&self, id: &str) -> Result<(), ViewError> {
+        match self {
+            SharedViewStore::Remote(config) => {
+                let url = views_url(config, Some(id))?;
+                match Request::delete(url.as_str()).send().await {
+                    Ok(response) => checked(response).await.map(|_| ()),
+                    Err(error) => Err(handle_gloo_error(error)),
+                }
+            }
+            SharedViewStore::Local => {
+                let mut views = LocalStorage::get::<Vec<NamedView>>(SHARED_VIEWS_STORAGE_KEY)
+                    .unwrap_or_default();
+                views.retain(|view| view.id != id);
+                LocalStorage::set(SHARED_VIEWS_STORAGE_KEY, views)
+                    .map_err(|error| ViewError::GlooError(format!("{error}")))
+            }
+        }
+    }
+}
+
+/// Looks a view up among the local views first, then among the shared ones.
+pub(crate) async fn find_view(
+    config: &StyewardConfig,
+    schema: &str,
+    table: &str,
+    id: &str,
+) -> Result<Option<NamedView>, ViewError> {
+    if let Some(view) = local_views(schema, table)
+        .into_iter()
+        .find(|view| view.id == id)
+    {
+        return Ok(Some(view));
+    }
+    let shared = SharedViewStore::from_config(config)
+        .list(schema, table)
+        .await?;
+    Ok(shared.into_iter().find(|view| view.id == id))
+}
+
+fn views_url(config: &StyewardConfig, id: Option<&str>) -> Result<Url, ViewError> {
+    let endpoint = match id {
+        Some(id) => format!(
+            "{}://{}:{}/api/v1/views/{id}",
+            config.scheme, config.host, config.port
+        ),
+        None => format!(
+            "{}://{}:{}/api/v1/views",
+            config.scheme, config.host, config.port
+        ),
+    };
+    Url::parse(&endpoint).map_err(|error| ViewError::EndpointError(format!("{error}")))
+}
+
+async fn checked(response: Response) -> Result<Response, ViewError> {
+    if response.ok() {
+        Ok(response)
+    } else {
+        match response.text().await {
+            Ok(body_string) => Err(ViewError::HttpStatusError(body_string)),
+            Err(_) => Err(ViewError::HttpStatusError(format!(
+                "Status: {}",
+                response.status()
+            ))),
+        }
+    }
+}
+
+async fn views_from_response(response: Response) -> Result<Vec<NamedView>, ViewError> {
+    match response.text().await {
+        Ok(text) => match serde_json::from_str::<Vec<NamedView>>(&text) {
+            Ok(views) => Ok(views),
+            Err(_) => Err(ViewError::DeserializeError(
+                "Unable to deserialize server response to list of views".to_string(),
+            )),
+        },
+        Err(error) => Err(handle_gloo_error(error)),
+    }
+}
+
+fn handle_gloo_error(gloo_error: gloo_net::Error) -> ViewError {
+    match gloo_error {
+        gloo_net::Error::SerdeError(error) => ViewError::GlooError(format!("{error}")),
+        gloo_net::Error::JsError(error) => ViewError::GlooError(error.message),
+        gloo_net::Error::GlooError(error) => ViewError::GlooError(error),
+    }
+}
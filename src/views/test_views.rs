@@ -0,0 +1,124 @@
+use super::{export_views, import_views, merge_views, ColumnSort, GridState, NamedView};
+use serde_json::json;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn view(id: &str, name: &str) -> NamedView {
+    NamedView {
+        id: String::from(id),
+        name: String::from(name),
+        schema: String::from("sdtm"),
+        table: String::from("ae"),
+        grid: GridState::default(),
+        pivot: None,
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_grid_state_from_ag_grid() {
+    let column_state = json!([
+        {"colId": "subject", "hide": false, "sort": null, "sortIndex": null},
+        {"colId": "aetoxgr", "hide": false, "sort": "desc", "sortIndex": 0},
+        {"colId": "siteid", "hide": false, "sort": "asc", "sortIndex": 1},
+        {"colId": "aeverbatim", "hide": true, "sort": null, "sortIndex": null}
+    ]);
+    let filter_model = json!({"siteid": {"filterType": "text", "type": "equals", "filter": "12"}});
+    let state = GridState::from_ag_grid(filter_model.clone(), &column_state);
+    assert_eq!(
+        state.sort,
+        vec![
+            ColumnSort {
+                column: String::from("aetoxgr"),
+                descending: true
+            },
+            ColumnSort {
+                column: String::from("siteid"),
+                descending: false
+            },
+        ]
+    );
+    assert_eq!(state.hidden_columns, vec![String::from("aeverbatim")]);
+    assert_eq!(state.column_order.len(), 4);
+    assert_eq!(state.filter_model, filter_model);
+}
+
+#[wasm_bindgen_test]
+fn test_grid_state_round_trips_through_ag_grid() {
+    let state = GridState {
+        filter_model: json!({}),
+        sort: vec![ColumnSort {
+            column: String::from("aetoxgr"),
+            descending: true,
+        }],
+        hidden_columns: vec![String::from("aeverbatim")],
+        column_order: vec![
+            String::from("subject"),
+            String::from("aetoxgr"),
+            String::from("aeverbatim"),
+        ],
+    };
+    let ag_grid = state.to_ag_grid();
+    let restored =
+        GridState::from_ag_grid(ag_grid["filter_model"].clone(), &ag_grid["column_state"]);
+    assert_eq!(restored, state);
+}
+
+#[wasm_bindgen_test]
+fn test_grid_state_without_grid_uses_empty_filter_model() {
+    let state = GridState::from_ag_grid(json!(null), &json!(null));
+    assert_eq!(state.filter_model, json!({}));
+    assert!(state.column_order.is_empty());
+}
+
+#[wasm_bindgen_test]
+fn test_grid_state_contains_filter() {
+    let state = GridState::contains_filter("aeterm", "pneumonitis");
+    assert_eq!(
+        state.to_ag_grid()["filter_model"],
+        json!({"aeterm": {"filterType": "text", "type": "contains", "filter": "pneumonitis"}})
+    );
+    assert!(state.sort.is_empty());
+}
+
+#[wasm_bindgen_test]
+fn test_import_views_reads_exported_views() {
+    let views = vec![view("a", "Grade 3+ AEs, site 12"), view("b", "Serious AEs")];
+    assert_eq!(import_views(&export_views(&views)).ok(), Some(views));
+}
+
+#[wasm_bindgen_test]
+fn test_import_views_accepts_a_single_view() {
+    let single = serde_json::to_string(&view("a", "Serious AEs")).unwrap();
+    assert_eq!(import_views(&single).ok().map(|views| views.len()), Some(1));
+    assert!(import_views("{\"not\": \"a view\"}").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_merge_views_replaces_views_with_the_same_id() {
+    let mut existing = vec![view("a", "old name"), view("b", "kept")];
+    merge_views(
+        &mut existing,
+        vec![view("a", "new name"), view("c", "added")],
+    );
+    let names: Vec<&str> = existing.iter().map(|view| view.name.as_str()).collect();
+    assert_eq!(names, vec!["new name", "kept", "added"]);
+}
+
+#[wasm_bindgen_test]
+fn test_view_link_encodes_the_view_id() {
+    let link = view("a b", "x").link("https://styeward.example.com");
+    assert_eq!(
+        link,
+        "https://styeward.example.com/site/datatable/sdtm/ae?view=a+b"
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_view_link_encodes_schema_and_table_names() {
+    let mut shared = view("v1", "x");
+    shared.schema = String::from("sdtm raw");
+    shared.table = String::from("ae#1?/2");
+    assert_eq!(
+        shared.link("https://styeward.example.com"),
+        "https://styeward.example.com/site/datatable/sdtm%20raw/ae%231%3F%2F2?view=v1"
+    );
+}
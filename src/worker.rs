@@ -0,0 +1,479 @@
+//! The work kept off the main thread: decoding table payloads, column statistics, pivots and
+//! searches. It runs in a Web Worker started from the `worker` binary, and talks to the app in
+//! `WorkerRequest`s and `WorkerResponse`s, see src/worker_client.rs for the app's side.
+
+#[cfg(test)]
+mod test_worker;
+
+use crate::csv::CsvOptions;
+use crate::payload::{PayloadDecoder, PayloadError, PayloadFormat};
+use crate::pivot::{compute_pivot, PivotDefinition, PivotResult};
+use crate::profile::{profile_table, ColumnProfile};
+use crate::row_stream::LoadProgress;
+use crate::search::{search_rows, SearchHit};
+use crate::table::Table;
+
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+
+use gloo_timers::future::TimeoutFuture;
+use gloo_worker::{HandlerId, Registrable, Worker, WorkerScope};
+use serde::{Deserialize, Serialize};
+
+/// Identifies a job across the worker boundary. A table is kept under the id of the job that
+/// loaded it.
+pub type JobId = u32;
+
+/// Tables kept for the jobs that follow their load, the least recently used is dropped past this.
+const KEPT_TABLES: usize = 8;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum WorkerRequest {
+    /// Asks the worker to answer `Ready`, once it has started.
+    Hello,
+    /// Starts decoding a table payload, its bytes follow in `TableChunk`s and a `TableEnd`.
+    TableStart {
+        job: JobId,
+        format: Option<PayloadFormat>,
+        csv: CsvOptions,
+        total_bytes: Option<u64>,
+    },
+    TableChunk {
+        job: JobId,
+        bytes: Vec<u8>,
+    },
+    TableEnd {
+        job: JobId,
+    },
+    /// Hands the worker a table it no longer keeps, under the id it was loaded with.
+    ShareTable {
+        table: JobId,
+        data: Table,
+    },
+    Profile {
+        job: JobId,
+        table: JobId,
+    },
+    Pivot {
+        job: JobId,
+        table: JobId,
+        definition: PivotDefinition,
+    },
+    Search {
+        job: JobId,
+        table: JobId,
+        /// The table's name, reported in the hits.
+        name: String,
+        query: String,
+        max_samples: usize,
+    },
+    /// Drops a table load, or a job that has not run yet.
+    Cancel {
+        job: JobId,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum WorkerResponse {
+    Ready,
+    /// How far a table load is, with a copy of the rows decoded so far each time their number
+    /// has doubled.
+    Progress {
+        job: JobId,
+        progress: LoadProgress,
+        partial: Option<Table>,
+        /// The payload's format, once recognized.
+        format: Option<PayloadFormat>,
+    },
+    Table {
+        job: JobId,
+        table: Rc<Table>,
+        format: Option<PayloadFormat>,
+    },
+    Profile {
+        job: JobId,
+        profile: Vec<ColumnProfile>,
+    },
+    Pivot {
+        job: JobId,
+        result: PivotResult,
+    },
+    Search {
+        job: JobId,
+        hits: Vec<SearchHit>,
+    },
+    Failed {
+        job: JobId,
+        error: WorkerError,
+    },
+}
+
+impl WorkerResponse {
+    /// The job the response is for, `None` for `Ready`.
+    pub fn job(&self) -> Option<JobId> {
+        match self {
+            WorkerResponse::Ready => None,
+            WorkerResponse::Progress { job, .. }
+            | WorkerResponse::Table { job, .. }
+            | WorkerResponse::Profile { job, .. }
+            | WorkerResponse::Pivot { job, .. }
+            | WorkerResponse::Search { job, .. }
+            | WorkerResponse::Failed { job, .. } => Some(*job),
+        }
+    }
+
+    /// Whether the job has nothing more to report.
+    pub fn is_final(&self) -> bool {
+        !matches!(
+            self,
+            WorkerResponse::Ready | WorkerResponse::Progress { .. }
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum WorkerError {
+    FormatError(String),
+    DeserializeError(String),
+    /// The worker does not keep the table the job is for, it has to be shared again.
+    TableNotLoadedError(String),
+    /// The worker stopped answering, or never started.
+    WorkerUnavailableError(String),
+}
+
+impl WorkerError {
+    pub fn message(&self) -> &String {
+        match self {
+            WorkerError::FormatError(msg)
+            | WorkerError::DeserializeError(msg)
+            | WorkerError::TableNotLoadedError(msg)
+            | WorkerError::WorkerUnavailableError(msg) => msg,
+        }
+    }
+}
+
+impl From<PayloadError> for WorkerError {
+    fn from(error: PayloadError) -> WorkerError {
+        match error {
+            PayloadError::FormatError(msg) | PayloadError::UnsupportedError(msg) => {
+                WorkerError::FormatError(msg)
+            }
+            PayloadError::DeserializeError(msg) => WorkerError::DeserializeError(msg),
+        }
+    }
+}
+
+/// A table being decoded.
+struct TableLoad {
+    decoder: PayloadDecoder,
+    table: Table,
+    progress: LoadProgress,
+    /// Rows sent in partial tables so far.
+    published_rows: usize,
+}
+
+impl TableLoad {
+    fn read(&mut self, chunk: &[u8]) -> Result<(), WorkerError> {
+        self.progress.bytes += chunk.len() as u64;
+        let records = self.decoder.feed(chunk)?;
+        self.push_records(records)
+    }
+
+    fn push_records(
+        &mut self,
+        records: Vec<serde_json::Map<String, serde_json::Value>>,
+    ) -> Result<(), WorkerError> {
+        for record in records {
+            self.table
+                .push_record(&record)
+                .map_err(|error| WorkerError::FormatError(error.message().clone()))?;
+        }
+        self.progress.rows = self.table.len();
+        Ok(())
+    }
+
+    /// A copy of the rows read so far, each time their number has doubled, so the grid shows the
+    /// first rows early without copying the table at every chunk.
+    fn partial(&mut self) -> Option<Table> {
+        if self.table.is_empty() || self.table.len() < self.published_rows * 2 {
+            return None;
+        }
+        self.published_rows = self.table.len();
+        Some(self.table.clone())
+    }
+}
+
+/// A job waiting for its turn.
+enum Task {
+    Profile {
+        table: JobId,
+    },
+    Pivot {
+        table: JobId,
+        definition: PivotDefinition,
+    },
+    Search {
+        table: JobId,
+        name: String,
+        query: String,
+        max_samples: usize,
+    },
+}
+
+/// A table kept for later jobs.
+struct KeptTable {
+    id: JobId,
+    table: Rc<Table>,
+}
+
+/// Runs the jobs of the protocol. Table loads are decoded as their chunks arrive; other jobs are
+/// queued and run one at a time by `run_next`, which the host calls between messages, so a job
+/// cancelled while it waits never runs.
+#[derive(Default)]
+pub struct Jobs {
+    loads: HashMap<JobId, TableLoad>,
+    /// Least recently used first.
+    tables: Vec<KeptTable>,
+    queue: VecDeque<(JobId, Task)>,
+}
+
+impl Jobs {
+    /// Handles a request and returns the responses it completes right away.
+    pub fn receive(&mut self, request: WorkerRequest) -> Vec<WorkerResponse> {
+        match request {
+            WorkerRequest::Hello => vec![WorkerResponse::Ready],
+            WorkerRequest::TableStart {
+                job,
+                format,
+                csv,
+                total_bytes,
+            } => {
+                self.loads.insert(
+                    job,
+                    TableLoad {
+                        decoder: PayloadDecoder::new(format, csv),
+                        table: Table::new(),
+                        progress: LoadProgress {
+                            total_bytes,
+                            ..LoadProgress::default()
+                        },
+                        published_rows: 0,
+                    },
+                );
+                Vec::new()
+            }
+            WorkerRequest::TableChunk { job, bytes } => {
+                // Chunks of a cancelled or failed load are dropped.
+                let Some(load) = self.loads.get_mut(&job) else {
+                    return Vec::new();
+                };
+                match load.read(&bytes) {
+                    Ok(()) => vec![WorkerResponse::Progress {
+                        job,
+                        progress: load.progress,
+                        partial: load.partial(),
+                        format: load.decoder.format(),
+                    }],
+                    Err(error) => {
+                        self.loads.remove(&job);
+                        vec![WorkerResponse::Failed { job, error }]
+                    }
+                }
+            }
+            WorkerRequest::TableEnd { job } => {
+                let Some(mut load) = self.loads.remove(&job) else {
+                    return Vec::new();
+                };
+                let finished = load
+                    .decoder
+                    .finish()
+                    .map_err(WorkerError::from)
+                    .and_then(|records| load.push_records(records));
+                match finished {
+                    Ok(()) => {
+                        let table = Rc::new(load.table);
+                        self.keep(job, table.clone());
+                        vec![WorkerResponse::Table {
+                            job,
+                            table,
+                            format: load.decoder.format(),
+                        }]
+                    }
+                    Err(error) => vec![WorkerResponse::Failed { job, error }],
+                }
+            }
+            WorkerRequest::ShareTable { table, data } => {
+                // A copy of a table still loading is older than the load itself.
+                if !self.loads.contains_key(&table) {
+                    self.keep(table, Rc::new(data));
+                }
+                Vec::new()
+            }
+            WorkerRequest::Profile { job, table } => {
+                self.queue.push_back((job, Task::Profile { table }));
+                Vec::new()
+            }
+            WorkerRequest::Pivot {
+                job,
+                table,
+                definition,
+            } => {
+                self.queue
+                    .push_back((job, Task::Pivot { table, definition }));
+                Vec::new()
+            }
+            WorkerRequest::Search {
+                job,
+                table,
+                name,
+                query,
+                max_samples,
+            } => {
+                self.queue.push_back((
+                    job,
+                    Task::Search {
+                        table,
+                        name,
+                        query,
+                        max_samples,
+                    },
+                ));
+                Vec::new()
+            }
+            WorkerRequest::Cancel { job } => {
+                self.loads.remove(&job);
+                self.queue.retain(|(queued, _)| *queued != job);
+                Vec::new()
+            }
+        }
+    }
+
+    pub fn has_queued(&self) -> bool {
+        !self.queue.is_empty()
+    }
+
+    /// Runs the oldest queued job. A job on a table still loading runs on the rows decoded so far.
+    pub fn run_next(&mut self) -> Option<WorkerResponse> {
+        let (job, task) = self.queue.pop_front()?;
+        let table_id = match &task {
+            Task::Profile { table } | Task::Pivot { table, .. } | Task::Search { table, .. } => {
+                *table
+            }
+        };
+        if let Some(load) = self.loads.get(&table_id) {
+            return Some(run_task(job, task, &load.table));
+        }
+        Some(match self.touch(table_id) {
+            Some(kept) => run_task(job, task, &kept.table),
+            None => WorkerResponse::Failed {
+                job,
+                error: WorkerError::TableNotLoadedError(format!(
+                    "The worker does not hold table {table_id}."
+                )),
+            },
+        })
+    }
+
+    fn keep(&mut self, id: JobId, table: Rc<Table>) {
+        self.tables.retain(|kept| kept.id != id);
+        if self.tables.len() >= KEPT_TABLES {
+            self.tables.remove(0);
+        }
+        self.tables.push(KeptTable { id, table });
+    }
+
+    /// The table kept under `id`, made the most recently used.
+    fn touch(&mut self, id: JobId) -> Option<&mut KeptTable> {
+        let position = self.tables.iter().position(|kept| kept.id == id)?;
+        let kept = self.tables.remove(position);
+        self.tables.push(kept);
+        self.tables.last_mut()
+    }
+}
+
+fn run_task(job: JobId, task: Task, table: &Table) -> WorkerResponse {
+    match task {
+        Task::Profile { .. } => WorkerResponse::Profile {
+            job,
+            profile: profile_table(table),
+        },
+        Task::Pivot { definition, .. } => WorkerResponse::Pivot {
+            job,
+            result: compute_pivot(&definition, table),
+        },
+        Task::Search {
+            name,
+            query,
+            max_samples,
+            ..
+        } => WorkerResponse::Search {
+            job,
+            hits: search_rows(&name, table, &query, max_samples),
+        },
+    }
+}
+
+/// The Web Worker, a `Jobs` answering the app's bridge.
+pub struct TableWorker {
+    jobs: Jobs,
+    /// Set while a `RunNext` is on its way.
+    scheduled: bool,
+    bridge: Option<HandlerId>,
+}
+
+pub enum TableWorkerMessage {
+    RunNext,
+}
+
+impl TableWorker {
+    /// Runs the next queued job after the messages already waiting, a cancellation among them
+    /// takes the job out of the queue first.
+    fn schedule(&mut self, scope: &WorkerScope<Self>) {
+        if !self.scheduled && self.jobs.has_queued() {
+            self.scheduled = true;
+            scope.send_future(async {
+                TimeoutFuture::new(0).await;
+                TableWorkerMessage::RunNext
+            });
+        }
+    }
+}
+
+impl Worker for TableWorker {
+    type Message = TableWorkerMessage;
+    type Input = WorkerRequest;
+    type Output = WorkerResponse;
+
+    fn create(_scope: &WorkerScope<Self>) -> Self {
+        TableWorker {
+            jobs: Jobs::default(),
+            scheduled: false,
+            bridge: None,
+        }
+    }
+
+    fn update(&mut self, scope: &WorkerScope<Self>, message: Self::Message) {
+        match message {
+            TableWorkerMessage::RunNext => {
+                self.scheduled = false;
+                if let (Some(response), Some(bridge)) = (self.jobs.run_next(), self.bridge) {
+                    scope.respond(bridge, response);
+                }
+                self.schedule(scope);
+            }
+        }
+    }
+
+    fn received(&mut self, scope: &WorkerScope<Self>, request: Self::Input, id: HandlerId) {
+        self.bridge = Some(id);
+        for response in self.jobs.receive(request) {
+            scope.respond(id, response);
+        }
+        self.schedule(scope);
+    }
+}
+
+/// Starts answering the app, the entry point of the `worker` binary.
+pub fn register() {
+    TableWorker::registrar().register();
+}
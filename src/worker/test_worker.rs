@@ -0,0 +1,228 @@
+use super::{Jobs, WorkerError, WorkerRequest, WorkerResponse};
+use crate::csv::CsvOptions;
+use crate::payload::PayloadFormat;
+use crate::pivot::PivotDefinition;
+use crate::table::Table;
+use serde_json::{json, Map, Value};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const CSV: &str = "USUBJID,AESEV,AESEQ\n1023-004,MILD,1\n1023-004,SEVERE,2\n1023-005,MILD,1\n";
+
+/// Loads `text` as job `job` in chunks of `size` bytes and returns every response.
+fn load(jobs: &mut Jobs, job: u32, text: &str, size: usize) -> Vec<WorkerResponse> {
+    // Rows are read as their lines arrive, rather than held back to recognize the format, the
+    // delimiter or the types of the columns.
+    let csv = CsvOptions {
+        delimiter: Some(','),
+        infer_types: false,
+    };
+    let mut responses = jobs.receive(WorkerRequest::TableStart {
+        job,
+        format: Some(PayloadFormat::Csv),
+        csv,
+        total_bytes: Some(text.len() as u64),
+    });
+    for chunk in text.as_bytes().chunks(size) {
+        responses.extend(jobs.receive(WorkerRequest::TableChunk {
+            job,
+            bytes: chunk.to_vec(),
+        }));
+    }
+    responses.extend(jobs.receive(WorkerRequest::TableEnd { job }));
+    responses
+}
+
+fn table(value: Value) -> Table {
+    let records: Vec<Map<String, Value>> = value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|record| record.as_object().unwrap().clone())
+        .collect();
+    Table::from_records(&records).ok().unwrap()
+}
+
+#[wasm_bindgen_test]
+fn test_loads_a_table_in_chunks() {
+    let mut jobs = Jobs::default();
+    let responses = load(&mut jobs, 1, CSV, 7);
+    let (last, progress) = responses.split_last().unwrap();
+    assert!(progress.iter().all(|response| !response.is_final()));
+    // Partial tables are sent as the rows double, not at every chunk.
+    let partials: Vec<usize> = progress
+        .iter()
+        .filter_map(|response| match response {
+            WorkerResponse::Progress {
+                partial: Some(table),
+                ..
+            } => Some(table.len()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(partials, vec![1, 2]);
+    match last {
+        WorkerResponse::Table { job, table, format } => {
+            assert_eq!(*job, 1);
+            assert_eq!(table.len(), 3);
+            assert_eq!(*format, Some(PayloadFormat::Csv));
+        }
+        other => panic!("unexpected response {other:?}"),
+    }
+
+    let responses = load(&mut jobs, 2, "USUBJID,AESEQ\n1023-004\n", 64);
+    assert!(matches!(
+        responses.last(),
+        Some(WorkerResponse::Failed {
+            job: 2,
+            error: WorkerError::FormatError(_)
+        })
+    ));
+}
+
+#[wasm_bindgen_test]
+fn test_jobs_run_on_kept_tables() {
+    let mut jobs = Jobs::default();
+    load(&mut jobs, 1, CSV, 1024);
+    jobs.receive(WorkerRequest::Profile { job: 2, table: 1 });
+    jobs.receive(WorkerRequest::Pivot {
+        job: 3,
+        table: 1,
+        definition: PivotDefinition {
+            rows: vec![String::from("AESEV")],
+            ..PivotDefinition::default()
+        },
+    });
+    jobs.receive(WorkerRequest::Search {
+        job: 4,
+        table: 1,
+        name: String::from("ae"),
+        query: String::from("severe"),
+        max_samples: 5,
+    });
+    match jobs.run_next() {
+        Some(WorkerResponse::Profile { job: 2, profile }) => assert_eq!(profile.len(), 3),
+        other => panic!("unexpected response {other:?}"),
+    }
+    match jobs.run_next() {
+        // MILD, SEVERE and the grand total.
+        Some(WorkerResponse::Pivot { job: 3, result }) => assert_eq!(result.rows.len(), 3),
+        other => panic!("unexpected response {other:?}"),
+    }
+    match jobs.run_next() {
+        Some(WorkerResponse::Search { job: 4, hits }) => {
+            assert_eq!(hits.len(), 1);
+            assert_eq!(hits[0].table, "ae");
+            assert_eq!(hits[0].row_count, 1);
+        }
+        other => panic!("unexpected response {other:?}"),
+    }
+    assert!(!jobs.has_queued());
+
+    // A table the worker does not hold has to be shared first.
+    jobs.receive(WorkerRequest::Profile { job: 5, table: 9 });
+    assert!(matches!(
+        jobs.run_next(),
+        Some(WorkerResponse::Failed {
+            job: 5,
+            error: WorkerError::TableNotLoadedError(_)
+        })
+    ));
+    jobs.receive(WorkerRequest::ShareTable {
+        table: 9,
+        data: table(json!([{"USUBJID": "1023-004"}])),
+    });
+    jobs.receive(WorkerRequest::Profile { job: 6, table: 9 });
+    assert!(matches!(
+        jobs.run_next(),
+        Some(WorkerResponse::Profile { job: 6, .. })
+    ));
+}
+
+#[wasm_bindgen_test]
+fn test_jobs_on_a_loading_table_see_the_rows_so_far() {
+    let mut jobs = Jobs::default();
+    jobs.receive(WorkerRequest::TableStart {
+        job: 1,
+        format: Some(PayloadFormat::Csv),
+        csv: CsvOptions {
+            delimiter: Some(','),
+            infer_types: false,
+        },
+        total_bytes: None,
+    });
+    jobs.receive(WorkerRequest::TableChunk {
+        job: 1,
+        bytes: CSV.as_bytes()[..40].to_vec(),
+    });
+    // The copy the app holds does not replace the load.
+    jobs.receive(WorkerRequest::ShareTable {
+        table: 1,
+        data: Table::new(),
+    });
+    jobs.receive(WorkerRequest::Search {
+        job: 2,
+        table: 1,
+        name: String::from("ae"),
+        query: String::from("1023"),
+        max_samples: 5,
+    });
+    match jobs.run_next() {
+        Some(WorkerResponse::Search { job: 2, hits }) => assert_eq!(hits[0].row_count, 1),
+        other => panic!("unexpected response {other:?}"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_cancelled_jobs_do_not_run() {
+    let mut jobs = Jobs::default();
+    load(&mut jobs, 1, CSV, 1024);
+    jobs.receive(WorkerRequest::Profile { job: 2, table: 1 });
+    jobs.receive(WorkerRequest::Profile { job: 3, table: 1 });
+    jobs.receive(WorkerRequest::Cancel { job: 2 });
+    assert!(matches!(
+        jobs.run_next(),
+        Some(WorkerResponse::Profile { job: 3, .. })
+    ));
+    assert!(jobs.run_next().is_none());
+
+    // The rest of a cancelled load is ignored.
+    jobs.receive(WorkerRequest::TableStart {
+        job: 4,
+        format: Some(PayloadFormat::Csv),
+        csv: CsvOptions::default(),
+        total_bytes: None,
+    });
+    jobs.receive(WorkerRequest::Cancel { job: 4 });
+    assert!(jobs
+        .receive(WorkerRequest::TableChunk {
+            job: 4,
+            bytes: CSV.as_bytes().to_vec(),
+        })
+        .is_empty());
+    assert!(jobs.receive(WorkerRequest::TableEnd { job: 4 }).is_empty());
+}
+
+#[wasm_bindgen_test]
+fn test_responses_cross_the_worker_boundary() {
+    // Mixed columns hold JSON values, which the worker's encoding cannot carry as such.
+    let data = table(json!([
+        {"USUBJID": "1023-004", "LBORRES": 12, "LBSEQ": 1},
+        {"USUBJID": "1023-005", "LBORRES": "<5", "LBSEQ": null},
+        {"USUBJID": "1023-006", "LBORRES": true},
+    ]));
+    let response = WorkerResponse::Table {
+        job: 1,
+        table: std::rc::Rc::new(data.clone()),
+        format: Some(PayloadFormat::Json),
+    };
+    let bytes = bincode::serialize(&response).unwrap();
+    match bincode::deserialize::<WorkerResponse>(&bytes).unwrap() {
+        WorkerResponse::Table { table, .. } => {
+            assert_eq!(table.records(), data.records());
+            for row in 0..data.len() {
+                assert_eq!(table.display_row(row), data.display_row(row));
+            }
+        }
+        other => panic!("unexpected response {other:?}"),
+    }
+}
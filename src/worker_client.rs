@@ -0,0 +1,352 @@
+//! The app's side of the worker in src/worker.rs: starts it, hands out job ids and routes its
+//! responses to the jobs waiting for them. When the worker does not start, the same jobs run on
+//! the main thread instead, between tasks.
+
+use crate::pivot::{PivotDefinition, PivotResult};
+use crate::profile::ColumnProfile;
+use crate::search::SearchHit;
+use crate::table::Table;
+use crate::worker::{JobId, Jobs, TableWorker, WorkerError, WorkerRequest, WorkerResponse};
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::rc::Rc;
+
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures::future::{abortable, AbortHandle};
+use futures::StreamExt;
+use gloo_timers::callback::Timeout;
+use gloo_timers::future::TimeoutFuture;
+use gloo_worker::{Spawnable, WorkerBridge};
+use wasm_bindgen_futures::spawn_local;
+
+/// The worker's script, which trunk builds from the `worker` binary, see index.html.
+const WORKER_PATH: &str = "/site/worker.js";
+/// How long the worker has to answer `Hello` before its jobs run on the main thread.
+const START_TIMEOUT_MS: u32 = 10_000;
+
+enum Backend {
+    /// Waiting for the worker's `Ready`. Requests wait with it, to run on the main thread should
+    /// it never come.
+    Starting(WorkerBridge<TableWorker>, Vec<WorkerRequest>),
+    Worker(WorkerBridge<TableWorker>),
+    Inline(Jobs),
+}
+
+struct Client {
+    backend: Backend,
+    listeners: HashMap<JobId, UnboundedSender<WorkerResponse>>,
+    last_job: JobId,
+    /// Set while queued jobs are run on the main thread.
+    running: bool,
+}
+
+thread_local! {
+    static CLIENT: RefCell<Option<Client>> = const { RefCell::new(None) };
+}
+
+impl Client {
+    fn start() -> Client {
+        // Without a window, e.g. under the test runner, there is no worker to start.
+        let backend = if web_sys::window().is_some() {
+            let bridge = TableWorker::spawner()
+                .callback(on_response)
+                .spawn(WORKER_PATH);
+            bridge.send(WorkerRequest::Hello);
+            Timeout::new(START_TIMEOUT_MS, || with_client(Client::fall_back)).forget();
+            Backend::Starting(bridge, Vec::new())
+        } else {
+            Backend::Inline(Jobs::default())
+        };
+        Client {
+            backend,
+            listeners: HashMap::new(),
+            last_job: 0,
+            running: false,
+        }
+    }
+
+    fn send(&mut self, request: WorkerRequest) {
+        match &mut self.backend {
+            Backend::Starting(_, queued) => queued.push(request),
+            Backend::Worker(bridge) => bridge.send(request),
+            Backend::Inline(jobs) => {
+                for response in jobs.receive(request) {
+                    dispatch(&mut self.listeners, response);
+                }
+                self.run_inline();
+            }
+        }
+    }
+
+    fn ready(&mut self) {
+        let backend = std::mem::replace(&mut self.backend, Backend::Inline(Jobs::default()));
+        self.backend = match backend {
+            Backend::Starting(bridge, queued) => {
+                for request in queued {
+                    bridge.send(request);
+                }
+                Backend::Worker(bridge)
+            }
+            backend => backend,
+        };
+    }
+
+    /// Runs the jobs on the main thread from now on, when the worker has not answered in time.
+    fn fall_back(&mut self) {
+        let backend = std::mem::replace(&mut self.backend, Backend::Inline(Jobs::default()));
+        match backend {
+            Backend::Starting(_, queued) => {
+                log::warn!("The worker did not start, tables are processed on the main thread.");
+                for request in queued {
+                    self.send(request);
+                }
+            }
+            backend => self.backend = backend,
+        }
+    }
+
+    /// Runs the queued jobs one per task, so the page stays responsive and cancellations arriving
+    /// in between are honored.
+    fn run_inline(&mut self) {
+        let queued = matches!(&self.backend, Backend::Inline(jobs) if jobs.has_queued());
+        if self.running || !queued {
+            return;
+        }
+        self.running = true;
+        spawn_local(async {
+            loop {
+                TimeoutFuture::new(0).await;
+                let running = with_client(|client| {
+                    if let Backend::Inline(jobs) = &mut client.backend {
+                        if let Some(response) = jobs.run_next() {
+                            dispatch(&mut client.listeners, response);
+                        }
+                        client.running = jobs.has_queued();
+                    } else {
+                        client.running = false;
+                    }
+                    client.running
+                });
+                if !running {
+                    break;
+                }
+            }
+        });
+    }
+}
+
+fn with_client<T>(action: impl FnOnce(&mut Client) -> T) -> T {
+    CLIENT.with(|client| action(client.borrow_mut().get_or_insert_with(Client::start)))
+}
+
+fn on_response(response: WorkerResponse) {
+    with_client(|client| match response {
+        WorkerResponse::Ready => client.ready(),
+        response => dispatch(&mut client.listeners, response),
+    });
+}
+
+fn dispatch(
+    listeners: &mut HashMap<JobId, UnboundedSender<WorkerResponse>>,
+    response: WorkerResponse,
+) {
+    let Some(job) = response.job() else {
+        return;
+    };
+    let is_final = response.is_final();
+    if let Some(listener) = listeners.get(&job) {
+        // The job may have been dropped since, its responses are of no use then.
+        let _ = listener.unbounded_send(response);
+    }
+    if is_final {
+        listeners.remove(&job);
+    }
+}
+
+fn send(request: WorkerRequest) {
+    with_client(|client| client.send(request));
+}
+
+/// A job's responses, in order. Dropping the job before its final response cancels it.
+pub struct WorkerJob {
+    id: JobId,
+    responses: UnboundedReceiver<WorkerResponse>,
+    finished: bool,
+}
+
+impl WorkerJob {
+    /// Starts the job `request` describes, given the job's id.
+    pub fn start(request: impl FnOnce(JobId) -> WorkerRequest) -> WorkerJob {
+        let (sender, responses) = unbounded();
+        let id = with_client(|client| {
+            client.last_job += 1;
+            client.listeners.insert(client.last_job, sender);
+            client.last_job
+        });
+        send(request(id));
+        WorkerJob {
+            id,
+            responses,
+            finished: false,
+        }
+    }
+
+    pub fn id(&self) -> JobId {
+        self.id
+    }
+
+    /// Sends a further request of the job, such as the chunks of a table load.
+    pub fn send(&self, request: WorkerRequest) {
+        send(request);
+    }
+
+    /// The next response if it has already arrived.
+    pub fn try_next(&mut self) -> Option<WorkerResponse> {
+        if self.finished {
+            return None;
+        }
+        let response = self.responses.try_recv().ok()?;
+        self.finished = response.is_final();
+        Some(response)
+    }
+
+    /// The next response, `None` after the final one.
+    pub async fn next(&mut self) -> Option<WorkerResponse> {
+        if self.finished {
+            return None;
+        }
+        let response = self.responses.next().await;
+        self.finished = response.as_ref().is_none_or(WorkerResponse::is_final);
+        response
+    }
+}
+
+impl Drop for WorkerJob {
+    fn drop(&mut self) {
+        if !self.finished {
+            let job = self.id;
+            with_client(|client| {
+                client.listeners.remove(&job);
+                client.send(WorkerRequest::Cancel { job });
+            });
+        }
+    }
+}
+
+/// Runs a future on the main thread until it completes or the handle is dropped, which cancels
+/// the jobs it waits on. Components keep the handle of the jobs behind what they show, so a
+/// newer job or leaving the page cancels the older one.
+pub struct JobHandle(AbortHandle);
+
+impl JobHandle {
+    pub fn spawn(future: impl Future<Output = ()> + 'static) -> JobHandle {
+        let (future, handle) = abortable(future);
+        spawn_local(async {
+            let _ = future.await;
+        });
+        JobHandle(handle)
+    }
+}
+
+impl Drop for JobHandle {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// An id for a table the worker did not load, such as an offline copy. The table is shared with
+/// the worker by the first job run on it.
+pub fn table_id() -> JobId {
+    with_client(|client| {
+        client.last_job += 1;
+        client.last_job
+    })
+}
+
+/// Runs a job on the table the worker keeps under `table`, sharing `data` with it first when it
+/// no longer keeps it, e.g. after keeping newer tables or for a table loaded before the worker
+/// took over.
+async fn run_on_table(
+    table: JobId,
+    data: &Table,
+    request: impl Fn(JobId) -> WorkerRequest,
+) -> Result<WorkerResponse, WorkerError> {
+    let mut job = WorkerJob::start(&request);
+    match job.next().await {
+        Some(WorkerResponse::Failed {
+            error: WorkerError::TableNotLoadedError(_),
+            ..
+        }) => {
+            send(WorkerRequest::ShareTable {
+                table,
+                data: data.clone(),
+            });
+            let mut job = WorkerJob::start(&request);
+            final_response(job.next().await)
+        }
+        response => final_response(response),
+    }
+}
+
+fn final_response(response: Option<WorkerResponse>) -> Result<WorkerResponse, WorkerError> {
+    match response {
+        Some(WorkerResponse::Failed { error, .. }) => Err(error),
+        Some(response) => Ok(response),
+        None => Err(WorkerError::WorkerUnavailableError(String::from(
+            "The worker stopped before answering.",
+        ))),
+    }
+}
+
+fn unexpected<T>(response: WorkerResponse) -> Result<T, WorkerError> {
+    Err(WorkerError::WorkerUnavailableError(format!(
+        "The worker answered with an unexpected {response:?}."
+    )))
+}
+
+/// Statistics of every column of a table.
+pub async fn profile(table: JobId, data: Rc<Table>) -> Result<Vec<ColumnProfile>, WorkerError> {
+    match run_on_table(table, &data, |job| WorkerRequest::Profile { job, table }).await? {
+        WorkerResponse::Profile { profile, .. } => Ok(profile),
+        response => unexpected(response),
+    }
+}
+
+pub async fn pivot(
+    table: JobId,
+    data: Rc<Table>,
+    definition: PivotDefinition,
+) -> Result<PivotResult, WorkerError> {
+    let request = |job| WorkerRequest::Pivot {
+        job,
+        table,
+        definition: definition.clone(),
+    };
+    match run_on_table(table, &data, request).await? {
+        WorkerResponse::Pivot { result, .. } => Ok(result),
+        response => unexpected(response),
+    }
+}
+
+/// Finds the columns of a table containing `query`, see `search::search_rows`.
+pub async fn search(
+    table: JobId,
+    data: Rc<Table>,
+    name: &str,
+    query: &str,
+    max_samples: usize,
+) -> Result<Vec<SearchHit>, WorkerError> {
+    let request = |job| WorkerRequest::Search {
+        job,
+        table,
+        name: String::from(name),
+        query: String::from(query),
+        max_samples,
+    };
+    match run_on_table(table, &data, request).await? {
+        WorkerResponse::Search { hits, .. } => Ok(hits),
+        response => unexpected(response),
+    }
+}
@@ -0,0 +1,90 @@
+use crate::checks::RuleSet;
+use crate::codelists::CodelistRegistry;
+use crate::formatting::FormatRule;
+use crate::i18n::Locale;
+use crate::pivot::PivotDefinition;
+use crate::views::NamedView;
+
+use std::collections::HashMap;
+
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsValue;
+use web_sys::console::error_1;
+
+const WORKSPACE_STORAGE_KEY: &str = "styeward.workspace";
+
+/// Per-table settings the user builds up while exploring a table.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct TableWorkspace {
+    pub pivot: Option<PivotDefinition>,
+    /// Formatting rules of the grid, applied in order.
+    #[serde(default)]
+    pub format_rules: Vec<FormatRule>,
+    /// Show coded cells as stored instead of "code — decode".
+    #[serde(default)]
+    pub show_raw_codes: bool,
+    /// Read every field of a CSV table as text, rather than inferring numbers and booleans.
+    #[serde(default)]
+    pub csv_as_text: bool,
+}
+
+/// Everything the user has configured, persisted in the browser's local storage.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct Workspace {
+    pub tables: HashMap<String, TableWorkspace>,
+    #[serde(default)]
+    pub views: Vec<NamedView>,
+    /// Data quality checks run over every table, see src/checks.rs.
+    #[serde(default)]
+    pub checks: RuleSet,
+    /// Code lists imported from a local file, used before the ones the backend serves.
+    #[serde(default)]
+    pub codelists: CodelistRegistry,
+    /// Key bindings of commands by command id, an empty one unbinds the command. See
+    /// src/commands.rs.
+    #[serde(default)]
+    pub keybindings: HashMap<String, String>,
+    /// The UI locale the user picked, the browser's preferred one while unset.
+    #[serde(default)]
+    pub locale: Option<Locale>,
+}
+
+impl Workspace {
+    pub fn table_key(schema: &str, table: &str) -> String {
+        format!("{schema}.{table}")
+    }
+
+    pub fn load() -> Workspace {
+        LocalStorage::get::<Workspace>(WORKSPACE_STORAGE_KEY).unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Err(error) = LocalStorage::set(WORKSPACE_STORAGE_KEY, self) {
+            error_1(&JsValue::from(format!(
+                "workspace error: unable to save the workspace: {error}"
+            )));
+        }
+    }
+
+    pub fn table(&self, schema: &str, table: &str) -> TableWorkspace {
+        self.tables
+            .get(&Workspace::table_key(schema, table))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Applies `update` to the settings of one table and saves the whole workspace.
+    pub fn update_table<F>(schema: &str, table: &str, update: F)
+    where
+        F: FnOnce(&mut TableWorkspace),
+    {
+        let mut workspace = Workspace::load();
+        let entry = workspace
+            .tables
+            .entry(Workspace::table_key(schema, table))
+            .or_default();
+        update(entry);
+        workspace.save();
+    }
+}